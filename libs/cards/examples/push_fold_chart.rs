@@ -0,0 +1,33 @@
+use cards::nash::{PreflopEquities, PushFoldSolver};
+
+fn main() {
+    println!("Sampling all-in equities for every pair of starting hands...");
+    let equities = PreflopEquities::monte_carlo(1_000, 7).expect("valid table");
+
+    for stack in [5.0, 10.0, 15.0, 20.0] {
+        let chart = PushFoldSolver::new(stack)
+            .solve(&equities)
+            .expect("valid spot");
+        let push = chart.push_range().total_weight() / 1326.0;
+        let call = chart.call_range().total_weight() / 1326.0;
+        println!(
+            "{stack} bb: push {:.1}%, call {:.1}%, exploitability {:.4} bb",
+            push * 100.0,
+            call * 100.0,
+            chart.exploitability()
+        );
+
+        #[cfg(feature = "display")]
+        {
+            use cards::display::HandGrid;
+            println!(
+                "Push:\n{}",
+                HandGrid::from_range(&chart.push_range()).render_plain()
+            );
+            println!(
+                "Call:\n{}",
+                HandGrid::from_range(&chart.call_range()).render_plain()
+            );
+        }
+    }
+}
@@ -0,0 +1,36 @@
+#[cfg(feature = "display")]
+use cards::range::Range;
+
+fn main() {
+    // Only run this example with the display feature
+    #[cfg(not(feature = "display"))]
+    {
+        println!("This example requires the 'display' feature.");
+        println!("Run with: cargo run --example range_grid --features display");
+    }
+
+    #[cfg(feature = "display")]
+    {
+        use cards::display::{HandGrid, RangeGridDisplay, ValueFormat};
+        use cards::range::StartingHand;
+
+        println!("Range Grid Demo\n");
+
+        let range: Range = "22+, A2s+, K9s+, QTs+, JTs, ATo+, KJo+, QJo:0.5"
+            .parse()
+            .expect("valid range");
+        println!("Opening range: {range}\n");
+        println!("{}\n", range.to_grid());
+
+        println!("Shaded:\n");
+        println!("{}\n", HandGrid::from_range(&range).colored(true).render());
+
+        // Any per-hand value can be shown, here the share of combos that are suited
+        let grid = HandGrid::from_values(|hand: StartingHand| {
+            Some(if hand.is_suited() { 1.0 } else { 0.0 })
+        })
+        .format(ValueFormat::Decimal(1));
+        println!("Plain text:\n");
+        println!("{}", grid.render_plain());
+    }
+}
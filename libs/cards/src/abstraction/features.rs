@@ -0,0 +1,217 @@
+use std::ops::ControlFlow;
+
+use rand::SeedableRng;
+use rand::rngs::StdRng;
+use rand::seq::index::sample;
+
+use super::{AbstractionError, Metric, Street};
+use crate::equity::{combinations, for_each_board};
+use crate::iso::canonicalize;
+use crate::strength::StrengthCalculator;
+use crate::{Card, Deck};
+
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
+/// Number of equal-width equity bins in a histogram when none is configured
+pub const DEFAULT_BINS: usize = 20;
+
+/// Most runouts walked per hand when no limit is configured; hands with more are sampled
+pub const DEFAULT_SAMPLES: usize = 1_000;
+
+/// What a hand is worth by the river against one random hand, over the runouts to come
+///
+/// Expected hand strength (EHS) is the average river hand strength, and EHS² the average of
+/// its square, which rewards hands that often end up very strong over hands that end up
+/// middling. The histogram holds the share of runouts ending in each equal-width band of
+/// river hand strength, keeping the shape that both averages lose.
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct HandFeatures {
+    ehs: f64,
+    ehs_squared: f64,
+    histogram: Vec<f64>,
+}
+
+impl HandFeatures {
+    pub fn ehs(&self) -> f64 {
+        self.ehs
+    }
+
+    pub fn ehs_squared(&self) -> f64 {
+        self.ehs_squared
+    }
+
+    /// Share of runouts ending in each band of hand strength, weakest first; sums to one
+    pub fn histogram(&self) -> &[f64] {
+        &self.histogram
+    }
+
+    /// The point the hand is clustered at under a metric
+    pub fn point(&self, metric: Metric) -> Vec<f64> {
+        match metric {
+            Metric::Ehs => vec![self.ehs],
+            Metric::EhsSquared => vec![self.ehs_squared],
+            Metric::Histogram => self.histogram.clone(),
+        }
+    }
+}
+
+/// Calculates the features hands are bucketed by
+///
+/// Runouts are walked exhaustively when there are at most `samples` of them (always on the
+/// turn and river), and sampled otherwise. Hands are worked on in their suit-normalised form
+/// with a fixed seed, so isomorphic hands always get the same features.
+///
+/// # Examples
+///
+/// ```
+/// use cards::abstraction::FeatureCalculator;
+/// use cards::notation::parse_cards;
+///
+/// let hole = parse_cards("Ah Kh").unwrap();
+/// let board = parse_cards("Qh 7h 2c 9s").unwrap();
+/// let features = FeatureCalculator::new().bins(10).features(&[hole[0], hole[1]], &board).unwrap();
+///
+/// // The flush comes in about a fifth of the time, and then it is nearly always best
+/// assert!(features.histogram()[9] > 0.15);
+/// assert!(features.ehs_squared() < features.ehs());
+/// ```
+#[derive(Debug)]
+pub struct FeatureCalculator {
+    bins: usize,
+    samples: usize,
+    seed: u64,
+    strength: StrengthCalculator,
+}
+
+impl Default for FeatureCalculator {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl FeatureCalculator {
+    pub fn new() -> Self {
+        Self {
+            bins: DEFAULT_BINS,
+            samples: DEFAULT_SAMPLES,
+            seed: 0,
+            strength: StrengthCalculator::new(),
+        }
+    }
+
+    /// Sets the number of bins in each histogram
+    pub fn bins(mut self, bins: usize) -> Self {
+        self.bins = bins;
+        self
+    }
+
+    /// Sets the most runouts walked per hand before sampling that many instead
+    pub fn samples(mut self, samples: usize) -> Self {
+        self.samples = samples;
+        self
+    }
+
+    /// Sets the seed runouts are sampled with
+    pub fn seed(mut self, seed: u64) -> Self {
+        self.seed = seed;
+        self
+    }
+
+    pub(super) fn settings(&self) -> (usize, usize, u64) {
+        (self.bins, self.samples, self.seed)
+    }
+
+    /// Calculates the features of hole cards on a board of 0, 3, 4 or 5 cards
+    pub fn features(
+        &self,
+        hole: &[Card; 2],
+        board: &[Card],
+    ) -> Result<HandFeatures, AbstractionError> {
+        Street::from_board(board)?;
+        if self.bins == 0 {
+            return Err(AbstractionError::NoBins);
+        }
+        if self.samples == 0 {
+            return Err(AbstractionError::NoSamples);
+        }
+        let (hole, board) = canonicalize(hole, board)?;
+
+        let known: Vec<Card> = hole.iter().chain(&board).copied().collect();
+        let remaining: Vec<Card> = Deck::new()
+            .cards()
+            .iter()
+            .filter(|card| card.suit.is_some() && !known.contains(card))
+            .copied()
+            .collect();
+        let to_come = 5 - board.len();
+
+        let mut tally = Tally::new(self.bins);
+        let mut result = Ok(());
+        let mut record = |river: &[Card]| match self.strength.hand_strength(&hole, river) {
+            Ok(strength) => {
+                tally.add(strength);
+                ControlFlow::Continue(())
+            }
+            Err(e) => {
+                result = Err(e);
+                ControlFlow::Break(())
+            }
+        };
+
+        if combinations(remaining.len(), to_come) <= self.samples as u64 {
+            for_each_board(&board, &remaining, to_come, record);
+        } else {
+            let mut rng = StdRng::seed_from_u64(self.seed);
+            let mut river = board.clone();
+            for _ in 0..self.samples {
+                river.truncate(board.len());
+                river.extend(
+                    sample(&mut rng, remaining.len(), to_come)
+                        .into_iter()
+                        .map(|i| remaining[i]),
+                );
+                if record(&river).is_break() {
+                    break;
+                }
+            }
+        }
+        result?;
+        Ok(tally.finish())
+    }
+}
+
+struct Tally {
+    count: f64,
+    sum: f64,
+    sum_squares: f64,
+    histogram: Vec<f64>,
+}
+
+impl Tally {
+    fn new(bins: usize) -> Self {
+        Self {
+            count: 0.0,
+            sum: 0.0,
+            sum_squares: 0.0,
+            histogram: vec![0.0; bins],
+        }
+    }
+
+    fn add(&mut self, strength: f64) {
+        let bins = self.histogram.len();
+        self.count += 1.0;
+        self.sum += strength;
+        self.sum_squares += strength * strength;
+        self.histogram[((strength * bins as f64) as usize).min(bins - 1)] += 1.0;
+    }
+
+    fn finish(self) -> HandFeatures {
+        HandFeatures {
+            ehs: self.sum / self.count,
+            ehs_squared: self.sum_squares / self.count,
+            histogram: self.histogram.iter().map(|n| n / self.count).collect(),
+        }
+    }
+}
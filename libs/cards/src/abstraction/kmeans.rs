@@ -0,0 +1,243 @@
+use rand::rngs::StdRng;
+use rand::{Rng, SeedableRng};
+
+use super::AbstractionError;
+
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
+/// Rounds of k-means run when no limit is configured
+pub const DEFAULT_ITERATIONS: usize = 100;
+
+/// How far apart two points are when clustering
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub enum Distance {
+    /// Straight-line distance
+    #[default]
+    Euclidean,
+    /// Earth mover's distance between histograms over equal-width bins of [0, 1]
+    EarthMovers,
+}
+
+impl Distance {
+    pub fn between(&self, a: &[f64], b: &[f64]) -> f64 {
+        match self {
+            Distance::Euclidean => a
+                .iter()
+                .zip(b)
+                .map(|(x, y)| (x - y) * (x - y))
+                .sum::<f64>()
+                .sqrt(),
+            Distance::EarthMovers => emd(a, b),
+        }
+    }
+}
+
+/// The earth mover's distance between two histograms over equal-width bins of [0, 1]
+///
+/// This is the least mass times distance that turns one histogram into the other, so moving
+/// all of the mass from the lowest bin to the highest costs nearly one. Both histograms
+/// should hold the same total.
+pub fn emd(a: &[f64], b: &[f64]) -> f64 {
+    let mut carried = 0.0;
+    let mut total = 0.0;
+    for (x, y) in a.iter().zip(b) {
+        carried += x - y;
+        total += carried.abs();
+    }
+    total / a.len().max(1) as f64
+}
+
+/// Groups points into clusters around their means with Lloyd's algorithm
+///
+/// The first centroids are picked with k-means++ from a seeded generator, so the same points
+/// always give the same clusters. Means are taken coordinate by coordinate under either
+/// distance, which for histograms is the mean histogram. Fewer clusters than asked for are
+/// returned when there are fewer distinct points.
+///
+/// # Examples
+///
+/// ```
+/// use cards::abstraction::KMeans;
+///
+/// let points = vec![vec![0.1], vec![0.15], vec![0.8], vec![0.9], vec![0.85]];
+/// let clustering = KMeans::new(2).seed(3).fit(&points).unwrap();
+///
+/// let assignments = clustering.assignments();
+/// assert_eq!(assignments[0], assignments[1]);
+/// assert_eq!(assignments[2], assignments[4]);
+/// assert_ne!(assignments[0], assignments[2]);
+/// ```
+#[derive(Debug, Clone)]
+pub struct KMeans {
+    clusters: usize,
+    distance: Distance,
+    iterations: usize,
+    seed: u64,
+}
+
+impl KMeans {
+    /// Creates a clusterer for up to `clusters` clusters by Euclidean distance
+    pub fn new(clusters: usize) -> Self {
+        Self {
+            clusters,
+            distance: Distance::Euclidean,
+            iterations: DEFAULT_ITERATIONS,
+            seed: 0,
+        }
+    }
+
+    pub fn distance(mut self, distance: Distance) -> Self {
+        self.distance = distance;
+        self
+    }
+
+    /// Sets the most rounds run before stopping short of convergence
+    pub fn iterations(mut self, iterations: usize) -> Self {
+        self.iterations = iterations;
+        self
+    }
+
+    /// Sets the seed the first centroids are picked with
+    pub fn seed(mut self, seed: u64) -> Self {
+        self.seed = seed;
+        self
+    }
+
+    /// Clusters points that all have the same number of coordinates
+    pub fn fit(&self, points: &[Vec<f64>]) -> Result<Clustering, AbstractionError> {
+        if self.clusters == 0 {
+            return Err(AbstractionError::NoClusters);
+        }
+        let Some(first) = points.first() else {
+            return Err(AbstractionError::NoHands);
+        };
+        if let Some(index) = points.iter().position(|p| p.len() != first.len()) {
+            return Err(AbstractionError::MismatchedPoint(index));
+        }
+
+        let mut centroids = self.seed_centroids(points);
+        let mut assignments = self.assign(points, &centroids);
+        for _ in 0..self.iterations {
+            centroids = means(points, &assignments, &centroids);
+            let next = self.assign(points, &centroids);
+            if next == assignments {
+                break;
+            }
+            assignments = next;
+        }
+        Ok(Clustering {
+            centroids,
+            assignments,
+            distance: self.distance,
+        })
+    }
+
+    // k-means++: each further centroid is a point picked with probability proportional to
+    // its squared distance from the nearest centroid so far
+    fn seed_centroids(&self, points: &[Vec<f64>]) -> Vec<Vec<f64>> {
+        let mut rng = StdRng::seed_from_u64(self.seed);
+        let mut centroids = vec![points[rng.random_range(0..points.len())].clone()];
+        let mut nearest: Vec<f64> = points
+            .iter()
+            .map(|p| self.distance.between(p, &centroids[0]).powi(2))
+            .collect();
+        while centroids.len() < self.clusters {
+            let total: f64 = nearest.iter().sum();
+            if total <= 0.0 {
+                break;
+            }
+            let mut target = rng.random_range(0.0..total);
+            let mut chosen = points.len() - 1;
+            for (i, &d) in nearest.iter().enumerate() {
+                if target < d {
+                    chosen = i;
+                    break;
+                }
+                target -= d;
+            }
+            let centroid = points[chosen].clone();
+            for (n, p) in nearest.iter_mut().zip(points) {
+                *n = n.min(self.distance.between(p, &centroid).powi(2));
+            }
+            centroids.push(centroid);
+        }
+        centroids
+    }
+
+    fn assign(&self, points: &[Vec<f64>], centroids: &[Vec<f64>]) -> Vec<usize> {
+        points
+            .iter()
+            .map(|p| nearest(self.distance, p, centroids))
+            .collect()
+    }
+}
+
+// The closest centroid, the lowest index among equals
+pub(super) fn nearest(distance: Distance, point: &[f64], centroids: &[Vec<f64>]) -> usize {
+    let mut best = (0, f64::INFINITY);
+    for (i, centroid) in centroids.iter().enumerate() {
+        let d = distance.between(point, centroid);
+        if d < best.1 {
+            best = (i, d);
+        }
+    }
+    best.0
+}
+
+// The mean of each cluster's points; a cluster left empty keeps its centroid
+fn means(points: &[Vec<f64>], assignments: &[usize], centroids: &[Vec<f64>]) -> Vec<Vec<f64>> {
+    let mut sums = vec![vec![0.0; centroids[0].len()]; centroids.len()];
+    let mut counts = vec![0usize; centroids.len()];
+    for (point, &cluster) in points.iter().zip(assignments) {
+        counts[cluster] += 1;
+        for (sum, x) in sums[cluster].iter_mut().zip(point) {
+            *sum += x;
+        }
+    }
+    sums.into_iter()
+        .zip(counts)
+        .zip(centroids)
+        .map(|((sum, count), centroid)| {
+            if count == 0 {
+                centroid.clone()
+            } else {
+                sum.into_iter().map(|s| s / count as f64).collect()
+            }
+        })
+        .collect()
+}
+
+/// Clusters found by `KMeans`
+#[derive(Debug, Clone, PartialEq)]
+pub struct Clustering {
+    centroids: Vec<Vec<f64>>,
+    assignments: Vec<usize>,
+    distance: Distance,
+}
+
+impl Clustering {
+    pub fn centroids(&self) -> &[Vec<f64>] {
+        &self.centroids
+    }
+
+    /// The cluster of each point, in the order the points were given
+    pub fn assignments(&self) -> &[usize] {
+        &self.assignments
+    }
+
+    /// The cluster whose centroid is closest to a point
+    pub fn nearest(&self, point: &[f64]) -> usize {
+        nearest(self.distance, point, &self.centroids)
+    }
+
+    /// The sum of each point's squared distance from its centroid
+    pub fn inertia(&self, points: &[Vec<f64>]) -> f64 {
+        points
+            .iter()
+            .zip(&self.assignments)
+            .map(|(p, &c)| self.distance.between(p, &self.centroids[c]).powi(2))
+            .sum()
+    }
+}
@@ -0,0 +1,357 @@
+use std::collections::BTreeMap;
+use std::fmt;
+use std::io;
+
+use rand::SeedableRng;
+use rand::rngs::StdRng;
+use rand::seq::SliceRandom;
+
+use crate::iso::{IsoError, canonical_index};
+use crate::strength::StrengthError;
+use crate::{Card, Deck};
+
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
+mod features;
+mod kmeans;
+mod table;
+
+pub use features::{DEFAULT_BINS, DEFAULT_SAMPLES, FeatureCalculator, HandFeatures};
+pub use kmeans::{Clustering, DEFAULT_ITERATIONS, Distance, KMeans, emd};
+pub use table::BucketTable;
+
+#[cfg(test)]
+mod tests;
+
+/// Number of buckets per street when none is configured
+pub const DEFAULT_BUCKETS: usize = 8;
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum AbstractionError {
+    /// Hands are bucketed with 0, 3, 4 or 5 board cards
+    InvalidBoardSize(usize),
+    /// A hand was given for a different street than the table's
+    WrongStreet { expected: Street, found: Street },
+    /// The cards could not be put in canonical form, such as when one is repeated
+    Iso(IsoError),
+    /// A hand's strength could not be calculated
+    Strength(StrengthError),
+    /// Histograms need at least one bin
+    NoBins,
+    /// Each hand needs at least one runout
+    NoSamples,
+    /// At least one bucket is needed
+    NoClusters,
+    /// There is nothing to cluster
+    NoHands,
+    /// A point has a different number of coordinates than the first
+    MismatchedPoint(usize),
+    /// A saved table could not be read
+    Io(io::ErrorKind),
+    /// A saved table is malformed at this line
+    InvalidTable(usize),
+}
+
+impl fmt::Display for AbstractionError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            AbstractionError::InvalidBoardSize(size) => {
+                write!(f, "board must have 0, 3, 4 or 5 cards, got {size}")
+            }
+            AbstractionError::WrongStreet { expected, found } => {
+                write!(f, "expected a {expected} hand, got a {found} hand")
+            }
+            AbstractionError::Iso(e) => write!(f, "invalid cards: {e}"),
+            AbstractionError::Strength(e) => write!(f, "hand strength failed: {e}"),
+            AbstractionError::NoBins => write!(f, "histograms need at least one bin"),
+            AbstractionError::NoSamples => write!(f, "sample count must be greater than zero"),
+            AbstractionError::NoClusters => write!(f, "bucket count must be greater than zero"),
+            AbstractionError::NoHands => write!(f, "no hands to bucket"),
+            AbstractionError::MismatchedPoint(index) => {
+                write!(f, "point {index} has a different number of coordinates")
+            }
+            AbstractionError::Io(kind) => write!(f, "could not read bucket table: {kind}"),
+            AbstractionError::InvalidTable(line) => {
+                write!(f, "bucket table is malformed at line {line}")
+            }
+        }
+    }
+}
+
+impl std::error::Error for AbstractionError {}
+
+impl From<IsoError> for AbstractionError {
+    fn from(e: IsoError) -> Self {
+        AbstractionError::Iso(e)
+    }
+}
+
+impl From<StrengthError> for AbstractionError {
+    fn from(e: StrengthError) -> Self {
+        AbstractionError::Strength(e)
+    }
+}
+
+impl From<io::Error> for AbstractionError {
+    fn from(e: io::Error) -> Self {
+        AbstractionError::Io(e.kind())
+    }
+}
+
+/// A betting round, known by how many board cards have been dealt
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub enum Street {
+    Preflop,
+    Flop,
+    Turn,
+    River,
+}
+
+impl Street {
+    pub const ALL: [Street; 4] = [Street::Preflop, Street::Flop, Street::Turn, Street::River];
+
+    /// The street with this board
+    pub fn from_board(board: &[Card]) -> Result<Self, AbstractionError> {
+        match board.len() {
+            0 => Ok(Street::Preflop),
+            3 => Ok(Street::Flop),
+            4 => Ok(Street::Turn),
+            5 => Ok(Street::River),
+            size => Err(AbstractionError::InvalidBoardSize(size)),
+        }
+    }
+
+    /// Number of board cards dealt by this street
+    pub fn board_len(&self) -> usize {
+        match self {
+            Street::Preflop => 0,
+            Street::Flop => 3,
+            Street::Turn => 4,
+            Street::River => 5,
+        }
+    }
+}
+
+impl fmt::Display for Street {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let name = match self {
+            Street::Preflop => "preflop",
+            Street::Flop => "flop",
+            Street::Turn => "turn",
+            Street::River => "river",
+        };
+        write!(f, "{name}")
+    }
+}
+
+/// What hands are clustered by
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub enum Metric {
+    /// Expected hand strength
+    Ehs,
+    /// Expected hand strength squared
+    EhsSquared,
+    /// The histogram of river hand strength, compared by earth mover's distance
+    Histogram,
+}
+
+impl Metric {
+    pub const ALL: [Metric; 3] = [Metric::Ehs, Metric::EhsSquared, Metric::Histogram];
+
+    /// The distance points are compared by under this metric
+    pub fn distance(&self) -> Distance {
+        match self {
+            Metric::Ehs | Metric::EhsSquared => Distance::Euclidean,
+            Metric::Histogram => Distance::EarthMovers,
+        }
+    }
+}
+
+impl fmt::Display for Metric {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let name = match self {
+            Metric::Ehs => "ehs",
+            Metric::EhsSquared => "ehs2",
+            Metric::Histogram => "histogram",
+        };
+        write!(f, "{name}")
+    }
+}
+
+/// Hole cards and a board
+pub type Deal = ([Card; 2], Vec<Card>);
+
+/// Groups strategically similar hands on one street into buckets
+///
+/// Each hand's features are calculated with a `FeatureCalculator`, the hands are clustered
+/// under the chosen metric with `KMeans`, and the buckets are numbered from the weakest to
+/// the strongest by their hands' average EHS. Isomorphic hands are only clustered once, and
+/// everything is seeded, so the same hands always give the same table.
+///
+/// # Examples
+///
+/// ```
+/// use cards::abstraction::{BucketBuilder, Metric, Street};
+/// use cards::notation::parse_cards;
+///
+/// let hole = |text: &str| {
+///     let cards = parse_cards(text).unwrap();
+///     [cards[0], cards[1]]
+/// };
+/// let board = parse_cards("Ks 9d 5c 3h 2s").unwrap();
+/// let deals: Vec<_> = ["As Kh", "Kc Qh", "9s 9c", "7c 6c", "Qd Jd", "8h 4h"]
+///     .iter()
+///     .map(|text| (hole(text), board.clone()))
+///     .collect();
+///
+/// let table = BucketBuilder::new(Street::River, Metric::Ehs)
+///     .buckets(3)
+///     .build(&deals)
+///     .unwrap();
+///
+/// // The set is in the strongest bucket, and the missed draws share the weakest
+/// let bucket = |text: &str| table.bucket(&hole(text), &board).unwrap();
+/// assert_eq!(bucket("9s 9c"), 2);
+/// assert_eq!(bucket("7c 6c"), 0);
+/// assert_eq!(bucket("8h 4h"), 0);
+/// ```
+#[derive(Debug, Clone)]
+pub struct BucketBuilder {
+    street: Street,
+    metric: Metric,
+    buckets: usize,
+    bins: usize,
+    samples: usize,
+    iterations: usize,
+    seed: u64,
+}
+
+impl BucketBuilder {
+    pub fn new(street: Street, metric: Metric) -> Self {
+        Self {
+            street,
+            metric,
+            buckets: DEFAULT_BUCKETS,
+            bins: DEFAULT_BINS,
+            samples: DEFAULT_SAMPLES,
+            iterations: DEFAULT_ITERATIONS,
+            seed: 0,
+        }
+    }
+
+    /// Sets the most buckets the hands are grouped into
+    pub fn buckets(mut self, buckets: usize) -> Self {
+        self.buckets = buckets;
+        self
+    }
+
+    /// Sets the number of bins in each hand's histogram
+    pub fn bins(mut self, bins: usize) -> Self {
+        self.bins = bins;
+        self
+    }
+
+    /// Sets the most runouts walked per hand before sampling that many instead
+    pub fn samples(mut self, samples: usize) -> Self {
+        self.samples = samples;
+        self
+    }
+
+    /// Sets the most rounds of k-means
+    pub fn iterations(mut self, iterations: usize) -> Self {
+        self.iterations = iterations;
+        self
+    }
+
+    /// Sets the seed used for sampling runouts, dealing hands and picking first centroids
+    pub fn seed(mut self, seed: u64) -> Self {
+        self.seed = seed;
+        self
+    }
+
+    /// Buckets the given hands, which must all be on this street
+    pub fn build(&self, deals: &[Deal]) -> Result<BucketTable, AbstractionError> {
+        let calculator = FeatureCalculator::new()
+            .bins(self.bins)
+            .samples(self.samples)
+            .seed(self.seed);
+
+        let mut seen = BTreeMap::new();
+        let mut features = Vec::new();
+        for (hole, board) in deals {
+            let street = Street::from_board(board)?;
+            if street != self.street {
+                return Err(AbstractionError::WrongStreet {
+                    expected: self.street,
+                    found: street,
+                });
+            }
+            let index = canonical_index(hole, board)?;
+            if seen.contains_key(&index) {
+                continue;
+            }
+            seen.insert(index, features.len());
+            features.push(calculator.features(hole, board)?);
+        }
+
+        let points: Vec<Vec<f64>> = features.iter().map(|f| f.point(self.metric)).collect();
+        let clustering = KMeans::new(self.buckets)
+            .distance(self.metric.distance())
+            .iterations(self.iterations)
+            .seed(self.seed)
+            .fit(&points)?;
+
+        // Number the buckets by their hands' average EHS, weakest first
+        let count = clustering.centroids().len();
+        let mut strength = vec![(0.0, 0usize); count];
+        for (f, &cluster) in features.iter().zip(clustering.assignments()) {
+            strength[cluster].0 += f.ehs();
+            strength[cluster].1 += 1;
+        }
+        let mut order: Vec<usize> = (0..count).collect();
+        let average = |(total, n): (f64, usize)| if n > 0 { total / n as f64 } else { 0.0 };
+        order.sort_by(|&a, &b| average(strength[a]).total_cmp(&average(strength[b])));
+        let mut renumbered = vec![0; count];
+        for (bucket, &cluster) in order.iter().enumerate() {
+            renumbered[cluster] = bucket;
+        }
+
+        let centroids = order
+            .iter()
+            .map(|&cluster| clustering.centroids()[cluster].clone())
+            .collect();
+        let buckets = seen
+            .into_iter()
+            .map(|(index, i)| (index, renumbered[clustering.assignments()[i]]))
+            .collect();
+        Ok(BucketTable::new(
+            self.street,
+            self.metric,
+            calculator.settings(),
+            centroids,
+            buckets,
+        ))
+    }
+
+    /// Deals `count` random hands on this street from the seed and buckets them
+    pub fn build_sampled(&self, count: usize) -> Result<BucketTable, AbstractionError> {
+        let mut rng = StdRng::seed_from_u64(self.seed);
+        let mut cards: Vec<Card> = Deck::new()
+            .cards()
+            .iter()
+            .filter(|card| card.suit.is_some())
+            .copied()
+            .collect();
+        let needed = 2 + self.street.board_len();
+        let deals: Vec<Deal> = (0..count)
+            .map(|_| {
+                let (dealt, _) = cards.partial_shuffle(&mut rng, needed);
+                ([dealt[0], dealt[1]], dealt[2..].to_vec())
+            })
+            .collect();
+        self.build(&deals)
+    }
+}
@@ -0,0 +1,259 @@
+use std::collections::BTreeMap;
+use std::io::{BufRead, Write};
+
+use super::kmeans::nearest;
+use super::{AbstractionError, FeatureCalculator, HandFeatures, Metric, Street};
+use crate::Card;
+use crate::iso::canonical_index;
+
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
+/// First line of a saved table, naming the format and its version
+const HEADER: &str = "buckets 1";
+
+/// The buckets of one street's hands, as built by `BucketBuilder`
+///
+/// Hands the table was built from are looked up by their canonical index. Any other hand on
+/// the street is put in the bucket with the nearest centroid, its features calculated with
+/// the same settings the table was built with.
+///
+/// A table can be saved as text with `write_to` and loaded with `read_from`, or with the
+/// `serde` feature.
+///
+/// # Examples
+///
+/// ```
+/// use cards::abstraction::{BucketBuilder, BucketTable, Metric, Street};
+///
+/// let table = BucketBuilder::new(Street::River, Metric::Ehs)
+///     .buckets(4)
+///     .seed(5)
+///     .build_sampled(30)
+///     .unwrap();
+///
+/// let mut saved = Vec::new();
+/// table.write_to(&mut saved).unwrap();
+/// let loaded = BucketTable::read_from(saved.as_slice()).unwrap();
+/// assert_eq!(loaded, table);
+/// ```
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct BucketTable {
+    street: Street,
+    metric: Metric,
+    bins: usize,
+    samples: usize,
+    seed: u64,
+    centroids: Vec<Vec<f64>>,
+    buckets: BTreeMap<u64, usize>,
+}
+
+impl BucketTable {
+    pub(super) fn new(
+        street: Street,
+        metric: Metric,
+        (bins, samples, seed): (usize, usize, u64),
+        centroids: Vec<Vec<f64>>,
+        buckets: BTreeMap<u64, usize>,
+    ) -> Self {
+        Self {
+            street,
+            metric,
+            bins,
+            samples,
+            seed,
+            centroids,
+            buckets,
+        }
+    }
+
+    pub fn street(&self) -> Street {
+        self.street
+    }
+
+    pub fn metric(&self) -> Metric {
+        self.metric
+    }
+
+    /// Number of buckets, weakest numbered 0
+    pub fn bucket_count(&self) -> usize {
+        self.centroids.len()
+    }
+
+    /// Each bucket's centre under the table's metric
+    pub fn centroids(&self) -> &[Vec<f64>] {
+        &self.centroids
+    }
+
+    /// Number of canonical hands the table holds
+    pub fn len(&self) -> usize {
+        self.buckets.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.buckets.is_empty()
+    }
+
+    /// Iterates over the canonical hand indices the table holds and their buckets
+    pub fn iter(&self) -> impl Iterator<Item = (u64, usize)> + '_ {
+        self.buckets.iter().map(|(&index, &bucket)| (index, bucket))
+    }
+
+    /// The bucket of a hand the table was built from
+    pub fn get(&self, hole: &[Card; 2], board: &[Card]) -> Result<Option<usize>, AbstractionError> {
+        self.check_street(board)?;
+        let index = canonical_index(hole, board)?;
+        Ok(self.buckets.get(&index).copied())
+    }
+
+    /// The bucket of any hand on the table's street
+    pub fn bucket(&self, hole: &[Card; 2], board: &[Card]) -> Result<usize, AbstractionError> {
+        if let Some(bucket) = self.get(hole, board)? {
+            return Ok(bucket);
+        }
+        let features = self.feature_calculator().features(hole, board)?;
+        Ok(self.classify(&features))
+    }
+
+    /// The bucket whose centroid is nearest to a hand's features
+    pub fn classify(&self, features: &HandFeatures) -> usize {
+        nearest(
+            self.metric.distance(),
+            &features.point(self.metric),
+            &self.centroids,
+        )
+    }
+
+    /// A calculator with the settings the table was built with
+    pub fn feature_calculator(&self) -> FeatureCalculator {
+        FeatureCalculator::new()
+            .bins(self.bins)
+            .samples(self.samples)
+            .seed(self.seed)
+    }
+
+    /// Writes the table as text, one setting, centroid or hand per line
+    pub fn write_to<W: Write>(&self, mut writer: W) -> std::io::Result<()> {
+        writeln!(writer, "{HEADER}")?;
+        writeln!(writer, "street {}", self.street)?;
+        writeln!(writer, "metric {}", self.metric)?;
+        writeln!(writer, "bins {}", self.bins)?;
+        writeln!(writer, "samples {}", self.samples)?;
+        writeln!(writer, "seed {}", self.seed)?;
+        writeln!(writer, "centroids {}", self.centroids.len())?;
+        for centroid in &self.centroids {
+            let values: Vec<String> = centroid.iter().map(f64::to_string).collect();
+            writeln!(writer, "{}", values.join(" "))?;
+        }
+        writeln!(writer, "hands {}", self.buckets.len())?;
+        for (index, bucket) in &self.buckets {
+            writeln!(writer, "{index} {bucket}")?;
+        }
+        Ok(())
+    }
+
+    /// Reads a table written by `write_to`
+    pub fn read_from<R: BufRead>(reader: R) -> Result<Self, AbstractionError> {
+        let mut lines = Lines {
+            lines: reader.lines(),
+            number: 0,
+        };
+        if lines.next()? != HEADER {
+            return Err(AbstractionError::InvalidTable(lines.number));
+        }
+        let street = lines.setting("street", |value| {
+            Street::ALL.into_iter().find(|s| s.to_string() == value)
+        })?;
+        let metric = lines.setting("metric", |value| {
+            Metric::ALL.into_iter().find(|m| m.to_string() == value)
+        })?;
+        let bins = lines.setting("bins", |value| value.parse().ok())?;
+        let samples = lines.setting("samples", |value| value.parse().ok())?;
+        let seed = lines.setting("seed", |value| value.parse().ok())?;
+
+        // The counts come from the file, so nothing is allocated for them up front
+        let count: usize = lines.setting("centroids", |value| {
+            value.parse().ok().filter(|&count| count > 0)
+        })?;
+        let mut centroids = Vec::new();
+        for _ in 0..count {
+            let line = lines.next()?;
+            let centroid: Option<Vec<f64>> = line.split(' ').map(|v| v.parse().ok()).collect();
+            // Every centroid has as many coordinates as the first
+            let Some(centroid) = centroid.filter(|centroid| {
+                centroids
+                    .first()
+                    .is_none_or(|first: &Vec<f64>| first.len() == centroid.len())
+            }) else {
+                return Err(AbstractionError::InvalidTable(lines.number));
+            };
+            centroids.push(centroid);
+        }
+
+        let count: usize = lines.setting("hands", |value| value.parse().ok())?;
+        let mut buckets = BTreeMap::new();
+        for _ in 0..count {
+            let line = lines.next()?;
+            let entry = line.split_once(' ').and_then(|(index, bucket)| {
+                Some((index.parse().ok()?, bucket.parse::<usize>().ok()?))
+            });
+            match entry {
+                Some((index, bucket)) if bucket < centroids.len() => {
+                    buckets.insert(index, bucket);
+                }
+                _ => return Err(AbstractionError::InvalidTable(lines.number)),
+            }
+        }
+
+        Ok(Self {
+            street,
+            metric,
+            bins,
+            samples,
+            seed,
+            centroids,
+            buckets,
+        })
+    }
+
+    fn check_street(&self, board: &[Card]) -> Result<(), AbstractionError> {
+        let street = Street::from_board(board)?;
+        if street != self.street {
+            return Err(AbstractionError::WrongStreet {
+                expected: self.street,
+                found: street,
+            });
+        }
+        Ok(())
+    }
+}
+
+// Reads lines while counting them for error messages
+struct Lines<R> {
+    lines: std::io::Lines<R>,
+    number: usize,
+}
+
+impl<R: BufRead> Lines<R> {
+    fn next(&mut self) -> Result<String, AbstractionError> {
+        self.number += 1;
+        match self.lines.next() {
+            Some(line) => Ok(line?),
+            None => Err(AbstractionError::InvalidTable(self.number)),
+        }
+    }
+
+    // A `name value` line
+    fn setting<T>(
+        &mut self,
+        name: &str,
+        parse: impl FnOnce(&str) -> Option<T>,
+    ) -> Result<T, AbstractionError> {
+        let line = self.next()?;
+        line.strip_prefix(name)
+            .and_then(|rest| rest.strip_prefix(' '))
+            .and_then(parse)
+            .ok_or(AbstractionError::InvalidTable(self.number))
+    }
+}
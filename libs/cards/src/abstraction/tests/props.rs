@@ -0,0 +1,67 @@
+use super::super::*;
+use proptest::prelude::*;
+
+// A histogram over `bins` bins holding a total of one
+fn arb_histogram(bins: usize) -> impl Strategy<Value = Vec<f64>> {
+    prop::collection::vec(0.0f64..1.0, bins).prop_map(|mut weights| {
+        weights[0] += 1e-9;
+        let total: f64 = weights.iter().sum();
+        weights.iter_mut().for_each(|w| *w /= total);
+        weights
+    })
+}
+
+// Distinct hole cards and a river board
+fn arb_river() -> impl Strategy<Value = ([Card; 2], Vec<Card>)> {
+    Just(Deck::new().cards().to_vec())
+        .prop_shuffle()
+        .prop_map(|cards| {
+            let cards: Vec<Card> = cards.into_iter().filter(|c| c.suit.is_some()).collect();
+            ([cards[0], cards[1]], cards[2..7].to_vec())
+        })
+}
+
+proptest! {
+    #![proptest_config(ProptestConfig::with_cases(32))]
+
+    #[test]
+    fn emd_is_a_metric(
+        a in arb_histogram(8),
+        b in arb_histogram(8),
+        c in arb_histogram(8),
+    ) {
+        prop_assert!(emd(&a, &a).abs() < 1e-12);
+        prop_assert!(emd(&a, &b) >= 0.0);
+        prop_assert!((emd(&a, &b) - emd(&b, &a)).abs() < 1e-12);
+        prop_assert!(emd(&a, &c) <= emd(&a, &b) + emd(&b, &c) + 1e-12);
+        prop_assert!(emd(&a, &b) <= 1.0);
+    }
+
+    #[test]
+    fn points_belong_to_their_nearest_centroid(
+        points in prop::collection::vec(prop::collection::vec(0.0f64..1.0, 3), 1..40),
+        clusters in 1usize..6,
+        seed in any::<u64>(),
+        earth_movers in any::<bool>(),
+    ) {
+        let distance = if earth_movers { Distance::EarthMovers } else { Distance::Euclidean };
+        let clustering = KMeans::new(clusters)
+            .distance(distance)
+            .seed(seed)
+            .fit(&points)
+            .unwrap();
+        prop_assert!(clustering.centroids().len() <= clusters.min(points.len()));
+        for (point, &cluster) in points.iter().zip(clustering.assignments()) {
+            prop_assert_eq!(clustering.nearest(point), cluster);
+        }
+    }
+
+    #[test]
+    fn river_features_agree_with_each_other((hole, board) in arb_river()) {
+        let features = FeatureCalculator::new().bins(7).features(&hole, &board).unwrap();
+        prop_assert!((0.0..=1.0).contains(&features.ehs()));
+        prop_assert!((features.ehs_squared() - features.ehs().powi(2)).abs() < 1e-12);
+        let filled: Vec<&f64> = features.histogram().iter().filter(|&&h| h > 0.0).collect();
+        prop_assert_eq!(filled, vec![&1.0]);
+    }
+}
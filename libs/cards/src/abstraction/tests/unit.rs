@@ -0,0 +1,340 @@
+use super::super::*;
+use crate::iso::IsoError;
+use crate::notation::parse_cards;
+
+fn hole(notation: &str) -> [Card; 2] {
+    let cards = parse_cards(notation).unwrap();
+    [cards[0], cards[1]]
+}
+
+fn board(notation: &str) -> Vec<Card> {
+    parse_cards(notation).unwrap()
+}
+
+fn close(a: f64, b: f64, tolerance: f64) -> bool {
+    (a - b).abs() < tolerance
+}
+
+fn deals(holes: &[&str], on: &str) -> Vec<Deal> {
+    holes.iter().map(|h| (hole(h), board(on))).collect()
+}
+
+#[test]
+fn test_river_features_are_a_single_runout() {
+    let calculator = FeatureCalculator::new().bins(10);
+    let nuts = calculator
+        .features(&hole("Ah Kh"), &board("Qh Jh Th 2c 3d"))
+        .unwrap();
+    assert_eq!(nuts.ehs(), 1.0);
+    assert_eq!(nuts.ehs_squared(), 1.0);
+    assert_eq!(nuts.histogram()[9], 1.0);
+
+    let air = calculator
+        .features(&hole("3c 2d"), &board("Ah Kd 9s 8c 5h"))
+        .unwrap();
+    assert!(air.ehs() < 0.05);
+    assert!(close(air.ehs_squared(), air.ehs() * air.ehs(), 1e-12));
+    assert_eq!(air.histogram()[0], 1.0);
+    assert_eq!(air.point(Metric::Ehs), vec![air.ehs()]);
+    assert_eq!(air.point(Metric::Histogram).len(), 10);
+}
+
+#[test]
+fn test_turn_features_walk_every_river() {
+    let calculator = FeatureCalculator::new().bins(5);
+    let draw = calculator
+        .features(&hole("Ah Kh"), &board("Qh 7h 2c 9s"))
+        .unwrap();
+    assert!(close(draw.histogram().iter().sum(), 1.0, 1e-12));
+    assert!(draw.ehs_squared() < draw.ehs());
+
+    // Nine of the 46 rivers make the flush, each worth a bin's share of the runouts
+    let top = draw.histogram()[4] * 46.0;
+    assert!(close(top, top.round(), 1e-9));
+    assert!(top >= 9.0);
+
+    // Isomorphic hands have the same features
+    let same = calculator
+        .features(&hole("As Ks"), &board("Qs 7s 2d 9c"))
+        .unwrap();
+    assert_eq!(same, draw);
+}
+
+#[test]
+fn test_sampled_features_are_reproducible() {
+    let calculator = FeatureCalculator::new().samples(30).seed(4);
+    let aces = calculator.features(&hole("Ah As"), &[]).unwrap();
+    assert_eq!(calculator.features(&hole("Ac Ad"), &[]).unwrap(), aces);
+    let trash = calculator.features(&hole("7c 2d"), &[]).unwrap();
+    assert!(aces.ehs() > trash.ehs() + 0.3);
+
+    // The flop has more runouts than samples too
+    let flop = FeatureCalculator::new().samples(20);
+    let first = flop.features(&hole("Td 9d"), &board("8d 7c 2h")).unwrap();
+    assert_eq!(
+        flop.features(&hole("Td 9d"), &board("8d 7c 2h")).unwrap(),
+        first
+    );
+    assert!(close(first.histogram().iter().sum(), 1.0, 1e-12));
+}
+
+#[test]
+fn test_feature_errors() {
+    let calculator = FeatureCalculator::new();
+    let ace = parse_cards("As").unwrap()[0];
+    assert_eq!(
+        calculator.features(&hole("Ah Kh"), &board("Qh Jh")),
+        Err(AbstractionError::InvalidBoardSize(2))
+    );
+    assert_eq!(
+        calculator.features(&hole("As Kh"), &board("As Jh Th")),
+        Err(AbstractionError::Iso(IsoError::DuplicateCard(ace)))
+    );
+    assert_eq!(
+        FeatureCalculator::new()
+            .bins(0)
+            .features(&hole("As Kh"), &[]),
+        Err(AbstractionError::NoBins)
+    );
+    assert_eq!(
+        FeatureCalculator::new()
+            .samples(0)
+            .features(&hole("As Kh"), &[]),
+        Err(AbstractionError::NoSamples)
+    );
+}
+
+#[test]
+fn test_earth_movers_distance() {
+    let low = [1.0, 0.0, 0.0, 0.0];
+    let high = [0.0, 0.0, 0.0, 1.0];
+    assert_eq!(emd(&low, &low), 0.0);
+    assert_eq!(emd(&low, &high), 0.75);
+    assert_eq!(emd(&high, &low), 0.75);
+    assert_eq!(emd(&low, &[0.0, 1.0, 0.0, 0.0]), 0.25);
+
+    // Unlike straight-line distance, it cares how far the mass moves
+    let near = [0.0, 1.0, 0.0, 0.0];
+    assert_eq!(
+        Distance::Euclidean.between(&low, &near),
+        Distance::Euclidean.between(&low, &high)
+    );
+    assert!(
+        Distance::EarthMovers.between(&low, &near) < Distance::EarthMovers.between(&low, &high)
+    );
+}
+
+#[test]
+fn test_kmeans_finds_separate_groups() {
+    let points = vec![
+        vec![0.0, 0.0],
+        vec![0.1, 0.0],
+        vec![5.0, 5.0],
+        vec![5.1, 4.9],
+        vec![0.0, 9.0],
+        vec![0.2, 9.1],
+    ];
+    let clustering = KMeans::new(3).seed(11).fit(&points).unwrap();
+    let a = clustering.assignments();
+    assert_eq!(a[0], a[1]);
+    assert_eq!(a[2], a[3]);
+    assert_eq!(a[4], a[5]);
+    assert!(a[0] != a[2] && a[2] != a[4] && a[0] != a[4]);
+    assert_eq!(clustering.nearest(&[4.0, 4.0]), a[2]);
+    assert!(clustering.inertia(&points) < 0.1);
+
+    // The same seed gives the same clusters
+    assert_eq!(KMeans::new(3).seed(11).fit(&points).unwrap(), clustering);
+
+    // One cluster per point leaves nothing out of place
+    let exact = KMeans::new(6).fit(&points).unwrap();
+    assert_eq!(exact.inertia(&points), 0.0);
+}
+
+#[test]
+fn test_kmeans_with_fewer_distinct_points_than_clusters() {
+    let points = vec![vec![0.5]; 4];
+    let clustering = KMeans::new(3).fit(&points).unwrap();
+    assert_eq!(clustering.centroids(), &[vec![0.5]]);
+    assert_eq!(clustering.assignments(), &[0, 0, 0, 0]);
+}
+
+#[test]
+fn test_kmeans_errors() {
+    assert_eq!(
+        KMeans::new(0).fit(&[vec![1.0]]),
+        Err(AbstractionError::NoClusters)
+    );
+    assert_eq!(KMeans::new(2).fit(&[]), Err(AbstractionError::NoHands));
+    assert_eq!(
+        KMeans::new(2).fit(&[vec![1.0], vec![2.0], vec![1.0, 2.0]]),
+        Err(AbstractionError::MismatchedPoint(2))
+    );
+}
+
+#[test]
+fn test_river_buckets_are_ordered_by_strength() {
+    let table = BucketBuilder::new(Street::River, Metric::Ehs)
+        .buckets(4)
+        .seed(9)
+        .build_sampled(40)
+        .unwrap();
+    assert_eq!(table.street(), Street::River);
+    assert_eq!(table.metric(), Metric::Ehs);
+    assert_eq!(table.bucket_count(), 4);
+    assert!(table.len() <= 40 && !table.is_empty());
+
+    // In one dimension clusters are intervals, so bucket order follows EHS
+    let centroids: Vec<f64> = table.centroids().iter().map(|c| c[0]).collect();
+    assert!(centroids.windows(2).all(|pair| pair[0] < pair[1]));
+
+    // Hands outside the table go to the nearest centroid
+    let calculator = table.feature_calculator();
+    let (nuts, air) = (hole("Ah Kh"), hole("3c 2d"));
+    let royal = board("Qh Jh Th 4c 5d");
+    assert_eq!(table.bucket(&nuts, &royal).unwrap(), 3);
+    assert_eq!(table.bucket(&air, &board("Ah Kd 9s 8c 5h")).unwrap(), 0);
+    let features = calculator.features(&nuts, &royal).unwrap();
+    assert_eq!(table.classify(&features), 3);
+}
+
+#[test]
+fn test_built_hands_are_looked_up() {
+    let on = "Ks 9d 5d 3s 2s";
+    let table = BucketBuilder::new(Street::River, Metric::EhsSquared)
+        .buckets(2)
+        .build(&deals(&["9s 9c", "9s 9h", "7c 6c", "Qd Jd"], on))
+        .unwrap();
+
+    // With no clubs or hearts on the board, 9s9c and 9s9h are the same hand
+    assert_eq!(table.len(), 3);
+    assert_eq!(table.get(&hole("9s 9c"), &board(on)).unwrap(), Some(1));
+    assert_eq!(table.get(&hole("9h 9s"), &board(on)).unwrap(), Some(1));
+    assert_eq!(table.get(&hole("7c 6c"), &board(on)).unwrap(), Some(0));
+    assert_eq!(table.get(&hole("Ac Ah"), &board(on)).unwrap(), None);
+    assert_eq!(table.iter().count(), 3);
+
+    assert_eq!(
+        table.get(&hole("9s 9c"), &board("Ks 9d 5d 3s")),
+        Err(AbstractionError::WrongStreet {
+            expected: Street::River,
+            found: Street::Turn,
+        })
+    );
+    assert_eq!(
+        BucketBuilder::new(Street::Flop, Metric::Ehs).build(&deals(&["9s 9c"], on)),
+        Err(AbstractionError::WrongStreet {
+            expected: Street::Flop,
+            found: Street::River,
+        })
+    );
+    assert_eq!(
+        BucketBuilder::new(Street::Flop, Metric::Ehs).build(&[]),
+        Err(AbstractionError::NoHands)
+    );
+}
+
+#[test]
+fn test_histograms_separate_draws_from_made_hands() {
+    // Both have similar equity, but the draw is all or nothing
+    let on = "Qh 7h 2c 9s";
+    let table = BucketBuilder::new(Street::Turn, Metric::Histogram)
+        .buckets(2)
+        .bins(5)
+        .build(&deals(&["Ah 3h", "Kh 4h", "Qc 8d", "Qd 8c", "Qs 6d"], on))
+        .unwrap();
+    let draw = table.get(&hole("Ah 3h"), &board(on)).unwrap();
+    let made = table.get(&hole("Qc 8d"), &board(on)).unwrap();
+    assert_ne!(draw, made);
+    assert_eq!(table.get(&hole("Kh 4h"), &board(on)).unwrap(), draw);
+    assert_eq!(table.get(&hole("Qs 6d"), &board(on)).unwrap(), made);
+}
+
+#[test]
+fn test_tables_round_trip_through_text() {
+    let table = BucketBuilder::new(Street::Turn, Metric::Histogram)
+        .buckets(3)
+        .bins(4)
+        .seed(2)
+        .build_sampled(8)
+        .unwrap();
+    let mut saved = Vec::new();
+    table.write_to(&mut saved).unwrap();
+    let text = String::from_utf8(saved.clone()).unwrap();
+    assert!(text.starts_with("buckets 1\nstreet turn\nmetric histogram\nbins 4\n"));
+    assert_eq!(BucketTable::read_from(saved.as_slice()).unwrap(), table);
+
+    let read = |text: &str| BucketTable::read_from(text.as_bytes());
+    assert_eq!(read("buckets 2\n"), Err(AbstractionError::InvalidTable(1)));
+    assert_eq!(read(""), Err(AbstractionError::InvalidTable(1)));
+    let header = "buckets 1\nstreet river\nmetric ehs\nbins 4\nsamples 10\nseed 0\n";
+    assert_eq!(
+        read(&format!("{header}centroids 1\n0.5\nhands 1\n7 0\n"))
+            .unwrap()
+            .len(),
+        1
+    );
+    assert_eq!(
+        read("buckets 1\nstreet showdown\n"),
+        Err(AbstractionError::InvalidTable(2))
+    );
+    assert_eq!(
+        read(&format!("{header}centroids 2\n0.5\n0.1 0.2\n")),
+        Err(AbstractionError::InvalidTable(9))
+    );
+    assert_eq!(
+        read(&format!("{header}centroids 1\n0.5\nhands 1\n7 1\n")),
+        Err(AbstractionError::InvalidTable(10))
+    );
+    assert_eq!(
+        read(&format!("{header}centroids 1\n0.5\nhands 2\n7 0\n")),
+        Err(AbstractionError::InvalidTable(11))
+    );
+    // A table needs a bucket, and huge counts run out of lines rather than memory
+    assert_eq!(
+        read(&format!("{header}centroids 0\nhands 0\n")),
+        Err(AbstractionError::InvalidTable(7))
+    );
+    assert_eq!(
+        read(&format!("{header}centroids {}\n0.5\n", usize::MAX)),
+        Err(AbstractionError::InvalidTable(9))
+    );
+    assert_eq!(
+        read(&format!(
+            "{header}centroids 1\n0.5\nhands {}\n7 0\n",
+            usize::MAX
+        )),
+        Err(AbstractionError::InvalidTable(11))
+    );
+}
+
+#[test]
+fn test_read_errors_are_reported() {
+    struct Failing;
+
+    impl std::io::Read for Failing {
+        fn read(&mut self, _: &mut [u8]) -> std::io::Result<usize> {
+            Err(std::io::ErrorKind::PermissionDenied.into())
+        }
+    }
+
+    assert_eq!(
+        BucketTable::read_from(std::io::BufReader::new(Failing)),
+        Err(AbstractionError::Io(std::io::ErrorKind::PermissionDenied))
+    );
+}
+
+#[test]
+fn test_streets() {
+    assert_eq!(Street::from_board(&[]), Ok(Street::Preflop));
+    assert_eq!(Street::from_board(&board("Ah Kd 9s")), Ok(Street::Flop));
+    assert_eq!(
+        Street::from_board(&board("Ah")),
+        Err(AbstractionError::InvalidBoardSize(1))
+    );
+    let lengths: Vec<usize> = Street::ALL.iter().map(Street::board_len).collect();
+    assert_eq!(lengths, [0, 3, 4, 5]);
+    assert_eq!(Street::Turn.to_string(), "turn");
+    assert_eq!(Metric::EhsSquared.to_string(), "ehs2");
+    assert_eq!(Metric::Histogram.distance(), Distance::EarthMovers);
+}
@@ -0,0 +1,278 @@
+use crate::range::{RANKS_DESCENDING, Range, StartingHand};
+
+/// Width of a cell's contents inside the box-drawn grid
+const CELL_WIDTH: usize = 6;
+
+/// ANSI 256-color backgrounds from red (lowest value) through yellow to green (highest)
+const SHADES: [u8; 11] = [196, 202, 208, 214, 220, 226, 190, 154, 118, 82, 46];
+
+/// How the value of each hand is written in its cell
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ValueFormat {
+    /// As a whole percentage, so that 0.5 is written `50%`
+    Percent,
+    /// As a number with up to this many decimal places, fewer when the cell is too narrow
+    Decimal(usize),
+}
+
+/// The 13x13 grid of starting hands, with a value for some or all of them
+///
+/// Pairs run down the diagonal, suited hands sit above it and offsuit hands below, with aces
+/// in the top row and left column. A grid can be drawn with box-drawing characters, shaded
+/// with ANSI colors by value, or written as plain text for logs.
+///
+/// # Examples
+///
+/// ```
+/// use cards::display::HandGrid;
+/// use cards::range::Range;
+///
+/// let range: Range = "QQ+, AKs, AKo:0.5".parse().unwrap();
+/// let grid = HandGrid::from_range(&range);
+/// println!("{}", grid.render());
+/// // Output (first rows):
+/// // ┌──────┬──────┬──────┬──────┬ ...
+/// // │ AA   │ AKs  │ AQs  │ AJs  │ ...
+/// // │  100%│  100%│      │      │ ...
+/// // ├──────┼──────┼──────┼──────┼ ...
+/// // │ AKo  │ KK   │ KQs  │ KJs  │ ...
+/// // │   50%│  100%│      │      │ ...
+/// ```
+#[derive(Debug, Clone, PartialEq)]
+pub struct HandGrid {
+    values: [[Option<f64>; 13]; 13],
+    format: ValueFormat,
+    colored: bool,
+}
+
+impl HandGrid {
+    /// Creates a grid holding how much of each starting hand a range contains
+    ///
+    /// A hand's value is the average weight of its combos, so a hand with half of its combos
+    /// at full weight shows `50%`. Hands the range does not contain are left empty.
+    pub fn from_range(range: &Range) -> Self {
+        Self::from_values(|hand| {
+            let combos = hand.combos();
+            let total: f64 = combos.iter().map(|combo| range.weight(combo)).sum();
+            (total > 0.0).then(|| total / combos.len() as f64)
+        })
+        .format(ValueFormat::Percent)
+    }
+
+    /// Creates a grid from a value for each starting hand, such as its equity, frequency or
+    /// EV; hands given `None` are left empty
+    ///
+    /// Values are written as numbers with two decimal places unless `format` says otherwise.
+    pub fn from_values<F>(mut value: F) -> Self
+    where
+        F: FnMut(StartingHand) -> Option<f64>,
+    {
+        let mut values = [[None; 13]; 13];
+        for (row, cells) in values.iter_mut().enumerate() {
+            for (column, cell) in cells.iter_mut().enumerate() {
+                *cell = value(hand_at(row, column));
+            }
+        }
+        Self {
+            values,
+            format: ValueFormat::Decimal(2),
+            colored: false,
+        }
+    }
+
+    /// Sets how values are written, with at most as many decimal places as fit in a cell
+    pub fn format(mut self, format: ValueFormat) -> Self {
+        self.format = match format {
+            ValueFormat::Decimal(places) => ValueFormat::Decimal(places.min(CELL_WIDTH)),
+            ValueFormat::Percent => ValueFormat::Percent,
+        };
+        self
+    }
+
+    /// Shades each cell of `render` with an ANSI background color, from red for the lowest
+    /// value to green for the highest
+    pub fn colored(mut self, colored: bool) -> Self {
+        self.colored = colored;
+        self
+    }
+
+    /// The value held for a starting hand
+    pub fn value(&self, hand: StartingHand) -> Option<f64> {
+        let (row, column) = position(hand);
+        self.values[row][column]
+    }
+
+    /// Draws the grid with box-drawing characters, two lines per hand: its name and its value
+    pub fn render(&self) -> String {
+        let border = |left: &str, middle: &str, right: &str| {
+            let line = vec!["─".repeat(CELL_WIDTH); 13].join(middle);
+            format!("{left}{line}{right}")
+        };
+        let bounds = self.bounds();
+
+        let mut lines = vec![border("┌", "┬", "┐")];
+        for row in 0..13 {
+            if row > 0 {
+                lines.push(border("├", "┼", "┤"));
+            }
+            let mut names = String::from("│");
+            let mut values = String::from("│");
+            for column in 0..13 {
+                let value = self.values[row][column];
+                let name = format!(
+                    " {:<width$}",
+                    hand_at(row, column).to_string(),
+                    width = CELL_WIDTH - 1
+                );
+                let text = match value {
+                    Some(value) => format!("{:>CELL_WIDTH$}", self.write(value, CELL_WIDTH)),
+                    None => " ".repeat(CELL_WIDTH),
+                };
+                match (self.colored, value) {
+                    (true, Some(value)) => {
+                        let shade = shade(value, bounds);
+                        names.push_str(&format!("\x1b[30;48;5;{shade}m{name}\x1b[0m"));
+                        values.push_str(&format!("\x1b[30;48;5;{shade}m{text}\x1b[0m"));
+                    }
+                    _ => {
+                        names.push_str(&name);
+                        values.push_str(&text);
+                    }
+                }
+                names.push('│');
+                values.push('│');
+            }
+            lines.push(names);
+            lines.push(values);
+        }
+        lines.push(border("└", "┴", "┘"));
+
+        lines.join("\n")
+    }
+
+    /// Writes the grid as plain text, one row of hands per line with `-` for empty hands
+    ///
+    /// Each cell is the hand's name followed by its value, e.g. `AKs 50%`.
+    pub fn render_plain(&self) -> String {
+        let width = self
+            .values
+            .iter()
+            .flatten()
+            .flatten()
+            .map(|&value| self.write(value, usize::MAX).len())
+            .max()
+            .unwrap_or(1);
+
+        (0..13)
+            .map(|row| {
+                (0..13)
+                    .map(|column| {
+                        let value = match self.values[row][column] {
+                            Some(value) => self.write(value, usize::MAX),
+                            None => String::from("-"),
+                        };
+                        format!("{:<3} {value:>width$}", hand_at(row, column).to_string())
+                    })
+                    .collect::<Vec<_>>()
+                    .join("  ")
+            })
+            .collect::<Vec<_>>()
+            .join("\n")
+    }
+
+    // Writes a value in the grid's format, dropping decimal places until it fits `width`,
+    // then abbreviating to thousands, millions and so on, and filling the cell with `#` if
+    // even that is too wide
+    fn write(&self, value: f64, width: usize) -> String {
+        let (value, places, unit) = match self.format {
+            ValueFormat::Percent => (100.0 * value, 0, "%"),
+            ValueFormat::Decimal(places) => (value, places, ""),
+        };
+        let exact = (0..=places)
+            .rev()
+            .map(|places| format!("{value:.places$}{unit}"));
+        let abbreviated = ["k", "M", "G", "T"]
+            .into_iter()
+            .scan(value, |scaled, suffix| {
+                *scaled /= 1_000.0;
+                Some(format!("{:.0}{suffix}{unit}", *scaled))
+            });
+        exact
+            .chain(abbreviated)
+            .find(|text| text.len() <= width)
+            .unwrap_or_else(|| "#".repeat(width))
+    }
+
+    // The range of values to shade between: 0 to 1 for percentages, else the values present
+    fn bounds(&self) -> (f64, f64) {
+        if self.format == ValueFormat::Percent {
+            return (0.0, 1.0);
+        }
+        self.values
+            .iter()
+            .flatten()
+            .flatten()
+            .fold((f64::INFINITY, f64::NEG_INFINITY), |(low, high), &value| {
+                (low.min(value), high.max(value))
+            })
+    }
+}
+
+/// Extension trait for `Range` to draw it as the 13x13 grid of starting hands
+///
+/// # Examples
+///
+/// ```
+/// use cards::display::RangeGridDisplay;
+/// use cards::range::Range;
+///
+/// let range: Range = "TT+, AQs+, AKo".parse().unwrap();
+/// println!("{}", range.to_grid());
+/// ```
+pub trait RangeGridDisplay {
+    /// Returns the range drawn as a box-drawn grid, see `HandGrid::render`
+    fn to_grid(&self) -> String;
+
+    /// Returns the range written as a plain-text grid, see `HandGrid::render_plain`
+    fn to_plain_grid(&self) -> String;
+}
+
+impl RangeGridDisplay for Range {
+    fn to_grid(&self) -> String {
+        HandGrid::from_range(self).render()
+    }
+
+    fn to_plain_grid(&self) -> String {
+        HandGrid::from_range(self).render_plain()
+    }
+}
+
+// The starting hand in a cell: suited above the diagonal, offsuit below
+fn hand_at(row: usize, column: usize) -> StartingHand {
+    let (first, second) = (RANKS_DESCENDING[row], RANKS_DESCENDING[column]);
+    StartingHand::new(first, second, column > row)
+}
+
+fn position(hand: StartingHand) -> (usize, usize) {
+    let index = |rank| {
+        RANKS_DESCENDING
+            .iter()
+            .position(|&r| r == rank)
+            .unwrap_or(0)
+    };
+    let (high, low) = (index(hand.high()), index(hand.low()));
+    if hand.is_suited() {
+        (high, low)
+    } else {
+        (low, high)
+    }
+}
+
+fn shade(value: f64, (low, high): (f64, f64)) -> u8 {
+    let fraction = if high > low {
+        ((value - low) / (high - low)).clamp(0.0, 1.0)
+    } else {
+        1.0
+    };
+    SHADES[(fraction * (SHADES.len() - 1) as f64).round() as usize]
+}
@@ -1,9 +1,11 @@
 use crate::{Card, Rank, Suit};
 
 mod cards_display;
+mod hand_grid;
 mod large_cards_display;
 
 pub use cards_display::SmallCardsDisplay;
+pub use hand_grid::{HandGrid, RangeGridDisplay, ValueFormat};
 pub use large_cards_display::LargeCardsDisplay;
 
 #[cfg(test)]
@@ -0,0 +1,139 @@
+use crate::Rank;
+use crate::display::{HandGrid, RangeGridDisplay, ValueFormat};
+use crate::range::{Range, StartingHand};
+
+fn range(notation: &str) -> Range {
+    notation.parse().unwrap()
+}
+
+#[test]
+fn test_grid_has_thirteen_rows_of_boxes() {
+    let grid = range("AA").to_grid();
+    let lines: Vec<&str> = grid.lines().collect();
+
+    // A border above, below and between rows, and two lines per row
+    assert_eq!(lines.len(), 1 + 13 * 2 + 12 + 1);
+    assert!(lines[0].starts_with("┌──────┬"));
+    assert!(lines[0].ends_with("┬──────┐"));
+    assert!(lines[3].starts_with("├──────┼"));
+    assert!(lines[lines.len() - 1].starts_with("└──────┴"));
+    assert!(lines.iter().all(|l| l.chars().count() == 13 * 7 + 1));
+}
+
+#[test]
+fn test_grid_places_pairs_suited_and_offsuit_hands() {
+    let grid = range("AA").to_grid();
+    let lines: Vec<&str> = grid.lines().collect();
+
+    assert!(lines[1].starts_with("│ AA   │ AKs  │ AQs  │"));
+    assert!(lines[4].starts_with("│ AKo  │ KK   │ KQs  │"));
+    assert!(lines[lines.len() - 3].ends_with("│ 32o  │ 22   │"));
+}
+
+#[test]
+fn test_grid_shows_range_weights_as_percentages() {
+    let grid = range("AA, AKs:0.5, AhKd").to_grid();
+    let lines: Vec<&str> = grid.lines().collect();
+
+    assert!(lines[2].starts_with("│  100%│   50%│      │"));
+    // One of the twelve offsuit combos
+    assert!(lines[5].starts_with("│    8%│      │"));
+}
+
+#[test]
+fn test_from_range_averages_combo_weights() {
+    let grid = HandGrid::from_range(&range("KK:0.5, AhKh"));
+
+    assert_eq!(grid.value(StartingHand::Pair(Rank::King)), Some(0.5));
+    assert_eq!(
+        grid.value(StartingHand::Suited(Rank::Ace, Rank::King)),
+        Some(0.25)
+    );
+    assert_eq!(grid.value(StartingHand::Pair(Rank::Ace)), None);
+}
+
+#[test]
+fn test_from_values_keeps_each_hand_value() {
+    let grid = HandGrid::from_values(|hand| Some(hand.combo_count() as f64));
+
+    for hand in StartingHand::all() {
+        assert_eq!(grid.value(hand), Some(hand.combo_count() as f64));
+    }
+}
+
+#[test]
+fn test_decimal_values_drop_places_to_fit() {
+    let grid =
+        HandGrid::from_values(|hand| (hand == StartingHand::Pair(Rank::Ace)).then_some(-123.456))
+            .format(ValueFormat::Decimal(3));
+    let lines: Vec<String> = grid.render().lines().map(String::from).collect();
+
+    assert!(lines[2].starts_with("│-123.5│"));
+}
+
+#[test]
+fn test_decimal_places_are_capped_at_the_cell_width() {
+    let grid = HandGrid::from_values(|hand| (hand == StartingHand::Pair(Rank::Ace)).then_some(0.5))
+        .format(ValueFormat::Decimal(usize::MAX));
+
+    assert!(
+        grid.render()
+            .lines()
+            .nth(2)
+            .unwrap()
+            .starts_with("│0.5000│")
+    );
+    assert!(grid.render_plain().starts_with("AA  0.500000"));
+}
+
+#[test]
+fn test_values_too_wide_for_a_cell_are_abbreviated() {
+    let value = |hand| match hand {
+        StartingHand::Pair(Rank::Ace) => Some(1_234_567.0),
+        StartingHand::Pair(Rank::King) => Some(-987_654.3),
+        StartingHand::Pair(Rank::Queen) => Some(1e30),
+        _ => None,
+    };
+    let grid = HandGrid::from_values(value).render();
+    let lines: Vec<&str> = grid.lines().collect();
+
+    assert!(lines[2].starts_with("│ 1235k│"));
+    assert!(lines[5].contains("│ -988k│"));
+    assert!(lines[8].contains("│######│"));
+    assert!(lines.iter().all(|l| l.chars().count() == 13 * 7 + 1));
+
+    let percent = HandGrid::from_values(value)
+        .format(ValueFormat::Percent)
+        .render();
+    assert!(percent.lines().nth(2).unwrap().starts_with("│ 123M%│"));
+    assert!(percent.lines().all(|l| l.chars().count() == 13 * 7 + 1));
+}
+
+#[test]
+fn test_colored_grid_shades_only_filled_cells() {
+    let grid = HandGrid::from_range(&range("AA, KK:0.5"))
+        .colored(true)
+        .render();
+
+    // Two lines for each of the two hands
+    assert_eq!(grid.matches("\x1b[0m").count(), 4);
+    assert!(grid.contains("\x1b[30;48;5;46m AA   \x1b[0m"));
+    assert!(grid.contains("\x1b[30;48;5;226m KK   \x1b[0m"));
+}
+
+#[test]
+fn test_uncolored_grid_has_no_escape_codes() {
+    assert!(!range("22+").to_grid().contains('\x1b'));
+    assert!(!range("22+").to_plain_grid().contains('\x1b'));
+}
+
+#[test]
+fn test_plain_grid() {
+    let plain = range("AA, AKs:0.5").to_plain_grid();
+    let lines: Vec<&str> = plain.lines().collect();
+
+    assert_eq!(lines.len(), 13);
+    assert!(lines[0].starts_with("AA  100%  AKs  50%  AQs    -  "));
+    assert!(lines[12].ends_with("22     -"));
+    assert!(!plain.contains('│'));
+}
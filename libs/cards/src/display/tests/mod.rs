@@ -1,5 +1,7 @@
+mod hand_grid;
 mod large_cards;
 mod props;
+mod props_hand_grid;
 mod props_large_cards;
 mod props_small_cards;
 mod small_cards;
@@ -0,0 +1,38 @@
+use crate::display::HandGrid;
+use crate::range::{Range, StartingHand};
+use proptest::prelude::*;
+
+// A range of random starting hands at random weights
+fn arb_range() -> impl Strategy<Value = Range> {
+    let hands: Vec<StartingHand> = StartingHand::all().collect();
+    prop::collection::vec((prop::sample::select(hands), 0.01f64..=1.0), 0..30).prop_map(|hands| {
+        let mut range = Range::new();
+        for (hand, weight) in hands {
+            range.insert_hand(hand, weight);
+        }
+        range
+    })
+}
+
+proptest! {
+    // Test that every hand in the range, and only those, has a value in the grid
+    #[test]
+    fn grid_holds_range_hands(range in arb_range()) {
+        let grid = HandGrid::from_range(&range);
+        for hand in StartingHand::all() {
+            let held = hand.combos().iter().any(|combo| range.contains(combo));
+            prop_assert_eq!(grid.value(hand).is_some(), held);
+        }
+    }
+
+    // Test that every line of the drawn grid has the same width, shaded or not
+    #[test]
+    fn grid_lines_have_equal_width(range in arb_range(), colored in any::<bool>()) {
+        let rendered = HandGrid::from_range(&range).colored(colored).render();
+        let plain: String = rendered.split('\x1b').enumerate().map(|(i, part)| {
+            if i == 0 { part } else { part.split_once('m').map_or("", |(_, rest)| rest) }
+        }).collect();
+        let widths: Vec<usize> = plain.lines().map(|l| l.chars().count()).collect();
+        prop_assert!(widths.iter().all(|&w| w == widths[0]));
+    }
+}
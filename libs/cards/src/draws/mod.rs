@@ -0,0 +1,261 @@
+use std::fmt;
+
+use crate::{Card, Deck, Suit};
+
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
+#[cfg(test)]
+mod tests;
+
+/// A drawing hand that still needs help from the turn and/or river
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub enum Draw {
+    /// Four cards to a flush, at least one of them in the hole
+    Flush(Suit),
+    /// Four consecutive ranks that can be completed at either end
+    OpenEndedStraight,
+    /// Two inside straight draws at once, e.g. 9-7-6-5-3
+    DoubleGutshot,
+    /// A straight that only one rank completes
+    Gutshot,
+    /// Three cards to a flush on the flop, needing both the turn and river
+    BackdoorFlush(Suit),
+    /// Three cards to a straight on the flop, needing both the turn and river
+    BackdoorStraight,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum DrawError {
+    /// Draws only make sense on the flop, turn or river
+    InvalidBoardSize(usize),
+    /// The same card was given more than once across hole, board and dead cards
+    DuplicateCard(Card),
+    /// A card without a suit (a joker) was given
+    UnsuitedCard(Card),
+}
+
+impl fmt::Display for DrawError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            DrawError::InvalidBoardSize(size) => {
+                write!(f, "board must have 3, 4 or 5 cards, got {size}")
+            }
+            DrawError::DuplicateCard(card) => write!(f, "card {card:?} appears more than once"),
+            DrawError::UnsuitedCard(card) => write!(f, "card {card:?} has no suit"),
+        }
+    }
+}
+
+impl std::error::Error for DrawError {}
+
+/// The result of analysing a hand for draws
+///
+/// # Examples
+///
+/// ```
+/// use cards::{Card, Rank, Suit};
+/// use cards::draws::{analyze, Draw};
+///
+/// let hole = [
+///     Card::new(Rank::Ace, Some(Suit::Hearts)),
+///     Card::new(Rank::King, Some(Suit::Hearts)),
+/// ];
+/// let board = [
+///     Card::new(Rank::Seven, Some(Suit::Hearts)),
+///     Card::new(Rank::Two, Some(Suit::Hearts)),
+///     Card::new(Rank::Nine, Some(Suit::Clubs)),
+/// ];
+///
+/// let analysis = analyze(&hole, &board, &[]).unwrap();
+/// assert_eq!(analysis.draws(), &[Draw::Flush(Suit::Hearts)]);
+/// assert_eq!(analysis.outs().len(), 9);
+/// ```
+#[derive(Debug, Clone, PartialEq)]
+pub struct DrawAnalysis {
+    draws: Vec<Draw>,
+    outs: Vec<Card>,
+    unseen: usize,
+    cards_to_come: usize,
+}
+
+impl DrawAnalysis {
+    pub fn draws(&self) -> &[Draw] {
+        &self.draws
+    }
+
+    /// The cards still in the deck that complete one of the one-card draws
+    pub fn outs(&self) -> &[Card] {
+        &self.outs
+    }
+
+    /// Number of cards that are neither in the hole, on the board nor dead
+    pub fn unseen(&self) -> usize {
+        self.unseen
+    }
+
+    /// Probability of hitting an out on the next card, or `None` unless on the flop
+    pub fn hit_by_turn(&self) -> Option<f64> {
+        if self.cards_to_come == 2 {
+            Some(self.hit_probability(1))
+        } else {
+            None
+        }
+    }
+
+    /// Probability of hitting at least one out by the river
+    pub fn hit_by_river(&self) -> f64 {
+        self.hit_probability(self.cards_to_come)
+    }
+
+    fn hit_probability(&self, cards: usize) -> f64 {
+        let outs = self.outs.len();
+        if cards == 0 || outs == 0 || self.unseen == 0 {
+            return 0.0;
+        }
+
+        // Probability of missing with every one of the next `cards` cards
+        let mut miss = 1.0;
+        for i in 0..cards.min(self.unseen) {
+            miss *= (self.unseen - outs).saturating_sub(i) as f64 / (self.unseen - i) as f64;
+        }
+        1.0 - miss
+    }
+}
+
+/// Analyses two hole cards against a flop, turn or river board for draws
+///
+/// `dead` cards are known to be out of play (folded or exposed) and can never be outs.
+pub fn analyze(hole: &[Card; 2], board: &[Card], dead: &[Card]) -> Result<DrawAnalysis, DrawError> {
+    if !(3..=5).contains(&board.len()) {
+        return Err(DrawError::InvalidBoardSize(board.len()));
+    }
+
+    let mut deck = Deck::new();
+    for card in hole.iter().chain(board).chain(dead) {
+        if card.suit.is_none() {
+            return Err(DrawError::UnsuitedCard(*card));
+        }
+        if !deck.remove(card) {
+            return Err(DrawError::DuplicateCard(*card));
+        }
+    }
+
+    let cards_to_come = 5 - board.len();
+    let mut draws = Vec::new();
+    let mut flush_suits = Vec::new();
+    let mut straight_ranks = 0u16;
+
+    if cards_to_come > 0 {
+        for suit in Suit::ALL {
+            let on_board = board.iter().filter(|c| c.suit == Some(suit)).count();
+            let in_hole = hole.iter().filter(|c| c.suit == Some(suit)).count();
+            match (in_hole, on_board + in_hole) {
+                (0, _) => {}
+                (_, 4) => {
+                    draws.push(Draw::Flush(suit));
+                    flush_suits.push(suit);
+                }
+                (_, 3) if cards_to_come == 2 => {
+                    draws.push(Draw::BackdoorFlush(suit));
+                }
+                _ => {}
+            }
+        }
+        if made_flush(hole, board) {
+            draws.retain(|d| !matches!(d, Draw::Flush(_) | Draw::BackdoorFlush(_)));
+            flush_suits.clear();
+        }
+
+        let board_mask = rank_mask(board);
+        let known = board_mask | rank_mask(hole);
+        let hole_only = known & !board_mask;
+
+        if !windows().any(|window| known & window == window) {
+            straight_ranks = completing_ranks(known, hole_only);
+            match straight_ranks.count_ones() {
+                0 => {
+                    if cards_to_come == 2
+                        && windows().any(|w| (known & w).count_ones() == 3 && hole_only & w != 0)
+                    {
+                        draws.push(Draw::BackdoorStraight);
+                    }
+                }
+                1 => draws.push(Draw::Gutshot),
+                _ if is_open_ended(known, hole_only, straight_ranks) => {
+                    draws.push(Draw::OpenEndedStraight)
+                }
+                _ => draws.push(Draw::DoubleGutshot),
+            }
+        }
+    }
+
+    let outs = deck
+        .cards()
+        .iter()
+        .filter(|card| {
+            card.suit.is_some_and(|suit| flush_suits.contains(&suit))
+                || straight_ranks & (1 << card.rank.poker_value()) != 0
+        })
+        .copied()
+        .collect();
+
+    Ok(DrawAnalysis {
+        draws,
+        outs,
+        unseen: deck.cards().iter().filter(|c| c.suit.is_some()).count(),
+        cards_to_come,
+    })
+}
+
+fn made_flush(hole: &[Card; 2], board: &[Card]) -> bool {
+    Suit::ALL.iter().any(|&suit| {
+        hole.iter()
+            .chain(board)
+            .filter(|c| c.suit == Some(suit))
+            .count()
+            >= 5
+    })
+}
+
+// Bit `v` is set for each poker value `v` present, with aces also setting bit 1
+fn rank_mask(cards: &[Card]) -> u16 {
+    cards.iter().fold(0, |mask, card| {
+        let value = card.rank.poker_value();
+        let mask = mask | 1 << value;
+        if value == 14 { mask | 1 << 1 } else { mask }
+    })
+}
+
+// The ten five-rank straight windows, from the wheel up to broadway
+fn windows() -> impl Iterator<Item = u16> {
+    (1..=10).map(|low| 0b11111 << low)
+}
+
+// Ranks that complete a straight using at least one rank only held in the hole
+fn completing_ranks(known: u16, hole_only: u16) -> u16 {
+    windows()
+        .filter(|&w| (known & w).count_ones() == 4 && hole_only & w != 0)
+        .map(|w| {
+            let missing = w & !known;
+            // The low ace is the same card as the high ace
+            if missing == 1 << 1 { 1 << 14 } else { missing }
+        })
+        .fold(0, |mask, missing| mask | missing)
+}
+
+// Four consecutive ranks with both ends completing the straight
+fn is_open_ended(known: u16, hole_only: u16, completing: u16) -> bool {
+    (1..=11).any(|low| {
+        let run = 0b1111u16 << low;
+        let below = low - 1;
+        let above = low + 4;
+        let completes = |value: u16| match value {
+            1 => completing & 1 << 14 != 0,
+            2..=14 => completing & 1 << value != 0,
+            _ => false,
+        };
+        known & run == run && hole_only & run != 0 && completes(below) && completes(above)
+    })
+}
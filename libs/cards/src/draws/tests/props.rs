@@ -0,0 +1,55 @@
+use super::super::*;
+use crate::Deck;
+use proptest::prelude::*;
+
+// Picks distinct cards from a standard deck: two hole cards followed by the board
+fn arb_hand(board_len: usize) -> impl Strategy<Value = Vec<Card>> {
+    let deck: Vec<Card> = Deck::new()
+        .cards()
+        .iter()
+        .filter(|c| c.suit.is_some())
+        .copied()
+        .collect();
+    Just(deck)
+        .prop_shuffle()
+        .prop_map(move |cards| cards[..2 + board_len].to_vec())
+}
+
+proptest! {
+    #[test]
+    fn outs_are_unseen_and_distinct(cards in (3usize..=5).prop_flat_map(arb_hand)) {
+        let hole = [cards[0], cards[1]];
+        let analysis = analyze(&hole, &cards[2..], &[]).unwrap();
+
+        for out in analysis.outs() {
+            prop_assert!(!cards.contains(out));
+        }
+        let mut outs = analysis.outs().to_vec();
+        outs.dedup();
+        prop_assert_eq!(outs.len(), analysis.outs().len());
+        prop_assert_eq!(analysis.unseen(), 52 - cards.len());
+    }
+
+    #[test]
+    fn probabilities_are_bounded(cards in arb_hand(3)) {
+        let hole = [cards[0], cards[1]];
+        let analysis = analyze(&hole, &cards[2..], &[]).unwrap();
+
+        let by_turn = analysis.hit_by_turn().unwrap();
+        let by_river = analysis.hit_by_river();
+        prop_assert!((0.0..=1.0).contains(&by_turn));
+        prop_assert!((0.0..=1.0).contains(&by_river));
+        prop_assert!(by_river >= by_turn);
+    }
+
+    #[test]
+    fn draws_with_outs_have_one_card_draws(cards in arb_hand(4)) {
+        let hole = [cards[0], cards[1]];
+        let analysis = analyze(&hole, &cards[2..], &[]).unwrap();
+
+        let one_card_draw = analysis.draws().iter().any(|d| {
+            !matches!(d, Draw::BackdoorFlush(_) | Draw::BackdoorStraight)
+        });
+        prop_assert_eq!(one_card_draw, !analysis.outs().is_empty());
+    }
+}
@@ -0,0 +1,317 @@
+use super::super::*;
+use crate::{Card, Rank, Suit};
+
+fn card(rank: Rank, suit: Suit) -> Card {
+    Card::new(rank, Some(suit))
+}
+
+#[test]
+fn test_flush_draw_has_nine_outs() {
+    let hole = [
+        card(Rank::Ace, Suit::Hearts),
+        card(Rank::King, Suit::Hearts),
+    ];
+    let board = [
+        card(Rank::Seven, Suit::Hearts),
+        card(Rank::Two, Suit::Hearts),
+        card(Rank::Nine, Suit::Clubs),
+    ];
+
+    let analysis = analyze(&hole, &board, &[]).unwrap();
+    assert_eq!(analysis.draws(), &[Draw::Flush(Suit::Hearts)]);
+    assert_eq!(analysis.outs().len(), 9);
+    assert!(analysis.outs().iter().all(|c| c.suit == Some(Suit::Hearts)));
+    assert_eq!(analysis.unseen(), 47);
+}
+
+#[test]
+fn test_flush_draw_probabilities_on_flop() {
+    let hole = [
+        card(Rank::Ace, Suit::Hearts),
+        card(Rank::King, Suit::Hearts),
+    ];
+    let board = [
+        card(Rank::Seven, Suit::Hearts),
+        card(Rank::Two, Suit::Hearts),
+        card(Rank::Nine, Suit::Clubs),
+    ];
+
+    let analysis = analyze(&hole, &board, &[]).unwrap();
+    let by_turn = analysis.hit_by_turn().unwrap();
+    let by_river = analysis.hit_by_river();
+    assert!((by_turn - 9.0 / 47.0).abs() < 1e-12);
+    assert!((by_river - (1.0 - (38.0 * 37.0) / (47.0 * 46.0))).abs() < 1e-12);
+}
+
+#[test]
+fn test_open_ended_straight_draw() {
+    let hole = [
+        card(Rank::Nine, Suit::Spades),
+        card(Rank::Eight, Suit::Hearts),
+    ];
+    let board = [
+        card(Rank::Seven, Suit::Clubs),
+        card(Rank::Six, Suit::Diamonds),
+        card(Rank::King, Suit::Spades),
+    ];
+
+    let analysis = analyze(&hole, &board, &[]).unwrap();
+    assert_eq!(analysis.draws(), &[Draw::OpenEndedStraight]);
+    assert_eq!(analysis.outs().len(), 8);
+    assert!(
+        analysis
+            .outs()
+            .iter()
+            .all(|c| c.rank == Rank::Ten || c.rank == Rank::Five)
+    );
+}
+
+#[test]
+fn test_gutshot() {
+    let hole = [
+        card(Rank::Nine, Suit::Spades),
+        card(Rank::Eight, Suit::Hearts),
+    ];
+    let board = [
+        card(Rank::Six, Suit::Clubs),
+        card(Rank::Five, Suit::Diamonds),
+        card(Rank::King, Suit::Spades),
+    ];
+
+    let analysis = analyze(&hole, &board, &[]).unwrap();
+    assert_eq!(analysis.draws(), &[Draw::Gutshot]);
+    assert_eq!(analysis.outs().len(), 4);
+    assert!(analysis.outs().iter().all(|c| c.rank == Rank::Seven));
+}
+
+#[test]
+fn test_double_gutshot() {
+    let hole = [
+        card(Rank::Nine, Suit::Spades),
+        card(Rank::Seven, Suit::Hearts),
+    ];
+    let board = [
+        card(Rank::Six, Suit::Clubs),
+        card(Rank::Five, Suit::Diamonds),
+        card(Rank::Three, Suit::Spades),
+    ];
+
+    let analysis = analyze(&hole, &board, &[]).unwrap();
+    assert_eq!(analysis.draws(), &[Draw::DoubleGutshot]);
+    assert_eq!(analysis.outs().len(), 8);
+}
+
+#[test]
+fn test_wheel_draw_is_a_gutshot() {
+    let hole = [card(Rank::Ace, Suit::Spades), card(Rank::Two, Suit::Hearts)];
+    let board = [
+        card(Rank::Three, Suit::Clubs),
+        card(Rank::Four, Suit::Diamonds),
+        card(Rank::King, Suit::Spades),
+    ];
+
+    let analysis = analyze(&hole, &board, &[]).unwrap();
+    assert_eq!(analysis.draws(), &[Draw::Gutshot]);
+    assert!(analysis.outs().iter().all(|c| c.rank == Rank::Five));
+}
+
+#[test]
+fn test_broadway_draw_needs_a_ten() {
+    let hole = [
+        card(Rank::Ace, Suit::Spades),
+        card(Rank::King, Suit::Hearts),
+    ];
+    let board = [
+        card(Rank::Queen, Suit::Clubs),
+        card(Rank::Jack, Suit::Diamonds),
+        card(Rank::Four, Suit::Spades),
+    ];
+
+    let analysis = analyze(&hole, &board, &[]).unwrap();
+    assert_eq!(analysis.draws(), &[Draw::Gutshot]);
+    assert!(analysis.outs().iter().all(|c| c.rank == Rank::Ten));
+}
+
+#[test]
+fn test_combo_draw_does_not_double_count_outs() {
+    let hole = [
+        card(Rank::Nine, Suit::Hearts),
+        card(Rank::Eight, Suit::Hearts),
+    ];
+    let board = [
+        card(Rank::Seven, Suit::Hearts),
+        card(Rank::Six, Suit::Clubs),
+        card(Rank::Two, Suit::Hearts),
+    ];
+
+    let analysis = analyze(&hole, &board, &[]).unwrap();
+    assert!(analysis.draws().contains(&Draw::Flush(Suit::Hearts)));
+    assert!(analysis.draws().contains(&Draw::OpenEndedStraight));
+    // 9 flush outs plus 6 straight outs that are not hearts
+    assert_eq!(analysis.outs().len(), 15);
+}
+
+#[test]
+fn test_backdoor_draws_on_flop() {
+    let hole = [
+        card(Rank::Nine, Suit::Hearts),
+        card(Rank::Eight, Suit::Hearts),
+    ];
+    let board = [
+        card(Rank::Seven, Suit::Hearts),
+        card(Rank::King, Suit::Clubs),
+        card(Rank::Two, Suit::Spades),
+    ];
+
+    let analysis = analyze(&hole, &board, &[]).unwrap();
+    assert!(
+        analysis
+            .draws()
+            .contains(&Draw::BackdoorFlush(Suit::Hearts))
+    );
+    assert!(analysis.draws().contains(&Draw::BackdoorStraight));
+    assert!(analysis.outs().is_empty());
+}
+
+#[test]
+fn test_no_backdoor_draws_on_turn() {
+    let hole = [
+        card(Rank::Nine, Suit::Hearts),
+        card(Rank::Eight, Suit::Hearts),
+    ];
+    let board = [
+        card(Rank::Seven, Suit::Hearts),
+        card(Rank::King, Suit::Clubs),
+        card(Rank::Two, Suit::Spades),
+        card(Rank::Two, Suit::Diamonds),
+    ];
+
+    let analysis = analyze(&hole, &board, &[]).unwrap();
+    assert!(analysis.draws().is_empty());
+    assert_eq!(analysis.hit_by_turn(), None);
+    assert_eq!(analysis.hit_by_river(), 0.0);
+}
+
+#[test]
+fn test_board_only_draws_are_ignored() {
+    let hole = [
+        card(Rank::King, Suit::Spades),
+        card(Rank::King, Suit::Clubs),
+    ];
+    let board = [
+        card(Rank::Eight, Suit::Hearts),
+        card(Rank::Seven, Suit::Hearts),
+        card(Rank::Six, Suit::Hearts),
+        card(Rank::Five, Suit::Hearts),
+    ];
+
+    let analysis = analyze(&hole, &board, &[]).unwrap();
+    assert!(analysis.draws().is_empty());
+    assert!(analysis.outs().is_empty());
+}
+
+#[test]
+fn test_made_flush_is_not_a_draw() {
+    let hole = [
+        card(Rank::Ace, Suit::Hearts),
+        card(Rank::King, Suit::Hearts),
+    ];
+    let board = [
+        card(Rank::Seven, Suit::Hearts),
+        card(Rank::Two, Suit::Hearts),
+        card(Rank::Nine, Suit::Hearts),
+    ];
+
+    let analysis = analyze(&hole, &board, &[]).unwrap();
+    assert!(analysis.draws().is_empty());
+}
+
+#[test]
+fn test_dead_cards_are_not_outs() {
+    let hole = [
+        card(Rank::Ace, Suit::Hearts),
+        card(Rank::King, Suit::Hearts),
+    ];
+    let board = [
+        card(Rank::Seven, Suit::Hearts),
+        card(Rank::Two, Suit::Hearts),
+        card(Rank::Nine, Suit::Clubs),
+    ];
+    let dead = [
+        card(Rank::Queen, Suit::Hearts),
+        card(Rank::Jack, Suit::Hearts),
+    ];
+
+    let analysis = analyze(&hole, &board, &dead).unwrap();
+    assert_eq!(analysis.outs().len(), 7);
+    assert!(!analysis.outs().contains(&dead[0]));
+    assert_eq!(analysis.unseen(), 45);
+    assert!((analysis.hit_by_turn().unwrap() - 7.0 / 45.0).abs() < 1e-12);
+}
+
+#[test]
+fn test_turn_probability_uses_one_card() {
+    let hole = [
+        card(Rank::Nine, Suit::Spades),
+        card(Rank::Eight, Suit::Hearts),
+    ];
+    let board = [
+        card(Rank::Seven, Suit::Clubs),
+        card(Rank::Six, Suit::Diamonds),
+        card(Rank::King, Suit::Spades),
+        card(Rank::Two, Suit::Spades),
+    ];
+
+    let analysis = analyze(&hole, &board, &[]).unwrap();
+    assert_eq!(analysis.hit_by_turn(), None);
+    assert!((analysis.hit_by_river() - 8.0 / 46.0).abs() < 1e-12);
+}
+
+#[test]
+fn test_invalid_board_size() {
+    let hole = [
+        card(Rank::Ace, Suit::Hearts),
+        card(Rank::King, Suit::Hearts),
+    ];
+    let board = [card(Rank::Seven, Suit::Hearts)];
+
+    assert_eq!(
+        analyze(&hole, &board, &[]),
+        Err(DrawError::InvalidBoardSize(1))
+    );
+}
+
+#[test]
+fn test_duplicate_card_is_rejected() {
+    let hole = [
+        card(Rank::Ace, Suit::Hearts),
+        card(Rank::King, Suit::Hearts),
+    ];
+    let board = [
+        card(Rank::Ace, Suit::Hearts),
+        card(Rank::Two, Suit::Hearts),
+        card(Rank::Nine, Suit::Clubs),
+    ];
+
+    assert_eq!(
+        analyze(&hole, &board, &[]),
+        Err(DrawError::DuplicateCard(card(Rank::Ace, Suit::Hearts)))
+    );
+}
+
+#[test]
+#[cfg(feature = "jokers")]
+fn test_joker_is_rejected() {
+    let joker = Card::new(Rank::Joker, None);
+    let hole = [joker, card(Rank::King, Suit::Hearts)];
+    let board = [
+        card(Rank::Ace, Suit::Hearts),
+        card(Rank::Two, Suit::Hearts),
+        card(Rank::Nine, Suit::Clubs),
+    ];
+
+    assert_eq!(
+        analyze(&hole, &board, &[]),
+        Err(DrawError::UnsuitedCard(joker))
+    );
+}
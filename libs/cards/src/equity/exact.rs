@@ -0,0 +1,71 @@
+use std::ops::ControlFlow;
+
+use super::parallel::{REPORT_EVERY, Reporter};
+use super::{Spot, Tally};
+use crate::Card;
+
+// Walks every combination of the remaining cards that completes the board, evaluating
+// every `stride`th one starting from the `offset`th
+pub(super) fn run(spot: &Spot, offset: usize, stride: usize, reporter: &Reporter) -> Tally {
+    let mut tally = Tally::new(spot.hands.len());
+    let mut values = Vec::with_capacity(spot.hands.len());
+    let mut sequence = 0;
+    let mut pending = 0;
+
+    for_each_board(&spot.board, &spot.remaining, spot.to_come, |board| {
+        sequence += 1;
+        if (sequence - 1) % stride != offset {
+            return ControlFlow::Continue(());
+        }
+        spot.showdown(board, &mut values);
+        tally.record(&values);
+
+        pending += 1;
+        if pending == REPORT_EVERY {
+            pending = 0;
+            if !reporter.report(REPORT_EVERY) {
+                return ControlFlow::Break(());
+            }
+        }
+        ControlFlow::Continue(())
+    });
+
+    reporter.report(pending);
+    tally
+}
+
+// Calls `f` with every completion of `board` by `to_come` of the `remaining` cards, in
+// lexicographic order, until it breaks
+pub(crate) fn for_each_board<F>(board: &[Card], remaining: &[Card], to_come: usize, mut f: F)
+where
+    F: FnMut(&[Card]) -> ControlFlow<()>,
+{
+    let n = remaining.len();
+    let k = to_come;
+    let mut cards = board.to_vec();
+    let mut indices: Vec<usize> = (0..k).collect();
+
+    loop {
+        cards.truncate(board.len());
+        cards.extend(indices.iter().map(|&i| remaining[i]));
+        if f(&cards).is_break() {
+            return;
+        }
+
+        let Some(i) = (0..k).rev().find(|&i| indices[i] != i + n - k) else {
+            return;
+        };
+        indices[i] += 1;
+        for j in i + 1..k {
+            indices[j] = indices[j - 1] + 1;
+        }
+    }
+}
+
+// Binomial coefficient n choose k, saturating rather than overflowing
+pub(crate) fn combinations(n: usize, k: usize) -> u64 {
+    if k > n {
+        return 0;
+    }
+    (0..k.min(n - k) as u64).fold(1u64, |acc, i| acc.saturating_mul(n as u64 - i) / (i + 1))
+}
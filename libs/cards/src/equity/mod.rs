@@ -0,0 +1,607 @@
+use std::fmt;
+use std::ops::ControlFlow;
+use std::thread;
+
+use crate::eval::{HandValue, evaluate};
+use crate::{Card, Deck};
+
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
+mod exact;
+mod monte_carlo;
+mod parallel;
+mod ranges;
+
+pub use ranges::{ComboEquity, RangeEquity, RangeEquityCalculator, RangeEquityResult};
+
+pub(crate) use exact::{combinations, for_each_board};
+use parallel::Merge;
+
+#[cfg(test)]
+mod tests;
+
+/// Number of Monte Carlo trials run when no iteration count is configured
+pub const DEFAULT_ITERATIONS: u64 = 100_000;
+
+/// Largest number of board completions `calculate` will enumerate before sampling instead
+///
+/// This covers every heads-up spot, including preflop (1,712,304 boards).
+pub const DEFAULT_MAX_COMBINATIONS: u64 = 2_000_000;
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum EquityError {
+    /// Equity needs at least two players
+    NotEnoughPlayers(usize),
+    /// The board can have at most five cards
+    InvalidBoardSize(usize),
+    /// The same card was given more than once across hands, board and dead cards
+    DuplicateCard(Card),
+    /// A card without a suit (a joker) was given
+    UnsuitedCard(Card),
+    /// Not enough cards remain in the deck to complete the board
+    NotEnoughCards { needed: usize, remaining: usize },
+    /// A Monte Carlo run was asked for zero iterations
+    NoIterations,
+    /// The progress callback asked for the run to stop
+    Cancelled,
+    /// A player's range has no combos left once known cards are removed
+    EmptyRange(usize),
+    /// No combination of the players' ranges can be dealt without sharing a card
+    NoValidMatchup,
+}
+
+impl fmt::Display for EquityError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            EquityError::NotEnoughPlayers(count) => {
+                write!(f, "equity needs at least two players, got {count}")
+            }
+            EquityError::InvalidBoardSize(size) => {
+                write!(f, "board can have at most 5 cards, got {size}")
+            }
+            EquityError::DuplicateCard(card) => write!(f, "card {card:?} appears more than once"),
+            EquityError::UnsuitedCard(card) => write!(f, "card {card:?} has no suit"),
+            EquityError::NotEnoughCards { needed, remaining } => {
+                write!(f, "{needed} cards are needed but only {remaining} remain")
+            }
+            EquityError::NoIterations => write!(f, "iteration count must be greater than zero"),
+            EquityError::Cancelled => write!(f, "equity calculation was cancelled"),
+            EquityError::EmptyRange(player) => {
+                write!(f, "range of player {player} has no live combos")
+            }
+            EquityError::NoValidMatchup => {
+                write!(f, "no combos from the ranges can be dealt together")
+            }
+        }
+    }
+}
+
+impl std::error::Error for EquityError {}
+
+/// Calculates each player's share of the pot from any point in a hand
+///
+/// # Examples
+///
+/// ```
+/// use cards::{Card, Rank, Suit};
+/// use cards::equity::EquityCalculator;
+///
+/// let aces = [
+///     Card::new(Rank::Ace, Some(Suit::Spades)),
+///     Card::new(Rank::Ace, Some(Suit::Hearts)),
+/// ];
+/// let kings = [
+///     Card::new(Rank::King, Some(Suit::Spades)),
+///     Card::new(Rank::King, Some(Suit::Hearts)),
+/// ];
+///
+/// let result = EquityCalculator::new(&[aces, kings])
+///     .iterations(20_000)
+///     .seed(7)
+///     .monte_carlo()
+///     .unwrap();
+///
+/// assert!(result.players()[0].equity() > 0.75);
+/// ```
+#[derive(Debug, Clone)]
+pub struct EquityCalculator {
+    hands: Vec<[Card; 2]>,
+    board: Vec<Card>,
+    dead: Vec<Card>,
+    iterations: u64,
+    seed: Option<u64>,
+    max_combinations: u64,
+    threads: usize,
+}
+
+impl EquityCalculator {
+    pub fn new(hands: &[[Card; 2]]) -> Self {
+        Self {
+            hands: hands.to_vec(),
+            board: Vec::new(),
+            dead: Vec::new(),
+            iterations: DEFAULT_ITERATIONS,
+            seed: None,
+            max_combinations: DEFAULT_MAX_COMBINATIONS,
+            threads: 1,
+        }
+    }
+
+    /// Sets the community cards already dealt (up to five)
+    pub fn board(mut self, board: &[Card]) -> Self {
+        self.board = board.to_vec();
+        self
+    }
+
+    /// Sets cards known to be out of play, such as folded or exposed cards
+    pub fn dead(mut self, dead: &[Card]) -> Self {
+        self.dead = dead.to_vec();
+        self
+    }
+
+    /// Sets the number of boards sampled by `monte_carlo`
+    pub fn iterations(mut self, iterations: u64) -> Self {
+        self.iterations = iterations;
+        self
+    }
+
+    /// Fixes the random seed so that sampled results are reproducible
+    pub fn seed(mut self, seed: u64) -> Self {
+        self.seed = Some(seed);
+        self
+    }
+
+    /// Sets how many board completions `calculate` may enumerate before it samples instead
+    pub fn max_combinations(mut self, max_combinations: u64) -> Self {
+        self.max_combinations = max_combinations;
+        self
+    }
+
+    /// Sets the number of worker threads, or 0 to use one per available core
+    ///
+    /// Sampled results are reproducible for a given seed and thread count: each thread
+    /// samples its share of the iterations from its own generator derived from the seed.
+    pub fn threads(mut self, threads: usize) -> Self {
+        self.threads = threads;
+        self
+    }
+
+    /// Calculates equity exactly when the remaining boards number at most `max_combinations`,
+    /// and by Monte Carlo sampling otherwise
+    pub fn calculate(&self) -> Result<EquityResult, EquityError> {
+        self.calculate_with_progress(|_| ControlFlow::Continue(()))
+    }
+
+    /// Like `calculate`, reporting progress as it goes
+    ///
+    /// `on_progress` is called on the calling thread; returning `ControlFlow::Break` stops
+    /// every worker and makes the calculation fail with `EquityError::Cancelled`.
+    pub fn calculate_with_progress<F>(&self, on_progress: F) -> Result<EquityResult, EquityError>
+    where
+        F: FnMut(Progress) -> ControlFlow<()>,
+    {
+        let spot = self.spot()?;
+        if spot.combinations() <= self.max_combinations {
+            self.run_exact(&spot, on_progress)
+        } else {
+            self.run_monte_carlo(&spot, on_progress)
+        }
+    }
+
+    /// Calculates equity exactly by walking every possible completion of the board
+    ///
+    /// This is only practical for a few million boards or fewer; see `calculate` for a
+    /// version that falls back to sampling.
+    pub fn exact(&self) -> Result<EquityResult, EquityError> {
+        self.exact_with_progress(|_| ControlFlow::Continue(()))
+    }
+
+    /// Like `exact`, reporting progress as it goes; see `calculate_with_progress`
+    pub fn exact_with_progress<F>(&self, on_progress: F) -> Result<EquityResult, EquityError>
+    where
+        F: FnMut(Progress) -> ControlFlow<()>,
+    {
+        let spot = self.spot()?;
+        self.run_exact(&spot, on_progress)
+    }
+
+    /// Estimates equity by sampling random completions of the board
+    ///
+    /// Without an explicit seed a random one is chosen and reported in the result, so any
+    /// run can be repeated exactly.
+    pub fn monte_carlo(&self) -> Result<EquityResult, EquityError> {
+        self.monte_carlo_with_progress(|_| ControlFlow::Continue(()))
+    }
+
+    /// Like `monte_carlo`, reporting progress as it goes; see `calculate_with_progress`
+    pub fn monte_carlo_with_progress<F>(&self, on_progress: F) -> Result<EquityResult, EquityError>
+    where
+        F: FnMut(Progress) -> ControlFlow<()>,
+    {
+        let spot = self.spot()?;
+        self.run_monte_carlo(&spot, on_progress)
+    }
+
+    fn run_exact<F>(&self, spot: &Spot, on_progress: F) -> Result<EquityResult, EquityError>
+    where
+        F: FnMut(Progress) -> ControlFlow<()>,
+    {
+        let threads = thread_count(self.threads);
+        let tally = parallel::run(
+            threads,
+            spot.combinations(),
+            on_progress,
+            |thread, reporter| exact::run(spot, thread, threads, reporter),
+        )?;
+        Ok(tally.into_result(None))
+    }
+
+    fn run_monte_carlo<F>(&self, spot: &Spot, on_progress: F) -> Result<EquityResult, EquityError>
+    where
+        F: FnMut(Progress) -> ControlFlow<()>,
+    {
+        if self.iterations == 0 {
+            return Err(EquityError::NoIterations);
+        }
+        let seed = self.seed.unwrap_or_else(rand::random);
+        let threads = thread_count(self.threads);
+        let tally = parallel::run(threads, self.iterations, on_progress, |thread, reporter| {
+            // Spread the remainder over the first threads
+            let share = self.iterations / threads as u64
+                + u64::from((thread as u64) < self.iterations % threads as u64);
+            monte_carlo::run(spot, share, seed.wrapping_add(thread as u64), reporter)
+        })?;
+        Ok(tally.into_result(Some(seed)))
+    }
+
+    // Validates the inputs and works out which cards are left to deal from
+    fn spot(&self) -> Result<Spot, EquityError> {
+        if self.hands.len() < 2 {
+            return Err(EquityError::NotEnoughPlayers(self.hands.len()));
+        }
+        if self.board.len() > 5 {
+            return Err(EquityError::InvalidBoardSize(self.board.len()));
+        }
+
+        let known = self
+            .hands
+            .iter()
+            .flatten()
+            .chain(&self.board)
+            .chain(&self.dead);
+        let remaining = remaining_cards(known)?;
+        let to_come = 5 - self.board.len();
+        if remaining.len() < to_come {
+            return Err(EquityError::NotEnoughCards {
+                needed: to_come,
+                remaining: remaining.len(),
+            });
+        }
+
+        Ok(Spot {
+            hands: self.hands.clone(),
+            board: self.board.clone(),
+            remaining,
+            to_come,
+        })
+    }
+}
+
+/// How far an equity calculation has got
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Progress {
+    /// Boards evaluated so far
+    pub completed: u64,
+    /// Boards that will be evaluated in total
+    pub total: u64,
+}
+
+impl Progress {
+    /// Completed proportion of the work, from 0 to 1
+    pub fn fraction(&self) -> f64 {
+        if self.total == 0 {
+            1.0
+        } else {
+            self.completed as f64 / self.total as f64
+        }
+    }
+}
+
+/// A player's result from an equity calculation
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct PlayerEquity {
+    wins: u64,
+    ties: u64,
+    trials: u64,
+    equity: f64,
+    std_error: f64,
+    exact: Option<Ratio>,
+}
+
+impl PlayerEquity {
+    /// Boards on which this player won the whole pot
+    pub fn wins(&self) -> u64 {
+        self.wins
+    }
+
+    /// Boards on which this player split the pot with one or more others
+    pub fn ties(&self) -> u64 {
+        self.ties
+    }
+
+    /// Boards on which this player won nothing
+    pub fn losses(&self) -> u64 {
+        self.trials - self.wins - self.ties
+    }
+
+    pub fn win_percentage(&self) -> f64 {
+        100.0 * self.wins as f64 / self.trials as f64
+    }
+
+    pub fn tie_percentage(&self) -> f64 {
+        100.0 * self.ties as f64 / self.trials as f64
+    }
+
+    /// Expected share of the pot, from 0 to 1, counting split pots fractionally
+    pub fn equity(&self) -> f64 {
+        self.equity
+    }
+
+    pub fn equity_percentage(&self) -> f64 {
+        100.0 * self.equity
+    }
+
+    /// Equity as an exact fraction, when every board was enumerated
+    pub fn exact_equity(&self) -> Option<Ratio> {
+        self.exact
+    }
+
+    /// Standard error of the sampled equity estimate
+    pub fn std_error(&self) -> f64 {
+        self.std_error
+    }
+}
+
+/// The outcome of an equity calculation for every player, in the order the hands were given
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct EquityResult {
+    trials: u64,
+    seed: Option<u64>,
+    players: Vec<PlayerEquity>,
+}
+
+impl EquityResult {
+    /// Number of boards evaluated
+    pub fn trials(&self) -> u64 {
+        self.trials
+    }
+
+    /// The seed used for sampling, if the result was sampled
+    pub fn seed(&self) -> Option<u64> {
+        self.seed
+    }
+
+    /// Whether every possible board was enumerated rather than sampled
+    pub fn is_exact(&self) -> bool {
+        self.seed.is_none()
+    }
+
+    pub fn players(&self) -> &[PlayerEquity] {
+        &self.players
+    }
+}
+
+// A validated equity problem
+#[derive(Debug, Clone)]
+struct Spot {
+    hands: Vec<[Card; 2]>,
+    board: Vec<Card>,
+    remaining: Vec<Card>,
+    to_come: usize,
+}
+
+impl Spot {
+    // Number of distinct ways to complete the board
+    fn combinations(&self) -> u64 {
+        exact::combinations(self.remaining.len(), self.to_come)
+    }
+
+    fn showdown(&self, board: &[Card], values: &mut Vec<HandValue>) {
+        showdown(&self.hands, board, values);
+    }
+}
+
+// Evaluates every hand against a complete board
+fn showdown<'a>(
+    hands: impl IntoIterator<Item = &'a [Card; 2]>,
+    board: &[Card],
+    values: &mut Vec<HandValue>,
+) {
+    values.clear();
+    let mut cards = [board[0]; 7];
+    cards[2..2 + board.len()].copy_from_slice(board);
+    for hand in hands {
+        cards[..2].copy_from_slice(hand);
+        values.push(evaluate(&cards[..2 + board.len()]));
+    }
+}
+
+// Resolves a configured thread count, where 0 means one per available core
+fn thread_count(threads: usize) -> usize {
+    match threads {
+        0 => thread::available_parallelism().map_or(1, |n| n.get()),
+        threads => threads,
+    }
+}
+
+// Checks the known cards are distinct real cards and returns the rest of the deck
+fn remaining_cards<'a>(
+    known: impl IntoIterator<Item = &'a Card>,
+) -> Result<Vec<Card>, EquityError> {
+    let mut deck = Deck::new();
+    for card in known {
+        if card.suit.is_none() {
+            return Err(EquityError::UnsuitedCard(*card));
+        }
+        if !deck.remove(card) {
+            return Err(EquityError::DuplicateCard(*card));
+        }
+    }
+    Ok(deck
+        .cards()
+        .iter()
+        .filter(|c| c.suit.is_some())
+        .copied()
+        .collect())
+}
+
+// Running totals over the boards seen so far
+#[derive(Debug, Clone, Default)]
+struct Tally {
+    trials: u64,
+    // Pot shares are also counted exactly in units of 1/`unit`, which every split divides
+    unit: u64,
+    players: Vec<PlayerTally>,
+}
+
+#[derive(Debug, Clone, Copy, Default)]
+struct PlayerTally {
+    wins: u64,
+    ties: u64,
+    share: f64,
+    share_squared: f64,
+    share_units: u64,
+}
+
+impl Tally {
+    fn new(players: usize) -> Self {
+        Self {
+            trials: 0,
+            unit: (1..=players as u64).fold(1, |lcm, n| lcm / gcd(lcm, n) * n),
+            players: vec![PlayerTally::default(); players],
+        }
+    }
+
+    fn record(&mut self, values: &[HandValue]) {
+        self.trials += 1;
+        let best = values.iter().max().copied();
+        let winners = values.iter().filter(|&&v| Some(v) == best).count();
+        let share = 1.0 / winners as f64;
+        for (player, value) in self.players.iter_mut().zip(values) {
+            if Some(*value) != best {
+                continue;
+            }
+            if winners == 1 {
+                player.wins += 1;
+            } else {
+                player.ties += 1;
+            }
+            player.share += share;
+            player.share_squared += share * share;
+            player.share_units += self.unit / winners as u64;
+        }
+    }
+
+    fn into_result(self, seed: Option<u64>) -> EquityResult {
+        let trials = self.trials;
+        let unit = self.unit;
+        let n = trials as f64;
+        let players = self
+            .players
+            .into_iter()
+            .map(|p| {
+                let equity = p.share / n;
+                let std_error = if seed.is_some() && trials > 1 {
+                    let variance = (p.share_squared - n * equity * equity) / (n - 1.0);
+                    (variance.max(0.0) / n).sqrt()
+                } else {
+                    0.0
+                };
+                PlayerEquity {
+                    wins: p.wins,
+                    ties: p.ties,
+                    trials,
+                    equity,
+                    std_error,
+                    exact: seed
+                        .is_none()
+                        .then(|| Ratio::new(p.share_units as u128, trials as u128 * unit as u128)),
+                }
+            })
+            .collect();
+
+        EquityResult {
+            trials,
+            seed,
+            players,
+        }
+    }
+}
+
+impl Merge for Tally {
+    fn merge(&mut self, other: Tally) {
+        self.trials += other.trials;
+        for (player, other) in self.players.iter_mut().zip(other.players) {
+            player.wins += other.wins;
+            player.ties += other.ties;
+            player.share += other.share;
+            player.share_squared += other.share_squared;
+            player.share_units += other.share_units;
+        }
+    }
+}
+
+/// An exact fraction in lowest terms
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct Ratio {
+    numer: u128,
+    denom: u128,
+}
+
+impl Ratio {
+    /// Creates a fraction reduced to lowest terms
+    ///
+    /// # Panics
+    ///
+    /// Panics if `denom` is zero.
+    pub fn new(numer: u128, denom: u128) -> Self {
+        assert!(denom != 0, "denominator must not be zero");
+        let divisor = gcd(numer, denom);
+        Self {
+            numer: numer / divisor,
+            denom: denom / divisor,
+        }
+    }
+
+    pub fn numer(&self) -> u128 {
+        self.numer
+    }
+
+    pub fn denom(&self) -> u128 {
+        self.denom
+    }
+
+    pub fn to_f64(&self) -> f64 {
+        self.numer as f64 / self.denom as f64
+    }
+}
+
+impl fmt::Display for Ratio {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}/{}", self.numer, self.denom)
+    }
+}
+
+fn gcd<T>(mut a: T, mut b: T) -> T
+where
+    T: Copy + PartialEq + Default + std::ops::Rem<Output = T>,
+{
+    while b != T::default() {
+        (a, b) = (b, a % b);
+    }
+    a
+}
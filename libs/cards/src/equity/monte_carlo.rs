@@ -0,0 +1,34 @@
+use rand::SeedableRng;
+use rand::rngs::StdRng;
+use rand::seq::SliceRandom;
+
+use super::parallel::{REPORT_EVERY, Reporter};
+use super::{Spot, Tally};
+
+// Samples `iterations` random board completions from the remaining cards
+pub(super) fn run(spot: &Spot, iterations: u64, seed: u64, reporter: &Reporter) -> Tally {
+    let mut rng = StdRng::seed_from_u64(seed);
+    let mut tally = Tally::new(spot.hands.len());
+    let mut remaining = spot.remaining.clone();
+    let mut board = spot.board.clone();
+    let mut values = Vec::with_capacity(spot.hands.len());
+
+    let mut done = 0;
+    while done < iterations {
+        let batch = REPORT_EVERY.min(iterations - done);
+        for _ in 0..batch {
+            let (drawn, _) = remaining.partial_shuffle(&mut rng, spot.to_come);
+            board.truncate(spot.board.len());
+            board.extend_from_slice(drawn);
+
+            spot.showdown(&board, &mut values);
+            tally.record(&values);
+        }
+        done += batch;
+        if !reporter.report(batch) {
+            break;
+        }
+    }
+
+    tally
+}
@@ -0,0 +1,83 @@
+use std::ops::ControlFlow;
+use std::panic;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::mpsc::{self, Sender};
+use std::thread;
+
+use super::{EquityError, Progress};
+
+/// Boards a worker evaluates between progress reports
+pub(super) const REPORT_EVERY: u64 = 4096;
+
+// A worker's link back to the calling thread
+pub(super) struct Reporter<'a> {
+    sender: Sender<u64>,
+    cancelled: &'a AtomicBool,
+}
+
+impl Reporter<'_> {
+    // Records `done` more boards, returning whether the worker should carry on
+    pub(super) fn report(&self, done: u64) -> bool {
+        // The receiver only hangs up once every worker has finished
+        let _ = self.sender.send(done);
+        !self.cancelled.load(Ordering::Relaxed)
+    }
+}
+
+// Per-thread results that can be combined into one
+pub(super) trait Merge {
+    fn merge(&mut self, other: Self);
+}
+
+// Runs `work` on `threads` scoped threads and merges their results in thread order, so the
+// result does not depend on how the threads were scheduled
+pub(super) fn run<T, F, W>(
+    threads: usize,
+    total: u64,
+    mut on_progress: F,
+    work: W,
+) -> Result<T, EquityError>
+where
+    T: Merge + Send,
+    F: FnMut(Progress) -> ControlFlow<()>,
+    W: Fn(usize, &Reporter) -> T + Sync,
+{
+    let cancelled = AtomicBool::new(false);
+    let (sender, receiver) = mpsc::channel();
+
+    thread::scope(|scope| {
+        let workers: Vec<_> = (0..threads)
+            .map(|thread| {
+                let reporter = Reporter {
+                    sender: sender.clone(),
+                    cancelled: &cancelled,
+                };
+                let work = &work;
+                scope.spawn(move || work(thread, &reporter))
+            })
+            .collect();
+        drop(sender);
+
+        let mut completed = 0;
+        for done in receiver {
+            completed += done;
+            if !cancelled.load(Ordering::Relaxed)
+                && on_progress(Progress { completed, total }).is_break()
+            {
+                cancelled.store(true, Ordering::Relaxed);
+            }
+        }
+
+        let mut tallies = workers
+            .into_iter()
+            .map(|worker| worker.join().unwrap_or_else(|e| panic::resume_unwind(e)));
+        let mut tally = tallies.next().expect("at least one worker thread");
+        tallies.for_each(|other| tally.merge(other));
+
+        if cancelled.load(Ordering::Relaxed) {
+            Err(EquityError::Cancelled)
+        } else {
+            Ok(tally)
+        }
+    })
+}
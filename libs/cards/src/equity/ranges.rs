@@ -0,0 +1,723 @@
+use std::ops::ControlFlow;
+
+use rand::rngs::StdRng;
+use rand::seq::SliceRandom;
+use rand::{Rng, SeedableRng};
+
+use super::exact::{combinations, for_each_board};
+use super::parallel::{self, Merge, REPORT_EVERY, Reporter};
+use super::{
+    DEFAULT_ITERATIONS, DEFAULT_MAX_COMBINATIONS, EquityError, Progress, remaining_cards, showdown,
+    thread_count,
+};
+use crate::Card;
+use crate::range::{Combo, Range};
+
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
+/// Calculates how a hand or range performs against one or more opposing ranges
+///
+/// Combos are dealt in proportion to their weights, and never together with a combo, board
+/// card or dead card they share a card with.
+///
+/// # Examples
+///
+/// ```
+/// use cards::equity::RangeEquityCalculator;
+/// use cards::notation::parse_cards;
+/// use cards::range::Range;
+///
+/// let hero: Range = "AhKh".parse().unwrap();
+/// let villain: Range = "QQ+, AK".parse().unwrap();
+/// let board = parse_cards("Kd 7h 2h").unwrap();
+///
+/// let result = RangeEquityCalculator::new(&[hero, villain])
+///     .board(&board)
+///     .exact()
+///     .unwrap();
+///
+/// assert!(result.is_exact());
+/// assert!(result.players()[0].equity() > 0.5);
+/// ```
+#[derive(Debug, Clone)]
+pub struct RangeEquityCalculator {
+    ranges: Vec<Range>,
+    board: Vec<Card>,
+    dead: Vec<Card>,
+    iterations: u64,
+    seed: Option<u64>,
+    max_combinations: u64,
+    threads: usize,
+}
+
+impl RangeEquityCalculator {
+    pub fn new(ranges: &[Range]) -> Self {
+        Self {
+            ranges: ranges.to_vec(),
+            board: Vec::new(),
+            dead: Vec::new(),
+            iterations: DEFAULT_ITERATIONS,
+            seed: None,
+            max_combinations: DEFAULT_MAX_COMBINATIONS,
+            threads: 1,
+        }
+    }
+
+    /// Sets the community cards already dealt (up to five)
+    pub fn board(mut self, board: &[Card]) -> Self {
+        self.board = board.to_vec();
+        self
+    }
+
+    /// Sets cards known to be out of play, such as folded or exposed cards
+    pub fn dead(mut self, dead: &[Card]) -> Self {
+        self.dead = dead.to_vec();
+        self
+    }
+
+    /// Sets the number of deals sampled by `monte_carlo`
+    pub fn iterations(mut self, iterations: u64) -> Self {
+        self.iterations = iterations;
+        self
+    }
+
+    /// Fixes the random seed so that sampled results are reproducible
+    pub fn seed(mut self, seed: u64) -> Self {
+        self.seed = Some(seed);
+        self
+    }
+
+    /// Sets how many deals `calculate` may enumerate before it samples instead
+    ///
+    /// A deal is one combo for each player together with one completion of the board.
+    pub fn max_combinations(mut self, max_combinations: u64) -> Self {
+        self.max_combinations = max_combinations;
+        self
+    }
+
+    /// Sets the number of worker threads, or 0 to use one per available core
+    pub fn threads(mut self, threads: usize) -> Self {
+        self.threads = threads;
+        self
+    }
+
+    /// Calculates equity exactly when there are at most `max_combinations` deals to walk,
+    /// and by Monte Carlo sampling otherwise
+    pub fn calculate(&self) -> Result<RangeEquityResult, EquityError> {
+        self.calculate_with_progress(|_| ControlFlow::Continue(()))
+    }
+
+    /// Like `calculate`, reporting progress as it goes
+    ///
+    /// `on_progress` is called on the calling thread; returning `ControlFlow::Break` stops
+    /// every worker and makes the calculation fail with `EquityError::Cancelled`.
+    pub fn calculate_with_progress<F>(
+        &self,
+        on_progress: F,
+    ) -> Result<RangeEquityResult, EquityError>
+    where
+        F: FnMut(Progress) -> ControlFlow<()>,
+    {
+        let spot = self.spot()?;
+        // An upper bound, since combos that share cards are never dealt together
+        let deals = spot
+            .live
+            .iter()
+            .fold(spot.boards_per_matchup(), |deals, combos| {
+                deals.saturating_mul(combos.len() as u64)
+            });
+        if deals <= self.max_combinations {
+            self.run_exact(&spot, on_progress)
+        } else {
+            self.run_monte_carlo(&spot, on_progress)
+        }
+    }
+
+    /// Calculates equity exactly by walking every combination of combos and boards
+    ///
+    /// There is no limit on the number of deals, so a multiway spot with wide ranges can take
+    /// a very long time; `calculate` samples such spots instead.
+    pub fn exact(&self) -> Result<RangeEquityResult, EquityError> {
+        self.exact_with_progress(|_| ControlFlow::Continue(()))
+    }
+
+    /// Like `exact`, reporting progress as it goes; see `calculate_with_progress`
+    pub fn exact_with_progress<F>(&self, on_progress: F) -> Result<RangeEquityResult, EquityError>
+    where
+        F: FnMut(Progress) -> ControlFlow<()>,
+    {
+        let spot = self.spot()?;
+        self.run_exact(&spot, on_progress)
+    }
+
+    /// Estimates equity by sampling combos by weight and random completions of the board
+    pub fn monte_carlo(&self) -> Result<RangeEquityResult, EquityError> {
+        self.monte_carlo_with_progress(|_| ControlFlow::Continue(()))
+    }
+
+    /// Like `monte_carlo`, reporting progress as it goes; see `calculate_with_progress`
+    pub fn monte_carlo_with_progress<F>(
+        &self,
+        on_progress: F,
+    ) -> Result<RangeEquityResult, EquityError>
+    where
+        F: FnMut(Progress) -> ControlFlow<()>,
+    {
+        let spot = self.spot()?;
+        self.run_monte_carlo(&spot, on_progress)
+    }
+
+    fn run_exact<F>(
+        &self,
+        spot: &RangeSpot,
+        on_progress: F,
+    ) -> Result<RangeEquityResult, EquityError>
+    where
+        F: FnMut(Progress) -> ControlFlow<()>,
+    {
+        let boards = spot.boards_per_matchup();
+        let threads = thread_count(self.threads);
+
+        // Each thread walks the matchups itself and takes every `threads`th one, so they are
+        // never all held at once
+        let total = spot.matchup_count().saturating_mul(boards);
+        let tally = parallel::run(threads, total, on_progress, |thread, reporter| {
+            let mut tally = RangeTally::new(spot);
+            let mut pending = 0;
+            let mut index = 0;
+            let mut cancelled = false;
+            spot.for_each_matchup(|combos, weight| {
+                index += 1;
+                if (index - 1) % threads != thread {
+                    return ControlFlow::Continue(());
+                }
+                let outcomes = spot.enumerate_boards(combos);
+                tally.record(combos, weight, &outcomes, boards);
+
+                pending += boards;
+                if pending >= REPORT_EVERY {
+                    if !reporter.report(pending) {
+                        cancelled = true;
+                        return ControlFlow::Break(());
+                    }
+                    pending = 0;
+                }
+                ControlFlow::Continue(())
+            });
+            if !cancelled {
+                reporter.report(pending);
+            }
+            tally
+        })?;
+        Ok(tally.into_result(None))
+    }
+
+    fn run_monte_carlo<F>(
+        &self,
+        spot: &RangeSpot,
+        on_progress: F,
+    ) -> Result<RangeEquityResult, EquityError>
+    where
+        F: FnMut(Progress) -> ControlFlow<()>,
+    {
+        if self.iterations == 0 {
+            return Err(EquityError::NoIterations);
+        }
+        let seed = self.seed.unwrap_or_else(rand::random);
+        let threads = thread_count(self.threads);
+        let tally = parallel::run(threads, self.iterations, on_progress, |thread, reporter| {
+            let share = self.iterations / threads as u64
+                + u64::from((thread as u64) < self.iterations % threads as u64);
+            spot.sample(share, seed.wrapping_add(thread as u64), reporter)
+        })?;
+        Ok(tally.into_result(Some(seed)))
+    }
+
+    // Validates the inputs and works out each range's live combos
+    fn spot(&self) -> Result<RangeSpot, EquityError> {
+        if self.ranges.len() < 2 {
+            return Err(EquityError::NotEnoughPlayers(self.ranges.len()));
+        }
+        if self.board.len() > 5 {
+            return Err(EquityError::InvalidBoardSize(self.board.len()));
+        }
+
+        let known: Vec<Card> = self.board.iter().chain(&self.dead).copied().collect();
+        let deck = remaining_cards(&known)?;
+        let to_come = 5 - self.board.len();
+        let needed = to_come + 2 * self.ranges.len();
+        if deck.len() < needed {
+            return Err(EquityError::NotEnoughCards {
+                needed,
+                remaining: deck.len(),
+            });
+        }
+
+        let mut live = Vec::with_capacity(self.ranges.len());
+        for (player, range) in self.ranges.iter().enumerate() {
+            let combos: Vec<(Combo, f64)> = range.without_cards(&known).combos().collect();
+            if combos.is_empty() {
+                return Err(EquityError::EmptyRange(player));
+            }
+            live.push(combos);
+        }
+
+        let spot = RangeSpot {
+            cumulative: live
+                .iter()
+                .map(|combos| {
+                    combos
+                        .iter()
+                        .scan(0.0, |total, (_, weight)| {
+                            *total += weight;
+                            Some(*total)
+                        })
+                        .collect()
+                })
+                .collect(),
+            live,
+            board: self.board.clone(),
+            deck,
+            to_come,
+        };
+        if !spot.any_matchup(&mut Vec::new()) {
+            return Err(EquityError::NoValidMatchup);
+        }
+        Ok(spot)
+    }
+}
+
+/// A player's result from a range equity calculation
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct RangeEquity {
+    equity: f64,
+    win: f64,
+    tie: f64,
+    std_error: f64,
+    combos: Vec<ComboEquity>,
+}
+
+impl RangeEquity {
+    /// Expected share of the pot, from 0 to 1, over the whole range
+    pub fn equity(&self) -> f64 {
+        self.equity
+    }
+
+    pub fn equity_percentage(&self) -> f64 {
+        100.0 * self.equity
+    }
+
+    pub fn win_percentage(&self) -> f64 {
+        100.0 * self.win
+    }
+
+    pub fn tie_percentage(&self) -> f64 {
+        100.0 * self.tie
+    }
+
+    /// Standard error of the sampled equity estimate
+    pub fn std_error(&self) -> f64 {
+        self.std_error
+    }
+
+    /// Results for each combo that was dealt, best equity first
+    pub fn combos(&self) -> &[ComboEquity] {
+        &self.combos
+    }
+}
+
+/// How one combo of a player's range performed
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct ComboEquity {
+    combo: Combo,
+    frequency: f64,
+    equity: f64,
+    win: f64,
+    tie: f64,
+}
+
+impl ComboEquity {
+    pub fn combo(&self) -> Combo {
+        self.combo
+    }
+
+    /// How often this combo is the one dealt, once weights and blockers are accounted for
+    pub fn frequency(&self) -> f64 {
+        self.frequency
+    }
+
+    /// Expected share of the pot, from 0 to 1, when holding this combo
+    pub fn equity(&self) -> f64 {
+        self.equity
+    }
+
+    pub fn win_percentage(&self) -> f64 {
+        100.0 * self.win
+    }
+
+    pub fn tie_percentage(&self) -> f64 {
+        100.0 * self.tie
+    }
+}
+
+/// The outcome of a range equity calculation for every player, in the order the ranges were given
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct RangeEquityResult {
+    trials: u64,
+    seed: Option<u64>,
+    players: Vec<RangeEquity>,
+}
+
+impl RangeEquityResult {
+    /// Number of boards evaluated
+    pub fn trials(&self) -> u64 {
+        self.trials
+    }
+
+    /// The seed used for sampling, if the result was sampled
+    pub fn seed(&self) -> Option<u64> {
+        self.seed
+    }
+
+    /// Whether every possible deal was enumerated rather than sampled
+    pub fn is_exact(&self) -> bool {
+        self.seed.is_none()
+    }
+
+    pub fn players(&self) -> &[RangeEquity] {
+        &self.players
+    }
+}
+
+// A validated range equity problem
+#[derive(Debug, Clone)]
+struct RangeSpot {
+    // Each player's combos that do not touch the board or dead cards, with their weights
+    live: Vec<Vec<(Combo, f64)>>,
+    // Running totals of the weights, for sampling combos by weight
+    cumulative: Vec<Vec<f64>>,
+    board: Vec<Card>,
+    // Every card not on the board or dead, including those in the players' combos
+    deck: Vec<Card>,
+    to_come: usize,
+}
+
+// What one player got out of a set of boards
+#[derive(Debug, Clone, Copy, Default)]
+struct Outcome {
+    wins: f64,
+    ties: f64,
+    share: f64,
+}
+
+impl RangeSpot {
+    fn boards_per_matchup(&self) -> u64 {
+        combinations(self.deck.len() - 2 * self.live.len(), self.to_come)
+    }
+
+    fn hands(&self, matchup: &[usize]) -> Vec<[Card; 2]> {
+        matchup
+            .iter()
+            .zip(&self.live)
+            .map(|(&i, combos)| combos[i].0.cards())
+            .collect()
+    }
+
+    fn conflicts(&self, matchup: &[usize], player: usize, index: usize) -> bool {
+        let combo = self.live[player][index].0;
+        matchup
+            .iter()
+            .enumerate()
+            .any(|(p, &i)| self.live[p][i].0.conflicts(&combo))
+    }
+
+    // Depth-first search for any deal of non-conflicting combos
+    fn any_matchup(&self, matchup: &mut Vec<usize>) -> bool {
+        let player = matchup.len();
+        if player == self.live.len() {
+            return true;
+        }
+        for index in 0..self.live[player].len() {
+            if !self.conflicts(matchup, player, index) {
+                matchup.push(index);
+                let found = self.any_matchup(matchup);
+                matchup.pop();
+                if found {
+                    return true;
+                }
+            }
+        }
+        false
+    }
+
+    // Calls `f` with every deal of non-conflicting combos, as indices into the live combos,
+    // and its combined weight, stopping early if `f` breaks
+    fn for_each_matchup<F>(&self, mut f: F)
+    where
+        F: FnMut(&[usize], f64) -> ControlFlow<()>,
+    {
+        fn walk<F>(spot: &RangeSpot, current: &mut Vec<usize>, weight: f64, f: &mut F) -> bool
+        where
+            F: FnMut(&[usize], f64) -> ControlFlow<()>,
+        {
+            let player = current.len();
+            if player == spot.live.len() {
+                return f(current, weight).is_continue();
+            }
+            for (index, (_, w)) in spot.live[player].iter().enumerate() {
+                if !spot.conflicts(current, player, index) {
+                    current.push(index);
+                    let go_on = walk(spot, current, weight * w, f);
+                    current.pop();
+                    if !go_on {
+                        return false;
+                    }
+                }
+            }
+            true
+        }
+
+        walk(self, &mut Vec::with_capacity(self.live.len()), 1.0, &mut f);
+    }
+
+    fn matchup_count(&self) -> u64 {
+        let mut count = 0;
+        self.for_each_matchup(|_, _| {
+            count += 1;
+            ControlFlow::Continue(())
+        });
+        count
+    }
+
+    // Totals each player's outcome over every completion of the board for one deal
+    fn enumerate_boards(&self, matchup: &[usize]) -> Vec<Outcome> {
+        let hands = self.hands(matchup);
+        let remaining: Vec<Card> = self
+            .deck
+            .iter()
+            .filter(|card| !hands.iter().flatten().any(|c| c == *card))
+            .copied()
+            .collect();
+
+        let mut outcomes = vec![Outcome::default(); hands.len()];
+        let mut values = Vec::with_capacity(hands.len());
+        for_each_board(&self.board, &remaining, self.to_come, |board| {
+            showdown(&hands, board, &mut values);
+            add_showdown(&values, &mut outcomes);
+            ControlFlow::Continue(())
+        });
+        outcomes
+    }
+
+    // Deals `iterations` random combos and boards
+    fn sample(&self, iterations: u64, seed: u64, reporter: &Reporter) -> RangeTally {
+        let mut rng = StdRng::seed_from_u64(seed);
+        let mut tally = RangeTally::new(self);
+        let mut deck = self.deck.clone();
+        let mut board = self.board.clone();
+        let mut matchup = Vec::with_capacity(self.live.len());
+        let mut values = Vec::with_capacity(self.live.len());
+        let mut outcomes = vec![Outcome::default(); self.live.len()];
+
+        let mut done = 0;
+        while done < iterations {
+            let batch = REPORT_EVERY.min(iterations - done);
+            for _ in 0..batch {
+                self.sample_matchup(&mut rng, &mut matchup);
+                let hands = self.hands(&matchup);
+
+                // The first cards of a shuffle that are not in anyone's hand
+                let (shuffled, _) = deck.partial_shuffle(&mut rng, self.to_come + 2 * hands.len());
+                board.truncate(self.board.len());
+                board.extend(
+                    shuffled
+                        .iter()
+                        .filter(|card| !hands.iter().flatten().any(|c| c == *card))
+                        .take(self.to_come),
+                );
+
+                showdown(&hands, &board, &mut values);
+                outcomes.fill(Outcome::default());
+                add_showdown(&values, &mut outcomes);
+                tally.record(&matchup, 1.0, &outcomes, 1);
+            }
+            done += batch;
+            if !reporter.report(batch) {
+                break;
+            }
+        }
+
+        tally
+    }
+
+    // Picks a combo for every player by weight, starting again whenever two collide
+    fn sample_matchup<R: Rng>(&self, rng: &mut R, matchup: &mut Vec<usize>) {
+        'deal: loop {
+            matchup.clear();
+            for (player, cumulative) in self.cumulative.iter().enumerate() {
+                let total = cumulative[cumulative.len() - 1];
+                let target = rng.random::<f64>() * total;
+                let index = cumulative
+                    .partition_point(|&w| w <= target)
+                    .min(cumulative.len() - 1);
+                if self.conflicts(matchup, player, index) {
+                    continue 'deal;
+                }
+                matchup.push(index);
+            }
+            return;
+        }
+    }
+}
+
+// Adds one showdown's results to each player's outcome
+fn add_showdown(values: &[crate::eval::HandValue], outcomes: &mut [Outcome]) {
+    let best = values.iter().max();
+    let winners = values.iter().filter(|&v| Some(v) == best).count();
+    for (outcome, value) in outcomes.iter_mut().zip(values) {
+        if Some(value) == best {
+            if winners == 1 {
+                outcome.wins += 1.0;
+            } else {
+                outcome.ties += 1.0;
+            }
+            outcome.share += 1.0 / winners as f64;
+        }
+    }
+}
+
+// Weighted running totals for each player and each of their combos
+#[derive(Debug, Clone)]
+struct RangeTally {
+    trials: u64,
+    samples: u64,
+    players: Vec<PlayerRangeTally>,
+}
+
+#[derive(Debug, Clone, Default)]
+struct PlayerRangeTally {
+    total: WeightedOutcome,
+    share_squared: f64,
+    combos: Vec<(Combo, WeightedOutcome)>,
+}
+
+#[derive(Debug, Clone, Copy, Default)]
+struct WeightedOutcome {
+    weight: f64,
+    wins: f64,
+    ties: f64,
+    share: f64,
+}
+
+impl WeightedOutcome {
+    fn add(&mut self, weight: f64, outcome: &Outcome, boards: f64) {
+        self.weight += weight;
+        self.wins += weight * outcome.wins / boards;
+        self.ties += weight * outcome.ties / boards;
+        self.share += weight * outcome.share / boards;
+    }
+
+    fn merge(&mut self, other: &WeightedOutcome) {
+        self.weight += other.weight;
+        self.wins += other.wins;
+        self.ties += other.ties;
+        self.share += other.share;
+    }
+}
+
+impl RangeTally {
+    fn new(spot: &RangeSpot) -> Self {
+        Self {
+            trials: 0,
+            samples: 0,
+            players: spot
+                .live
+                .iter()
+                .map(|combos| PlayerRangeTally {
+                    combos: combos
+                        .iter()
+                        .map(|&(combo, _)| (combo, WeightedOutcome::default()))
+                        .collect(),
+                    ..Default::default()
+                })
+                .collect(),
+        }
+    }
+
+    // Records a deal, weighted by `weight`, whose outcomes were totalled over `boards` boards
+    fn record(&mut self, matchup: &[usize], weight: f64, outcomes: &[Outcome], boards: u64) {
+        self.trials += boards;
+        self.samples += 1;
+        let boards = boards as f64;
+        for ((player, &index), outcome) in self.players.iter_mut().zip(matchup).zip(outcomes) {
+            player.total.add(weight, outcome, boards);
+            player.combos[index].1.add(weight, outcome, boards);
+            let share = outcome.share / boards;
+            player.share_squared += weight * share * share;
+        }
+    }
+
+    fn into_result(self, seed: Option<u64>) -> RangeEquityResult {
+        let n = self.samples as f64;
+        let players = self
+            .players
+            .into_iter()
+            .map(|player| {
+                let total = player.total;
+                let equity = total.share / total.weight;
+                let std_error = if seed.is_some() && self.samples > 1 {
+                    let variance = (player.share_squared - n * equity * equity) / (n - 1.0);
+                    (variance.max(0.0) / n).sqrt()
+                } else {
+                    0.0
+                };
+
+                let mut combos: Vec<ComboEquity> = player
+                    .combos
+                    .iter()
+                    .filter(|(_, outcome)| outcome.weight > 0.0)
+                    .map(|&(combo, outcome)| ComboEquity {
+                        combo,
+                        frequency: outcome.weight / total.weight,
+                        equity: outcome.share / outcome.weight,
+                        win: outcome.wins / outcome.weight,
+                        tie: outcome.ties / outcome.weight,
+                    })
+                    .collect();
+                combos.sort_by(|a, b| b.equity.total_cmp(&a.equity));
+
+                RangeEquity {
+                    equity,
+                    win: total.wins / total.weight,
+                    tie: total.ties / total.weight,
+                    std_error,
+                    combos,
+                }
+            })
+            .collect();
+
+        RangeEquityResult {
+            trials: self.trials,
+            seed,
+            players,
+        }
+    }
+}
+
+impl Merge for RangeTally {
+    fn merge(&mut self, other: RangeTally) {
+        self.trials += other.trials;
+        self.samples += other.samples;
+        for (player, other) in self.players.iter_mut().zip(other.players) {
+            player.total.merge(&other.total);
+            player.share_squared += other.share_squared;
+            for ((_, combo), (_, other)) in player.combos.iter_mut().zip(&other.combos) {
+                combo.merge(other);
+            }
+        }
+    }
+}
@@ -0,0 +1,109 @@
+use super::super::*;
+use crate::{Card, Deck};
+use proptest::prelude::*;
+
+// Distinct hole cards for `players` players followed by a board of `board_len` cards
+fn arb_spot(
+    players: usize,
+    board_len: usize,
+) -> impl Strategy<Value = (Vec<[Card; 2]>, Vec<Card>)> {
+    let deck: Vec<Card> = Deck::new()
+        .cards()
+        .iter()
+        .filter(|c| c.suit.is_some())
+        .copied()
+        .collect();
+    Just(deck).prop_shuffle().prop_map(move |cards| {
+        let hands = cards[..2 * players]
+            .chunks(2)
+            .map(|c| [c[0], c[1]])
+            .collect();
+        let board = cards[2 * players..2 * players + board_len].to_vec();
+        (hands, board)
+    })
+}
+
+proptest! {
+    #![proptest_config(ProptestConfig::with_cases(32))]
+
+    #[test]
+    fn equities_sum_to_one(
+        (hands, board) in (2usize..=4, 0usize..=5).prop_flat_map(|(p, b)| arb_spot(p, b)),
+        seed in any::<u64>(),
+    ) {
+        let result = EquityCalculator::new(&hands)
+            .board(&board)
+            .iterations(200)
+            .seed(seed)
+            .monte_carlo()
+            .unwrap();
+
+        let total: f64 = result.players().iter().map(|p| p.equity()).sum();
+        prop_assert!((total - 1.0).abs() < 1e-9);
+        for player in result.players() {
+            prop_assert_eq!(player.wins() + player.ties() + player.losses(), 200);
+            prop_assert!(player.equity() >= 0.0 && player.equity() <= 1.0);
+        }
+    }
+}
+
+proptest! {
+    #![proptest_config(ProptestConfig::with_cases(16))]
+
+    #[test]
+    fn exact_shares_sum_to_one(
+        (hands, board) in (2usize..=4, 4usize..=5).prop_flat_map(|(p, b)| arb_spot(p, b)),
+    ) {
+        let result = EquityCalculator::new(&hands).board(&board).exact().unwrap();
+
+        let expected_trials = if board.len() == 5 { 1 } else { 52 - 2 * hands.len() as u64 - 4 };
+        prop_assert_eq!(result.trials(), expected_trials);
+
+        let (numer, denom) = result.players().iter().fold((0u128, 1u128), |(n, d), p| {
+            let ratio = p.exact_equity().unwrap();
+            (n * ratio.denom() + ratio.numer() * d, d * ratio.denom())
+        });
+        prop_assert_eq!(numer, denom);
+    }
+}
+
+// Between one and eight combos from the non-ace hands, at random weights
+fn arb_range() -> impl Strategy<Value = crate::range::Range> {
+    let combos: Vec<crate::range::Combo> = crate::range::StartingHand::all()
+        .filter(|hand| hand.high() != crate::Rank::Ace)
+        .flat_map(|hand| hand.combos())
+        .collect();
+    (
+        proptest::sample::subsequence(combos, 1..=8),
+        proptest::collection::vec(0.1f64..=1.0, 8),
+    )
+        .prop_map(|(combos, weights)| {
+            let mut range = crate::range::Range::new();
+            for (combo, weight) in combos.into_iter().zip(weights) {
+                range.insert(combo, weight);
+            }
+            range
+        })
+}
+
+proptest! {
+    #![proptest_config(ProptestConfig::with_cases(32))]
+
+    #[test]
+    fn range_equities_sum_to_one(
+        ranges in proptest::collection::vec(arb_range(), 2..=3),
+    ) {
+        let board = crate::notation::parse_cards("Ah Ad Ac As").unwrap();
+        let Ok(result) = RangeEquityCalculator::new(&ranges).board(&board).exact() else {
+            // Every combo of some range collides with the other ranges
+            return Ok(());
+        };
+
+        let total: f64 = result.players().iter().map(|p| p.equity()).sum();
+        prop_assert!((total - 1.0).abs() < 1e-9);
+        for player in result.players() {
+            let frequency: f64 = player.combos().iter().map(|c| c.frequency()).sum();
+            prop_assert!((frequency - 1.0).abs() < 1e-9);
+        }
+    }
+}
@@ -0,0 +1,582 @@
+use super::super::*;
+use crate::{Card, Rank, Suit};
+use std::ops::ControlFlow;
+
+fn card(rank: Rank, suit: Suit) -> Card {
+    Card::new(rank, Some(suit))
+}
+
+fn aces() -> [Card; 2] {
+    [card(Rank::Ace, Suit::Spades), card(Rank::Ace, Suit::Hearts)]
+}
+
+fn kings() -> [Card; 2] {
+    [
+        card(Rank::King, Suit::Spades),
+        card(Rank::King, Suit::Hearts),
+    ]
+}
+
+#[test]
+fn test_aces_are_favourites_against_kings() {
+    let result = EquityCalculator::new(&[aces(), kings()])
+        .iterations(20_000)
+        .seed(1)
+        .monte_carlo()
+        .unwrap();
+
+    let aces = &result.players()[0];
+    let kings = &result.players()[1];
+    assert_eq!(result.trials(), 20_000);
+    assert!((aces.equity() - 0.82).abs() < 0.02, "{}", aces.equity());
+    assert!((aces.equity() + kings.equity() - 1.0).abs() < 1e-9);
+    assert!(aces.std_error() > 0.0 && aces.std_error() < 0.01);
+}
+
+#[test]
+fn test_same_seed_gives_same_result() {
+    let calculator = EquityCalculator::new(&[aces(), kings()])
+        .iterations(2_000)
+        .seed(42);
+
+    assert_eq!(calculator.monte_carlo(), calculator.monte_carlo());
+}
+
+#[test]
+fn test_unseeded_run_reports_its_seed() {
+    let calculator = EquityCalculator::new(&[aces(), kings()]).iterations(500);
+    let first = calculator.monte_carlo().unwrap();
+
+    let repeated = calculator
+        .clone()
+        .seed(first.seed().unwrap())
+        .monte_carlo()
+        .unwrap();
+    assert_eq!(first, repeated);
+}
+
+#[test]
+fn test_complete_board_is_decided() {
+    let board = [
+        card(Rank::Ace, Suit::Clubs),
+        card(Rank::Seven, Suit::Diamonds),
+        card(Rank::Two, Suit::Clubs),
+        card(Rank::Nine, Suit::Hearts),
+        card(Rank::Four, Suit::Spades),
+    ];
+    let result = EquityCalculator::new(&[aces(), kings()])
+        .board(&board)
+        .iterations(100)
+        .seed(3)
+        .monte_carlo()
+        .unwrap();
+
+    assert_eq!(result.players()[0].wins(), 100);
+    assert_eq!(result.players()[0].win_percentage(), 100.0);
+    assert_eq!(result.players()[1].losses(), 100);
+    assert_eq!(result.players()[0].std_error(), 0.0);
+}
+
+#[test]
+fn test_split_pot_counts_as_ties() {
+    let board = [
+        card(Rank::Ace, Suit::Clubs),
+        card(Rank::King, Suit::Diamonds),
+        card(Rank::Queen, Suit::Clubs),
+        card(Rank::Jack, Suit::Hearts),
+        card(Rank::Ten, Suit::Spades),
+    ];
+    let first = [
+        card(Rank::Two, Suit::Spades),
+        card(Rank::Three, Suit::Hearts),
+    ];
+    let second = [
+        card(Rank::Two, Suit::Hearts),
+        card(Rank::Three, Suit::Spades),
+    ];
+    let result = EquityCalculator::new(&[first, second])
+        .board(&board)
+        .iterations(10)
+        .seed(3)
+        .monte_carlo()
+        .unwrap();
+
+    for player in result.players() {
+        assert_eq!(player.ties(), 10);
+        assert_eq!(player.tie_percentage(), 100.0);
+        assert_eq!(player.equity(), 0.5);
+    }
+}
+
+#[test]
+fn test_dead_cards_reduce_outs() {
+    // A flush draw against a set; killing the remaining hearts leaves the draw nothing
+    let draw = [
+        card(Rank::Ace, Suit::Hearts),
+        card(Rank::King, Suit::Hearts),
+    ];
+    let set = [
+        card(Rank::Seven, Suit::Spades),
+        card(Rank::Seven, Suit::Clubs),
+    ];
+    let board = [
+        card(Rank::Seven, Suit::Hearts),
+        card(Rank::Two, Suit::Hearts),
+        card(Rank::Nine, Suit::Clubs),
+        card(Rank::Three, Suit::Diamonds),
+    ];
+    let dead: Vec<Card> = [
+        Rank::Four,
+        Rank::Five,
+        Rank::Six,
+        Rank::Eight,
+        Rank::Nine,
+        Rank::Ten,
+        Rank::Jack,
+        Rank::Queen,
+        Rank::Three,
+    ]
+    .into_iter()
+    .map(|rank| card(rank, Suit::Hearts))
+    .collect();
+
+    let result = EquityCalculator::new(&[draw, set])
+        .board(&board)
+        .dead(&dead)
+        .iterations(1_000)
+        .seed(5)
+        .monte_carlo()
+        .unwrap();
+    assert_eq!(result.players()[0].wins(), 0);
+}
+
+#[test]
+fn test_multiway_equities_sum_to_one() {
+    let queens = [
+        card(Rank::Queen, Suit::Clubs),
+        card(Rank::Queen, Suit::Diamonds),
+    ];
+    let result = EquityCalculator::new(&[aces(), kings(), queens])
+        .iterations(5_000)
+        .seed(9)
+        .monte_carlo()
+        .unwrap();
+
+    let total: f64 = result.players().iter().map(|p| p.equity()).sum();
+    assert!((total - 1.0).abs() < 1e-9);
+}
+
+#[test]
+fn test_validation_errors() {
+    assert_eq!(
+        EquityCalculator::new(&[aces()]).monte_carlo(),
+        Err(EquityError::NotEnoughPlayers(1))
+    );
+    assert_eq!(
+        EquityCalculator::new(&[aces(), aces()]).monte_carlo(),
+        Err(EquityError::DuplicateCard(card(Rank::Ace, Suit::Spades)))
+    );
+    assert_eq!(
+        EquityCalculator::new(&[aces(), kings()])
+            .iterations(0)
+            .monte_carlo(),
+        Err(EquityError::NoIterations)
+    );
+
+    let board = vec![card(Rank::Two, Suit::Clubs); 6];
+    assert_eq!(
+        EquityCalculator::new(&[aces(), kings()])
+            .board(&board)
+            .monte_carlo(),
+        Err(EquityError::InvalidBoardSize(6))
+    );
+}
+
+#[test]
+fn test_not_enough_cards_to_complete_board() {
+    let hands: Vec<[Card; 2]> = Deck::new()
+        .cards()
+        .chunks(2)
+        .filter(|c| c.len() == 2 && c.iter().all(|c| c.suit.is_some()))
+        .map(|c| [c[0], c[1]])
+        .take(24)
+        .collect();
+
+    assert_eq!(
+        EquityCalculator::new(&hands).monte_carlo(),
+        Err(EquityError::NotEnoughCards {
+            needed: 5,
+            remaining: 4
+        })
+    );
+}
+
+fn flop() -> [Card; 3] {
+    [
+        card(Rank::Queen, Suit::Hearts),
+        card(Rank::Seven, Suit::Clubs),
+        card(Rank::Two, Suit::Diamonds),
+    ]
+}
+
+#[test]
+fn test_exact_enumerates_every_board() {
+    let result = EquityCalculator::new(&[aces(), kings()])
+        .board(&flop())
+        .exact()
+        .unwrap();
+
+    // 45 unseen cards, two to come
+    assert_eq!(result.trials(), 990);
+    assert!(result.is_exact());
+    assert_eq!(result.seed(), None);
+
+    let aces = &result.players()[0];
+    let kings = &result.players()[1];
+    assert_eq!(aces.wins() + aces.ties() + aces.losses(), 990);
+    assert_eq!(aces.wins(), kings.losses());
+    // Kings need one of the two remaining kings, 990 - C(43, 2) boards, without an ace
+    assert_eq!(kings.wins(), 990 - 903 - 4);
+    assert_eq!(kings.exact_equity(), Some(Ratio::new(83, 990)));
+    assert_eq!(aces.std_error(), 0.0);
+}
+
+#[test]
+fn test_exact_ratios_with_three_way_splits() {
+    let board = [
+        card(Rank::Ace, Suit::Clubs),
+        card(Rank::King, Suit::Diamonds),
+        card(Rank::Queen, Suit::Clubs),
+        card(Rank::Jack, Suit::Hearts),
+        card(Rank::Ten, Suit::Spades),
+    ];
+    let hands = [
+        [
+            card(Rank::Two, Suit::Spades),
+            card(Rank::Three, Suit::Hearts),
+        ],
+        [
+            card(Rank::Two, Suit::Hearts),
+            card(Rank::Three, Suit::Spades),
+        ],
+        [
+            card(Rank::Four, Suit::Hearts),
+            card(Rank::Five, Suit::Spades),
+        ],
+    ];
+    let result = EquityCalculator::new(&hands).board(&board).exact().unwrap();
+
+    assert_eq!(result.trials(), 1);
+    for player in result.players() {
+        assert_eq!(player.exact_equity(), Some(Ratio::new(1, 3)));
+        assert_eq!(player.exact_equity().unwrap().to_string(), "1/3");
+    }
+}
+
+#[test]
+fn test_monte_carlo_has_no_exact_equity() {
+    let result = EquityCalculator::new(&[aces(), kings()])
+        .iterations(100)
+        .seed(1)
+        .monte_carlo()
+        .unwrap();
+
+    assert!(!result.is_exact());
+    assert_eq!(result.players()[0].exact_equity(), None);
+}
+
+#[test]
+fn test_calculate_switches_to_monte_carlo_above_threshold() {
+    let calculator = EquityCalculator::new(&[aces(), kings()])
+        .board(&flop())
+        .iterations(500)
+        .seed(11);
+
+    let exact = calculator
+        .clone()
+        .max_combinations(990)
+        .calculate()
+        .unwrap();
+    assert!(exact.is_exact());
+    assert_eq!(exact.trials(), 990);
+
+    let sampled = calculator.max_combinations(989).calculate().unwrap();
+    assert!(!sampled.is_exact());
+    assert_eq!(sampled.trials(), 500);
+}
+
+#[test]
+fn test_exact_and_monte_carlo_agree() {
+    let calculator = EquityCalculator::new(&[aces(), kings()])
+        .board(&flop())
+        .iterations(20_000)
+        .seed(2);
+
+    let exact = calculator.exact().unwrap().players()[0].equity();
+    let sampled = calculator.monte_carlo().unwrap();
+    let player = &sampled.players()[0];
+    assert!((exact - player.equity()).abs() < 4.0 * player.std_error());
+}
+
+#[test]
+fn test_ratio_is_reduced() {
+    let ratio = Ratio::new(6, 8);
+    assert_eq!((ratio.numer(), ratio.denom()), (3, 4));
+    assert_eq!(ratio.to_f64(), 0.75);
+}
+
+#[test]
+fn test_threaded_monte_carlo_is_reproducible() {
+    let calculator = EquityCalculator::new(&[aces(), kings()])
+        .iterations(10_001)
+        .seed(8)
+        .threads(4);
+
+    let first = calculator.monte_carlo().unwrap();
+    assert_eq!(first.trials(), 10_001);
+    assert_eq!(first, calculator.monte_carlo().unwrap());
+}
+
+#[test]
+fn test_single_thread_matches_default() {
+    let calculator = EquityCalculator::new(&[aces(), kings()])
+        .iterations(3_000)
+        .seed(8);
+
+    assert_eq!(
+        calculator.monte_carlo(),
+        calculator.clone().threads(1).monte_carlo()
+    );
+}
+
+#[test]
+fn test_threaded_exact_matches_single_thread() {
+    let calculator = EquityCalculator::new(&[aces(), kings()]).board(&flop());
+
+    let single = calculator.exact().unwrap();
+    let threaded = calculator.clone().threads(3).exact().unwrap();
+    assert_eq!(single, threaded);
+
+    let all_cores = calculator.threads(0).exact().unwrap();
+    assert_eq!(single, all_cores);
+}
+
+#[test]
+fn test_progress_reaches_total() {
+    let mut reports = Vec::new();
+    let result = EquityCalculator::new(&[aces(), kings()])
+        .iterations(10_000)
+        .seed(4)
+        .threads(2)
+        .monte_carlo_with_progress(|progress| {
+            reports.push(progress);
+            ControlFlow::Continue(())
+        })
+        .unwrap();
+
+    assert_eq!(result.trials(), 10_000);
+    let last = reports.last().unwrap();
+    assert_eq!((last.completed, last.total), (10_000, 10_000));
+    assert_eq!(last.fraction(), 1.0);
+    assert!(reports.windows(2).all(|w| w[0].completed < w[1].completed));
+}
+
+#[test]
+fn test_progress_callback_can_cancel() {
+    let mut calls = 0;
+    let result = EquityCalculator::new(&[aces(), kings()])
+        .iterations(1_000_000)
+        .seed(4)
+        .threads(2)
+        .monte_carlo_with_progress(|_| {
+            calls += 1;
+            ControlFlow::Break(())
+        });
+
+    assert_eq!(result, Err(EquityError::Cancelled));
+    assert_eq!(calls, 1);
+}
+
+#[test]
+fn test_exact_progress_counts_every_board() {
+    let mut last = None;
+    EquityCalculator::new(&[aces(), kings()])
+        .board(&flop())
+        .threads(2)
+        .exact_with_progress(|progress| {
+            last = Some(progress);
+            ControlFlow::Continue(())
+        })
+        .unwrap();
+
+    assert_eq!(
+        last,
+        Some(Progress {
+            completed: 990,
+            total: 990
+        })
+    );
+}
+
+fn range(notation: &str) -> crate::range::Range {
+    notation.parse().unwrap()
+}
+
+#[test]
+fn test_single_combo_ranges_match_hand_equity() {
+    let hands = EquityCalculator::new(&[aces(), kings()])
+        .board(&flop())
+        .exact()
+        .unwrap();
+    let ranges = RangeEquityCalculator::new(&[range("AsAh"), range("KsKh")])
+        .board(&flop())
+        .exact()
+        .unwrap();
+
+    assert!(ranges.is_exact());
+    assert_eq!(ranges.trials(), hands.trials());
+    for (range, hand) in ranges.players().iter().zip(hands.players()) {
+        assert!((range.equity() - hand.equity()).abs() < 1e-12);
+        assert!((range.win_percentage() - hand.win_percentage()).abs() < 1e-9);
+    }
+}
+
+#[test]
+fn test_blocked_combos_are_never_dealt() {
+    let result = RangeEquityCalculator::new(&[range("AsAh"), range("AA, KK")])
+        .exact()
+        .unwrap();
+
+    let combos = result.players()[1].combos();
+    assert_eq!(combos.len(), 7);
+    assert!(combos.iter().all(|c| !c.combo().contains(&aces()[0])));
+    assert!(combos.iter().all(|c| !c.combo().contains(&aces()[1])));
+}
+
+#[test]
+fn test_board_and_dead_cards_remove_combos() {
+    let result = RangeEquityCalculator::new(&[range("QQ"), range("KK")])
+        .board(&flop())
+        .dead(&[card(Rank::Queen, Suit::Clubs)])
+        .exact()
+        .unwrap();
+
+    assert_eq!(result.players()[0].combos().len(), 1);
+    assert_eq!(result.players()[1].combos().len(), 6);
+}
+
+#[test]
+fn test_combos_are_dealt_by_weight() {
+    let board = [
+        card(Rank::Nine, Suit::Clubs),
+        card(Rank::Seven, Suit::Diamonds),
+        card(Rank::Three, Suit::Hearts),
+        card(Rank::Jack, Suit::Clubs),
+        card(Rank::Four, Suit::Spades),
+    ];
+    let result = RangeEquityCalculator::new(&[range("AsAh"), range("KK, 22:0.5")])
+        .board(&board)
+        .exact()
+        .unwrap();
+
+    let villain = &result.players()[1];
+    let twos: f64 = villain
+        .combos()
+        .iter()
+        .filter(|c| c.combo().high().rank == Rank::Two)
+        .map(|c| c.frequency())
+        .sum();
+    assert!((twos - 1.0 / 3.0).abs() < 1e-12, "{twos}");
+    let total: f64 = villain.combos().iter().map(|c| c.frequency()).sum();
+    assert!((total - 1.0).abs() < 1e-12);
+    // Aces beat every kings and deuces combo on this board
+    assert_eq!(result.players()[0].equity(), 1.0);
+}
+
+#[test]
+fn test_combo_breakdown_is_sorted_by_equity() {
+    let result = RangeEquityCalculator::new(&[range("JJ"), range("QQ, 33")])
+        .board(&flop())
+        .exact()
+        .unwrap();
+
+    let combos = result.players()[1].combos();
+    assert!(combos.windows(2).all(|w| w[0].equity() >= w[1].equity()));
+    assert!(combos[0].equity() > 0.9);
+    assert!(combos[combos.len() - 1].equity() < 0.1);
+}
+
+#[test]
+fn test_sampled_range_equity_agrees_with_exact() {
+    let calculator = RangeEquityCalculator::new(&[range("AKs"), range("QQ+, AK")])
+        .board(&flop())
+        .iterations(40_000)
+        .seed(9)
+        .threads(2);
+    let exact = calculator.exact().unwrap();
+    let sampled = calculator.monte_carlo().unwrap();
+
+    assert!(!sampled.is_exact());
+    assert_eq!(sampled.seed(), Some(9));
+    assert_eq!(sampled.trials(), 40_000);
+    let (exact, sampled) = (&exact.players()[0], &sampled.players()[0]);
+    assert!(
+        (exact.equity() - sampled.equity()).abs() < 0.02,
+        "{} vs {}",
+        exact.equity(),
+        sampled.equity()
+    );
+    assert!(sampled.std_error() > 0.0);
+}
+
+#[test]
+fn test_range_calculate_switches_to_sampling() {
+    let calculator = RangeEquityCalculator::new(&[range("AKs"), range("QQ+")])
+        .board(&flop())
+        .iterations(1_000)
+        .seed(3);
+
+    assert!(calculator.calculate().unwrap().is_exact());
+    assert!(
+        !calculator
+            .max_combinations(100)
+            .calculate()
+            .unwrap()
+            .is_exact()
+    );
+}
+
+#[test]
+fn test_range_threads_give_same_exact_result() {
+    let calculator = RangeEquityCalculator::new(&[range("TT+"), range("AK, AQs")]).board(&flop());
+
+    let single = calculator.clone().exact().unwrap();
+    let multi = calculator.threads(3).exact().unwrap();
+    for (a, b) in single.players().iter().zip(multi.players()) {
+        assert!((a.equity() - b.equity()).abs() < 1e-12);
+    }
+}
+
+#[test]
+fn test_range_errors() {
+    assert_eq!(
+        RangeEquityCalculator::new(&[range("AA")]).exact(),
+        Err(EquityError::NotEnoughPlayers(1))
+    );
+    assert_eq!(
+        RangeEquityCalculator::new(&[range("AA"), range("KsKh")])
+            .dead(&[card(Rank::King, Suit::Spades)])
+            .exact(),
+        Err(EquityError::EmptyRange(1))
+    );
+    assert_eq!(
+        RangeEquityCalculator::new(&[range("AsAh"), range("AsAh")]).exact(),
+        Err(EquityError::NoValidMatchup)
+    );
+    assert_eq!(
+        RangeEquityCalculator::new(&[range("AA"), range("KK")])
+            .iterations(0)
+            .monte_carlo(),
+        Err(EquityError::NoIterations)
+    );
+}
@@ -0,0 +1,278 @@
+use std::fmt;
+
+use crate::Card;
+
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
+#[cfg(test)]
+mod tests;
+
+/// The category of a poker hand, ordered from weakest to strongest
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub enum HandCategory {
+    HighCard,
+    OnePair,
+    TwoPair,
+    ThreeOfAKind,
+    Straight,
+    Flush,
+    FullHouse,
+    FourOfAKind,
+    StraightFlush,
+}
+
+impl HandCategory {
+    const ALL: [HandCategory; 9] = [
+        HandCategory::HighCard,
+        HandCategory::OnePair,
+        HandCategory::TwoPair,
+        HandCategory::ThreeOfAKind,
+        HandCategory::Straight,
+        HandCategory::Flush,
+        HandCategory::FullHouse,
+        HandCategory::FourOfAKind,
+        HandCategory::StraightFlush,
+    ];
+}
+
+impl fmt::Display for HandCategory {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let name = match self {
+            HandCategory::HighCard => "high card",
+            HandCategory::OnePair => "a pair",
+            HandCategory::TwoPair => "two pair",
+            HandCategory::ThreeOfAKind => "three of a kind",
+            HandCategory::Straight => "a straight",
+            HandCategory::Flush => "a flush",
+            HandCategory::FullHouse => "a full house",
+            HandCategory::FourOfAKind => "four of a kind",
+            HandCategory::StraightFlush => "a straight flush",
+        };
+        f.write_str(name)
+    }
+}
+
+/// The strength of the best five-card poker hand among a set of cards
+///
+/// Values compare directly: a greater `HandValue` wins at showdown and equal values split.
+///
+/// # Examples
+///
+/// ```
+/// use cards::{Card, Rank, Suit};
+/// use cards::eval::{evaluate, HandCategory};
+///
+/// let full_house = evaluate(&[
+///     Card::new(Rank::King, Some(Suit::Spades)),
+///     Card::new(Rank::King, Some(Suit::Hearts)),
+///     Card::new(Rank::King, Some(Suit::Clubs)),
+///     Card::new(Rank::Two, Some(Suit::Hearts)),
+///     Card::new(Rank::Two, Some(Suit::Diamonds)),
+/// ]);
+/// let flush = evaluate(&[
+///     Card::new(Rank::Ace, Some(Suit::Hearts)),
+///     Card::new(Rank::Jack, Some(Suit::Hearts)),
+///     Card::new(Rank::Eight, Some(Suit::Hearts)),
+///     Card::new(Rank::Four, Some(Suit::Hearts)),
+///     Card::new(Rank::Three, Some(Suit::Hearts)),
+/// ]);
+///
+/// assert_eq!(full_house.category(), HandCategory::FullHouse);
+/// assert!(full_house > flush);
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct HandValue(u32);
+
+impl HandValue {
+    fn new(category: HandCategory, ranks: &[u8]) -> Self {
+        let mut value = (category as u32) << 20;
+        for (i, &rank) in ranks.iter().enumerate().take(5) {
+            value |= (rank as u32) << (16 - 4 * i);
+        }
+        Self(value)
+    }
+
+    pub fn category(&self) -> HandCategory {
+        HandCategory::ALL[(self.0 >> 20) as usize]
+    }
+
+    /// The poker values (2 to 14) deciding the hand within its category, most significant first
+    ///
+    /// A straight is described by its high card alone, with 5 for the wheel.
+    pub fn ranks(&self) -> Vec<u8> {
+        (0..5)
+            .map(|i| ((self.0 >> (16 - 4 * i)) & 0xF) as u8)
+            .take_while(|&rank| rank != 0)
+            .collect()
+    }
+}
+
+/// Evaluates the best five-card hand that can be made from `cards`
+///
+/// Any number of cards may be given; with fewer than five the hand is judged on what is
+/// there, so two hole cards evaluate to a pair or high card. Cards without a suit are ignored.
+pub fn evaluate(cards: &[Card]) -> HandValue {
+    let mut suits = [0u16; 4];
+    let mut counts = [0u8; 15];
+    for card in cards {
+        let Some(suit) = card.suit else { continue };
+        let value = card.rank.poker_value();
+        suits[suit as usize] |= 1 << value;
+        counts[value as usize] = counts[value as usize].saturating_add(1);
+    }
+
+    // With more than seven cards several suits can flush, and a flush can come with quads or
+    // a full house, so the best flush is only taken once those are ruled out
+    let flush = suits
+        .iter()
+        .filter(|mask| mask.count_ones() >= 5)
+        .map(|&mask| match straight_high(mask) {
+            Some(high) => HandValue::new(HandCategory::StraightFlush, &[high]),
+            None => HandValue::new(HandCategory::Flush, &top::<5>(mask)),
+        })
+        .max();
+    if let Some(value) = flush
+        && value.category() == HandCategory::StraightFlush
+    {
+        return value;
+    }
+
+    // Cards from more than one deck can hold five or more of a rank, which still make quads
+    let mut groups = [0u16; 5];
+    for value in 2..=14 {
+        groups[counts[value].min(4) as usize] |= 1 << value;
+    }
+    let [_, singles, pairs, trips, quads] = groups;
+    let ranks = singles | pairs | trips | quads;
+
+    if quads != 0 {
+        let [quad] = top::<1>(quads);
+        let [kicker] = top::<1>(ranks & !(1 << quad));
+        return HandValue::new(HandCategory::FourOfAKind, &[quad, kicker]);
+    }
+    if trips != 0 {
+        let [trip] = top::<1>(trips);
+        let [pair] = top::<1>((trips & !(1 << trip)) | pairs);
+        if pair != 0 {
+            return HandValue::new(HandCategory::FullHouse, &[trip, pair]);
+        }
+    }
+    if let Some(value) = flush {
+        return value;
+    }
+    if let Some(high) = straight_high(ranks) {
+        return HandValue::new(HandCategory::Straight, &[high]);
+    }
+    if trips != 0 {
+        let [trip] = top::<1>(trips);
+        let [a, b] = top::<2>(singles);
+        return HandValue::new(HandCategory::ThreeOfAKind, &[trip, a, b]);
+    }
+    if pairs.count_ones() >= 2 {
+        let [high, low] = top::<2>(pairs);
+        let [kicker] = top::<1>(ranks & !(1 << high | 1 << low));
+        return HandValue::new(HandCategory::TwoPair, &[high, low, kicker]);
+    }
+    if pairs != 0 {
+        let [pair] = top::<1>(pairs);
+        let [a, b, c] = top::<3>(singles);
+        return HandValue::new(HandCategory::OnePair, &[pair, a, b, c]);
+    }
+    HandValue::new(HandCategory::HighCard, &top::<5>(singles))
+}
+
+/// The strength of the best ace-to-five low among a set of cards, eight or better
+///
+/// A low is five cards of different ranks from eight down, with aces low; straights and
+/// flushes do not count against it. Values compare directly: a greater `LowValue` is a better
+/// low, so the wheel (`5-4-3-2-A`) beats every other.
+///
+/// # Examples
+///
+/// ```
+/// use cards::eval::evaluate_low;
+/// use cards::notation::parse_cards;
+///
+/// let wheel = evaluate_low(&parse_cards("Ah 2c 3d 4s 5h Kd Kc").unwrap()).unwrap();
+/// let eight = evaluate_low(&parse_cards("8h 6c 4d 3s 2h").unwrap()).unwrap();
+///
+/// assert!(wheel > eight);
+/// assert_eq!(eight.ranks(), [8, 6, 4, 3, 2]);
+/// assert_eq!(evaluate_low(&parse_cards("9h 6c 4d 3s 2h").unwrap()), None);
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct LowValue(u32);
+
+impl LowValue {
+    // Five ranks from high to low; a smaller packed value is a better low, so it is inverted
+    fn new(ranks: [u8; 5]) -> Self {
+        let packed = ranks.iter().enumerate().fold(0, |packed, (i, &rank)| {
+            packed | (rank as u32) << (16 - 4 * i)
+        });
+        Self(LOW_MASK - packed)
+    }
+
+    /// The ranks of the low from highest to lowest, with 1 for an ace
+    pub fn ranks(&self) -> [u8; 5] {
+        let packed = LOW_MASK - self.0;
+        std::array::from_fn(|i| ((packed >> (16 - 4 * i)) & 0xF) as u8)
+    }
+}
+
+const LOW_MASK: u32 = 0xF_FFFF;
+
+/// Evaluates the best eight-or-better ace-to-five low that can be made from `cards`, or `None`
+/// if there are not five different ranks from eight down
+///
+/// Cards without a suit are ignored.
+pub fn evaluate_low(cards: &[Card]) -> Option<LowValue> {
+    let mut mask = 0u16;
+    for card in cards.iter().filter(|card| card.suit.is_some()) {
+        let value = match card.rank.poker_value() {
+            14 => 1,
+            value => value,
+        };
+        if value <= 8 {
+            mask |= 1 << value;
+        }
+    }
+
+    // The five lowest ranks make the best low, written highest first
+    let mut ranks = [0; 5];
+    for slot in ranks.iter_mut().rev() {
+        if mask == 0 {
+            return None;
+        }
+        let low = mask.trailing_zeros() as u8;
+        *slot = low;
+        mask &= !(1 << low);
+    }
+    Some(LowValue::new(ranks))
+}
+
+// The `N` highest values set in `mask`, padded with zeros when there are fewer
+fn top<const N: usize>(mut mask: u16) -> [u8; N] {
+    let mut values = [0; N];
+    for value in values.iter_mut() {
+        if mask == 0 {
+            break;
+        }
+        let high = 15 - mask.leading_zeros() as u8;
+        *value = high;
+        mask &= !(1 << high);
+    }
+    values
+}
+
+// The high card of the best straight in a rank mask, treating the ace as low as well
+fn straight_high(mask: u16) -> Option<u8> {
+    let mask = mask | (mask >> 14 & 1) << 1;
+    (5..=14).rev().find(|&high| {
+        let window = 0b11111 << (high - 4);
+        mask & window == window
+    })
+}
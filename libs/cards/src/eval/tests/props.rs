@@ -0,0 +1,69 @@
+use super::super::*;
+use crate::{Card, Deck};
+use proptest::prelude::*;
+
+fn arb_cards(count: usize) -> impl Strategy<Value = Vec<Card>> {
+    let deck: Vec<Card> = Deck::new()
+        .cards()
+        .iter()
+        .filter(|c| c.suit.is_some())
+        .copied()
+        .collect();
+    Just(deck)
+        .prop_shuffle()
+        .prop_map(move |cards| cards[..count].to_vec())
+}
+
+// Reference evaluation: the best of every five-card subset
+fn best_of_fives(cards: &[Card]) -> HandValue {
+    let mut best = None;
+    for a in 0..cards.len() {
+        for b in a + 1..cards.len() {
+            let five: Vec<Card> = cards
+                .iter()
+                .enumerate()
+                .filter(|&(i, _)| i != a && i != b)
+                .map(|(_, c)| *c)
+                .collect();
+            let value = evaluate(&five);
+            best = best.max(Some(value));
+        }
+    }
+    best.unwrap()
+}
+
+proptest! {
+    #[test]
+    fn seven_cards_match_best_five_card_subset(cards in arb_cards(7)) {
+        prop_assert_eq!(evaluate(&cards), best_of_fives(&cards));
+    }
+
+    #[test]
+    fn card_order_does_not_matter(cards in arb_cards(7)) {
+        let mut reversed = cards.clone();
+        reversed.reverse();
+        prop_assert_eq!(evaluate(&cards), evaluate(&reversed));
+    }
+
+    #[test]
+    fn adding_a_card_never_weakens_the_hand(cards in arb_cards(7)) {
+        prop_assert!(evaluate(&cards) >= evaluate(&cards[..6]));
+    }
+
+    #[test]
+    fn seven_card_low_matches_best_five_card_subset(cards in arb_cards(7)) {
+        let mut best = None;
+        for a in 0..cards.len() {
+            for b in a + 1..cards.len() {
+                let five: Vec<Card> = cards
+                    .iter()
+                    .enumerate()
+                    .filter(|&(i, _)| i != a && i != b)
+                    .map(|(_, c)| *c)
+                    .collect();
+                best = best.max(evaluate_low(&five));
+            }
+        }
+        prop_assert_eq!(evaluate_low(&cards), best);
+    }
+}
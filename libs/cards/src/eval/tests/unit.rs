@@ -0,0 +1,309 @@
+use super::super::*;
+use crate::{Card, Rank, Suit};
+
+fn card(rank: Rank, suit: Suit) -> Card {
+    Card::new(rank, Some(suit))
+}
+
+#[test]
+fn test_high_card() {
+    let value = evaluate(&[
+        card(Rank::Ace, Suit::Spades),
+        card(Rank::Jack, Suit::Hearts),
+        card(Rank::Eight, Suit::Clubs),
+        card(Rank::Five, Suit::Diamonds),
+        card(Rank::Three, Suit::Spades),
+    ]);
+    assert_eq!(value.category(), HandCategory::HighCard);
+    assert_eq!(value.ranks(), vec![14, 11, 8, 5, 3]);
+}
+
+#[test]
+fn test_categories_of_seven_card_hands() {
+    let cases = [
+        (
+            [
+                card(Rank::King, Suit::Spades),
+                card(Rank::King, Suit::Hearts),
+                card(Rank::Nine, Suit::Clubs),
+                card(Rank::Five, Suit::Diamonds),
+                card(Rank::Three, Suit::Spades),
+                card(Rank::Two, Suit::Spades),
+                card(Rank::Seven, Suit::Hearts),
+            ],
+            HandCategory::OnePair,
+        ),
+        (
+            [
+                card(Rank::King, Suit::Spades),
+                card(Rank::King, Suit::Hearts),
+                card(Rank::Nine, Suit::Clubs),
+                card(Rank::Nine, Suit::Diamonds),
+                card(Rank::Three, Suit::Spades),
+                card(Rank::Three, Suit::Clubs),
+                card(Rank::Seven, Suit::Hearts),
+            ],
+            HandCategory::TwoPair,
+        ),
+        (
+            [
+                card(Rank::Four, Suit::Spades),
+                card(Rank::Four, Suit::Hearts),
+                card(Rank::Four, Suit::Clubs),
+                card(Rank::Nine, Suit::Diamonds),
+                card(Rank::King, Suit::Spades),
+                card(Rank::Two, Suit::Clubs),
+                card(Rank::Seven, Suit::Hearts),
+            ],
+            HandCategory::ThreeOfAKind,
+        ),
+        (
+            [
+                card(Rank::Ace, Suit::Spades),
+                card(Rank::Two, Suit::Hearts),
+                card(Rank::Three, Suit::Clubs),
+                card(Rank::Four, Suit::Diamonds),
+                card(Rank::Five, Suit::Spades),
+                card(Rank::King, Suit::Clubs),
+                card(Rank::King, Suit::Hearts),
+            ],
+            HandCategory::Straight,
+        ),
+        (
+            [
+                card(Rank::Ace, Suit::Hearts),
+                card(Rank::Two, Suit::Hearts),
+                card(Rank::Nine, Suit::Hearts),
+                card(Rank::Four, Suit::Hearts),
+                card(Rank::Five, Suit::Hearts),
+                card(Rank::Three, Suit::Clubs),
+                card(Rank::King, Suit::Spades),
+            ],
+            HandCategory::Flush,
+        ),
+        (
+            [
+                card(Rank::Four, Suit::Spades),
+                card(Rank::Four, Suit::Hearts),
+                card(Rank::Four, Suit::Clubs),
+                card(Rank::Nine, Suit::Diamonds),
+                card(Rank::Nine, Suit::Spades),
+                card(Rank::Nine, Suit::Clubs),
+                card(Rank::Seven, Suit::Hearts),
+            ],
+            HandCategory::FullHouse,
+        ),
+        (
+            [
+                card(Rank::Four, Suit::Spades),
+                card(Rank::Four, Suit::Hearts),
+                card(Rank::Four, Suit::Clubs),
+                card(Rank::Four, Suit::Diamonds),
+                card(Rank::Nine, Suit::Spades),
+                card(Rank::Nine, Suit::Clubs),
+                card(Rank::Nine, Suit::Hearts),
+            ],
+            HandCategory::FourOfAKind,
+        ),
+        (
+            [
+                card(Rank::Ace, Suit::Hearts),
+                card(Rank::Two, Suit::Hearts),
+                card(Rank::Three, Suit::Hearts),
+                card(Rank::Four, Suit::Hearts),
+                card(Rank::Five, Suit::Hearts),
+                card(Rank::Six, Suit::Clubs),
+                card(Rank::King, Suit::Spades),
+            ],
+            HandCategory::StraightFlush,
+        ),
+    ];
+
+    for (cards, category) in cases {
+        assert_eq!(evaluate(&cards).category(), category, "{cards:?}");
+    }
+}
+
+#[test]
+fn test_wheel_loses_to_six_high_straight() {
+    let wheel = evaluate(&[
+        card(Rank::Ace, Suit::Spades),
+        card(Rank::Two, Suit::Hearts),
+        card(Rank::Three, Suit::Clubs),
+        card(Rank::Four, Suit::Diamonds),
+        card(Rank::Five, Suit::Spades),
+    ]);
+    let six_high = evaluate(&[
+        card(Rank::Six, Suit::Spades),
+        card(Rank::Two, Suit::Hearts),
+        card(Rank::Three, Suit::Clubs),
+        card(Rank::Four, Suit::Diamonds),
+        card(Rank::Five, Suit::Spades),
+    ]);
+    assert_eq!(wheel.ranks(), vec![5]);
+    assert!(six_high > wheel);
+}
+
+#[test]
+fn test_kickers_break_ties() {
+    let board = [
+        card(Rank::King, Suit::Spades),
+        card(Rank::King, Suit::Hearts),
+        card(Rank::Nine, Suit::Clubs),
+        card(Rank::Five, Suit::Diamonds),
+        card(Rank::Three, Suit::Spades),
+    ];
+    let mut ace_kicker = board.to_vec();
+    ace_kicker.extend([card(Rank::Ace, Suit::Clubs), card(Rank::Two, Suit::Clubs)]);
+    let mut queen_kicker = board.to_vec();
+    queen_kicker.extend([
+        card(Rank::Queen, Suit::Clubs),
+        card(Rank::Two, Suit::Hearts),
+    ]);
+
+    assert!(evaluate(&ace_kicker) > evaluate(&queen_kicker));
+}
+
+#[test]
+fn test_board_plays_is_a_tie() {
+    let board = [
+        card(Rank::Ace, Suit::Spades),
+        card(Rank::King, Suit::Hearts),
+        card(Rank::Queen, Suit::Clubs),
+        card(Rank::Jack, Suit::Diamonds),
+        card(Rank::Ten, Suit::Spades),
+    ];
+    let mut first = board.to_vec();
+    first.extend([card(Rank::Two, Suit::Clubs), card(Rank::Three, Suit::Clubs)]);
+    let mut second = board.to_vec();
+    second.extend([
+        card(Rank::Four, Suit::Hearts),
+        card(Rank::Six, Suit::Hearts),
+    ]);
+
+    assert_eq!(evaluate(&first), evaluate(&second));
+}
+
+#[test]
+fn test_third_pair_can_be_the_two_pair_kicker() {
+    let value = evaluate(&[
+        card(Rank::King, Suit::Spades),
+        card(Rank::King, Suit::Hearts),
+        card(Rank::Nine, Suit::Clubs),
+        card(Rank::Nine, Suit::Diamonds),
+        card(Rank::Seven, Suit::Spades),
+        card(Rank::Seven, Suit::Clubs),
+        card(Rank::Two, Suit::Hearts),
+    ]);
+    assert_eq!(value.category(), HandCategory::TwoPair);
+    assert_eq!(value.ranks(), vec![13, 9, 7]);
+}
+
+#[test]
+fn test_hole_cards_alone() {
+    let pair = evaluate(&[card(Rank::Ace, Suit::Spades), card(Rank::Ace, Suit::Hearts)]);
+    let high = evaluate(&[
+        card(Rank::Ace, Suit::Spades),
+        card(Rank::King, Suit::Spades),
+    ]);
+    assert_eq!(pair.category(), HandCategory::OnePair);
+    assert_eq!(high.category(), HandCategory::HighCard);
+    assert!(pair > high);
+}
+
+#[test]
+fn test_more_than_seven_cards() {
+    use crate::notation::parse_cards;
+
+    // A club flush and a higher spade flush
+    let flushes = evaluate(&parse_cards("2c 5c 7c 9c Jc 3s 6s 8s Ts Ks").unwrap());
+    assert_eq!(flushes.category(), HandCategory::Flush);
+    assert_eq!(flushes.ranks(), vec![13, 10, 8, 6, 3]);
+
+    // A straight flush in the second suit to flush
+    let straight_flush = evaluate(&parse_cards("Ac Kc 9c 7c 2c 5s 6s 7s 8s 9s").unwrap());
+    assert_eq!(straight_flush.category(), HandCategory::StraightFlush);
+    assert_eq!(straight_flush.ranks(), vec![9]);
+
+    // Quads and a full house beat a flush in the same cards
+    let quads = evaluate(&parse_cards("Ah Kh 9h 7h 2h Ac Ad As").unwrap());
+    assert_eq!(quads.category(), HandCategory::FourOfAKind);
+    let full_house = evaluate(&parse_cards("Ah Kh 9h 7h 2h Ac Ad Kc").unwrap());
+    assert_eq!(full_house.category(), HandCategory::FullHouse);
+}
+
+#[test]
+fn test_five_of_a_rank_make_quads() {
+    use crate::notation::parse_cards;
+
+    let value = evaluate(&parse_cards("As Ah Ad Ac As Kd 7c").unwrap());
+    assert_eq!(value.category(), HandCategory::FourOfAKind);
+    assert_eq!(value.ranks(), vec![14, 13]);
+}
+
+#[test]
+fn test_category_display() {
+    assert_eq!(HandCategory::FullHouse.to_string(), "a full house");
+    assert_eq!(HandCategory::TwoPair.to_string(), "two pair");
+}
+
+#[test]
+fn test_low_takes_the_five_lowest_ranks() {
+    let low = evaluate_low(&[
+        card(Rank::Ace, Suit::Spades),
+        card(Rank::Seven, Suit::Hearts),
+        card(Rank::Three, Suit::Clubs),
+        card(Rank::Three, Suit::Diamonds),
+        card(Rank::Six, Suit::Spades),
+        card(Rank::Two, Suit::Clubs),
+        card(Rank::King, Suit::Hearts),
+    ])
+    .unwrap();
+    assert_eq!(low.ranks(), [7, 6, 3, 2, 1]);
+}
+
+#[test]
+fn test_low_compares_from_the_highest_card() {
+    let seven_six = evaluate_low(&[
+        card(Rank::Seven, Suit::Spades),
+        card(Rank::Six, Suit::Hearts),
+        card(Rank::Three, Suit::Clubs),
+        card(Rank::Two, Suit::Diamonds),
+        card(Rank::Ace, Suit::Spades),
+    ]);
+    let seven_five = evaluate_low(&[
+        card(Rank::Seven, Suit::Spades),
+        card(Rank::Five, Suit::Hearts),
+        card(Rank::Four, Suit::Clubs),
+        card(Rank::Three, Suit::Diamonds),
+        card(Rank::Two, Suit::Spades),
+    ]);
+    assert!(seven_five > seven_six);
+}
+
+#[test]
+fn test_straights_and_flushes_do_not_spoil_a_low() {
+    let wheel = evaluate_low(&[
+        card(Rank::Five, Suit::Hearts),
+        card(Rank::Four, Suit::Hearts),
+        card(Rank::Three, Suit::Hearts),
+        card(Rank::Two, Suit::Hearts),
+        card(Rank::Ace, Suit::Hearts),
+    ])
+    .unwrap();
+    assert_eq!(wheel.ranks(), [5, 4, 3, 2, 1]);
+}
+
+#[test]
+fn test_no_low_without_five_low_ranks() {
+    let paired = [
+        card(Rank::Ace, Suit::Spades),
+        card(Rank::Ace, Suit::Hearts),
+        card(Rank::Two, Suit::Clubs),
+        card(Rank::Three, Suit::Diamonds),
+        card(Rank::Four, Suit::Spades),
+        card(Rank::Nine, Suit::Clubs),
+        card(Rank::Ten, Suit::Clubs),
+    ];
+    assert_eq!(evaluate_low(&paired), None);
+}
@@ -0,0 +1,332 @@
+use std::collections::HashMap;
+use std::fmt;
+
+use rand::rngs::StdRng;
+use rand::{Rng, SeedableRng};
+
+use crate::equity::EquityError;
+
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
+mod push_fold;
+
+pub use push_fold::{PushFold, PushFoldDecision};
+
+#[cfg(test)]
+mod tests;
+
+/// Number of finishing orders sampled by the approximation when no iteration count is configured
+pub const DEFAULT_ITERATIONS: u64 = 100_000;
+
+/// Largest number of partial finishing orders `calculate` will work through exactly before
+/// approximating instead
+///
+/// This covers any final table and fields of around thirty players paying a few places.
+pub const DEFAULT_MAX_STATES: u64 = 1_000_000;
+
+// Players are tracked in a bitmask during the exact calculation
+const MAX_EXACT_PLAYERS: usize = 128;
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum IcmError {
+    /// No player has any chips
+    NoChips,
+    /// The payout for a place is negative or not a number
+    InvalidPayout(usize),
+    /// The exact calculation can only follow up to 128 players with chips
+    TooManyPlayers(usize),
+    /// The approximation was asked for zero iterations
+    NoIterations,
+    /// A seat that is not at the table, or one given two roles that exclude each other
+    InvalidSeat(usize),
+    /// A seat involved in a push or call has no chips
+    EmptyStack(usize),
+    /// The preflop equity of a push/fold spot could not be calculated
+    Equity(EquityError),
+}
+
+impl fmt::Display for IcmError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            IcmError::NoChips => write!(f, "no player has any chips"),
+            IcmError::InvalidPayout(place) => {
+                write!(f, "payout for place {} is not a valid amount", place + 1)
+            }
+            IcmError::TooManyPlayers(count) => write!(
+                f,
+                "exact ICM supports at most {MAX_EXACT_PLAYERS} players with chips, got {count}"
+            ),
+            IcmError::NoIterations => write!(f, "iteration count must be greater than zero"),
+            IcmError::InvalidSeat(seat) => write!(f, "seat {seat} is not valid here"),
+            IcmError::EmptyStack(seat) => write!(f, "seat {seat} has no chips"),
+            IcmError::Equity(e) => write!(f, "equity calculation failed: {e}"),
+        }
+    }
+}
+
+impl std::error::Error for IcmError {}
+
+impl From<EquityError> for IcmError {
+    fn from(e: EquityError) -> Self {
+        IcmError::Equity(e)
+    }
+}
+
+/// Calculates each player's share of a prize pool from their chip stacks with the Independent
+/// Chip Model
+///
+/// The model (Malmuth–Harville) takes a player's chance of finishing first to be their share of
+/// the chips, and their chance of each later place to follow the same rule among the players
+/// not yet placed. Players with no chips are out and win nothing; places beyond the number of
+/// players with chips are not paid.
+///
+/// # Examples
+///
+/// ```
+/// use cards::icm::IcmCalculator;
+///
+/// let result = IcmCalculator::new(&[5_000, 3_000, 2_000], &[50.0, 30.0, 20.0])
+///     .exact()
+///     .unwrap();
+///
+/// assert!((result.equities()[0] - 38.392857).abs() < 1e-6);
+/// assert!((result.equities().iter().sum::<f64>() - 100.0).abs() < 1e-9);
+/// ```
+#[derive(Debug, Clone)]
+pub struct IcmCalculator {
+    stacks: Vec<f64>,
+    payouts: Vec<f64>,
+    iterations: u64,
+    seed: Option<u64>,
+    max_states: u64,
+}
+
+impl IcmCalculator {
+    /// Creates a calculator for chip stacks by seat and prize amounts from first place down
+    pub fn new(stacks: &[u64], payouts: &[f64]) -> Self {
+        Self::with_stacks(stacks.iter().map(|&chips| chips as f64).collect(), payouts)
+    }
+
+    // Stacks may be fractional once a pot has been split
+    pub(crate) fn with_stacks(stacks: Vec<f64>, payouts: &[f64]) -> Self {
+        Self {
+            stacks,
+            payouts: payouts.to_vec(),
+            iterations: DEFAULT_ITERATIONS,
+            seed: None,
+            max_states: DEFAULT_MAX_STATES,
+        }
+    }
+
+    /// Sets the number of finishing orders sampled by `approximate`
+    pub fn iterations(mut self, iterations: u64) -> Self {
+        self.iterations = iterations;
+        self
+    }
+
+    /// Fixes the random seed so that approximate results are reproducible
+    pub fn seed(mut self, seed: u64) -> Self {
+        self.seed = Some(seed);
+        self
+    }
+
+    /// Sets how many partial finishing orders `calculate` may work through before it
+    /// approximates instead
+    pub fn max_states(mut self, max_states: u64) -> Self {
+        self.max_states = max_states;
+        self
+    }
+
+    /// Calculates equities exactly when there are at most `max_states` partial finishing orders
+    /// to follow, and by sampling finishing orders otherwise
+    pub fn calculate(&self) -> Result<IcmResult, IcmError> {
+        let live = self.validate()?;
+        let places = self.payouts.len().min(live.len());
+        let states = (0..places).fold(0u64, |states, k| {
+            states.saturating_add(binomial(live.len() as u64, k as u64))
+        });
+        if live.len() <= MAX_EXACT_PLAYERS && states <= self.max_states {
+            Ok(self.run_exact(&live))
+        } else {
+            self.run_approximate(&live)
+        }
+    }
+
+    /// Calculates equities exactly by following every order in which the paid places can fill
+    ///
+    /// The work grows with the number of ways to choose the paid places from the field, so
+    /// this suits final tables and small fields.
+    pub fn exact(&self) -> Result<IcmResult, IcmError> {
+        let live = self.validate()?;
+        if live.len() > MAX_EXACT_PLAYERS {
+            return Err(IcmError::TooManyPlayers(live.len()));
+        }
+        Ok(self.run_exact(&live))
+    }
+
+    /// Estimates equities by sampling finishing orders, which scales to fields of any size
+    ///
+    /// Each sample draws an exponential time for every player at a rate equal to their stack
+    /// and finishes them in that order, which follows the same distribution as the exact model.
+    pub fn approximate(&self) -> Result<IcmResult, IcmError> {
+        let live = self.validate()?;
+        self.run_approximate(&live)
+    }
+
+    // Checks the inputs and returns the seats that still have chips
+    fn validate(&self) -> Result<Vec<usize>, IcmError> {
+        if let Some(place) = self
+            .payouts
+            .iter()
+            .position(|payout| !payout.is_finite() || *payout < 0.0)
+        {
+            return Err(IcmError::InvalidPayout(place));
+        }
+        let live: Vec<usize> = (0..self.stacks.len())
+            .filter(|&seat| self.stacks[seat] > 0.0)
+            .collect();
+        if live.is_empty() {
+            return Err(IcmError::NoChips);
+        }
+        Ok(live)
+    }
+
+    fn run_exact(&self, live: &[usize]) -> IcmResult {
+        let places = self.payouts.len().min(live.len());
+        let total: f64 = live.iter().map(|&seat| self.stacks[seat]).sum();
+        let mut finishes = vec![vec![0.0; self.payouts.len()]; self.stacks.len()];
+
+        // The chance that exactly the players in each mask took the places so far
+        let mut level: HashMap<u128, f64> = HashMap::from([(0, 1.0)]);
+        for place in 0..places {
+            let mut next = HashMap::new();
+            let mut placed_here = vec![0.0; self.stacks.len()];
+            for (&mask, &chance) in &level {
+                let placed: f64 = live
+                    .iter()
+                    .enumerate()
+                    .filter(|(bit, _)| mask & (1 << bit) != 0)
+                    .map(|(_, &seat)| self.stacks[seat])
+                    .sum();
+                let remaining = total - placed;
+                for (bit, &seat) in live.iter().enumerate() {
+                    if mask & (1 << bit) != 0 {
+                        continue;
+                    }
+                    let chance = chance * self.stacks[seat] / remaining;
+                    placed_here[seat] += chance;
+                    if place + 1 < places {
+                        *next.entry(mask | (1 << bit)).or_insert(0.0) += chance;
+                    }
+                }
+            }
+            for (chances, chance) in finishes.iter_mut().zip(placed_here) {
+                chances[place] = chance;
+            }
+            level = next;
+        }
+
+        IcmResult::new(finishes, &self.payouts, None)
+    }
+
+    fn run_approximate(&self, live: &[usize]) -> Result<IcmResult, IcmError> {
+        if self.iterations == 0 {
+            return Err(IcmError::NoIterations);
+        }
+        let seed = self.seed.unwrap_or_else(rand::random);
+        let mut rng = StdRng::seed_from_u64(seed);
+        let places = self.payouts.len().min(live.len());
+        let mut counts = vec![vec![0u64; self.payouts.len()]; self.stacks.len()];
+
+        let mut times: Vec<(f64, usize)> = Vec::with_capacity(live.len());
+        for _ in 0..self.iterations {
+            times.clear();
+            times.extend(live.iter().map(|&seat| {
+                let uniform = 1.0 - rng.random::<f64>();
+                (-uniform.ln() / self.stacks[seat], seat)
+            }));
+            if places < times.len() {
+                times.select_nth_unstable_by(places, |a, b| a.0.total_cmp(&b.0));
+            }
+            times[..places].sort_unstable_by(|a, b| a.0.total_cmp(&b.0));
+            for (place, &(_, seat)) in times[..places].iter().enumerate() {
+                counts[seat][place] += 1;
+            }
+        }
+
+        let n = self.iterations as f64;
+        let finishes = counts
+            .into_iter()
+            .map(|places| places.into_iter().map(|count| count as f64 / n).collect())
+            .collect();
+        Ok(IcmResult::new(finishes, &self.payouts, Some(seed)))
+    }
+}
+
+/// Each player's prize equity and chances of finishing in each paid place
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct IcmResult {
+    equities: Vec<f64>,
+    finishes: Vec<Vec<f64>>,
+    seed: Option<u64>,
+}
+
+impl IcmResult {
+    fn new(finishes: Vec<Vec<f64>>, payouts: &[f64], seed: Option<u64>) -> Self {
+        let equities = finishes
+            .iter()
+            .map(|chances| chances.iter().zip(payouts).map(|(c, p)| c * p).sum())
+            .collect();
+        Self {
+            equities,
+            finishes,
+            seed,
+        }
+    }
+
+    /// Each player's expected prize, in the order the stacks were given
+    pub fn equities(&self) -> &[f64] {
+        &self.equities
+    }
+
+    /// The chance of a player finishing in each paid place, first place first
+    pub fn finishes(&self, player: usize) -> &[f64] {
+        &self.finishes[player]
+    }
+
+    /// The seed used for sampling, if the result was approximated
+    pub fn seed(&self) -> Option<u64> {
+        self.seed
+    }
+
+    /// Whether every finishing order was followed rather than sampled
+    pub fn is_exact(&self) -> bool {
+        self.seed.is_none()
+    }
+}
+
+/// Calculates each player's prize equity, exactly for small fields and approximately for
+/// large ones; see `IcmCalculator`
+pub fn equities(stacks: &[u64], payouts: &[f64]) -> Result<Vec<f64>, IcmError> {
+    IcmCalculator::new(stacks, payouts)
+        .calculate()
+        .map(|result| result.equities)
+}
+
+// The number of ways to choose `k` of `n`, or `u64::MAX` if that does not fit
+fn binomial(n: u64, k: u64) -> u64 {
+    let mut value: u128 = 1;
+    for i in 0..k.min(n) {
+        match value.checked_mul(u128::from(n - i)) {
+            Some(product) => value = product / u128::from(i + 1),
+            None => return u64::MAX,
+        }
+    }
+    if k > n {
+        0
+    } else {
+        u64::try_from(value).unwrap_or(u64::MAX)
+    }
+}
@@ -0,0 +1,298 @@
+use super::{DEFAULT_ITERATIONS, IcmCalculator, IcmError};
+use crate::Card;
+use crate::equity::{EquityError, RangeEquityCalculator};
+use crate::range::{Combo, Range, StartingHand};
+
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
+/// Number of two-card combos a player could hold once their opponent's two cards are known
+const UNBLOCKED_COMBOS: f64 = 1225.0;
+
+/// Weighs moving all in against folding in tournament money rather than chips
+///
+/// One player (the pusher) either folds or moves all in, and one opponent (the caller) calls
+/// with a given range while everyone else folds. Each outcome is valued with the Independent
+/// Chip Model, using the pusher's preflop equity against the calling range. When the pusher
+/// folds, the pot goes to the big blind, or to the caller when no blinds are set.
+///
+/// # Examples
+///
+/// ```
+/// use cards::icm::PushFold;
+/// use cards::notation::parse_cards;
+///
+/// let hand = parse_cards("Ah Qd").unwrap();
+/// let decision = PushFold::new(&[4_000, 2_500, 2_000, 1_500], &[50.0, 30.0, 20.0], 0, 2)
+///     .blinds(1, 100, 2, 200)
+///     .calling_range("TT+, AK".parse().unwrap())
+///     .iterations(2_000)
+///     .seed(7)
+///     .evaluate([hand[0], hand[1]])
+///     .unwrap();
+///
+/// println!("push: {:.2}, fold: {:.2}", decision.push_ev(), decision.fold_ev());
+/// assert!(decision.call_probability() < 0.05);
+/// ```
+#[derive(Debug, Clone)]
+pub struct PushFold {
+    stacks: Vec<u64>,
+    payouts: Vec<f64>,
+    pusher: usize,
+    caller: usize,
+    blinds: Option<[(usize, u64); 2]>,
+    ante: u64,
+    calling_range: Range,
+    iterations: u64,
+    seed: Option<u64>,
+}
+
+impl PushFold {
+    /// Creates a spot from chip stacks at the start of the hand, prizes from first place down,
+    /// the seat moving all in and the seat that may call
+    pub fn new(stacks: &[u64], payouts: &[f64], pusher: usize, caller: usize) -> Self {
+        Self {
+            stacks: stacks.to_vec(),
+            payouts: payouts.to_vec(),
+            pusher,
+            caller,
+            blinds: None,
+            ante: 0,
+            calling_range: Range::full(),
+            iterations: DEFAULT_ITERATIONS,
+            seed: None,
+        }
+    }
+
+    /// Sets the seats and amounts of the small and big blinds
+    pub fn blinds(
+        mut self,
+        small_blind_seat: usize,
+        small_blind: u64,
+        big_blind_seat: usize,
+        big_blind: u64,
+    ) -> Self {
+        self.blinds = Some([(small_blind_seat, small_blind), (big_blind_seat, big_blind)]);
+        self
+    }
+
+    /// Sets the ante every player posts
+    pub fn ante(mut self, ante: u64) -> Self {
+        self.ante = ante;
+        self
+    }
+
+    /// Sets the hands the caller calls with, every hand unless set
+    pub fn calling_range(mut self, range: Range) -> Self {
+        self.calling_range = range;
+        self
+    }
+
+    /// Sets the number of boards sampled for the pusher's equity when called
+    pub fn iterations(mut self, iterations: u64) -> Self {
+        self.iterations = iterations;
+        self
+    }
+
+    /// Fixes the random seed so that results are reproducible
+    pub fn seed(mut self, seed: u64) -> Self {
+        self.seed = Some(seed);
+        self
+    }
+
+    /// Values pushing and folding with the given hole cards
+    pub fn evaluate(&self, hand: [Card; 2]) -> Result<PushFoldDecision, IcmError> {
+        self.validate()?;
+        if let Some(&card) = hand.iter().find(|card| card.suit.is_none()) {
+            return Err(EquityError::UnsuitedCard(card).into());
+        }
+        if hand[0] == hand[1] {
+            return Err(EquityError::DuplicateCard(hand[0]).into());
+        }
+        let combo = Combo::new(hand[0], hand[1]);
+
+        let seed = self.seed.unwrap_or_else(rand::random);
+        let calls = self.calling_range.without_cards(&combo.cards());
+        let call_probability = calls.total_weight() / UNBLOCKED_COMBOS;
+
+        let posted = self.posted();
+        let after_fold = self.after_fold(&posted);
+        let fold_ev = self.icm(after_fold, seed)?;
+
+        let (equity, push_ev) = if calls.is_empty() {
+            (None, self.icm(self.after_steal(&posted), seed)?)
+        } else {
+            let mut hero = Range::new();
+            hero.insert(combo, 1.0);
+            let result = RangeEquityCalculator::new(&[hero, calls])
+                .iterations(self.iterations)
+                .seed(seed)
+                .monte_carlo()?;
+            let player = &result.players()[0];
+            let (win, tie) = (
+                player.win_percentage() / 100.0,
+                player.tie_percentage() / 100.0,
+            );
+
+            let [won, split, lost] = self.after_call(&posted);
+            let called = win * self.icm(won, seed)?
+                + tie * self.icm(split, seed)?
+                + (1.0 - win - tie) * self.icm(lost, seed)?;
+            let stolen = self.icm(self.after_steal(&posted), seed)?;
+            (
+                Some(player.equity()),
+                call_probability * called + (1.0 - call_probability) * stolen,
+            )
+        };
+
+        Ok(PushFoldDecision {
+            push_ev,
+            fold_ev,
+            call_probability,
+            equity_when_called: equity,
+        })
+    }
+
+    /// The starting hands worth pushing, valuing one combo of each
+    ///
+    /// This runs an equity calculation for each of the 169 starting hands, so a modest
+    /// iteration count keeps it quick.
+    pub fn push_range(&self) -> Result<Range, IcmError> {
+        let mut range = Range::new();
+        for hand in StartingHand::all() {
+            if self.evaluate(hand.combos()[0].cards())?.should_push() {
+                range.insert_hand(hand, 1.0);
+            }
+        }
+        Ok(range)
+    }
+
+    fn validate(&self) -> Result<(), IcmError> {
+        let seats = self.stacks.len();
+        let blind_seats = self.blinds.iter().flatten().map(|&(seat, _)| seat);
+        if let Some(seat) = [self.pusher, self.caller]
+            .into_iter()
+            .chain(blind_seats)
+            .find(|&seat| seat >= seats)
+        {
+            return Err(IcmError::InvalidSeat(seat));
+        }
+        if self.pusher == self.caller {
+            return Err(IcmError::InvalidSeat(self.caller));
+        }
+        if let Some(seat) = [self.pusher, self.caller]
+            .into_iter()
+            .find(|&seat| self.stacks[seat] == 0)
+        {
+            return Err(IcmError::EmptyStack(seat));
+        }
+        Ok(())
+    }
+
+    // Chips each seat has in the pot before the push: antes, then blinds, limited by the stack
+    fn posted(&self) -> Vec<u64> {
+        let mut posted: Vec<u64> = self.stacks.iter().map(|&s| s.min(self.ante)).collect();
+        for &(seat, amount) in self.blinds.iter().flatten() {
+            posted[seat] = self.stacks[seat].min(posted[seat] + amount);
+        }
+        posted
+    }
+
+    fn behind(&self, posted: &[u64]) -> Vec<f64> {
+        self.stacks
+            .iter()
+            .zip(posted)
+            .map(|(&stack, &posted)| (stack - posted) as f64)
+            .collect()
+    }
+
+    // The pusher folds and the pot goes to the big blind
+    fn after_fold(&self, posted: &[u64]) -> Vec<f64> {
+        let winner = self.blinds.map_or(self.caller, |[_, (seat, _)]| seat);
+        let mut stacks = self.behind(posted);
+        stacks[winner] += posted.iter().sum::<u64>() as f64;
+        stacks
+    }
+
+    // The pusher moves in and everyone folds
+    fn after_steal(&self, posted: &[u64]) -> Vec<f64> {
+        let mut stacks = self.behind(posted);
+        stacks[self.pusher] += posted.iter().sum::<u64>() as f64;
+        stacks
+    }
+
+    // The caller calls and the pusher wins, splits or loses
+    fn after_call(&self, posted: &[u64]) -> [Vec<f64>; 3] {
+        let (pusher, caller) = (self.pusher, self.caller);
+        // Chips beyond the shorter stack are never at risk
+        let risk = self.stacks[pusher].min(self.stacks[caller]);
+        let dead: u64 = posted
+            .iter()
+            .enumerate()
+            .filter(|&(seat, _)| seat != pusher && seat != caller)
+            .map(|(_, &chips)| chips)
+            .sum();
+        let pot = (2 * risk + dead) as f64;
+
+        let mut base: Vec<f64> = self.behind(posted);
+        base[pusher] = (self.stacks[pusher] - risk) as f64;
+        base[caller] = (self.stacks[caller] - risk) as f64;
+
+        let mut won = base.clone();
+        won[pusher] += pot;
+        let mut split = base.clone();
+        split[pusher] += pot / 2.0;
+        split[caller] += pot / 2.0;
+        let mut lost = base;
+        lost[caller] += pot;
+        [won, split, lost]
+    }
+
+    fn icm(&self, stacks: Vec<f64>, seed: u64) -> Result<f64, IcmError> {
+        let result = IcmCalculator::with_stacks(stacks, &self.payouts)
+            .seed(seed)
+            .calculate()?;
+        Ok(result.equities()[self.pusher])
+    }
+}
+
+/// The value of pushing and of folding a hand, in the same units as the payouts
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct PushFoldDecision {
+    push_ev: f64,
+    fold_ev: f64,
+    call_probability: f64,
+    equity_when_called: Option<f64>,
+}
+
+impl PushFoldDecision {
+    /// The pusher's prize equity after moving all in
+    pub fn push_ev(&self) -> f64 {
+        self.push_ev
+    }
+
+    /// The pusher's prize equity after folding
+    pub fn fold_ev(&self) -> f64 {
+        self.fold_ev
+    }
+
+    /// How much pushing gains over folding, negative when folding is better
+    pub fn gain(&self) -> f64 {
+        self.push_ev - self.fold_ev
+    }
+
+    pub fn should_push(&self) -> bool {
+        self.push_ev > self.fold_ev
+    }
+
+    /// How often the caller's range calls, given the cards the pusher holds
+    pub fn call_probability(&self) -> f64 {
+        self.call_probability
+    }
+
+    /// The pusher's share of the pot when called, or `None` if the caller never calls
+    pub fn equity_when_called(&self) -> Option<f64> {
+        self.equity_when_called
+    }
+}
@@ -0,0 +1,48 @@
+use super::super::*;
+use proptest::prelude::*;
+
+// Between two and seven stacks, at least one with chips, and up to four descending payouts
+fn arb_spot() -> impl Strategy<Value = (Vec<u64>, Vec<f64>)> {
+    (
+        prop::collection::vec(1u64..100_000, 2..=7),
+        prop::collection::vec(1.0f64..1_000.0, 1..=4),
+    )
+        .prop_map(|(stacks, mut payouts)| {
+            payouts.sort_by(|a, b| b.total_cmp(a));
+            (stacks, payouts)
+        })
+}
+
+proptest! {
+    #[test]
+    fn equities_add_up_to_the_paid_places((stacks, payouts) in arb_spot()) {
+        let result = IcmCalculator::new(&stacks, &payouts).exact().unwrap();
+
+        let paid: f64 = payouts.iter().take(stacks.len()).sum();
+        let total: f64 = result.equities().iter().sum();
+        prop_assert!((total - paid).abs() < 1e-6 * paid.max(1.0));
+    }
+
+    #[test]
+    fn bigger_stacks_are_worth_more((stacks, payouts) in arb_spot()) {
+        let result = IcmCalculator::new(&stacks, &payouts).exact().unwrap();
+
+        for a in 0..stacks.len() {
+            for b in 0..stacks.len() {
+                if stacks[a] > stacks[b] {
+                    prop_assert!(result.equities()[a] >= result.equities()[b] - 1e-9);
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn equity_never_exceeds_first_place((stacks, payouts) in arb_spot()) {
+        let result = IcmCalculator::new(&stacks, &payouts).exact().unwrap();
+
+        for &equity in result.equities() {
+            prop_assert!(equity >= 0.0);
+            prop_assert!(equity <= payouts[0] + 1e-9);
+        }
+    }
+}
@@ -0,0 +1,256 @@
+use super::super::*;
+use crate::notation::parse_cards;
+use crate::range::Range;
+
+fn hand(notation: &str) -> [crate::Card; 2] {
+    let cards = parse_cards(notation).unwrap();
+    [cards[0], cards[1]]
+}
+
+fn close(a: f64, b: f64, tolerance: f64) -> bool {
+    (a - b).abs() < tolerance
+}
+
+#[test]
+fn test_three_player_equities() {
+    let result = IcmCalculator::new(&[5_000, 3_000, 2_000], &[0.5, 0.3, 0.2])
+        .exact()
+        .unwrap();
+
+    // First place by chip share, second and third by Harville's rule
+    let first = result.finishes(0);
+    assert!(close(first[0], 0.5, 1e-12));
+    assert!(close(first[1], 0.3 * 5.0 / 7.0 + 0.2 * 5.0 / 8.0, 1e-12));
+    assert!(close(result.equities()[0], 0.3839285714285714, 1e-12));
+    assert!(close(result.equities().iter().sum::<f64>(), 1.0, 1e-12));
+    assert!(result.is_exact());
+    assert_eq!(result.seed(), None);
+}
+
+#[test]
+fn test_equal_stacks_share_equally() {
+    let result = IcmCalculator::new(&[1_500; 6], &[50.0, 30.0, 20.0])
+        .exact()
+        .unwrap();
+
+    assert!(
+        result
+            .equities()
+            .iter()
+            .all(|&e| close(e, 100.0 / 6.0, 1e-9))
+    );
+}
+
+#[test]
+fn test_heads_up_equity_is_linear_in_chips() {
+    let result = IcmCalculator::new(&[7_000, 3_000], &[100.0, 60.0])
+        .exact()
+        .unwrap();
+
+    assert!(close(result.equities()[0], 60.0 + 0.7 * 40.0, 1e-9));
+    assert!(close(result.equities()[1], 60.0 + 0.3 * 40.0, 1e-9));
+}
+
+#[test]
+fn test_players_without_chips_win_nothing() {
+    let result = IcmCalculator::new(&[4_000, 0, 6_000], &[50.0, 30.0, 20.0])
+        .exact()
+        .unwrap();
+
+    assert_eq!(result.equities()[1], 0.0);
+    assert!(result.finishes(1).iter().all(|&chance| chance == 0.0));
+    // Only two places can be filled
+    assert!(close(result.equities().iter().sum::<f64>(), 80.0, 1e-9));
+}
+
+#[test]
+fn test_each_place_is_filled_once() {
+    let stacks = [12_000, 9_000, 7_500, 4_000, 2_500, 1_000];
+    let payouts = [40.0, 25.0, 15.0, 10.0];
+    let result = IcmCalculator::new(&stacks, &payouts).exact().unwrap();
+
+    for place in 0..payouts.len() {
+        let total: f64 = (0..stacks.len()).map(|p| result.finishes(p)[place]).sum();
+        assert!(close(total, 1.0, 1e-12), "place {place}: {total}");
+    }
+    assert!(result.equities().windows(2).all(|w| w[0] > w[1]));
+}
+
+#[test]
+fn test_approximation_agrees_with_exact() {
+    let stacks = [12_000, 9_000, 7_500, 4_000, 2_500, 1_000];
+    let payouts = [40.0, 25.0, 15.0, 10.0];
+    let calculator = IcmCalculator::new(&stacks, &payouts)
+        .iterations(50_000)
+        .seed(3);
+    let exact = calculator.exact().unwrap();
+    let approximate = calculator.approximate().unwrap();
+
+    assert!(!approximate.is_exact());
+    assert_eq!(approximate.seed(), Some(3));
+    for (a, b) in exact.equities().iter().zip(approximate.equities()) {
+        assert!(close(*a, *b, 0.5), "{a} vs {b}");
+    }
+}
+
+#[test]
+fn test_same_seed_gives_same_approximation() {
+    let calculator = IcmCalculator::new(&[3_000, 2_000, 1_000], &[60.0, 40.0])
+        .iterations(1_000)
+        .seed(11);
+
+    assert_eq!(calculator.approximate(), calculator.approximate());
+}
+
+#[test]
+fn test_calculate_switches_to_approximation() {
+    let calculator = IcmCalculator::new(&[3_000, 2_000, 1_000], &[60.0, 40.0])
+        .iterations(1_000)
+        .seed(1);
+
+    assert!(calculator.calculate().unwrap().is_exact());
+    assert!(!calculator.max_states(2).calculate().unwrap().is_exact());
+}
+
+#[test]
+fn test_large_field_is_approximated() {
+    let stacks: Vec<u64> = (1..=500).map(|i| 1_000 + 37 * i).collect();
+    let payouts: Vec<f64> = (1..=60).map(|place| 1_000.0 / place as f64).collect();
+    let result = IcmCalculator::new(&stacks, &payouts)
+        .iterations(2_000)
+        .seed(5)
+        .calculate()
+        .unwrap();
+
+    assert!(!result.is_exact());
+    let paid: f64 = payouts.iter().sum();
+    assert!(close(result.equities().iter().sum::<f64>(), paid, 1e-6));
+}
+
+#[test]
+fn test_equities_function() {
+    let equities = equities(&[5_000, 3_000, 2_000], &[50.0, 30.0, 20.0]).unwrap();
+    assert!(close(equities[0], 38.392857142857146, 1e-9));
+}
+
+#[test]
+fn test_icm_errors() {
+    assert_eq!(
+        IcmCalculator::new(&[0, 0], &[10.0]).exact(),
+        Err(IcmError::NoChips)
+    );
+    assert_eq!(
+        IcmCalculator::new(&[10, 20], &[10.0, -1.0]).exact(),
+        Err(IcmError::InvalidPayout(1))
+    );
+    assert_eq!(
+        IcmCalculator::new(&[10, 20], &[10.0])
+            .iterations(0)
+            .approximate(),
+        Err(IcmError::NoIterations)
+    );
+    assert_eq!(
+        IcmCalculator::new(&[10; 129], &[10.0]).exact(),
+        Err(IcmError::TooManyPlayers(129))
+    );
+}
+
+// Four equal stacks on the bubble: three places are paid
+fn bubble() -> PushFold {
+    PushFold::new(&[5_000; 4], &[50.0, 30.0, 20.0], 0, 2)
+        .blinds(1, 100, 2, 200)
+        .iterations(2_000)
+        .seed(8)
+}
+
+#[test]
+fn test_aces_push_and_seven_deuce_folds() {
+    let spot = bubble().calling_range(Range::full());
+
+    let aces = spot.evaluate(hand("As Ah")).unwrap();
+    assert!(aces.should_push(), "{aces:?}");
+    assert!(aces.equity_when_called().unwrap() > 0.8);
+    assert!(close(aces.call_probability(), 1.0, 1e-12));
+
+    let trash = spot.evaluate(hand("7c 2d")).unwrap();
+    assert!(!trash.should_push(), "{trash:?}");
+    assert!(trash.gain() < 0.0);
+}
+
+#[test]
+fn test_folding_gives_up_nothing_but_the_blinds() {
+    let decision = bubble().evaluate(hand("7c 2d")).unwrap();
+    let expected = IcmCalculator::new(&[5_000, 4_900, 5_100, 5_000], &[50.0, 30.0, 20.0])
+        .exact()
+        .unwrap();
+
+    assert!(close(decision.fold_ev(), expected.equities()[0], 1e-12));
+}
+
+#[test]
+fn test_pushing_into_a_range_that_never_calls_steals_the_blinds() {
+    let decision = bubble()
+        .calling_range(Range::new())
+        .evaluate(hand("7c 2d"))
+        .unwrap();
+    let expected = IcmCalculator::new(&[5_300, 4_900, 4_800, 5_000], &[50.0, 30.0, 20.0])
+        .exact()
+        .unwrap();
+
+    assert_eq!(decision.call_probability(), 0.0);
+    assert_eq!(decision.equity_when_called(), None);
+    assert!(close(decision.push_ev(), expected.equities()[0], 1e-12));
+    assert!(decision.should_push());
+}
+
+#[test]
+fn test_call_probability_accounts_for_blockers() {
+    let decision = bubble()
+        .calling_range("AA".parse().unwrap())
+        .evaluate(hand("As Kd"))
+        .unwrap();
+
+    assert!(close(decision.call_probability(), 3.0 / 1225.0, 1e-12));
+}
+
+#[test]
+fn test_push_range_against_a_tight_caller() {
+    let range = PushFold::new(&[3_000, 3_000], &[100.0], 0, 1)
+        .blinds(0, 100, 1, 200)
+        .calling_range("QQ+".parse().unwrap())
+        .iterations(200)
+        .seed(2)
+        .push_range()
+        .unwrap();
+
+    // Winner takes all, so ICM is chip EV and a caller this tight is easily exploited
+    assert!(range.len() > 1_000);
+    assert!(range.contains(&crate::range::Combo::new(
+        hand("7c 2d")[0],
+        hand("7c 2d")[1]
+    )));
+}
+
+#[test]
+fn test_push_fold_errors() {
+    let payouts = [50.0, 30.0, 20.0];
+    assert_eq!(
+        PushFold::new(&[100; 4], &payouts, 1, 1).evaluate(hand("As Ah")),
+        Err(IcmError::InvalidSeat(1))
+    );
+    assert_eq!(
+        PushFold::new(&[100; 4], &payouts, 0, 4).evaluate(hand("As Ah")),
+        Err(IcmError::InvalidSeat(4))
+    );
+    assert_eq!(
+        PushFold::new(&[100, 0, 100], &payouts, 0, 1).evaluate(hand("As Ah")),
+        Err(IcmError::EmptyStack(1))
+    );
+    let ace = hand("As Ah")[0];
+    assert_eq!(
+        PushFold::new(&[100; 4], &payouts, 0, 1).evaluate([ace, ace]),
+        Err(IcmError::Equity(crate::equity::EquityError::DuplicateCard(
+            ace
+        )))
+    );
+}
@@ -0,0 +1,572 @@
+use std::collections::HashMap;
+use std::fmt;
+use std::sync::OnceLock;
+
+use crate::{Card, Rank, Suit};
+
+#[cfg(test)]
+mod tests;
+
+// Ranks from deuce to ace; a rank's position is its bit in a rank set
+const RANKS: [Rank; 13] = [
+    Rank::Two,
+    Rank::Three,
+    Rank::Four,
+    Rank::Five,
+    Rank::Six,
+    Rank::Seven,
+    Rank::Eight,
+    Rank::Nine,
+    Rank::Ten,
+    Rank::Jack,
+    Rank::Queen,
+    Rank::King,
+    Rank::Ace,
+];
+
+const MAX_ROUNDS: usize = 8;
+
+// Suits in the order canonical hands use them: the most significant suit is spades
+const CANONICAL_SUITS: [Suit; 4] = [Suit::Spades, Suit::Hearts, Suit::Diamonds, Suit::Clubs];
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum IsoError {
+    /// Rounds must deal at least one card each and no more than 52 in total, over at most
+    /// eight rounds
+    InvalidRounds(Vec<usize>),
+    /// The number of cards does not match the cards dealt by the end of any round
+    WrongCardCount(usize),
+    /// The same card was given more than once
+    DuplicateCard(Card),
+    /// A card without a suit (a joker) was given
+    UnsuitedCard(Card),
+    /// The indexer has no such round
+    InvalidRound(usize),
+    /// The index is not below the number of canonical hands in the round
+    IndexOutOfRange(u64),
+}
+
+impl fmt::Display for IsoError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            IsoError::InvalidRounds(rounds) => write!(f, "invalid rounds {rounds:?}"),
+            IsoError::WrongCardCount(count) => {
+                write!(f, "{count} cards do not complete any round")
+            }
+            IsoError::DuplicateCard(card) => write!(f, "card {card:?} appears more than once"),
+            IsoError::UnsuitedCard(card) => write!(f, "card {card:?} has no suit"),
+            IsoError::InvalidRound(round) => write!(f, "round {round} does not exist"),
+            IsoError::IndexOutOfRange(index) => write!(f, "index {index} is out of range"),
+        }
+    }
+}
+
+impl std::error::Error for IsoError {}
+
+// How many cards of one suit were dealt in each round so far
+type Shape = Vec<u8>;
+
+/// Maps hands that are identical up to a relabelling of the suits to one dense index
+///
+/// A hand is dealt in rounds, such as two hole cards then three flop cards; the order of cards
+/// within a round does not matter but the round each card came in does. Every hand through a
+/// given round gets an index below `size(round)`, isomorphic hands get the same index, and
+/// `unindex` turns an index back into its canonical hand. For Hold'em with rounds
+/// `[2, 3, 1, 1]` there are 169 preflop, 1,286,792 flop, 55,190,538 turn and 2,428,287,420
+/// river indices; treating the board as one round, `[2, 4]` and `[2, 5]` give 13,960,050 and
+/// 123,156,254.
+///
+/// The index follows Waugh's hand isomorphism scheme: suits are grouped by how many cards they
+/// received in each round, each suit's ranks are numbered within its group, and suits sharing
+/// a group are combined as a multiset.
+///
+/// # Examples
+///
+/// ```
+/// use cards::iso::HandIndexer;
+/// use cards::notation::parse_cards;
+///
+/// let indexer = HandIndexer::new(&[2, 3]).unwrap();
+/// assert_eq!(indexer.size(0), 169);
+/// assert_eq!(indexer.size(1), 1_286_792);
+///
+/// let first = indexer.index(&parse_cards("Ah Kh Qh 7c 2d").unwrap()).unwrap();
+/// let second = indexer.index(&parse_cards("Ks As 2c Qs 7h").unwrap()).unwrap();
+/// assert_eq!(first, second);
+///
+/// let canonical = indexer.unindex(1, first).unwrap();
+/// assert_eq!(canonical, parse_cards("As Ks Qs 7h 2d").unwrap());
+/// ```
+#[derive(Debug, Clone)]
+pub struct HandIndexer {
+    rounds: Vec<usize>,
+    // Everything needed to index hands through each round
+    tables: Vec<RoundTable>,
+}
+
+#[derive(Debug, Clone)]
+struct RoundTable {
+    configurations: Vec<Configuration>,
+    lookup: HashMap<Vec<Shape>, usize>,
+    // Index of the first hand of each configuration
+    offsets: Vec<u64>,
+    size: u64,
+}
+
+// One way of splitting each round's cards between the suits, with the suits ordered by shape
+#[derive(Debug, Clone)]
+struct Configuration {
+    groups: Vec<Group>,
+}
+
+// Suits that received the same number of cards in every round
+#[derive(Debug, Clone)]
+struct Group {
+    shape: Shape,
+    suits: usize,
+    // Ways of choosing the ranks of one suit with this shape
+    options: u64,
+    // Ways of choosing the ranks of every suit in the group, up to their order
+    size: u64,
+}
+
+impl HandIndexer {
+    /// Creates an indexer for hands dealt in rounds of the given sizes
+    pub fn new(rounds: &[usize]) -> Result<Self, IsoError> {
+        if rounds.is_empty()
+            || rounds.len() > MAX_ROUNDS
+            || rounds.contains(&0)
+            || rounds.iter().sum::<usize>() > 52
+        {
+            return Err(IsoError::InvalidRounds(rounds.to_vec()));
+        }
+        let tables = (1..=rounds.len())
+            .map(|count| RoundTable::new(&rounds[..count]))
+            .collect();
+        Ok(Self {
+            rounds: rounds.to_vec(),
+            tables,
+        })
+    }
+
+    /// The number of cards dealt in each round
+    pub fn rounds(&self) -> &[usize] {
+        &self.rounds
+    }
+
+    /// The number of canonical hands through `round`, counting from zero
+    ///
+    /// # Panics
+    ///
+    /// Panics if the indexer has no such round.
+    pub fn size(&self, round: usize) -> u64 {
+        self.tables[round].size
+    }
+
+    /// The index of a hand, given as its cards in the order of the rounds that dealt them
+    ///
+    /// The round indexed is the one the number of cards completes, so the same indexer can
+    /// index hole cards alone, hole cards and flop, and so on.
+    pub fn index(&self, cards: &[Card]) -> Result<u64, IsoError> {
+        let round = self.round_of(cards.len())?;
+        let mut seen = 0u64;
+        let mut suits = [SuitCards::default(); 4];
+        let mut start = 0;
+        for (r, &count) in self.rounds[..=round].iter().enumerate() {
+            for card in &cards[start..start + count] {
+                let Some(suit) = card.suit else {
+                    return Err(IsoError::UnsuitedCard(*card));
+                };
+                let rank = rank_position(card.rank).ok_or(IsoError::UnsuitedCard(*card))?;
+                let bit = 1u64 << (suit as usize * 13 + rank);
+                if seen & bit != 0 {
+                    return Err(IsoError::DuplicateCard(*card));
+                }
+                seen |= bit;
+                suits[suit as usize].ranks[r] |= 1 << rank;
+            }
+            start += count;
+        }
+
+        let mut described: Vec<(Shape, u64)> = suits
+            .iter()
+            .map(|suit| {
+                let shape: Shape = suit.ranks[..=round]
+                    .iter()
+                    .map(|ranks| ranks.count_ones() as u8)
+                    .collect();
+                let index = rank_sets_index(&suit.ranks[..=round]);
+                (shape, index)
+            })
+            .collect();
+        described.sort_by(|a, b| b.cmp(a));
+
+        let table = &self.tables[round];
+        let shapes: Vec<Shape> = described.iter().map(|(shape, _)| shape.clone()).collect();
+        let configuration = table.lookup[&shapes];
+
+        let mut index = 0;
+        let mut scale = 1;
+        let mut suits = described.iter();
+        for group in &table.configurations[configuration].groups {
+            let mut values: Vec<u64> = suits
+                .by_ref()
+                .take(group.suits)
+                .map(|&(_, index)| index)
+                .collect();
+            values.sort_unstable();
+            index += scale * multiset_index(&values);
+            scale *= group.size;
+        }
+        Ok(table.offsets[configuration] + index)
+    }
+
+    /// The canonical hand with the given index through `round`
+    ///
+    /// Cards come round by round, each round from the highest card down. Canonical hands use
+    /// spades first, then hearts, diamonds and clubs.
+    pub fn unindex(&self, round: usize, index: u64) -> Result<Vec<Card>, IsoError> {
+        let table = self
+            .tables
+            .get(round)
+            .ok_or(IsoError::InvalidRound(round))?;
+        if index >= table.size {
+            return Err(IsoError::IndexOutOfRange(index));
+        }
+        let configuration = table.offsets.partition_point(|&offset| offset <= index) - 1;
+        let mut local = index - table.offsets[configuration];
+
+        let mut suits: Vec<Vec<u16>> = Vec::with_capacity(4);
+        for group in &table.configurations[configuration].groups {
+            let group_index = local % group.size;
+            local /= group.size;
+            let values = multiset_unindex(group_index, group.suits);
+            for &value in values.iter().rev() {
+                suits.push(rank_sets_unindex(value, &group.shape));
+            }
+        }
+
+        let mut cards = Vec::with_capacity(self.rounds[..=round].iter().sum());
+        for r in 0..=round {
+            let start = cards.len();
+            for (suit, ranks) in CANONICAL_SUITS.iter().zip(&suits) {
+                cards.extend(
+                    (0..13)
+                        .filter(|&rank| ranks[r] & (1 << rank) != 0)
+                        .map(|rank| Card::new(RANKS[rank], Some(*suit))),
+                );
+            }
+            cards[start..].sort_by_key(|card| {
+                let suit = CANONICAL_SUITS.iter().position(|s| Some(*s) == card.suit);
+                (std::cmp::Reverse(card.rank.poker_value()), suit)
+            });
+        }
+        Ok(cards)
+    }
+
+    /// The canonical form of a hand, which every isomorphic hand shares
+    pub fn canonicalize(&self, cards: &[Card]) -> Result<Vec<Card>, IsoError> {
+        let round = self.round_of(cards.len())?;
+        self.unindex(round, self.index(cards)?)
+    }
+
+    // The round completed by `count` cards
+    fn round_of(&self, count: usize) -> Result<usize, IsoError> {
+        let mut dealt = 0;
+        for (round, &cards) in self.rounds.iter().enumerate() {
+            dealt += cards;
+            if dealt == count {
+                return Ok(round);
+            }
+        }
+        Err(IsoError::WrongCardCount(count))
+    }
+}
+
+/// The canonical form of hole cards and a board of up to five cards, treating the board as
+/// one round
+///
+/// # Examples
+///
+/// ```
+/// use cards::iso::canonicalize;
+/// use cards::notation::parse_cards;
+///
+/// let hole = parse_cards("Th 9h").unwrap();
+/// let board = parse_cards("8c 2h 7d").unwrap();
+/// let (hole, board) = canonicalize(&[hole[0], hole[1]], &board).unwrap();
+///
+/// assert_eq!(hole, [parse_cards("Ts").unwrap()[0], parse_cards("9s").unwrap()[0]]);
+/// assert_eq!(board, parse_cards("8h 7d 2s").unwrap());
+/// ```
+pub fn canonicalize(hole: &[Card; 2], board: &[Card]) -> Result<([Card; 2], Vec<Card>), IsoError> {
+    let cards: Vec<Card> = hole.iter().chain(board).copied().collect();
+    let canonical = hole_and_board(board.len())?.canonicalize(&cards)?;
+    Ok(([canonical[0], canonical[1]], canonical[2..].to_vec()))
+}
+
+/// The dense index of hole cards and a board of up to five cards, treating the board as one
+/// round
+///
+/// Indices count from zero separately for each board size, so tables over one street can be
+/// sized with `HandIndexer::size`.
+pub fn canonical_index(hole: &[Card; 2], board: &[Card]) -> Result<u64, IsoError> {
+    let cards: Vec<Card> = hole.iter().chain(board).copied().collect();
+    hole_and_board(board.len())?.index(&cards)
+}
+
+// Shared indexers for two hole cards and a board of each size
+fn hole_and_board(board: usize) -> Result<&'static HandIndexer, IsoError> {
+    static INDEXERS: [OnceLock<HandIndexer>; 6] = [const { OnceLock::new() }; 6];
+    let indexer = INDEXERS
+        .get(board)
+        .ok_or(IsoError::WrongCardCount(2 + board))?;
+    Ok(indexer.get_or_init(|| {
+        let rounds: &[usize] = if board == 0 { &[2] } else { &[2, board] };
+        HandIndexer::new(rounds).expect("hole and board rounds are valid")
+    }))
+}
+
+// The ranks of one suit dealt in each round, as bits by rank position
+#[derive(Debug, Clone, Copy, Default)]
+struct SuitCards {
+    ranks: [u16; MAX_ROUNDS],
+}
+
+impl RoundTable {
+    fn new(rounds: &[usize]) -> Self {
+        // Every shape one suit could have, largest first
+        let mut shapes: Vec<Shape> = vec![Vec::new()];
+        for &count in rounds {
+            shapes = shapes
+                .into_iter()
+                .flat_map(|shape| {
+                    (0..=count.min(13)).map(move |c| {
+                        let mut shape = shape.clone();
+                        shape.push(c as u8);
+                        shape
+                    })
+                })
+                .filter(|shape| shape.iter().map(|&c| c as usize).sum::<usize>() <= 13)
+                .collect();
+        }
+        shapes.sort_by(|a, b| b.cmp(a));
+
+        let mut table = RoundTable {
+            configurations: Vec::new(),
+            lookup: HashMap::new(),
+            offsets: Vec::new(),
+            size: 0,
+        };
+        let mut chosen = Vec::with_capacity(4);
+        table.enumerate(&shapes, 0, &mut chosen, &mut rounds.to_vec());
+        table
+    }
+
+    // Chooses four shapes in non-increasing order that together deal every round's cards
+    fn enumerate(
+        &mut self,
+        shapes: &[Shape],
+        from: usize,
+        chosen: &mut Vec<Shape>,
+        left: &mut Vec<usize>,
+    ) {
+        if chosen.len() == 4 {
+            if left.iter().all(|&c| c == 0) {
+                self.add(chosen);
+            }
+            return;
+        }
+        for (i, shape) in shapes.iter().enumerate().skip(from) {
+            if shape.iter().zip(left.iter()).any(|(&c, &l)| c as usize > l) {
+                continue;
+            }
+            for (l, &c) in left.iter_mut().zip(shape) {
+                *l -= c as usize;
+            }
+            chosen.push(shape.clone());
+            self.enumerate(shapes, i, chosen, left);
+            chosen.pop();
+            for (l, &c) in left.iter_mut().zip(shape) {
+                *l += c as usize;
+            }
+        }
+    }
+
+    fn add(&mut self, shapes: &[Shape]) {
+        let mut groups: Vec<Group> = Vec::new();
+        for shape in shapes {
+            match groups.last_mut() {
+                Some(group) if group.shape == *shape => group.suits += 1,
+                _ => groups.push(Group {
+                    shape: shape.clone(),
+                    suits: 1,
+                    options: rank_sets_count(shape),
+                    size: 0,
+                }),
+            }
+        }
+        for group in &mut groups {
+            group.size = choose(group.options + group.suits as u64 - 1, group.suits as u64);
+        }
+        let size: u64 = groups.iter().map(|g| g.size).product();
+
+        self.lookup
+            .insert(shapes.to_vec(), self.configurations.len());
+        self.configurations.push(Configuration { groups });
+        self.offsets.push(self.size);
+        self.size += size;
+    }
+}
+
+fn rank_position(rank: Rank) -> Option<usize> {
+    RANKS.iter().position(|&r| r == rank)
+}
+
+// Ways of dealing one suit the given number of cards in each round
+fn rank_sets_count(shape: &[u8]) -> u64 {
+    let mut used = 0;
+    shape
+        .iter()
+        .map(|&count| {
+            let options = choose(13 - used, u64::from(count));
+            used += u64::from(count);
+            options
+        })
+        .product()
+}
+
+// Numbers one suit's ranks round by round, each round's ranks counted among the ranks that
+// earlier rounds left
+fn rank_sets_index(ranks: &[u16]) -> u64 {
+    let mut index = 0;
+    let mut scale = 1;
+    let mut used = 0u16;
+    for &set in ranks {
+        let free = 13 - used.count_ones() as u64;
+        let compressed = compress(set, !used & 0x1fff);
+        index += scale * colex_index(compressed);
+        scale *= choose(free, u64::from(set.count_ones()));
+        used |= set;
+    }
+    index
+}
+
+fn rank_sets_unindex(mut index: u64, shape: &[u8]) -> Vec<u16> {
+    let mut used = 0u16;
+    shape
+        .iter()
+        .map(|&count| {
+            let free = 13 - used.count_ones() as u64;
+            let options = choose(free, u64::from(count));
+            let compressed = colex_unindex(index % options, count);
+            index /= options;
+            let set = expand(compressed, !used & 0x1fff);
+            used |= set;
+            set
+        })
+        .collect()
+}
+
+// Packs the bits of `set` that lie in `mask` down to the low bits
+fn compress(set: u16, mask: u16) -> u16 {
+    let mut packed = 0;
+    let mut position = 0;
+    for bit in 0..13 {
+        if mask & (1 << bit) != 0 {
+            if set & (1 << bit) != 0 {
+                packed |= 1 << position;
+            }
+            position += 1;
+        }
+    }
+    packed
+}
+
+// Spreads the low bits of `packed` out over the bits set in `mask`
+fn expand(packed: u16, mask: u16) -> u16 {
+    let mut set = 0;
+    let mut position = 0;
+    for bit in 0..13 {
+        if mask & (1 << bit) != 0 {
+            if packed & (1 << position) != 0 {
+                set |= 1 << bit;
+            }
+            position += 1;
+        }
+    }
+    set
+}
+
+// The position of a set among sets of its size in colexicographic order
+fn colex_index(set: u16) -> u64 {
+    (0..16)
+        .filter(|&bit| set & (1 << bit) != 0)
+        .enumerate()
+        .map(|(i, bit)| choose(bit, i as u64 + 1))
+        .sum()
+}
+
+fn colex_unindex(mut index: u64, size: u8) -> u16 {
+    let mut set = 0;
+    for i in (1..=u64::from(size)).rev() {
+        let bit = largest_below(index, i);
+        set |= 1 << bit;
+        index -= choose(bit, i);
+    }
+    set
+}
+
+// The position of a sorted multiset among multisets of its size, using the combinatorial
+// number system on `values[i] + i`
+fn multiset_index(sorted: &[u64]) -> u64 {
+    sorted
+        .iter()
+        .enumerate()
+        .map(|(i, &value)| choose(value + i as u64, i as u64 + 1))
+        .sum()
+}
+
+fn multiset_unindex(mut index: u64, size: usize) -> Vec<u64> {
+    let mut values = vec![0; size];
+    for i in (1..=size as u64).rev() {
+        let position = largest_below(index, i);
+        values[i as usize - 1] = position - (i - 1);
+        index -= choose(position, i);
+    }
+    values
+}
+
+// The largest `n` with `choose(n, k) <= index`
+fn largest_below(index: u64, k: u64) -> u64 {
+    let (mut low, mut high) = (k - 1, k);
+    while choose(high, k) <= index {
+        low = high;
+        high *= 2;
+    }
+    while high - low > 1 {
+        let middle = low + (high - low) / 2;
+        if choose(middle, k) <= index {
+            low = middle;
+        } else {
+            high = middle;
+        }
+    }
+    low
+}
+
+// Binomial coefficient n choose k, saturating rather than overflowing
+fn choose(n: u64, k: u64) -> u64 {
+    if k > n {
+        return 0;
+    }
+    let k = k.min(n - k);
+    let mut value: u128 = 1;
+    for i in 0..k {
+        value = value * u128::from(n - i) / u128::from(i + 1);
+        if value > u128::from(u64::MAX) {
+            return u64::MAX;
+        }
+    }
+    value as u64
+}
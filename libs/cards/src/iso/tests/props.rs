@@ -0,0 +1,98 @@
+use super::super::*;
+use crate::Deck;
+use proptest::prelude::*;
+
+// Seven distinct cards, enough for a Hold'em hand through the river
+fn arb_hand() -> impl Strategy<Value = Vec<Card>> {
+    let deck: Vec<Card> = Deck::new()
+        .cards()
+        .iter()
+        .filter(|c| c.suit.is_some())
+        .copied()
+        .collect();
+    Just(deck)
+        .prop_shuffle()
+        .prop_map(|cards| cards[..7].to_vec())
+}
+
+fn arb_permutation() -> impl Strategy<Value = Vec<Suit>> {
+    Just(Suit::ALL.to_vec()).prop_shuffle()
+}
+
+fn relabel(cards: &[Card], permutation: &[Suit]) -> Vec<Card> {
+    cards
+        .iter()
+        .map(|card| Card::new(card.rank, card.suit.map(|s| permutation[s as usize])))
+        .collect()
+}
+
+fn holdem() -> &'static HandIndexer {
+    static INDEXER: OnceLock<HandIndexer> = OnceLock::new();
+    INDEXER.get_or_init(|| HandIndexer::new(&[2, 3, 1, 1]).unwrap())
+}
+
+proptest! {
+    #[test]
+    fn index_is_invariant_under_suit_relabelling(
+        hand in arb_hand(),
+        permutation in arb_permutation(),
+    ) {
+        let relabelled = relabel(&hand, &permutation);
+        for count in [2, 5, 6, 7] {
+            prop_assert_eq!(
+                holdem().index(&hand[..count]).unwrap(),
+                holdem().index(&relabelled[..count]).unwrap()
+            );
+        }
+    }
+
+    #[test]
+    fn canonical_hand_is_isomorphic_and_round_trips(hand in arb_hand()) {
+        for (round, count) in [(0, 2), (1, 5), (2, 6), (3, 7)] {
+            let index = holdem().index(&hand[..count]).unwrap();
+            prop_assert!(index < holdem().size(round));
+
+            let canonical = holdem().unindex(round, index).unwrap();
+            prop_assert_eq!(holdem().index(&canonical).unwrap(), index);
+            prop_assert_eq!(holdem().canonicalize(&canonical).unwrap(), canonical.clone());
+
+            // Some relabelling of the suits turns the hand into its canonical form
+            let matches = Suit::ALL.iter().flat_map(|&a| Suit::ALL.iter().flat_map(move |&b| {
+                Suit::ALL.iter().flat_map(move |&c| Suit::ALL.iter().map(move |&d| vec![a, b, c, d]))
+            }))
+            .filter(|p| Suit::ALL.iter().all(|s| p.contains(s)))
+            .any(|p| {
+                let relabelled = relabel(&hand[..count], &p);
+                holdem().index(&relabelled).unwrap() == index
+                    && same_rounds(&relabelled, &canonical, holdem().rounds())
+            });
+            prop_assert!(matches);
+        }
+    }
+
+    #[test]
+    fn random_indices_round_trip(index in 0u64..2_428_287_420) {
+        let hand = holdem().unindex(3, index).unwrap();
+        prop_assert_eq!(holdem().index(&hand).unwrap(), index);
+    }
+}
+
+// Whether two hands deal the same cards in each round, in any order within a round
+fn same_rounds(a: &[Card], b: &[Card], rounds: &[usize]) -> bool {
+    let mut start = 0;
+    for &count in rounds {
+        let end = (start + count).min(a.len());
+        if start >= end {
+            break;
+        }
+        let mut x: Vec<String> = a[start..end].iter().map(|c| c.to_string()).collect();
+        let mut y: Vec<String> = b[start..end].iter().map(|c| c.to_string()).collect();
+        x.sort();
+        y.sort();
+        if x != y {
+            return false;
+        }
+        start = end;
+    }
+    true
+}
@@ -0,0 +1,161 @@
+use super::super::*;
+use crate::notation::parse_cards;
+use crate::range::StartingHand;
+
+fn cards(notation: &str) -> Vec<Card> {
+    parse_cards(notation).unwrap()
+}
+
+#[test]
+fn test_holdem_round_sizes() {
+    let indexer = HandIndexer::new(&[2, 3, 1, 1]).unwrap();
+
+    assert_eq!(indexer.size(0), 169);
+    assert_eq!(indexer.size(1), 1_286_792);
+    assert_eq!(indexer.size(2), 55_190_538);
+    assert_eq!(indexer.size(3), 2_428_287_420);
+}
+
+#[test]
+fn test_hole_and_board_sizes() {
+    assert_eq!(HandIndexer::new(&[2, 4]).unwrap().size(1), 13_960_050);
+    assert_eq!(HandIndexer::new(&[2, 5]).unwrap().size(1), 123_156_254);
+}
+
+#[test]
+fn test_unordered_hand_sizes() {
+    assert_eq!(HandIndexer::new(&[5]).unwrap().size(0), 134_459);
+    assert_eq!(HandIndexer::new(&[7]).unwrap().size(0), 6_009_159);
+}
+
+#[test]
+fn test_preflop_indices_are_starting_hands() {
+    let indexer = HandIndexer::new(&[2]).unwrap();
+    let mut seen = [false; 169];
+
+    for hand in StartingHand::all() {
+        let indices: Vec<u64> = hand
+            .combos()
+            .iter()
+            .map(|combo| indexer.index(&combo.cards()).unwrap())
+            .collect();
+        assert!(indices.iter().all(|&i| i == indices[0]), "{hand}");
+        assert!(!seen[indices[0] as usize], "{hand}");
+        seen[indices[0] as usize] = true;
+    }
+}
+
+#[test]
+fn test_every_preflop_index_round_trips() {
+    let indexer = HandIndexer::new(&[2]).unwrap();
+    for index in 0..169 {
+        let hand = indexer.unindex(0, index).unwrap();
+        assert_eq!(indexer.index(&hand).unwrap(), index);
+    }
+}
+
+#[test]
+fn test_order_within_a_round_does_not_matter() {
+    let indexer = HandIndexer::new(&[2, 3]).unwrap();
+
+    assert_eq!(
+        indexer.index(&cards("Ah Kd Qh 7c 2d")).unwrap(),
+        indexer.index(&cards("Kd Ah 2d Qh 7c")).unwrap()
+    );
+}
+
+#[test]
+fn test_round_of_each_card_matters() {
+    let indexer = HandIndexer::new(&[2, 3]).unwrap();
+
+    assert_ne!(
+        indexer.index(&cards("Ah Kh Qh 7c 2d")).unwrap(),
+        indexer.index(&cards("Ah Qh Kh 7c 2d")).unwrap()
+    );
+}
+
+#[test]
+fn test_indexes_each_round_of_a_hand() {
+    let indexer = HandIndexer::new(&[2, 3, 1, 1]).unwrap();
+    let hand = cards("As Kd 9h 8h 2c Ts 3d");
+
+    for (round, count) in [(0, 2), (1, 5), (2, 6), (3, 7)] {
+        let index = indexer.index(&hand[..count]).unwrap();
+        assert!(index < indexer.size(round));
+        let canonical = indexer.unindex(round, index).unwrap();
+        assert_eq!(canonical.len(), count);
+        assert_eq!(indexer.index(&canonical).unwrap(), index);
+    }
+}
+
+#[test]
+fn test_canonical_form_uses_spades_first() {
+    let indexer = HandIndexer::new(&[2, 3]).unwrap();
+
+    assert_eq!(
+        indexer.canonicalize(&cards("Kd Ad 2c Qd 7h")).unwrap(),
+        cards("As Ks Qs 7h 2d")
+    );
+    assert_eq!(
+        HandIndexer::new(&[2])
+            .unwrap()
+            .canonicalize(&cards("7c 7d"))
+            .unwrap(),
+        cards("7s 7h")
+    );
+}
+
+#[test]
+fn test_canonicalize_hole_and_board() {
+    let (hole, board) =
+        canonicalize(&[cards("Jc")[0], cards("Tc")[0]], &cards("9d 2c 4h 4d")).unwrap();
+
+    assert_eq!(hole, [cards("Js")[0], cards("Ts")[0]]);
+    assert_eq!(board, cards("9h 4h 4d 2s"));
+    assert_eq!(
+        canonical_index(&[cards("Jc")[0], cards("Tc")[0]], &cards("9d 2c 4h 4d")).unwrap(),
+        canonical_index(&[cards("Jh")[0], cards("Th")[0]], &cards("4s 9c 4c 2h")).unwrap()
+    );
+}
+
+#[test]
+fn test_iso_errors() {
+    assert_eq!(
+        HandIndexer::new(&[2, 0]).unwrap_err(),
+        IsoError::InvalidRounds(vec![2, 0])
+    );
+    assert_eq!(
+        HandIndexer::new(&[30, 30]).unwrap_err(),
+        IsoError::InvalidRounds(vec![30, 30])
+    );
+
+    let indexer = HandIndexer::new(&[2, 3]).unwrap();
+    assert_eq!(
+        indexer.index(&cards("Ah Kh Qh")),
+        Err(IsoError::WrongCardCount(3))
+    );
+    assert_eq!(
+        indexer.index(&cards("Ah Kh Qh Ah 2c")),
+        Err(IsoError::DuplicateCard(cards("Ah")[0]))
+    );
+    assert_eq!(indexer.unindex(2, 0), Err(IsoError::InvalidRound(2)));
+    assert_eq!(indexer.unindex(0, 169), Err(IsoError::IndexOutOfRange(169)));
+    assert_eq!(
+        canonical_index(
+            &[cards("Ah")[0], cards("Kh")[0]],
+            &cards("2c 3c 4c 5c 6c 7c")
+        ),
+        Err(IsoError::WrongCardCount(8))
+    );
+}
+
+#[cfg(feature = "jokers")]
+#[test]
+fn test_jokers_are_rejected() {
+    let joker = Card::new(Rank::Joker, None);
+    let indexer = HandIndexer::new(&[2]).unwrap();
+    assert_eq!(
+        indexer.index(&[joker, cards("Ah")[0]]),
+        Err(IsoError::UnsuitedCard(joker))
+    );
+}
@@ -1,3 +1,5 @@
+use rand::SeedableRng;
+use rand::rngs::StdRng;
 use rand::seq::SliceRandom;
 
 #[cfg(feature = "serde")]
@@ -9,6 +11,17 @@ mod tests;
 #[cfg(feature = "display")]
 pub mod display;
 
+pub mod abstraction;
+pub mod draws;
+pub mod equity;
+pub mod eval;
+pub mod icm;
+pub mod iso;
+pub mod nash;
+pub mod notation;
+pub mod range;
+pub mod strength;
+
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
 #[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 pub enum Suit {
@@ -18,6 +31,10 @@ pub enum Suit {
     Spades,
 }
 
+impl Suit {
+    pub const ALL: [Suit; 4] = [Suit::Clubs, Suit::Diamonds, Suit::Hearts, Suit::Spades];
+}
+
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
 #[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 pub enum Rank {
@@ -38,6 +55,48 @@ pub enum Rank {
     Joker,
 }
 
+impl Rank {
+    /// The thirteen standard ranks in deck order (ace low)
+    pub const STANDARD: [Rank; 13] = [
+        Rank::Ace,
+        Rank::Two,
+        Rank::Three,
+        Rank::Four,
+        Rank::Five,
+        Rank::Six,
+        Rank::Seven,
+        Rank::Eight,
+        Rank::Nine,
+        Rank::Ten,
+        Rank::Jack,
+        Rank::Queen,
+        Rank::King,
+    ];
+
+    /// Value of the rank in poker terms: 2 for a deuce up to 14 for an ace
+    ///
+    /// Jokers have no poker value and return 0.
+    pub fn poker_value(&self) -> u8 {
+        match self {
+            Rank::Two => 2,
+            Rank::Three => 3,
+            Rank::Four => 4,
+            Rank::Five => 5,
+            Rank::Six => 6,
+            Rank::Seven => 7,
+            Rank::Eight => 8,
+            Rank::Nine => 9,
+            Rank::Ten => 10,
+            Rank::Jack => 11,
+            Rank::Queen => 12,
+            Rank::King => 13,
+            Rank::Ace => 14,
+            #[cfg(feature = "jokers")]
+            Rank::Joker => 0,
+        }
+    }
+}
+
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
 #[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 pub struct Card {
@@ -65,22 +124,8 @@ impl Deck {
     pub fn new() -> Self {
         let mut cards = Vec::with_capacity(54); // up to 54 if jokers are enabled
 
-        for &suit in &[Suit::Clubs, Suit::Diamonds, Suit::Hearts, Suit::Spades] {
-            for &rank in &[
-                Rank::Ace,
-                Rank::Two,
-                Rank::Three,
-                Rank::Four,
-                Rank::Five,
-                Rank::Six,
-                Rank::Seven,
-                Rank::Eight,
-                Rank::Nine,
-                Rank::Ten,
-                Rank::Jack,
-                Rank::Queen,
-                Rank::King,
-            ] {
+        for suit in Suit::ALL {
+            for rank in Rank::STANDARD {
                 cards.push(Card::new(rank, Some(suit)));
             }
         }
@@ -94,17 +139,34 @@ impl Deck {
         Self { cards }
     }
 
+    /// Creates a deck holding exactly `cards`, which are drawn from the last one back
+    pub fn from_cards(cards: Vec<Card>) -> Self {
+        Self { cards }
+    }
+
     pub fn new_shuffled() -> Self {
         let mut deck = Self::new();
         deck.shuffle();
         deck
     }
 
+    /// Creates a full deck shuffled from `seed`, so the same seed always deals the same cards
+    pub fn new_seeded(seed: u64) -> Self {
+        let mut deck = Self::new();
+        deck.shuffle_seeded(seed);
+        deck
+    }
+
     pub fn shuffle(&mut self) {
         let mut rng = rand::rng();
         self.cards.shuffle(&mut rng);
     }
 
+    /// Shuffles the deck reproducibly from `seed`
+    pub fn shuffle_seeded(&mut self, seed: u64) {
+        self.cards.shuffle(&mut StdRng::seed_from_u64(seed));
+    }
+
     pub fn draw(&mut self) -> Option<Card> {
         self.cards.pop()
     }
@@ -120,4 +182,29 @@ impl Deck {
     pub fn reset(&mut self) {
         *self = Self::new();
     }
+
+    pub fn cards(&self) -> &[Card] {
+        &self.cards
+    }
+
+    pub fn contains(&self, card: &Card) -> bool {
+        self.cards.contains(card)
+    }
+
+    /// Removes the first copy of `card` from the deck, returning whether it was present
+    pub fn remove(&mut self, card: &Card) -> bool {
+        match self.cards.iter().position(|c| c == card) {
+            Some(index) => {
+                self.cards.remove(index);
+                true
+            }
+            None => false,
+        }
+    }
+}
+
+impl Default for Deck {
+    fn default() -> Self {
+        Self::new()
+    }
 }
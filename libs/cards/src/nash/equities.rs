@@ -0,0 +1,146 @@
+use crate::equity::{EquityError, RangeEquityCalculator};
+use crate::range::{RANKS_DESCENDING, Range, StartingHand};
+
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
+/// Number of distinct starting hands
+pub(super) const HANDS: usize = 169;
+
+// A starting hand's cell in the 13x13 grid, read row by row: pairs on the diagonal, suited
+// hands above it and offsuit hands below
+pub(super) fn index(hand: StartingHand) -> usize {
+    let rank = |rank| RANKS_DESCENDING.iter().position(|&r| r == rank).unwrap();
+    let (high, low) = (rank(hand.high()), rank(hand.low()));
+    match hand {
+        StartingHand::Suited(..) => high * 13 + low,
+        StartingHand::Pair(_) | StartingHand::Offsuit(..) => low * 13 + high,
+    }
+}
+
+pub(super) fn hand_at(index: usize) -> StartingHand {
+    let (row, column) = (index / 13, index % 13);
+    StartingHand::new(
+        RANKS_DESCENDING[row],
+        RANKS_DESCENDING[column],
+        row < column,
+    )
+}
+
+/// The all-in preflop equity of each of the 169 starting hands against each other
+///
+/// Alongside each equity the table keeps how many ways the two hands can be dealt together,
+/// so that card removal is accounted for when hands are weighed against a range. Computing
+/// the whole table by sampling takes a while; it can be kept with the `serde` feature.
+///
+/// # Examples
+///
+/// ```
+/// use cards::nash::PreflopEquities;
+/// use cards::range::StartingHand;
+///
+/// // Every hand is a coin flip against every other, but AA meets AA only six ways
+/// let table = PreflopEquities::from_fn(|_, _| 0.5);
+/// let aces: StartingHand = "AA".parse().unwrap();
+/// assert_eq!(table.equity(aces, aces), 0.5);
+/// assert_eq!(table.matchups(aces, aces), 6);
+/// ```
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct PreflopEquities {
+    equities: Vec<f64>,
+    matchups: Vec<u32>,
+}
+
+impl PreflopEquities {
+    /// Builds a table from the equity of the first hand against the second
+    ///
+    /// Only one direction of each matchup is asked for; the other is taken to be its
+    /// complement, and a hand against itself is even.
+    pub fn from_fn<F>(mut equity: F) -> Self
+    where
+        F: FnMut(StartingHand, StartingHand) -> f64,
+    {
+        let mut equities = vec![0.5; HANDS * HANDS];
+        for first in 0..HANDS {
+            for second in first + 1..HANDS {
+                let value = equity(hand_at(first), hand_at(second)).clamp(0.0, 1.0);
+                equities[first * HANDS + second] = value;
+                equities[second * HANDS + first] = 1.0 - value;
+            }
+        }
+        Self {
+            equities,
+            matchups: matchups(),
+        }
+    }
+
+    /// Samples every matchup's equity with `iterations` deals each
+    ///
+    /// There are 14,196 matchups between different hands, so this is slow with more than a
+    /// few thousand iterations.
+    pub fn monte_carlo(iterations: u64, seed: u64) -> Result<Self, EquityError> {
+        if iterations == 0 {
+            return Err(EquityError::NoIterations);
+        }
+        let ranges: Vec<Range> = (0..HANDS)
+            .map(|i| {
+                let mut range = Range::new();
+                range.insert_hand(hand_at(i), 1.0);
+                range
+            })
+            .collect();
+
+        let mut result = Ok(());
+        let table = Self::from_fn(|first, second| {
+            if result.is_err() {
+                return 0.5;
+            }
+            let ranges = [ranges[index(first)].clone(), ranges[index(second)].clone()];
+            match RangeEquityCalculator::new(&ranges)
+                .iterations(iterations)
+                .seed(seed)
+                .monte_carlo()
+            {
+                Ok(equity) => equity.players()[0].equity(),
+                Err(e) => {
+                    result = Err(e);
+                    0.5
+                }
+            }
+        });
+        result.map(|()| table)
+    }
+
+    /// The first hand's share of the pot when both are all in before the flop
+    pub fn equity(&self, hand: StartingHand, against: StartingHand) -> f64 {
+        self.equities[index(hand) * HANDS + index(against)]
+    }
+
+    /// Number of ways the two hands can be dealt without sharing a card
+    pub fn matchups(&self, hand: StartingHand, against: StartingHand) -> u32 {
+        self.matchups[index(hand) * HANDS + index(against)]
+    }
+
+    // Both tables indexed by the pair of hands
+    pub(super) fn raw(&self) -> (&[f64], &[u32]) {
+        (&self.equities, &self.matchups)
+    }
+}
+
+// Counts the pairs of disjoint combos for every pair of hands
+fn matchups() -> Vec<u32> {
+    let combos: Vec<_> = (0..HANDS).map(|i| hand_at(i).combos()).collect();
+    let mut counts = vec![0; HANDS * HANDS];
+    for first in 0..HANDS {
+        for second in first..HANDS {
+            let count = combos[first]
+                .iter()
+                .map(|a| combos[second].iter().filter(|b| !a.conflicts(b)).count())
+                .sum::<usize>() as u32;
+            counts[first * HANDS + second] = count;
+            counts[second * HANDS + first] = count;
+        }
+    }
+    counts
+}
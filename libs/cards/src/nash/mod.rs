@@ -0,0 +1,336 @@
+use std::fmt;
+
+use crate::range::{Range, StartingHand};
+
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
+mod equities;
+
+pub use equities::PreflopEquities;
+
+use equities::{HANDS, hand_at, index};
+
+#[cfg(test)]
+mod tests;
+
+/// Rounds of fictitious play run when no iteration count is configured
+pub const DEFAULT_ITERATIONS: u64 = 1_000;
+
+/// Frequencies this close to never or always are reported as pure, since fictitious play
+/// only wears off its early mistakes slowly
+const PURE_MARGIN: f64 = 0.01;
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum PushFoldError {
+    /// The effective stack must cover the big blind and ante
+    InvalidStack(f64),
+    /// The small blind must be between zero and the big blind
+    InvalidSmallBlind(f64),
+    /// The ante must not be negative
+    InvalidAnte(f64),
+    /// The solver was asked for zero iterations
+    NoIterations,
+}
+
+impl fmt::Display for PushFoldError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            PushFoldError::InvalidStack(stack) => {
+                write!(
+                    f,
+                    "stack of {stack} big blinds does not cover the blind and ante"
+                )
+            }
+            PushFoldError::InvalidSmallBlind(blind) => {
+                write!(f, "small blind of {blind} big blinds is not valid")
+            }
+            PushFoldError::InvalidAnte(ante) => write!(f, "ante of {ante} big blinds is not valid"),
+            PushFoldError::NoIterations => write!(f, "iteration count must be greater than zero"),
+        }
+    }
+}
+
+impl std::error::Error for PushFoldError {}
+
+/// Solves heads-up push/fold: the small blind moves all in or folds, and the big blind calls
+/// or folds
+///
+/// Amounts are in big blinds, and the effective stack is counted before the blinds and antes
+/// are posted. Results are in chips rather than tournament money. The solver plays fictitious
+/// play: each round both players take their best response to the other's average strategy so
+/// far, and the averages approach a Nash equilibrium. Each matchup is weighed by how many ways
+/// it can be dealt, so card removal is taken into account.
+///
+/// # Examples
+///
+/// ```
+/// use cards::nash::{PreflopEquities, PushFoldSolver};
+///
+/// // A toy table where the higher top card always holds; see `PreflopEquities::monte_carlo`
+/// let equities = PreflopEquities::from_fn(|hand, against| {
+///     let (hand, against) = (hand.high().poker_value(), against.high().poker_value());
+///     if hand == against { 0.5 } else if hand > against { 0.7 } else { 0.3 }
+/// });
+///
+/// let chart = PushFoldSolver::new(10.0).ante(0.1).solve(&equities).unwrap();
+/// let aces = "AA".parse().unwrap();
+/// assert_eq!(chart.push_frequency(aces), 1.0);
+/// assert_eq!(chart.call_frequency(aces), 1.0);
+/// assert!(chart.exploitability() < 0.05);
+/// ```
+#[derive(Debug, Clone)]
+pub struct PushFoldSolver {
+    stack: f64,
+    small_blind: f64,
+    ante: f64,
+    iterations: u64,
+}
+
+impl PushFoldSolver {
+    /// Creates a solver for an effective stack in big blinds, with a half blind and no ante
+    pub fn new(stack: f64) -> Self {
+        Self {
+            stack,
+            small_blind: 0.5,
+            ante: 0.0,
+            iterations: DEFAULT_ITERATIONS,
+        }
+    }
+
+    /// Sets the small blind in big blinds
+    pub fn small_blind(mut self, small_blind: f64) -> Self {
+        self.small_blind = small_blind;
+        self
+    }
+
+    /// Sets the ante each player posts, in big blinds
+    pub fn ante(mut self, ante: f64) -> Self {
+        self.ante = ante;
+        self
+    }
+
+    /// Sets the number of rounds of fictitious play
+    pub fn iterations(mut self, iterations: u64) -> Self {
+        self.iterations = iterations;
+        self
+    }
+
+    /// Finds each player's equilibrium strategy using the given all-in equities
+    pub fn solve(&self, equities: &PreflopEquities) -> Result<PushFoldChart, PushFoldError> {
+        self.validate()?;
+        let game = Game::new(self, equities);
+
+        // The first round's best responses replace these outright
+        let mut push = vec![1.0; HANDS];
+        let mut call = vec![1.0; HANDS];
+        for round in 1..=self.iterations {
+            let step = 1.0 / round as f64;
+            for (average, best) in push.iter_mut().zip(game.push_gains(&call)) {
+                *average += (f64::from(u8::from(best > 0.0)) - *average) * step;
+            }
+            for (average, best) in call.iter_mut().zip(game.call_gains(&push)) {
+                *average += (f64::from(u8::from(best > 0.0)) - *average) * step;
+            }
+        }
+
+        for frequency in push.iter_mut().chain(call.iter_mut()) {
+            if *frequency < PURE_MARGIN {
+                *frequency = 0.0;
+            } else if *frequency > 1.0 - PURE_MARGIN {
+                *frequency = 1.0;
+            }
+        }
+        let push_gains = game.push_gains(&call);
+        let call_gains = game.call_gains(&push);
+        let value = game.value(&push, &call);
+        let best_push = game.value(&best_response(&push_gains), &call);
+        let best_call = game.value(&push, &best_response(&call_gains));
+        Ok(PushFoldChart {
+            stack: self.stack,
+            push,
+            call,
+            push_gains,
+            call_gains,
+            value,
+            exploitability: (best_push - best_call) / 2.0,
+        })
+    }
+
+    fn validate(&self) -> Result<(), PushFoldError> {
+        if !(0.0..=1.0).contains(&self.small_blind) {
+            return Err(PushFoldError::InvalidSmallBlind(self.small_blind));
+        }
+        if !(self.ante >= 0.0 && self.ante.is_finite()) {
+            return Err(PushFoldError::InvalidAnte(self.ante));
+        }
+        if !(self.stack > 1.0 + self.ante && self.stack.is_finite()) {
+            return Err(PushFoldError::InvalidStack(self.stack));
+        }
+        if self.iterations == 0 {
+            return Err(PushFoldError::NoIterations);
+        }
+        Ok(())
+    }
+}
+
+// Pure strategy playing each hand that gains by it
+fn best_response(gains: &[f64]) -> Vec<f64> {
+    gains
+        .iter()
+        .map(|&gain| f64::from(u8::from(gain > 0.0)))
+        .collect()
+}
+
+// The small blind's payoffs, in big blinds won from the start of the hand
+struct Game<'a> {
+    equities: &'a [f64],
+    matchups: &'a [u32],
+    stack: f64,
+    // Lost by folding the small blind, and won when the big blind folds
+    fold: f64,
+    steal: f64,
+}
+
+impl<'a> Game<'a> {
+    fn new(solver: &PushFoldSolver, equities: &'a PreflopEquities) -> Self {
+        let (equities, matchups) = equities.raw();
+        Self {
+            equities,
+            matchups,
+            stack: solver.stack,
+            fold: -(solver.small_blind + solver.ante),
+            steal: 1.0 + solver.ante,
+        }
+    }
+
+    // The small blind's result when both are all in
+    fn showdown(&self, pusher: usize, caller: usize) -> f64 {
+        (2.0 * self.equities[pusher * HANDS + caller] - 1.0) * self.stack
+    }
+
+    fn weight(&self, pusher: usize, caller: usize) -> f64 {
+        f64::from(self.matchups[pusher * HANDS + caller])
+    }
+
+    // What each small blind hand gains by pushing rather than folding, on average over the
+    // hands the big blind may hold
+    fn push_gains(&self, call: &[f64]) -> Vec<f64> {
+        (0..HANDS)
+            .map(|pusher| {
+                let (mut total, mut weight) = (0.0, 0.0);
+                for (caller, &calls) in call.iter().enumerate() {
+                    let w = self.weight(pusher, caller);
+                    total +=
+                        w * (calls * self.showdown(pusher, caller) + (1.0 - calls) * self.steal);
+                    weight += w;
+                }
+                total / weight - self.fold
+            })
+            .collect()
+    }
+
+    // What each big blind hand gains by calling rather than folding, on average over the
+    // hands the small blind pushes; a hand that never sees a push gains nothing
+    fn call_gains(&self, push: &[f64]) -> Vec<f64> {
+        (0..HANDS)
+            .map(|caller| {
+                let (mut total, mut weight) = (0.0, 0.0);
+                for (pusher, &pushes) in push.iter().enumerate() {
+                    let w = self.weight(pusher, caller) * pushes;
+                    total += w * (self.steal - self.showdown(pusher, caller));
+                    weight += w;
+                }
+                if weight > 0.0 { total / weight } else { 0.0 }
+            })
+            .collect()
+    }
+
+    // The small blind's expected result over every deal
+    fn value(&self, push: &[f64], call: &[f64]) -> f64 {
+        let (mut total, mut weight) = (0.0, 0.0);
+        for (pusher, &pushes) in push.iter().enumerate() {
+            for (caller, &calls) in call.iter().enumerate() {
+                let w = self.weight(pusher, caller);
+                let pushed = calls * self.showdown(pusher, caller) + (1.0 - calls) * self.steal;
+                total += w * (pushes * pushed + (1.0 - pushes) * self.fold);
+                weight += w;
+            }
+        }
+        total / weight
+    }
+}
+
+/// Heads-up push/fold strategies for one effective stack, as found by `PushFoldSolver`
+///
+/// Frequencies are per starting hand, and the ranges carry them as combo weights, so hands
+/// the solver mixes appear at part weight. Either range can be drawn as a 13x13 grid with
+/// `HandGrid::from_range` under the `display` feature, and the gains with
+/// `HandGrid::from_values`.
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct PushFoldChart {
+    stack: f64,
+    push: Vec<f64>,
+    call: Vec<f64>,
+    push_gains: Vec<f64>,
+    call_gains: Vec<f64>,
+    value: f64,
+    exploitability: f64,
+}
+
+impl PushFoldChart {
+    /// The effective stack in big blinds
+    pub fn stack(&self) -> f64 {
+        self.stack
+    }
+
+    /// How often the small blind moves all in with a hand
+    pub fn push_frequency(&self, hand: StartingHand) -> f64 {
+        self.push[index(hand)]
+    }
+
+    /// How often the big blind calls an all in with a hand
+    pub fn call_frequency(&self, hand: StartingHand) -> f64 {
+        self.call[index(hand)]
+    }
+
+    /// Big blinds the small blind gains by pushing a hand instead of folding it
+    pub fn push_gain(&self, hand: StartingHand) -> f64 {
+        self.push_gains[index(hand)]
+    }
+
+    /// Big blinds the big blind gains by calling with a hand instead of folding it
+    pub fn call_gain(&self, hand: StartingHand) -> f64 {
+        self.call_gains[index(hand)]
+    }
+
+    /// The small blind's pushing range
+    pub fn push_range(&self) -> Range {
+        to_range(&self.push)
+    }
+
+    /// The big blind's calling range
+    pub fn call_range(&self) -> Range {
+        to_range(&self.call)
+    }
+
+    /// The small blind's expected result per hand in big blinds
+    pub fn value(&self) -> f64 {
+        self.value
+    }
+
+    /// How far the strategies are from an equilibrium: the average of what each player
+    /// could gain per hand, in big blinds, by switching to a best response
+    pub fn exploitability(&self) -> f64 {
+        self.exploitability
+    }
+}
+
+fn to_range(frequencies: &[f64]) -> Range {
+    let mut range = Range::new();
+    for (i, &frequency) in frequencies.iter().enumerate() {
+        range.insert_hand(hand_at(i), frequency);
+    }
+    range
+}
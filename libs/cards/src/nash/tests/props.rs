@@ -0,0 +1,48 @@
+use super::super::*;
+use proptest::prelude::*;
+
+// A table from a strength for each hand, the stronger hand's equity growing with the gap
+fn arb_equities() -> impl Strategy<Value = PreflopEquities> {
+    prop::collection::vec(0.0f64..1.0, HANDS).prop_map(|strengths| {
+        PreflopEquities::from_fn(|hand, against| {
+            0.5 + (strengths[index(hand)] - strengths[index(against)]) * 0.35
+        })
+    })
+}
+
+proptest! {
+    #![proptest_config(ProptestConfig::with_cases(16))]
+
+    #[test]
+    fn charts_are_valid_strategies(
+        equities in arb_equities(),
+        stack in 1.5f64..40.0,
+        ante in 0.0f64..0.25,
+    ) {
+        let chart = PushFoldSolver::new(stack)
+            .ante(ante)
+            .iterations(50)
+            .solve(&equities)
+            .unwrap();
+        for hand in StartingHand::all() {
+            prop_assert!((0.0..=1.0).contains(&chart.push_frequency(hand)));
+            prop_assert!((0.0..=1.0).contains(&chart.call_frequency(hand)));
+        }
+        prop_assert!(chart.exploitability() > -1e-9);
+
+        // The small blind can lose no more than their stack, nor win more than the big blind's
+        prop_assert!(chart.value() >= -stack && chart.value() <= stack);
+        prop_assert!(chart.value() >= -(0.5 + ante) - chart.exploitability() * 2.0 - 1e-9);
+    }
+
+    #[test]
+    fn equities_are_complementary(equities in arb_equities()) {
+        for hand in StartingHand::all().step_by(7) {
+            for against in StartingHand::all().step_by(5) {
+                let sum = equities.equity(hand, against) + equities.equity(against, hand);
+                prop_assert!((sum - 1.0).abs() < 1e-12);
+                prop_assert_eq!(equities.matchups(hand, against), equities.matchups(against, hand));
+            }
+        }
+    }
+}
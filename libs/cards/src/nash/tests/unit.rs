@@ -0,0 +1,203 @@
+use super::super::*;
+
+fn hand(notation: &str) -> StartingHand {
+    notation.parse().unwrap()
+}
+
+fn close(a: f64, b: f64, tolerance: f64) -> bool {
+    (a - b).abs() < tolerance
+}
+
+// A toy table where the higher top card wins 70% of the time, then the higher kicker
+fn high_card() -> PreflopEquities {
+    PreflopEquities::from_fn(|hand, against| {
+        let key = |hand: StartingHand| (hand.high().poker_value(), hand.low().poker_value());
+        match key(hand).cmp(&key(against)) {
+            std::cmp::Ordering::Greater => 0.7,
+            std::cmp::Ordering::Less => 0.3,
+            std::cmp::Ordering::Equal => 0.5,
+        }
+    })
+}
+
+// Share of the 1326 combos in a range
+fn share(range: &Range) -> f64 {
+    range.total_weight() / 1326.0
+}
+
+#[test]
+fn test_every_hand_has_its_own_cell() {
+    let mut seen = [false; HANDS];
+    for hand in StartingHand::all() {
+        let i = index(hand);
+        assert!(!seen[i], "{hand} shares a cell");
+        seen[i] = true;
+        assert_eq!(hand_at(i), hand);
+    }
+    assert_eq!(index(hand("AA")), 0);
+    assert_eq!(index(hand("AKs")), 1);
+    assert_eq!(index(hand("AKo")), 13);
+    assert_eq!(index(hand("22")), HANDS - 1);
+}
+
+#[test]
+fn test_matchups_account_for_card_removal() {
+    let table = PreflopEquities::from_fn(|_, _| 0.5);
+    assert_eq!(table.matchups(hand("AA"), hand("AA")), 6);
+    assert_eq!(table.matchups(hand("AA"), hand("KK")), 36);
+    assert_eq!(table.matchups(hand("AKs"), hand("AKo")), 24);
+    assert_eq!(table.matchups(hand("AKo"), hand("AKs")), 24);
+    assert_eq!(table.matchups(hand("AKs"), hand("QJs")), 16);
+
+    // Each combo leaves 1225 for the opponent
+    for hand in StartingHand::all() {
+        let total: u32 = StartingHand::all()
+            .map(|against| table.matchups(hand, against))
+            .sum();
+        assert_eq!(total, hand.combo_count() as u32 * 1225);
+    }
+}
+
+#[test]
+fn test_equities_are_complementary() {
+    let table = high_card();
+    assert_eq!(table.equity(hand("AKs"), hand("KQs")), 0.7);
+    assert!(close(table.equity(hand("KQs"), hand("AKs")), 0.3, 1e-12));
+    assert_eq!(table.equity(hand("AKs"), hand("AKo")), 0.5);
+    assert_eq!(table.equity(hand("T9o"), hand("T9o")), 0.5);
+
+    // Out-of-range values are clamped
+    let table = PreflopEquities::from_fn(|_, _| 2.0);
+    assert_eq!(table.equity(hand("AA"), hand("KK")), 1.0);
+    assert_eq!(table.equity(hand("KK"), hand("AA")), 0.0);
+}
+
+#[test]
+fn test_monte_carlo_needs_iterations() {
+    assert_eq!(
+        PreflopEquities::monte_carlo(0, 1),
+        Err(crate::equity::EquityError::NoIterations)
+    );
+}
+
+#[test]
+fn test_coin_flips_are_always_pushed_and_called() {
+    // With no edge either way the pusher wins the blinds or breaks even
+    let chart = PushFoldSolver::new(10.0)
+        .iterations(20)
+        .solve(&PreflopEquities::from_fn(|_, _| 0.5))
+        .unwrap();
+    assert_eq!(share(&chart.push_range()), 1.0);
+    assert_eq!(share(&chart.call_range()), 1.0);
+    assert!(close(chart.value(), 0.0, 1e-12));
+    assert!(close(chart.push_gain(hand("72o")), 0.5, 1e-12));
+    assert!(close(chart.call_gain(hand("72o")), 1.0, 1e-12));
+    assert!(close(chart.exploitability(), 0.0, 1e-12));
+    assert_eq!(chart.stack(), 10.0);
+}
+
+#[test]
+fn test_strong_hands_push_and_weak_hands_fold() {
+    let chart = PushFoldSolver::new(15.0)
+        .iterations(300)
+        .solve(&high_card())
+        .unwrap();
+    assert_eq!(chart.push_frequency(hand("AA")), 1.0);
+    assert_eq!(chart.call_frequency(hand("AKs")), 1.0);
+    assert_eq!(chart.push_frequency(hand("32o")), 0.0);
+    assert_eq!(chart.call_frequency(hand("32o")), 0.0);
+    assert!(chart.push_gain(hand("AA")) > 0.0);
+    assert!(chart.call_gain(hand("32o")) < 0.0);
+
+    // The pusher needs more than the caller, who only has to beat a range
+    assert!(share(&chart.push_range()) > share(&chart.call_range()));
+    assert!(chart.value() > -0.5);
+    assert!(chart.exploitability() < 0.02);
+}
+
+#[test]
+fn test_deeper_stacks_push_less() {
+    let equities = high_card();
+    let pushes: Vec<f64> = [2.0, 5.0, 10.0, 25.0]
+        .into_iter()
+        .map(|stack| {
+            let chart = PushFoldSolver::new(stack)
+                .iterations(200)
+                .solve(&equities)
+                .unwrap();
+            share(&chart.push_range())
+        })
+        .collect();
+    assert!(
+        pushes.windows(2).all(|pair| pair[0] >= pair[1]),
+        "{pushes:?}"
+    );
+    assert!(pushes[0] > pushes[3]);
+}
+
+#[test]
+fn test_antes_widen_the_ranges() {
+    let equities = high_card();
+    let solve = |ante| {
+        PushFoldSolver::new(10.0)
+            .ante(ante)
+            .iterations(200)
+            .solve(&equities)
+            .unwrap()
+    };
+    let (plain, anted) = (solve(0.0), solve(0.25));
+    assert!(share(&anted.push_range()) >= share(&plain.push_range()));
+    assert!(share(&anted.call_range()) >= share(&plain.call_range()));
+}
+
+#[test]
+fn test_invalid_spots() {
+    let equities = high_card();
+    let solve = |solver: PushFoldSolver| solver.solve(&equities).unwrap_err();
+    assert_eq!(
+        solve(PushFoldSolver::new(1.0)),
+        PushFoldError::InvalidStack(1.0)
+    );
+    assert_eq!(
+        solve(PushFoldSolver::new(1.5).ante(0.5)),
+        PushFoldError::InvalidStack(1.5)
+    );
+    assert_eq!(
+        solve(PushFoldSolver::new(f64::INFINITY)),
+        PushFoldError::InvalidStack(f64::INFINITY)
+    );
+    assert_eq!(
+        solve(PushFoldSolver::new(10.0).small_blind(1.5)),
+        PushFoldError::InvalidSmallBlind(1.5)
+    );
+    assert_eq!(
+        solve(PushFoldSolver::new(10.0).ante(-0.1)),
+        PushFoldError::InvalidAnte(-0.1)
+    );
+    assert_eq!(
+        solve(PushFoldSolver::new(10.0).iterations(0)),
+        PushFoldError::NoIterations
+    );
+    assert_eq!(
+        PushFoldError::InvalidStack(1.0).to_string(),
+        "stack of 1 big blinds does not cover the blind and ante"
+    );
+}
+
+#[cfg(feature = "display")]
+#[test]
+fn test_chart_renders_as_a_grid() {
+    use crate::display::HandGrid;
+
+    let chart = PushFoldSolver::new(15.0)
+        .iterations(200)
+        .solve(&high_card())
+        .unwrap();
+    let grid = HandGrid::from_range(&chart.push_range());
+    assert_eq!(grid.value(hand("AA")), Some(1.0));
+    assert_eq!(grid.value(hand("32o")), None);
+    assert!(grid.render_plain().contains("AA"));
+
+    let gains = HandGrid::from_values(|hand| Some(chart.push_gain(hand)));
+    assert!(gains.value(hand("AA")).unwrap() > 0.0);
+}
@@ -0,0 +1,149 @@
+use std::fmt;
+use std::str::FromStr;
+
+use crate::{Card, Rank, Suit};
+
+#[cfg(test)]
+mod tests;
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ParseCardError {
+    /// The character is not one of `AKQJT98765432`
+    InvalidRank(char),
+    /// The character is not one of `cdhs`
+    InvalidSuit(char),
+    /// A card is written as exactly two characters, rank then suit
+    InvalidLength(String),
+}
+
+impl fmt::Display for ParseCardError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ParseCardError::InvalidRank(c) => write!(f, "invalid rank '{c}'"),
+            ParseCardError::InvalidSuit(c) => write!(f, "invalid suit '{c}'"),
+            ParseCardError::InvalidLength(s) => {
+                write!(f, "expected a rank and a suit such as \"Ah\", got \"{s}\"")
+            }
+        }
+    }
+}
+
+impl std::error::Error for ParseCardError {}
+
+impl Rank {
+    /// The single character used for the rank in text notation, with `T` for ten
+    pub fn to_char(&self) -> char {
+        match self {
+            Rank::Ace => 'A',
+            Rank::Two => '2',
+            Rank::Three => '3',
+            Rank::Four => '4',
+            Rank::Five => '5',
+            Rank::Six => '6',
+            Rank::Seven => '7',
+            Rank::Eight => '8',
+            Rank::Nine => '9',
+            Rank::Ten => 'T',
+            Rank::Jack => 'J',
+            Rank::Queen => 'Q',
+            Rank::King => 'K',
+            #[cfg(feature = "jokers")]
+            Rank::Joker => '*',
+        }
+    }
+
+    /// Parses a rank character, ignoring case
+    pub fn from_char(c: char) -> Result<Rank, ParseCardError> {
+        match c.to_ascii_uppercase() {
+            'A' => Ok(Rank::Ace),
+            '2' => Ok(Rank::Two),
+            '3' => Ok(Rank::Three),
+            '4' => Ok(Rank::Four),
+            '5' => Ok(Rank::Five),
+            '6' => Ok(Rank::Six),
+            '7' => Ok(Rank::Seven),
+            '8' => Ok(Rank::Eight),
+            '9' => Ok(Rank::Nine),
+            'T' => Ok(Rank::Ten),
+            'J' => Ok(Rank::Jack),
+            'Q' => Ok(Rank::Queen),
+            'K' => Ok(Rank::King),
+            _ => Err(ParseCardError::InvalidRank(c)),
+        }
+    }
+}
+
+impl Suit {
+    /// The lowercase letter used for the suit in text notation
+    pub fn to_char(&self) -> char {
+        match self {
+            Suit::Clubs => 'c',
+            Suit::Diamonds => 'd',
+            Suit::Hearts => 'h',
+            Suit::Spades => 's',
+        }
+    }
+
+    /// Parses a suit letter, ignoring case
+    pub fn from_char(c: char) -> Result<Suit, ParseCardError> {
+        match c.to_ascii_lowercase() {
+            'c' => Ok(Suit::Clubs),
+            'd' => Ok(Suit::Diamonds),
+            'h' => Ok(Suit::Hearts),
+            's' => Ok(Suit::Spades),
+            _ => Err(ParseCardError::InvalidSuit(c)),
+        }
+    }
+}
+
+/// Formats a card in two-character text notation, e.g. `Ah` or `Td`
+///
+/// # Examples
+///
+/// ```
+/// use cards::{Card, Rank, Suit};
+///
+/// let card = Card::new(Rank::Ten, Some(Suit::Diamonds));
+/// assert_eq!(card.to_string(), "Td");
+/// assert_eq!("Td".parse::<Card>(), Ok(card));
+/// ```
+impl fmt::Display for Card {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self.suit {
+            Some(suit) => write!(f, "{}{}", self.rank.to_char(), suit.to_char()),
+            None => write!(f, "{}", self.rank.to_char()),
+        }
+    }
+}
+
+impl FromStr for Card {
+    type Err = ParseCardError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let mut chars = s.chars();
+        match (chars.next(), chars.next(), chars.next()) {
+            (Some(rank), Some(suit), None) => Ok(Card::new(
+                Rank::from_char(rank)?,
+                Some(Suit::from_char(suit)?),
+            )),
+            _ => Err(ParseCardError::InvalidLength(s.to_string())),
+        }
+    }
+}
+
+/// Parses a run of cards such as `"AhKd"` or `"Ah Kd 7c"`
+pub fn parse_cards(s: &str) -> Result<Vec<Card>, ParseCardError> {
+    let chars: Vec<char> = s.chars().filter(|c| !c.is_whitespace()).collect();
+    if !chars.len().is_multiple_of(2) {
+        return Err(ParseCardError::InvalidLength(s.to_string()));
+    }
+    chars
+        .chunks(2)
+        .map(|pair| {
+            Ok(Card::new(
+                Rank::from_char(pair[0])?,
+                Some(Suit::from_char(pair[1])?),
+            ))
+        })
+        .collect()
+}
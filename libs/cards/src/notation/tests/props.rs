@@ -0,0 +1,22 @@
+use super::super::*;
+use crate::{Card, Deck};
+use proptest::prelude::*;
+
+proptest! {
+    #[test]
+    fn card_notation_round_trips(index in 0usize..52) {
+        let card: Card = Deck::new().cards()[index];
+        let text = card.to_string();
+        prop_assert_eq!(text.len(), 2);
+        prop_assert_eq!(text.parse::<Card>(), Ok(card));
+    }
+
+    #[test]
+    fn parse_cards_round_trips(indices in proptest::collection::vec(0usize..52, 0..8)) {
+        let deck = Deck::new();
+        let cards: Vec<Card> = indices.iter().map(|&i| deck.cards()[i]).collect();
+        let text: Vec<String> = cards.iter().map(|c| c.to_string()).collect();
+        prop_assert_eq!(parse_cards(&text.join(" ")), Ok(cards.clone()));
+        prop_assert_eq!(parse_cards(&text.concat()), Ok(cards));
+    }
+}
@@ -0,0 +1,53 @@
+use super::super::*;
+use crate::{Card, Rank, Suit};
+
+#[test]
+fn test_card_to_string() {
+    assert_eq!(Card::new(Rank::Ace, Some(Suit::Hearts)).to_string(), "Ah");
+    assert_eq!(Card::new(Rank::Ten, Some(Suit::Clubs)).to_string(), "Tc");
+    assert_eq!(Card::new(Rank::Two, Some(Suit::Spades)).to_string(), "2s");
+}
+
+#[test]
+fn test_parse_card() {
+    assert_eq!(
+        "Kd".parse(),
+        Ok(Card::new(Rank::King, Some(Suit::Diamonds)))
+    );
+    assert_eq!("tS".parse(), Ok(Card::new(Rank::Ten, Some(Suit::Spades))));
+}
+
+#[test]
+fn test_parse_card_errors() {
+    assert_eq!("Xd".parse::<Card>(), Err(ParseCardError::InvalidRank('X')));
+    assert_eq!("Ax".parse::<Card>(), Err(ParseCardError::InvalidSuit('x')));
+    assert_eq!(
+        "10h".parse::<Card>(),
+        Err(ParseCardError::InvalidLength("10h".to_string()))
+    );
+    assert_eq!(
+        "".parse::<Card>(),
+        Err(ParseCardError::InvalidLength(String::new()))
+    );
+}
+
+#[test]
+fn test_parse_cards() {
+    let expected = vec![
+        Card::new(Rank::Ace, Some(Suit::Hearts)),
+        Card::new(Rank::King, Some(Suit::Diamonds)),
+        Card::new(Rank::Seven, Some(Suit::Clubs)),
+    ];
+    assert_eq!(parse_cards("AhKd7c"), Ok(expected.clone()));
+    assert_eq!(parse_cards("Ah Kd 7c"), Ok(expected));
+    assert_eq!(parse_cards(""), Ok(Vec::new()));
+    assert!(parse_cards("AhK").is_err());
+}
+
+#[test]
+fn test_rank_chars() {
+    assert_eq!(Rank::Ten.to_char(), 'T');
+    assert_eq!(Rank::from_char('q'), Ok(Rank::Queen));
+    assert_eq!(Suit::Hearts.to_char(), 'h');
+    assert_eq!(Suit::from_char('C'), Ok(Suit::Clubs));
+}
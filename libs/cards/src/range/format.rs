@@ -0,0 +1,237 @@
+use super::{RANKS_DESCENDING, Range, StartingHand};
+use crate::Rank;
+
+// How much of a starting hand a range holds
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum Coverage {
+    Absent,
+    // Every combo, all at the same weight
+    Full(f64),
+    // Some combos, or combos at differing weights
+    Partial,
+}
+
+fn coverage(range: &Range, hand: StartingHand) -> Coverage {
+    let weights: Vec<f64> = hand.combos().iter().map(|c| range.weight(c)).collect();
+    if weights.iter().all(|&w| w == 0.0) {
+        Coverage::Absent
+    } else if weights.iter().all(|&w| w == weights[0]) {
+        Coverage::Full(weights[0])
+    } else {
+        Coverage::Partial
+    }
+}
+
+fn with_weight(token: String, weight: f64) -> String {
+    if weight == 1.0 {
+        token
+    } else {
+        format!("{token}:{weight}")
+    }
+}
+
+// Lists the individual combos of a partly held starting hand
+fn combo_tokens(range: &Range, hand: StartingHand, tokens: &mut Vec<String>) {
+    for combo in hand.combos().into_iter().rev() {
+        if range.contains(&combo) {
+            tokens.push(with_weight(combo.to_string(), range.weight(&combo)));
+        }
+    }
+}
+
+// Groups neighbouring entries that are fully held at the same weight into runs of
+// `(first index, last index, weight)`
+fn runs(weights: &[Option<f64>]) -> Vec<(usize, usize, f64)> {
+    let mut runs: Vec<(usize, usize, f64)> = Vec::new();
+    for (i, weight) in weights.iter().enumerate() {
+        let Some(weight) = *weight else { continue };
+        match runs.last_mut() {
+            Some((_, last, w)) if *last + 1 == i && *w == weight => *last = i,
+            _ => runs.push((i, i, weight)),
+        }
+    }
+    runs
+}
+
+// The non-pairs with one high card, by kicker from the highest down
+struct Row {
+    suited: Vec<Coverage>,
+    offsuit: Vec<Coverage>,
+    // Hands fully held as both halves at one weight, then suited only, then offsuit only
+    classes: [Vec<Option<f64>>; 3],
+}
+
+impl Row {
+    fn new(range: &Range, i: usize) -> Row {
+        let high = RANKS_DESCENDING[i];
+        let kickers = &RANKS_DESCENDING[i + 1..];
+        let suited: Vec<Coverage> = kickers
+            .iter()
+            .map(|&low| coverage(range, StartingHand::Suited(high, low)))
+            .collect();
+        let offsuit: Vec<Coverage> = kickers
+            .iter()
+            .map(|&low| coverage(range, StartingHand::Offsuit(high, low)))
+            .collect();
+
+        // Both halves fully held at the same weight are written without a suffix
+        let both: Vec<Option<f64>> = suited
+            .iter()
+            .zip(&offsuit)
+            .map(|pair| match pair {
+                (Coverage::Full(s), Coverage::Full(o)) if s == o => Some(*s),
+                _ => None,
+            })
+            .collect();
+        let only = |coverage: &[Coverage]| -> Vec<Option<f64>> {
+            coverage
+                .iter()
+                .zip(&both)
+                .map(|(c, b)| match (c, b) {
+                    (Coverage::Full(w), None) => Some(*w),
+                    _ => None,
+                })
+                .collect()
+        };
+        let (suited_only, offsuit_only) = (only(&suited), only(&offsuit));
+        Row {
+            suited,
+            offsuit,
+            classes: [both, suited_only, offsuit_only],
+        }
+    }
+}
+
+// Runs of two or more connectors held the same way, as `(top row, bottom row, weight)` for
+// each class of `Row`. A connector is only taken out of its row when it would otherwise be
+// written on its own there.
+fn connector_runs(rows: &mut [Row]) -> [Vec<(usize, usize, f64)>; 3] {
+    std::array::from_fn(|class| {
+        let lone: Vec<Option<f64>> = rows
+            .iter()
+            .map(|row| {
+                let weights = &row.classes[class];
+                weights[0].filter(|&w| weights.get(1) != Some(&Some(w)))
+            })
+            .collect();
+        let found: Vec<(usize, usize, f64)> = runs(&lone)
+            .into_iter()
+            .filter(|(top, bottom, _)| bottom > top)
+            .collect();
+        for &(top, bottom, _) in &found {
+            for row in &mut rows[top..=bottom] {
+                row.classes[class][0] = None;
+            }
+        }
+        found
+    })
+}
+
+pub(super) fn format(range: &Range) -> String {
+    let mut tokens = Vec::new();
+
+    // Pairs, from aces down: `TT+`, `99-77` or `55`
+    let pairs: Vec<Coverage> = RANKS_DESCENDING
+        .iter()
+        .map(|&rank| coverage(range, StartingHand::Pair(rank)))
+        .collect();
+    let full: Vec<Option<f64>> = pairs
+        .iter()
+        .map(|c| match c {
+            Coverage::Full(w) => Some(*w),
+            _ => None,
+        })
+        .collect();
+    let mut pair_tokens: Vec<(usize, String)> = runs(&full)
+        .into_iter()
+        .map(|(first, last, weight)| {
+            let (top, bottom) = (
+                RANKS_DESCENDING[first].to_char(),
+                RANKS_DESCENDING[last].to_char(),
+            );
+            let token = if first == last {
+                format!("{top}{top}")
+            } else if first == 0 {
+                format!("{bottom}{bottom}+")
+            } else {
+                format!("{top}{top}-{bottom}{bottom}")
+            };
+            (first, with_weight(token, weight))
+        })
+        .collect();
+    for (i, coverage) in pairs.iter().enumerate() {
+        if *coverage == Coverage::Partial {
+            let mut combos = Vec::new();
+            combo_tokens(range, StartingHand::Pair(RANKS_DESCENDING[i]), &mut combos);
+            pair_tokens.extend(combos.into_iter().map(|t| (i, t)));
+        }
+    }
+    pair_tokens.sort_by_key(|(i, _)| *i);
+    tokens.extend(pair_tokens.into_iter().map(|(_, t)| t));
+
+    // Non-pairs, grouped by high card then kicker, with runs of connectors pulled out first
+    let mut rows: Vec<Row> = (0..RANKS_DESCENDING.len() - 1)
+        .map(|i| Row::new(range, i))
+        .collect();
+    let connectors = connector_runs(&mut rows);
+    for (i, row) in rows.iter().enumerate() {
+        let high = RANKS_DESCENDING[i];
+        let kickers = &RANKS_DESCENDING[i + 1..];
+
+        let mut hand_tokens: Vec<(usize, u8, String)> = Vec::new();
+        for (order, suffix) in [(0, ""), (1, "s"), (2, "o")] {
+            for &(top, bottom, weight) in &connectors[order as usize] {
+                if top == i {
+                    let token = connector_run(top, bottom, suffix);
+                    hand_tokens.push((0, order, with_weight(token, weight)));
+                }
+            }
+            for (first, last, weight) in runs(&row.classes[order as usize]) {
+                let token = kicker_run(high, kickers, first, last, suffix);
+                hand_tokens.push((first, order, with_weight(token, weight)));
+            }
+        }
+        for (j, &low) in kickers.iter().enumerate() {
+            for (order, hand, coverage) in [
+                (3, StartingHand::Suited(high, low), row.suited[j]),
+                (4, StartingHand::Offsuit(high, low), row.offsuit[j]),
+            ] {
+                if coverage == Coverage::Partial {
+                    let mut combos = Vec::new();
+                    combo_tokens(range, hand, &mut combos);
+                    hand_tokens.extend(combos.into_iter().map(|t| (j, order, t)));
+                }
+            }
+        }
+        hand_tokens.sort_by_key(|(j, order, _)| (*j, *order));
+        tokens.extend(hand_tokens.into_iter().map(|(_, _, t)| t));
+    }
+
+    tokens.join(",")
+}
+
+// `AKs`, `A9s+` when the run reaches the top kicker, or `A5s-A2s`
+fn kicker_run(high: Rank, kickers: &[Rank], first: usize, last: usize, suffix: &str) -> String {
+    let high = high.to_char();
+    let (top, bottom) = (kickers[first].to_char(), kickers[last].to_char());
+    if first == last {
+        format!("{high}{top}{suffix}")
+    } else if first == 0 {
+        format!("{high}{bottom}{suffix}+")
+    } else {
+        format!("{high}{top}{suffix}-{high}{bottom}{suffix}")
+    }
+}
+
+// `76s+` when the run reaches `AKs`, or `T9s-76s`
+fn connector_run(top: usize, bottom: usize, suffix: &str) -> String {
+    let name = |i: usize| {
+        let (high, low) = (RANKS_DESCENDING[i], RANKS_DESCENDING[i + 1]);
+        format!("{}{}{suffix}", high.to_char(), low.to_char())
+    };
+    if top == 0 {
+        format!("{}+", name(bottom))
+    } else {
+        format!("{}-{}", name(top), name(bottom))
+    }
+}
@@ -0,0 +1,306 @@
+use std::cmp::Ordering;
+use std::collections::BTreeMap;
+use std::fmt;
+use std::str::FromStr;
+
+use crate::notation::ParseCardError;
+use crate::{Card, Rank};
+
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
+mod format;
+mod parse;
+mod starting_hand;
+
+pub use starting_hand::StartingHand;
+
+#[cfg(test)]
+mod tests;
+
+// Ranks from ace down to deuce, the order ranges are written and displayed in
+pub(crate) const RANKS_DESCENDING: [Rank; 13] = [
+    Rank::Ace,
+    Rank::King,
+    Rank::Queen,
+    Rank::Jack,
+    Rank::Ten,
+    Rank::Nine,
+    Rank::Eight,
+    Rank::Seven,
+    Rank::Six,
+    Rank::Five,
+    Rank::Four,
+    Rank::Three,
+    Rank::Two,
+];
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ParseRangeError {
+    /// Not a starting hand such as `AKs`, `T9o` or `77`
+    InvalidHand(String),
+    /// A token that is neither a starting hand, a `+` or `-` span, nor two concrete cards
+    InvalidToken(String),
+    /// A weight that is not a number between 0 and 1
+    InvalidWeight(String),
+    /// A `-` span whose ends are not pairs, do not share a high card and are not equally gapped
+    InvalidSpan(String),
+    /// A concrete combo with an invalid card
+    InvalidCard(ParseCardError),
+}
+
+impl fmt::Display for ParseRangeError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ParseRangeError::InvalidHand(s) => write!(f, "invalid starting hand \"{s}\""),
+            ParseRangeError::InvalidToken(s) => write!(f, "invalid range token \"{s}\""),
+            ParseRangeError::InvalidWeight(s) => write!(f, "invalid weight \"{s}\""),
+            ParseRangeError::InvalidSpan(s) => write!(f, "invalid span \"{s}\""),
+            ParseRangeError::InvalidCard(e) => write!(f, "invalid card: {e}"),
+        }
+    }
+}
+
+impl std::error::Error for ParseRangeError {}
+
+impl From<ParseCardError> for ParseRangeError {
+    fn from(e: ParseCardError) -> Self {
+        ParseRangeError::InvalidCard(e)
+    }
+}
+
+/// Two specific hole cards, stored with the higher card first
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct Combo {
+    high: Card,
+    low: Card,
+}
+
+impl Combo {
+    /// Creates a combo from two cards in either order
+    ///
+    /// # Panics
+    ///
+    /// Panics if the cards are the same or either has no suit.
+    pub fn new(first: Card, second: Card) -> Self {
+        assert!(first != second, "a combo needs two different cards");
+        assert!(
+            first.suit.is_some() && second.suit.is_some(),
+            "combo cards need a suit"
+        );
+        if card_index(&first) > card_index(&second) {
+            Self {
+                high: first,
+                low: second,
+            }
+        } else {
+            Self {
+                high: second,
+                low: first,
+            }
+        }
+    }
+
+    pub fn cards(&self) -> [Card; 2] {
+        [self.high, self.low]
+    }
+
+    pub fn high(&self) -> Card {
+        self.high
+    }
+
+    pub fn low(&self) -> Card {
+        self.low
+    }
+
+    pub fn contains(&self, card: &Card) -> bool {
+        self.high == *card || self.low == *card
+    }
+
+    /// Whether the two combos share a card and so cannot be dealt together
+    pub fn conflicts(&self, other: &Combo) -> bool {
+        other.contains(&self.high) || other.contains(&self.low)
+    }
+
+    pub fn starting_hand(&self) -> StartingHand {
+        StartingHand::new(
+            self.high.rank,
+            self.low.rank,
+            self.high.suit == self.low.suit,
+        )
+    }
+}
+
+impl Ord for Combo {
+    fn cmp(&self, other: &Self) -> Ordering {
+        (card_index(&self.high), card_index(&self.low))
+            .cmp(&(card_index(&other.high), card_index(&other.low)))
+    }
+}
+
+impl PartialOrd for Combo {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl fmt::Display for Combo {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}{}", self.high, self.low)
+    }
+}
+
+// Orders cards by rank (deuce lowest, ace highest), then by suit
+fn card_index(card: &Card) -> usize {
+    (card.rank.poker_value() as usize).saturating_sub(2) * 4 + card.suit.map_or(0, |s| s as usize)
+}
+
+/// A set of two-card combos, each held with a weight between 0 and 1
+///
+/// Ranges parse from and display as standard range notation: comma-separated pairs
+/// (`TT`), suited and offsuit hands (`AKs`, `KQo`, or `AK` for both), `+` to extend up
+/// (`TT+`, `A9s+`, and `76s+` for connectors moving up together), `-` spans (`A5s-A2s`,
+/// `99-66`, `T9s-76s`), concrete combos (`AhKh`) and an optional `:weight` on any token.
+///
+/// # Examples
+///
+/// ```
+/// use cards::range::Range;
+///
+/// let range: Range = "TT+, AKs, A5s-A2s, KQo:0.5".parse().unwrap();
+/// assert_eq!(range.len(), 30 + 4 + 16 + 12);
+/// assert_eq!(range.to_string(), "TT+,AKs,A5s-A2s,KQo:0.5");
+/// ```
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct Range {
+    combos: BTreeMap<Combo, f64>,
+}
+
+impl Range {
+    /// Creates an empty range
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Creates a range holding all 1326 combos at full weight
+    pub fn full() -> Self {
+        let mut range = Self::new();
+        for hand in StartingHand::all() {
+            range.insert_hand(hand, 1.0);
+        }
+        range
+    }
+
+    /// Adds a combo with the given weight, replacing any previous weight
+    ///
+    /// A weight of zero or less removes the combo; weights above one are capped at one.
+    pub fn insert(&mut self, combo: Combo, weight: f64) {
+        if weight > 0.0 {
+            self.combos.insert(combo, weight.min(1.0));
+        } else {
+            self.combos.remove(&combo);
+        }
+    }
+
+    /// Adds every combo of a starting hand with the given weight
+    pub fn insert_hand(&mut self, hand: StartingHand, weight: f64) {
+        for combo in hand.combos() {
+            self.insert(combo, weight);
+        }
+    }
+
+    pub fn remove(&mut self, combo: &Combo) {
+        self.combos.remove(combo);
+    }
+
+    /// The weight of a combo, zero when it is not in the range
+    pub fn weight(&self, combo: &Combo) -> f64 {
+        self.combos.get(combo).copied().unwrap_or(0.0)
+    }
+
+    pub fn contains(&self, combo: &Combo) -> bool {
+        self.combos.contains_key(combo)
+    }
+
+    /// Number of combos in the range, regardless of weight
+    pub fn len(&self) -> usize {
+        self.combos.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.combos.is_empty()
+    }
+
+    /// Sum of the weights of every combo
+    pub fn total_weight(&self) -> f64 {
+        self.combos.values().sum()
+    }
+
+    /// Iterates over the combos and their weights
+    pub fn combos(&self) -> impl Iterator<Item = (Combo, f64)> + '_ {
+        self.combos.iter().map(|(&combo, &weight)| (combo, weight))
+    }
+
+    /// The range left once combos holding any of the `dead` cards are removed
+    pub fn without_cards(&self, dead: &[Card]) -> Range {
+        Range {
+            combos: self
+                .combos
+                .iter()
+                .filter(|(combo, _)| !dead.iter().any(|card| combo.contains(card)))
+                .map(|(&combo, &weight)| (combo, weight))
+                .collect(),
+        }
+    }
+
+    /// Combos in either range, taking the greater weight when in both
+    pub fn union(&self, other: &Range) -> Range {
+        let mut combos = self.combos.clone();
+        for (&combo, &weight) in &other.combos {
+            let entry = combos.entry(combo).or_insert(weight);
+            *entry = entry.max(weight);
+        }
+        Range { combos }
+    }
+
+    /// Combos in both ranges, taking the lesser weight
+    pub fn intersection(&self, other: &Range) -> Range {
+        Range {
+            combos: self
+                .combos
+                .iter()
+                .filter_map(|(&combo, &weight)| {
+                    other.combos.get(&combo).map(|&w| (combo, weight.min(w)))
+                })
+                .collect(),
+        }
+    }
+
+    /// Combos in this range that are not in `other`
+    pub fn difference(&self, other: &Range) -> Range {
+        Range {
+            combos: self
+                .combos
+                .iter()
+                .filter(|(combo, _)| !other.combos.contains_key(combo))
+                .map(|(&combo, &weight)| (combo, weight))
+                .collect(),
+        }
+    }
+}
+
+impl FromStr for Range {
+    type Err = ParseRangeError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        parse::parse(s)
+    }
+}
+
+/// Formats the range in the shortest notation the formatter can find
+impl fmt::Display for Range {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(&format::format(self))
+    }
+}
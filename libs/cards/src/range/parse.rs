@@ -0,0 +1,159 @@
+use super::{Combo, ParseRangeError, RANKS_DESCENDING, Range, StartingHand};
+use crate::notation::parse_cards;
+use crate::{Rank, Suit};
+
+// Which of a starting hand's combos a written hand refers to
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Kind {
+    Pair,
+    Any,
+    Suited,
+    Offsuit,
+}
+
+// A starting hand as written, where `AK` stands for both `AKs` and `AKo`
+#[derive(Debug, Clone, Copy)]
+struct Spec {
+    high: u8,
+    low: u8,
+    kind: Kind,
+}
+
+impl Spec {
+    fn parse(s: &str) -> Result<Spec, ParseRangeError> {
+        let kind = match s.chars().nth(2) {
+            None => Kind::Any,
+            Some('s') => Kind::Suited,
+            Some('o') => Kind::Offsuit,
+            Some(_) => return Err(ParseRangeError::InvalidHand(s.to_string())),
+        };
+        let hand: StartingHand = match kind {
+            Kind::Any => format!("{s}o").parse().or_else(|_| s.parse())?,
+            _ => s.parse()?,
+        };
+        Ok(Spec {
+            high: hand.high().poker_value(),
+            low: hand.low().poker_value(),
+            kind: if hand.is_pair() { Kind::Pair } else { kind },
+        })
+    }
+
+    fn with_values(self, high: u8, low: u8) -> Spec {
+        Spec { high, low, ..self }
+    }
+
+    fn hands(&self) -> Vec<StartingHand> {
+        let (high, low) = (rank(self.high), rank(self.low));
+        match self.kind {
+            Kind::Pair => vec![StartingHand::Pair(high)],
+            Kind::Any => vec![
+                StartingHand::Suited(high, low),
+                StartingHand::Offsuit(high, low),
+            ],
+            Kind::Suited => vec![StartingHand::Suited(high, low)],
+            Kind::Offsuit => vec![StartingHand::Offsuit(high, low)],
+        }
+    }
+
+    // `TT+` is every pair from tens up, `76s+` moves connectors up together to `AKs`,
+    // and any other hand raises its kicker up to one below the high card
+    fn and_better(self) -> Vec<Spec> {
+        if self.kind == Kind::Pair {
+            (self.low..=14).map(|v| self.with_values(v, v)).collect()
+        } else if self.low + 1 == self.high {
+            (0..=14 - self.high)
+                .map(|step| self.with_values(self.high + step, self.low + step))
+                .collect()
+        } else {
+            (self.low..self.high)
+                .map(|low| self.with_values(self.high, low))
+                .collect()
+        }
+    }
+
+    // Every hand between two written ends, inclusive
+    fn span(self, other: Spec, token: &str) -> Result<Vec<Spec>, ParseRangeError> {
+        let invalid = || ParseRangeError::InvalidSpan(token.to_string());
+        if self.kind != other.kind {
+            return Err(invalid());
+        }
+        let (top, bottom) = if (self.high, self.low) >= (other.high, other.low) {
+            (self, other)
+        } else {
+            (other, self)
+        };
+
+        if top.kind == Kind::Pair {
+            Ok((bottom.low..=top.low)
+                .map(|v| top.with_values(v, v))
+                .collect())
+        } else if top.high == bottom.high {
+            Ok((bottom.low..=top.low)
+                .map(|low| top.with_values(top.high, low))
+                .collect())
+        } else if top.high - top.low == bottom.high - bottom.low {
+            Ok((0..=top.high - bottom.high)
+                .map(|step| top.with_values(bottom.high + step, bottom.low + step))
+                .collect())
+        } else {
+            Err(invalid())
+        }
+    }
+}
+
+fn rank(value: u8) -> Rank {
+    RANKS_DESCENDING[14 - value as usize]
+}
+
+pub(super) fn parse(s: &str) -> Result<Range, ParseRangeError> {
+    let mut range = Range::new();
+
+    for token in s.split(',').map(str::trim).filter(|t| !t.is_empty()) {
+        let (hands, weight) = match token.split_once(':') {
+            Some((hands, weight)) => (hands.trim(), parse_weight(weight.trim())?),
+            None => (token, 1.0),
+        };
+
+        if let Some(combo) = parse_combo(hands)? {
+            range.insert(combo, weight);
+            continue;
+        }
+
+        let specs = if let Some(base) = hands.strip_suffix('+') {
+            Spec::parse(base)?.and_better()
+        } else if let Some((first, second)) = hands.split_once('-') {
+            Spec::parse(first.trim())?.span(Spec::parse(second.trim())?, token)?
+        } else {
+            vec![Spec::parse(hands)?]
+        };
+
+        for spec in specs {
+            for hand in spec.hands() {
+                range.insert_hand(hand, weight);
+            }
+        }
+    }
+
+    Ok(range)
+}
+
+fn parse_weight(s: &str) -> Result<f64, ParseRangeError> {
+    match s.parse::<f64>() {
+        Ok(weight) if (0.0..=1.0).contains(&weight) => Ok(weight),
+        _ => Err(ParseRangeError::InvalidWeight(s.to_string())),
+    }
+}
+
+// Two concrete cards such as `AhKh`, told apart from hands like `AKs+` by the suit letter
+// in second place
+fn parse_combo(s: &str) -> Result<Option<Combo>, ParseRangeError> {
+    let chars: Vec<char> = s.chars().collect();
+    if chars.len() != 4 || Suit::from_char(chars[1]).is_err() || Rank::from_char(chars[0]).is_err()
+    {
+        return Ok(None);
+    }
+    match parse_cards(s)?.as_slice() {
+        [first, second] if first != second => Ok(Some(Combo::new(*first, *second))),
+        _ => Err(ParseRangeError::InvalidToken(s.to_string())),
+    }
+}
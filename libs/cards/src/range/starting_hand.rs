@@ -0,0 +1,137 @@
+use std::fmt;
+use std::str::FromStr;
+
+use super::{Combo, ParseRangeError, RANKS_DESCENDING};
+use crate::{Card, Rank, Suit};
+
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
+/// One of the 169 strategically distinct two-card starting hands, e.g. `AKs`, `T9o` or `77`
+///
+/// Non-pairs always hold the higher rank first.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub enum StartingHand {
+    Pair(Rank),
+    Suited(Rank, Rank),
+    Offsuit(Rank, Rank),
+}
+
+impl StartingHand {
+    /// Creates a starting hand from two ranks in either order; `suited` is ignored for pairs
+    pub fn new(first: Rank, second: Rank, suited: bool) -> Self {
+        let (high, low) = if first.poker_value() >= second.poker_value() {
+            (first, second)
+        } else {
+            (second, first)
+        };
+        match (high == low, suited) {
+            (true, _) => StartingHand::Pair(high),
+            (false, true) => StartingHand::Suited(high, low),
+            (false, false) => StartingHand::Offsuit(high, low),
+        }
+    }
+
+    /// All 169 starting hands, pairs and suited and offsuit hands, highest ranks first
+    pub fn all() -> impl Iterator<Item = StartingHand> {
+        RANKS_DESCENDING.iter().enumerate().flat_map(|(i, &high)| {
+            RANKS_DESCENDING[i..].iter().flat_map(move |&low| {
+                if high == low {
+                    vec![StartingHand::Pair(high)]
+                } else {
+                    vec![
+                        StartingHand::Suited(high, low),
+                        StartingHand::Offsuit(high, low),
+                    ]
+                }
+            })
+        })
+    }
+
+    pub fn high(&self) -> Rank {
+        match *self {
+            StartingHand::Pair(rank) => rank,
+            StartingHand::Suited(high, _) | StartingHand::Offsuit(high, _) => high,
+        }
+    }
+
+    pub fn low(&self) -> Rank {
+        match *self {
+            StartingHand::Pair(rank) => rank,
+            StartingHand::Suited(_, low) | StartingHand::Offsuit(_, low) => low,
+        }
+    }
+
+    pub fn is_pair(&self) -> bool {
+        matches!(self, StartingHand::Pair(_))
+    }
+
+    pub fn is_suited(&self) -> bool {
+        matches!(self, StartingHand::Suited(..))
+    }
+
+    /// Number of concrete combos: 6 for a pair, 4 suited or 12 offsuit
+    pub fn combo_count(&self) -> usize {
+        match self {
+            StartingHand::Pair(_) => 6,
+            StartingHand::Suited(..) => 4,
+            StartingHand::Offsuit(..) => 12,
+        }
+    }
+
+    /// Every concrete two-card combo of this starting hand
+    pub fn combos(&self) -> Vec<Combo> {
+        let card = |rank, suit| Card::new(rank, Some(suit));
+        let mut combos = Vec::with_capacity(self.combo_count());
+        for (i, &first) in Suit::ALL.iter().enumerate() {
+            for &second in &Suit::ALL {
+                let keep = match self {
+                    StartingHand::Pair(_) => Suit::ALL[i + 1..].contains(&second),
+                    StartingHand::Suited(..) => first == second,
+                    StartingHand::Offsuit(..) => first != second,
+                };
+                if keep {
+                    combos.push(Combo::new(
+                        card(self.high(), first),
+                        card(self.low(), second),
+                    ));
+                }
+            }
+        }
+        combos
+    }
+}
+
+impl fmt::Display for StartingHand {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let (high, low) = (self.high().to_char(), self.low().to_char());
+        match self {
+            StartingHand::Pair(_) => write!(f, "{high}{low}"),
+            StartingHand::Suited(..) => write!(f, "{high}{low}s"),
+            StartingHand::Offsuit(..) => write!(f, "{high}{low}o"),
+        }
+    }
+}
+
+impl FromStr for StartingHand {
+    type Err = ParseRangeError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let invalid = || ParseRangeError::InvalidHand(s.to_string());
+        let chars: Vec<char> = s.chars().collect();
+        let (first, second) = match chars.as_slice() {
+            [a, b] | [a, b, _] => (
+                Rank::from_char(*a).map_err(|_| invalid())?,
+                Rank::from_char(*b).map_err(|_| invalid())?,
+            ),
+            _ => return Err(invalid()),
+        };
+        match (chars.get(2), first == second) {
+            (None, true) => Ok(StartingHand::Pair(first)),
+            (Some('s'), false) => Ok(StartingHand::new(first, second, true)),
+            (Some('o'), false) => Ok(StartingHand::new(first, second, false)),
+            _ => Err(invalid()),
+        }
+    }
+}
@@ -0,0 +1,57 @@
+use super::super::*;
+use proptest::prelude::*;
+
+fn arb_range() -> impl Strategy<Value = Range> {
+    let hands: Vec<StartingHand> = StartingHand::all().collect();
+    proptest::collection::vec(
+        (
+            proptest::sample::select(hands),
+            prop_oneof![Just(1.0), Just(0.5), Just(0.25)],
+            any::<bool>(),
+            0usize..12,
+        ),
+        0..40,
+    )
+    .prop_map(|entries| {
+        let mut range = Range::new();
+        for (hand, weight, whole, combo) in entries {
+            if whole {
+                range.insert_hand(hand, weight);
+            } else {
+                let combos = hand.combos();
+                range.insert(combos[combo % combos.len()], weight);
+            }
+        }
+        range
+    })
+}
+
+proptest! {
+    #[test]
+    fn format_round_trips(range in arb_range()) {
+        let text = range.to_string();
+        let parsed: Range = text.parse().unwrap();
+        prop_assert_eq!(parsed, range, "{}", text);
+    }
+
+    #[test]
+    fn format_is_never_longer_than_listing_combos(range in arb_range()) {
+        let listed: Vec<String> = range.combos().map(|(c, w)| {
+            if w == 1.0 { c.to_string() } else { format!("{c}:{w}") }
+        }).collect();
+        prop_assert!(range.to_string().len() <= listed.join(",").len());
+    }
+
+    #[test]
+    fn set_operations_are_consistent(a in arb_range(), b in arb_range()) {
+        let union = a.union(&b);
+        let intersection = a.intersection(&b);
+        let difference = a.difference(&b);
+
+        prop_assert_eq!(union.len() + intersection.len(), a.len() + b.len());
+        prop_assert_eq!(difference.len() + intersection.len(), a.len());
+        for (combo, _) in difference.combos() {
+            prop_assert!(!b.contains(&combo));
+        }
+    }
+}
@@ -0,0 +1,199 @@
+use super::super::*;
+use crate::notation::parse_cards;
+use crate::{Card, Rank, Suit};
+
+fn range(s: &str) -> Range {
+    s.parse().unwrap()
+}
+
+fn combo(s: &str) -> Combo {
+    let cards = parse_cards(s).unwrap();
+    Combo::new(cards[0], cards[1])
+}
+
+fn hands(range: &Range) -> Vec<String> {
+    let mut hands: Vec<String> = range
+        .combos()
+        .map(|(c, _)| c.starting_hand().to_string())
+        .collect();
+    hands.dedup();
+    hands.sort();
+    hands.dedup();
+    hands
+}
+
+#[test]
+fn test_starting_hand_combo_counts() {
+    assert_eq!(StartingHand::Pair(Rank::Ten).combos().len(), 6);
+    assert_eq!(
+        StartingHand::Suited(Rank::Ace, Rank::King).combos().len(),
+        4
+    );
+    assert_eq!(
+        StartingHand::Offsuit(Rank::Ace, Rank::King).combos().len(),
+        12
+    );
+    assert_eq!(StartingHand::all().count(), 169);
+    let total: usize = StartingHand::all().map(|h| h.combo_count()).sum();
+    assert_eq!(total, 1326);
+}
+
+#[test]
+fn test_starting_hand_notation() {
+    assert_eq!(
+        "AKs".parse(),
+        Ok(StartingHand::Suited(Rank::Ace, Rank::King))
+    );
+    assert_eq!(
+        "KAo".parse(),
+        Ok(StartingHand::Offsuit(Rank::Ace, Rank::King))
+    );
+    assert_eq!("77".parse(), Ok(StartingHand::Pair(Rank::Seven)));
+    assert!("77s".parse::<StartingHand>().is_err());
+    assert!("AK".parse::<StartingHand>().is_err());
+    assert_eq!(
+        StartingHand::Offsuit(Rank::Ten, Rank::Nine).to_string(),
+        "T9o"
+    );
+}
+
+#[test]
+fn test_combo_is_normalised() {
+    let ah = Card::new(Rank::Ace, Some(Suit::Hearts));
+    let kd = Card::new(Rank::King, Some(Suit::Diamonds));
+    assert_eq!(Combo::new(kd, ah), Combo::new(ah, kd));
+    assert_eq!(Combo::new(kd, ah).cards(), [ah, kd]);
+    assert_eq!(Combo::new(kd, ah).to_string(), "AhKd");
+}
+
+#[test]
+fn test_parse_pairs() {
+    assert_eq!(range("TT+").len(), 30);
+    assert_eq!(hands(&range("99-66")), vec!["66", "77", "88", "99"]);
+    assert_eq!(range("66-99"), range("99-66"));
+    assert_eq!(range("AA").len(), 6);
+}
+
+#[test]
+fn test_parse_suited_and_offsuit() {
+    assert_eq!(range("AKs").len(), 4);
+    assert_eq!(range("KQo").len(), 12);
+    assert_eq!(range("AK").len(), 16);
+}
+
+#[test]
+fn test_parse_plus_raises_kicker() {
+    assert_eq!(
+        hands(&range("A9s+")),
+        vec!["A9s", "AJs", "AKs", "AQs", "ATs"]
+    );
+    assert_eq!(range("K9o+").len(), 4 * 12);
+}
+
+#[test]
+fn test_parse_plus_moves_connectors_up() {
+    assert_eq!(
+        hands(&range("T9s+")),
+        vec!["AKs", "JTs", "KQs", "QJs", "T9s"]
+    );
+    assert_eq!(range("76s+").len(), 8 * 4);
+}
+
+#[test]
+fn test_parse_spans() {
+    assert_eq!(hands(&range("A5s-A2s")), vec!["A2s", "A3s", "A4s", "A5s"]);
+    assert_eq!(hands(&range("T9s-76s")), vec!["76s", "87s", "98s", "T9s"]);
+    assert_eq!(
+        "A5s-K2s".parse::<Range>(),
+        Err(ParseRangeError::InvalidSpan("A5s-K2s".to_string()))
+    );
+    assert_eq!(
+        "A5s-A2o".parse::<Range>(),
+        Err(ParseRangeError::InvalidSpan("A5s-A2o".to_string()))
+    );
+}
+
+#[test]
+fn test_parse_concrete_combos() {
+    let range = range("AhKh, AsKs");
+    assert_eq!(range.len(), 2);
+    assert!(range.contains(&combo("KhAh")));
+    assert!("AhAh".parse::<Range>().is_err());
+}
+
+#[test]
+fn test_parse_weights() {
+    let range = range("AA, KK:0.5, AhKh:0.25");
+    assert_eq!(range.weight(&combo("AsAh")), 1.0);
+    assert_eq!(range.weight(&combo("KsKh")), 0.5);
+    assert_eq!(range.weight(&combo("AhKh")), 0.25);
+    assert_eq!(range.weight(&combo("AsKs")), 0.0);
+    assert_eq!(range.total_weight(), 6.0 + 3.0 + 0.25);
+    assert_eq!(
+        "AA:1.5".parse::<Range>(),
+        Err(ParseRangeError::InvalidWeight("1.5".to_string()))
+    );
+}
+
+#[test]
+fn test_parse_errors() {
+    assert!("XX".parse::<Range>().is_err());
+    assert!("AKx".parse::<Range>().is_err());
+    assert!("77s".parse::<Range>().is_err());
+    assert_eq!("".parse::<Range>(), Ok(Range::new()));
+}
+
+#[test]
+fn test_full_range() {
+    assert_eq!(Range::full().len(), 1326);
+}
+
+#[test]
+fn test_dead_cards_remove_combos() {
+    let range = range("AA, AKs");
+    let dead = parse_cards("Ah").unwrap();
+    let live = range.without_cards(&dead);
+    // AhAx (3 combos) and AhKh are blocked
+    assert_eq!(live.len(), 6 + 4 - 3 - 1);
+    assert!(!live.combos().any(|(c, _)| c.contains(&dead[0])));
+}
+
+#[test]
+fn test_set_operations() {
+    let a = range("TT+, AKs:0.5");
+    let b = range("QQ-88, AKs");
+
+    assert_eq!(a.union(&b).to_string(), "88+,AKs");
+    assert_eq!(a.intersection(&b).to_string(), "QQ-TT,AKs:0.5");
+    assert_eq!(a.difference(&b).to_string(), "KK+");
+}
+
+#[test]
+fn test_format_compresses() {
+    assert_eq!(range("AA,KK,QQ,JJ,TT").to_string(), "TT+");
+    assert_eq!(range("AKs, AQs, AJs").to_string(), "AJs+");
+    assert_eq!(range("AKs, AKo").to_string(), "AK");
+    assert_eq!(range("A5s-A2s, 99-66").to_string(), "99-66,A5s-A2s");
+    assert_eq!(range("KQo:0.5").to_string(), "KQo:0.5");
+    assert_eq!(range("AhKh, AsKs").to_string(), "AsKs,AhKh");
+    assert_eq!(
+        Range::full().to_string(),
+        "22+,A2+,K2+,Q2+,J2+,T2+,92+,82+,72+,62+,52+,42+,32"
+    );
+}
+
+#[test]
+fn test_format_mixes_halves() {
+    assert_eq!(range("ATs+, AKo").to_string(), "AK,AQs-ATs");
+    assert_eq!(range("AKs, AKo:0.5").to_string(), "AKs,AKo:0.5");
+}
+
+#[test]
+fn test_format_writes_connector_runs() {
+    assert_eq!(range("T9s+").to_string(), "T9s+");
+    assert_eq!(range("T9s-76s").to_string(), "T9s-76s");
+    assert_eq!(range("T9s-54s, T9o-54o").to_string(), "T9-54");
+    assert_eq!(range("KQo, QJo, JTs").to_string(), "KQo-QJo,JTs");
+    // Connectors that belong to a kicker run stay in it
+    assert_eq!(range("A2s+, 76s+").to_string(), "A2s+,KQs-76s");
+}
@@ -0,0 +1,357 @@
+use std::collections::HashMap;
+use std::fmt;
+use std::ops::ControlFlow;
+use std::sync::Mutex;
+
+use crate::equity::for_each_board;
+use crate::eval::{HandValue, evaluate};
+use crate::iso::canonical_index;
+use crate::range::{Combo, Range};
+use crate::{Card, Deck};
+
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
+#[cfg(test)]
+mod tests;
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum StrengthError {
+    /// Strength is measured on the flop, turn or river
+    InvalidBoardSize(usize),
+    /// The same card was given more than once across hole cards and board
+    DuplicateCard(Card),
+    /// A card without a suit (a joker) was given
+    UnsuitedCard(Card),
+    /// The opponent's range has no combos left once the known cards are removed
+    EmptyRange,
+}
+
+impl fmt::Display for StrengthError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            StrengthError::InvalidBoardSize(size) => {
+                write!(f, "board must have 3, 4 or 5 cards, got {size}")
+            }
+            StrengthError::DuplicateCard(card) => write!(f, "card {card:?} appears more than once"),
+            StrengthError::UnsuitedCard(card) => write!(f, "card {card:?} has no suit"),
+            StrengthError::EmptyRange => write!(f, "opponent range has no live combos"),
+        }
+    }
+}
+
+impl std::error::Error for StrengthError {}
+
+/// A hand's strength now and its potential to improve or fall behind by the river
+///
+/// Hand strength is the chance of being ahead of one opponent's hand right now, counting
+/// ties as half. Positive potential is the chance of ending ahead when currently behind, and
+/// negative potential the chance of ending behind when currently ahead (again counting ties
+/// as half), both over every runout to the river.
+#[derive(Debug, Clone, Copy, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct Strength {
+    hand_strength: f64,
+    positive_potential: f64,
+    negative_potential: f64,
+}
+
+impl Strength {
+    /// Chance of currently holding the best hand against one opponent
+    pub fn hand_strength(&self) -> f64 {
+        self.hand_strength
+    }
+
+    /// Chance of currently holding the best hand against `opponents` opponents, treating
+    /// their hands as independent
+    pub fn hand_strength_against(&self, opponents: u32) -> f64 {
+        self.hand_strength.powi(opponents as i32)
+    }
+
+    /// PPot: the chance of improving to the best hand when currently behind
+    pub fn positive_potential(&self) -> f64 {
+        self.positive_potential
+    }
+
+    /// NPot: the chance of falling behind when currently ahead
+    pub fn negative_potential(&self) -> f64 {
+        self.negative_potential
+    }
+
+    /// Effective hand strength against one opponent:
+    /// `HS × (1 - NPot) + (1 - HS) × PPot`
+    pub fn effective(&self) -> f64 {
+        self.effective_against(1)
+    }
+
+    /// Effective hand strength against `opponents` opponents
+    pub fn effective_against(&self, opponents: u32) -> f64 {
+        let strength = self.hand_strength_against(opponents);
+        strength * (1.0 - self.negative_potential) + (1.0 - strength) * self.positive_potential
+    }
+}
+
+// Where one hand stands against another
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Standing {
+    Ahead = 0,
+    Tied = 1,
+    Behind = 2,
+}
+
+impl Standing {
+    fn of(hero: HandValue, villain: HandValue) -> Standing {
+        match hero.cmp(&villain) {
+            std::cmp::Ordering::Greater => Standing::Ahead,
+            std::cmp::Ordering::Equal => Standing::Tied,
+            std::cmp::Ordering::Less => Standing::Behind,
+        }
+    }
+}
+
+// The board size and suit-isomorphic index of hole cards and board
+type CanonicalKey = (usize, u64);
+
+/// Calculates hand strength, hand potential and effective hand strength
+///
+/// Against a random hand the results depend only on the cards up to a relabelling of the
+/// suits, so they are cached under a suit-normalised key and isomorphic spots (such as
+/// `AhKh` on `Qh Jh 2c` and `AsKs` on `Qs Js 2d`) are only worked out once. Results against
+/// a given range are not cached, since a range need not treat the suits alike.
+///
+/// # Examples
+///
+/// ```
+/// use cards::notation::parse_cards;
+/// use cards::strength::StrengthCalculator;
+///
+/// let hole = parse_cards("Ah Kh").unwrap();
+/// let board = parse_cards("Qh 7h 2c 9s").unwrap();
+///
+/// let calculator = StrengthCalculator::new();
+/// let strength = calculator.strength(&[hole[0], hole[1]], &board).unwrap();
+///
+/// // Ace high with the nut flush draw improves often enough to be worth more than it shows
+/// assert!(strength.positive_potential() > 0.2);
+/// assert!(strength.effective() > strength.hand_strength());
+/// ```
+#[derive(Debug, Default)]
+pub struct StrengthCalculator {
+    range: Option<Range>,
+    hand_strengths: Mutex<HashMap<CanonicalKey, f64>>,
+    strengths: Mutex<HashMap<CanonicalKey, Strength>>,
+}
+
+impl StrengthCalculator {
+    /// Creates a calculator that measures hands against a random opponent hand
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Creates a calculator that measures hands against the combos of `range`, dealt in
+    /// proportion to their weights
+    pub fn against(range: Range) -> Self {
+        Self {
+            range: Some(range),
+            ..Self::default()
+        }
+    }
+
+    /// Calculates the hand strength alone, which is much cheaper than `strength` on the flop
+    /// and turn since no runouts are walked
+    pub fn hand_strength(&self, hole: &[Card; 2], board: &[Card]) -> Result<f64, StrengthError> {
+        let opponents = self.opponents(hole, board)?;
+        let key = self.canonical_key(hole, board);
+        if let Some(cached) = key.and_then(|key| lock(&self.hand_strengths).get(&key).copied()) {
+            return Ok(cached);
+        }
+
+        let mut cards: Vec<Card> = hole.iter().chain(board).copied().collect();
+        let hero = evaluate(&cards);
+        let mut totals = [0.0; 3];
+        for (combo, weight) in &opponents {
+            cards.clear();
+            cards.extend(combo.cards().iter().chain(board));
+            totals[Standing::of(hero, evaluate(&cards)) as usize] += weight;
+        }
+        let strength = share_ahead(totals);
+
+        if let Some(key) = key {
+            lock(&self.hand_strengths).insert(key, strength);
+        }
+        Ok(strength)
+    }
+
+    /// Calculates hand strength together with positive and negative potential, walking every
+    /// runout to the river
+    pub fn strength(&self, hole: &[Card; 2], board: &[Card]) -> Result<Strength, StrengthError> {
+        let opponents = self.opponents(hole, board)?;
+        let key = self.canonical_key(hole, board);
+        if let Some(cached) = key.and_then(|key| lock(&self.strengths).get(&key).copied()) {
+            return Ok(cached);
+        }
+
+        let strength = potential(hole, board, &opponents);
+
+        if let Some(key) = key {
+            lock(&self.strengths).insert(key, strength);
+            lock(&self.hand_strengths).insert(key, strength.hand_strength);
+        }
+        Ok(strength)
+    }
+
+    /// Number of spots whose results are cached
+    pub fn cache_len(&self) -> usize {
+        lock(&self.hand_strengths)
+            .len()
+            .max(lock(&self.strengths).len())
+    }
+
+    pub fn clear_cache(&self) {
+        lock(&self.hand_strengths).clear();
+        lock(&self.strengths).clear();
+    }
+
+    // Only results against a random hand are the same for isomorphic spots
+    fn canonical_key(&self, hole: &[Card; 2], board: &[Card]) -> Option<CanonicalKey> {
+        if self.range.is_some() {
+            return None;
+        }
+        canonical_index(hole, board)
+            .ok()
+            .map(|index| (board.len(), index))
+    }
+
+    // Validates the cards and returns the opponent combos that do not touch them
+    fn opponents(
+        &self,
+        hole: &[Card; 2],
+        board: &[Card],
+    ) -> Result<Vec<(Combo, f64)>, StrengthError> {
+        if !(3..=5).contains(&board.len()) {
+            return Err(StrengthError::InvalidBoardSize(board.len()));
+        }
+        let mut deck = Deck::new();
+        for card in hole.iter().chain(board) {
+            if card.suit.is_none() {
+                return Err(StrengthError::UnsuitedCard(*card));
+            }
+            if !deck.remove(card) {
+                return Err(StrengthError::DuplicateCard(*card));
+            }
+        }
+
+        let known: Vec<Card> = hole.iter().chain(board).copied().collect();
+        let opponents: Vec<(Combo, f64)> = match &self.range {
+            Some(range) => range.without_cards(&known).combos().collect(),
+            None => {
+                let live: Vec<Card> = deck
+                    .cards()
+                    .iter()
+                    .filter(|c| c.suit.is_some())
+                    .copied()
+                    .collect();
+                live.iter()
+                    .enumerate()
+                    .flat_map(|(i, &first)| {
+                        live[i + 1..]
+                            .iter()
+                            .map(move |&second| (Combo::new(first, second), 1.0))
+                    })
+                    .collect()
+            }
+        };
+        if opponents.is_empty() {
+            return Err(StrengthError::EmptyRange);
+        }
+        Ok(opponents)
+    }
+}
+
+// Walks every runout, tracking how each opponent combo's standing changes by the river
+fn potential(hole: &[Card; 2], board: &[Card], opponents: &[(Combo, f64)]) -> Strength {
+    let mut cards: Vec<Card> = hole.iter().chain(board).copied().collect();
+    let hero = evaluate(&cards);
+    let now: Vec<Standing> = opponents
+        .iter()
+        .map(|(combo, _)| {
+            cards.clear();
+            cards.extend(combo.cards().iter().chain(board));
+            Standing::of(hero, evaluate(&cards))
+        })
+        .collect();
+
+    let mut totals = [0.0; 3];
+    for ((_, weight), standing) in opponents.iter().zip(&now) {
+        totals[*standing as usize] += weight;
+    }
+    let hand_strength = share_ahead(totals);
+    if board.len() == 5 {
+        return Strength {
+            hand_strength,
+            positive_potential: 0.0,
+            negative_potential: 0.0,
+        };
+    }
+
+    let known: Vec<Card> = hole.iter().chain(board).copied().collect();
+    let remaining: Vec<Card> = Deck::new()
+        .cards()
+        .iter()
+        .filter(|c| c.suit.is_some() && !known.contains(c))
+        .copied()
+        .collect();
+
+    // Weight of opponent hands moving from each standing now to each standing at the river
+    let mut moves = [[0.0; 3]; 3];
+    let mut reached = [0.0; 3];
+    let runout_start = board.len();
+    for_each_board(board, &remaining, 5 - board.len(), |river_board| {
+        let runout = &river_board[runout_start..];
+        cards.clear();
+        cards.extend(hole.iter().chain(river_board));
+        let hero = evaluate(&cards);
+        for ((combo, weight), standing) in opponents.iter().zip(&now) {
+            if runout.iter().any(|card| combo.contains(card)) {
+                continue;
+            }
+            cards.clear();
+            cards.extend(combo.cards().iter().chain(river_board));
+            let later = Standing::of(hero, evaluate(&cards));
+            moves[*standing as usize][later as usize] += weight;
+            reached[*standing as usize] += weight;
+        }
+        ControlFlow::Continue(())
+    });
+
+    let (ahead, tied, behind) = (
+        Standing::Ahead as usize,
+        Standing::Tied as usize,
+        Standing::Behind as usize,
+    );
+    let ratio = |gained: f64, from: f64| if from > 0.0 { gained / from } else { 0.0 };
+    Strength {
+        hand_strength,
+        positive_potential: ratio(
+            moves[behind][ahead] + moves[behind][tied] / 2.0 + moves[tied][ahead] / 2.0,
+            reached[behind] + reached[tied] / 2.0,
+        ),
+        negative_potential: ratio(
+            moves[ahead][behind] + moves[tied][behind] / 2.0 + moves[ahead][tied] / 2.0,
+            reached[ahead] + reached[tied] / 2.0,
+        ),
+    }
+}
+
+// The share of opponent weight beaten, counting ties as half
+fn share_ahead(totals: [f64; 3]) -> f64 {
+    let [ahead, tied, behind] = totals;
+    (ahead + tied / 2.0) / (ahead + tied + behind)
+}
+
+// The caches only hold plain numbers, so a poisoned lock is still safe to use
+fn lock<T>(mutex: &Mutex<T>) -> std::sync::MutexGuard<'_, T> {
+    mutex
+        .lock()
+        .unwrap_or_else(|poisoned| poisoned.into_inner())
+}
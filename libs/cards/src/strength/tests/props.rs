@@ -0,0 +1,60 @@
+use super::super::*;
+use crate::Suit;
+use proptest::prelude::*;
+
+// Two hole cards and a board of `board_len` cards, all distinct
+fn arb_spot(board_len: usize) -> impl Strategy<Value = ([Card; 2], Vec<Card>)> {
+    let deck: Vec<Card> = Deck::new()
+        .cards()
+        .iter()
+        .filter(|c| c.suit.is_some())
+        .copied()
+        .collect();
+    Just(deck)
+        .prop_shuffle()
+        .prop_map(move |cards| ([cards[0], cards[1]], cards[2..2 + board_len].to_vec()))
+}
+
+fn arb_permutation() -> impl Strategy<Value = Vec<Suit>> {
+    Just(Suit::ALL.to_vec()).prop_shuffle()
+}
+
+fn relabel(card: &Card, permutation: &[Suit]) -> Card {
+    Card::new(card.rank, card.suit.map(|s| permutation[s as usize]))
+}
+
+proptest! {
+    #![proptest_config(ProptestConfig::with_cases(16))]
+
+    #[test]
+    fn strength_is_invariant_under_suit_relabelling(
+        (hole, board) in (4usize..=5).prop_flat_map(arb_spot),
+        permutation in arb_permutation(),
+    ) {
+        let calculator = StrengthCalculator::new();
+        let original = calculator.strength(&hole, &board).unwrap();
+
+        let hole = [relabel(&hole[0], &permutation), relabel(&hole[1], &permutation)];
+        let board: Vec<Card> = board.iter().map(|c| relabel(c, &permutation)).collect();
+        let uncached = StrengthCalculator::new().strength(&hole, &board).unwrap();
+        let cached = calculator.strength(&hole, &board).unwrap();
+
+        prop_assert_eq!(original, uncached);
+        prop_assert_eq!(original, cached);
+        prop_assert_eq!(calculator.cache_len(), 1);
+    }
+
+    #[test]
+    fn metrics_are_probabilities((hole, board) in arb_spot(4)) {
+        let strength = StrengthCalculator::new().strength(&hole, &board).unwrap();
+
+        for metric in [
+            strength.hand_strength(),
+            strength.positive_potential(),
+            strength.negative_potential(),
+            strength.effective(),
+        ] {
+            prop_assert!((0.0..=1.0).contains(&metric));
+        }
+    }
+}
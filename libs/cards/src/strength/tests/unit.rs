@@ -0,0 +1,163 @@
+use super::super::*;
+use crate::notation::parse_cards;
+use crate::{Rank, Suit};
+
+fn hole(notation: &str) -> [Card; 2] {
+    let cards = parse_cards(notation).unwrap();
+    [cards[0], cards[1]]
+}
+
+fn board(notation: &str) -> Vec<Card> {
+    parse_cards(notation).unwrap()
+}
+
+#[test]
+fn test_nuts_on_the_river() {
+    let strength = StrengthCalculator::new()
+        .strength(&hole("Ah Kh"), &board("Qh Jh Th 2c 3d"))
+        .unwrap();
+
+    assert_eq!(strength.hand_strength(), 1.0);
+    assert_eq!(strength.positive_potential(), 0.0);
+    assert_eq!(strength.negative_potential(), 0.0);
+    assert_eq!(strength.effective(), 1.0);
+}
+
+#[test]
+fn test_hand_strength_counts_ties_as_half() {
+    // The board plays: every opponent hand ties
+    let strength = StrengthCalculator::new()
+        .hand_strength(&hole("2c 3d"), &board("Ah Kh Qh Jh Th"))
+        .unwrap();
+
+    assert_eq!(strength, 0.5);
+}
+
+#[test]
+fn test_hand_strength_against_random_hand() {
+    let calculator = StrengthCalculator::new();
+    let overpair = calculator
+        .hand_strength(&hole("As Ad"), &board("9c 5h 2d"))
+        .unwrap();
+    let air = calculator
+        .hand_strength(&hole("7s 3c"), &board("Kc Qh 9d"))
+        .unwrap();
+
+    assert!(overpair > 0.9, "{overpair}");
+    assert!(air < 0.2, "{air}");
+}
+
+#[test]
+fn test_flush_draw_has_positive_potential() {
+    let strength = StrengthCalculator::new()
+        .strength(&hole("Ah Kh"), &board("Qh 7h 2c 9s"))
+        .unwrap();
+
+    // Nine flush cards and six more aces and kings among 44 unseen cards
+    assert!(strength.positive_potential() > 0.2);
+    assert!(strength.positive_potential() < 0.4);
+    assert!(strength.effective() > strength.hand_strength());
+}
+
+#[test]
+fn test_vulnerable_hand_has_negative_potential() {
+    let strength = StrengthCalculator::new()
+        .strength(&hole("Ts 9s"), &board("Th 8h 7h 2c"))
+        .unwrap();
+
+    assert!(strength.hand_strength() > 0.5);
+    assert!(strength.negative_potential() > 0.15);
+    assert!(strength.effective() < strength.hand_strength());
+}
+
+#[test]
+fn test_potential_on_the_flop() {
+    let strength = StrengthCalculator::new()
+        .strength(&hole("Ah Kh"), &board("Qh 7h 2c"))
+        .unwrap();
+
+    assert!(strength.positive_potential() > 0.3);
+    assert!((0.0..=1.0).contains(&strength.effective()));
+}
+
+#[test]
+fn test_more_opponents_lower_strength() {
+    let strength = StrengthCalculator::new()
+        .strength(&hole("Ks Qd"), &board("Kc 8h 3d 2s"))
+        .unwrap();
+
+    let hs = strength.hand_strength();
+    assert!((strength.hand_strength_against(3) - hs.powi(3)).abs() < 1e-12);
+    assert!(strength.effective_against(3) < strength.effective());
+}
+
+#[test]
+fn test_against_range() {
+    let range = "AA".parse().unwrap();
+    let calculator = StrengthCalculator::against(range);
+
+    assert_eq!(
+        calculator
+            .hand_strength(&hole("Ks Kd"), &board("9c 5h 2d"))
+            .unwrap(),
+        0.0
+    );
+    let set = calculator
+        .strength(&hole("9s 9d"), &board("9c 5h 2d 3s"))
+        .unwrap();
+    assert_eq!(set.hand_strength(), 1.0);
+    // Only the two aces and four fours left in the deck beat the set
+    assert!((set.negative_potential() - 6.0 / 44.0).abs() < 1e-12);
+    assert_eq!(calculator.cache_len(), 0);
+}
+
+#[test]
+fn test_isomorphic_spots_share_a_cache_entry() {
+    let calculator = StrengthCalculator::new();
+    let first = calculator
+        .strength(&hole("Ah Kh"), &board("Qh Jh 2c 5d"))
+        .unwrap();
+    let second = calculator
+        .strength(&hole("Ks As"), &board("5c Js 2d Qs"))
+        .unwrap();
+
+    assert_eq!(first, second);
+    assert_eq!(calculator.cache_len(), 1);
+
+    calculator.clear_cache();
+    assert_eq!(calculator.cache_len(), 0);
+}
+
+#[test]
+fn test_different_spots_are_cached_apart() {
+    let calculator = StrengthCalculator::new();
+    calculator
+        .hand_strength(&hole("Ah Kh"), &board("Qh Jh 2c"))
+        .unwrap();
+    calculator
+        .hand_strength(&hole("Ah Kd"), &board("Qh Jh 2c"))
+        .unwrap();
+
+    assert_eq!(calculator.cache_len(), 2);
+}
+
+#[test]
+fn test_strength_errors() {
+    let calculator = StrengthCalculator::new();
+    assert_eq!(
+        calculator.strength(&hole("Ah Kh"), &board("Qh Jh")),
+        Err(StrengthError::InvalidBoardSize(2))
+    );
+    assert_eq!(
+        calculator.hand_strength(&hole("Ah Kh"), &board("Ah Jh 2c")),
+        Err(StrengthError::DuplicateCard(Card::new(
+            Rank::Ace,
+            Some(Suit::Hearts)
+        )))
+    );
+    assert_eq!(
+        StrengthCalculator::against("AsAh".parse().unwrap())
+            .hand_strength(&hole("Ah Kh"), &board("Qh Jh 2c")),
+        Err(StrengthError::EmptyRange)
+    );
+}
@@ -34,6 +34,24 @@ fn test_reset_restores_full_deck() {
     assert_eq!(deck.len(), 52);
 }
 
+#[test]
+fn test_deck_from_cards_draws_last_card_first() {
+    let ace = Card::new(Rank::Ace, Some(Suit::Spades));
+    let king = Card::new(Rank::King, Some(Suit::Hearts));
+    let mut deck = Deck::from_cards(vec![ace, king]);
+    assert_eq!(deck.draw(), Some(king));
+    assert_eq!(deck.draw(), Some(ace));
+    assert!(deck.is_empty());
+}
+
+#[test]
+fn test_seeded_deck_is_reproducible() {
+    let deck = Deck::new_seeded(7);
+    assert_eq!(deck.cards(), Deck::new_seeded(7).cards());
+    assert_ne!(deck.cards(), Deck::new_seeded(8).cards());
+    assert_eq!(deck.len(), Deck::new().len());
+}
+
 #[test]
 fn test_card_creation() {
     let card = Card::new(Rank::Ace, Some(Suit::Spades));
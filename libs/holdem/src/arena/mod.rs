@@ -0,0 +1,312 @@
+use std::fmt;
+
+use cards::Deck;
+use rand::rngs::StdRng;
+use rand::{Rng, SeedableRng};
+
+use crate::Chips;
+use crate::betting::BettingStructure;
+use crate::hand::{Action, ActionError, Hand};
+use crate::strategy::{Strategy, View};
+use crate::table::{Stakes, Table, TableError};
+
+#[cfg(test)]
+mod tests;
+
+/// The normal quantile for a two-sided 95% confidence interval
+const Z_95: f64 = 1.959_963_984_540_054;
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ArenaError {
+    /// The bots could not be seated or dealt in
+    Table(TableError),
+    /// A bot chose an action the hand does not allow
+    IllegalAction {
+        bot: String,
+        action: Action,
+        error: ActionError,
+    },
+}
+
+impl fmt::Display for ArenaError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ArenaError::Table(e) => write!(f, "{e}"),
+            ArenaError::IllegalAction { bot, action, error } => {
+                write!(f, "{bot} chose {action:?}: {error}")
+            }
+        }
+    }
+}
+
+impl std::error::Error for ArenaError {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            ArenaError::Table(e) => Some(e),
+            ArenaError::IllegalAction { error, .. } => Some(error),
+        }
+    }
+}
+
+impl From<TableError> for ArenaError {
+    fn from(e: TableError) -> Self {
+        ArenaError::Table(e)
+    }
+}
+
+struct Bot {
+    name: String,
+    strategy: Box<dyn Strategy>,
+}
+
+/// Plays bots against each other at one table for as many hands as asked, and measures how
+/// much each one wins
+///
+/// Every bot sits down with the same stack each hand, so results are in big blinds won per
+/// 100 hands as in a cash game. Each deal comes from a seeded deck, and with duplicate
+/// dealing (the default) it is played once for every way of rotating the bots around the
+/// table, so every bot gets every seat with the same cards and the luck of the deal mostly
+/// cancels out. Without it each deal is played once, the bots moving one seat on per deal.
+///
+/// # Examples
+///
+/// ```
+/// use holdem::arena::Arena;
+/// use holdem::strategy::{AlwaysCall, StrengthThreshold};
+/// use holdem::table::Stakes;
+///
+/// let mut arena = Arena::new(Stakes::new(1, 2), 200)
+///     .with_seed(7)
+///     .with_bot("station", AlwaysCall)
+///     .with_bot("threshold", StrengthThreshold::new(0.5, 0.8));
+///
+/// let report = arena.run(50).unwrap();
+/// assert_eq!(report.hands, 100);
+///
+/// // Chips only change hands, so the winnings add up to nothing
+/// let net: i64 = report.bots.iter().map(|bot| bot.net).sum();
+/// assert_eq!(net, 0);
+/// ```
+pub struct Arena {
+    stakes: Stakes,
+    stack: Chips,
+    structure: BettingStructure,
+    duplicate: bool,
+    rng: StdRng,
+    bots: Vec<Bot>,
+}
+
+impl Arena {
+    /// Creates an arena playing at `stakes`, each bot starting every hand with `stack`
+    pub fn new(stakes: Stakes, stack: Chips) -> Self {
+        Arena {
+            stakes,
+            stack,
+            structure: BettingStructure::default(),
+            duplicate: true,
+            rng: StdRng::seed_from_u64(0),
+            bots: Vec::new(),
+        }
+    }
+
+    /// Seats a bot, to the left of those already seated
+    pub fn with_bot(mut self, name: impl Into<String>, strategy: impl Strategy + 'static) -> Self {
+        self.bots.push(Bot {
+            name: name.into(),
+            strategy: Box::new(strategy),
+        });
+        self
+    }
+
+    /// Sets the seed the decks are shuffled from, 0 unless set
+    pub fn with_seed(mut self, seed: u64) -> Self {
+        self.rng = StdRng::seed_from_u64(seed);
+        self
+    }
+
+    /// Sets how much the bots may bet and raise, no limit unless set
+    pub fn with_betting_structure(mut self, structure: BettingStructure) -> Self {
+        self.structure = structure;
+        self
+    }
+
+    /// Plays each deal from every seat rather than once, on unless set
+    pub fn with_duplicate(mut self, duplicate: bool) -> Self {
+        self.duplicate = duplicate;
+        self
+    }
+
+    /// Plays `deals` deals and reports how each bot did over them
+    ///
+    /// With duplicate dealing that is `deals` hands for each seat at the table. Running the
+    /// arena again carries on with fresh deals.
+    pub fn run(&mut self, deals: u64) -> Result<Report, ArenaError> {
+        let count = self.bots.len();
+        let mut totals = vec![Totals::default(); count];
+        let mut hands = 0;
+        for deal in 0..deals {
+            let seed = self.rng.random();
+            let rotations: Vec<usize> = if self.duplicate {
+                (0..count).collect()
+            } else {
+                vec![(deal % count.max(1) as u64) as usize]
+            };
+
+            let mut won = vec![0; count];
+            for &rotation in &rotations {
+                let hand = self.play(rotation, Deck::new_seeded(seed))?;
+                for (seat, player) in hand.players() {
+                    won[(seat + rotation) % count] +=
+                        player.stack() as i64 - player.starting_stack() as i64;
+                }
+                hands += 1;
+            }
+            for (totals, won) in totals.iter_mut().zip(won) {
+                totals.add(won, rotations.len() as u64);
+            }
+        }
+
+        let big_blind = self.stakes.big_blind as f64;
+        let bots = self
+            .bots
+            .iter()
+            .zip(totals)
+            .map(|(bot, totals)| totals.result(&bot.name, big_blind))
+            .collect();
+        Ok(Report { hands, bots })
+    }
+
+    // Plays one hand with each bot moved `rotation` seats round from where it was seated
+    fn play(&mut self, rotation: usize, deck: Deck) -> Result<Hand, ArenaError> {
+        let count = self.bots.len();
+        let mut table = Table::new(count, self.stakes)?.with_betting_structure(self.structure);
+        for seat in 0..count {
+            let bot = &self.bots[(seat + rotation) % count];
+            table.sit(seat, bot.name.as_str(), self.stack)?;
+        }
+
+        let mut hand = table.start_hand(deck)?;
+        while let Some(seat) = hand.to_act() {
+            let bot = &mut self.bots[(seat + rotation) % count];
+            // The seat to act always has a view
+            let Some(view) = View::new(&hand, seat) else {
+                break;
+            };
+            let action = bot.strategy.act(&view);
+            hand = hand
+                .apply(seat, action)
+                .map_err(|error| ArenaError::IllegalAction {
+                    bot: bot.name.clone(),
+                    action,
+                    error,
+                })?;
+        }
+        Ok(hand)
+    }
+}
+
+impl fmt::Debug for Arena {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let bots: Vec<&str> = self.bots.iter().map(|bot| bot.name.as_str()).collect();
+        f.debug_struct("Arena")
+            .field("stakes", &self.stakes)
+            .field("stack", &self.stack)
+            .field("structure", &self.structure)
+            .field("duplicate", &self.duplicate)
+            .field("bots", &bots)
+            .finish_non_exhaustive()
+    }
+}
+
+// Running sums of a bot's winnings per deal, for the mean and its standard error
+#[derive(Debug, Clone, Copy, Default)]
+struct Totals {
+    deals: u64,
+    hands: u64,
+    net: i64,
+    // Chips won per hand on each deal, summed and summed squared
+    sum: f64,
+    sum_squares: f64,
+}
+
+impl Totals {
+    fn add(&mut self, won: i64, hands: u64) {
+        let per_hand = won as f64 / hands as f64;
+        self.deals += 1;
+        self.hands += hands;
+        self.net += won;
+        self.sum += per_hand;
+        self.sum_squares += per_hand * per_hand;
+    }
+
+    fn result(&self, name: &str, big_blind: f64) -> BotResult {
+        let deals = self.deals as f64;
+        let mean = if self.deals == 0 {
+            0.0
+        } else {
+            self.sum / deals
+        };
+        let margin = if self.deals < 2 {
+            f64::INFINITY
+        } else {
+            let variance = ((self.sum_squares - deals * mean * mean) / (deals - 1.0)).max(0.0);
+            Z_95 * (variance / deals).sqrt()
+        };
+        BotResult {
+            name: name.to_string(),
+            hands: self.hands,
+            net: self.net,
+            bb_per_100: mean / big_blind * 100.0,
+            margin: margin / big_blind * 100.0,
+        }
+    }
+}
+
+/// How one bot did over an arena run
+#[derive(Debug, Clone, PartialEq)]
+pub struct BotResult {
+    pub name: String,
+    pub hands: u64,
+    /// Chips won, or lost when negative
+    pub net: i64,
+    /// Big blinds won per 100 hands
+    pub bb_per_100: f64,
+    /// Half the width of the 95% confidence interval around `bb_per_100`, infinite until
+    /// there are two deals to compare
+    pub margin: f64,
+}
+
+impl BotResult {
+    /// The 95% confidence interval for the bot's true win rate, in big blinds per 100 hands
+    pub fn interval(&self) -> (f64, f64) {
+        (self.bb_per_100 - self.margin, self.bb_per_100 + self.margin)
+    }
+}
+
+/// The results of an arena run, one per bot in the order they were seated
+#[derive(Debug, Clone, PartialEq)]
+pub struct Report {
+    /// Hands played in all, counting each replay of a duplicate deal
+    pub hands: u64,
+    pub bots: Vec<BotResult>,
+}
+
+impl Report {
+    pub fn bot(&self, name: &str) -> Option<&BotResult> {
+        self.bots.iter().find(|bot| bot.name == name)
+    }
+}
+
+impl fmt::Display for Report {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        writeln!(f, "{} hands", self.hands)?;
+        for bot in &self.bots {
+            writeln!(
+                f,
+                "{}: {:+.2} bb/100 ± {:.2} ({:+} chips)",
+                bot.name, bot.bb_per_100, bot.margin, bot.net
+            )?;
+        }
+        Ok(())
+    }
+}
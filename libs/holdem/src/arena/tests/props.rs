@@ -0,0 +1,47 @@
+use super::super::{Arena, Report};
+use crate::Chips;
+use crate::strategy::{AlwaysCall, RandomAction, StrengthThreshold};
+use crate::table::Stakes;
+use proptest::prelude::*;
+
+fn run(kinds: &[u8], stack: Chips, seed: u64, duplicate: bool, deals: u64) -> Report {
+    let mut arena = Arena::new(Stakes::new(5, 10).with_ante(1), stack)
+        .with_seed(seed)
+        .with_duplicate(duplicate);
+    for (index, &kind) in kinds.iter().enumerate() {
+        let name = format!("bot{index}");
+        arena = match kind {
+            0 => arena.with_bot(name, AlwaysCall),
+            1 => arena.with_bot(name, RandomAction::seeded(seed ^ index as u64)),
+            _ => arena.with_bot(name, StrengthThreshold::new(0.4, 0.8)),
+        };
+    }
+    arena.run(deals).unwrap()
+}
+
+proptest! {
+    #![proptest_config(ProptestConfig::with_cases(32))]
+
+    #[test]
+    fn winnings_balance_and_hands_add_up(
+        kinds in prop::collection::vec(0u8..3, 2..=6),
+        stack in 1 as Chips..500,
+        seed in any::<u64>(),
+        duplicate in any::<bool>(),
+        deals in 0u64..8,
+    ) {
+        let report = run(&kinds, stack, seed, duplicate, deals);
+        let per_deal = if duplicate { kinds.len() as u64 } else { 1 };
+        prop_assert_eq!(report.hands, deals * per_deal);
+        prop_assert_eq!(report.bots.len(), kinds.len());
+        prop_assert_eq!(report.bots.iter().map(|bot| bot.net).sum::<i64>(), 0);
+
+        for bot in &report.bots {
+            prop_assert_eq!(bot.hands, report.hands);
+            prop_assert!(bot.net.unsigned_abs() <= bot.hands * stack * kinds.len() as u64);
+            prop_assert!(bot.margin >= 0.0);
+            let (low, high) = bot.interval();
+            prop_assert!(low <= bot.bb_per_100 && bot.bb_per_100 <= high);
+        }
+    }
+}
@@ -0,0 +1,147 @@
+use super::super::*;
+use crate::strategy::{AlwaysCall, RandomAction, StrengthThreshold};
+
+fn arena() -> Arena {
+    Arena::new(Stakes::new(1, 2), 100)
+        .with_seed(3)
+        .with_bot("station", AlwaysCall)
+        .with_bot("random", RandomAction::seeded(5))
+        .with_bot("threshold", StrengthThreshold::new(0.5, 0.8))
+}
+
+#[test]
+fn test_duplicate_deals_are_played_from_every_seat() {
+    let report = arena().run(40).unwrap();
+    assert_eq!(report.hands, 120);
+    for bot in &report.bots {
+        assert_eq!(bot.hands, 120);
+        assert!(bot.margin.is_finite());
+    }
+    let names: Vec<&str> = report.bots.iter().map(|bot| bot.name.as_str()).collect();
+    assert_eq!(names, ["station", "random", "threshold"]);
+    assert_eq!(report.bots.iter().map(|bot| bot.net).sum::<i64>(), 0);
+}
+
+#[test]
+fn test_single_deals_move_the_bots_round() {
+    let report = arena().with_duplicate(false).run(40).unwrap();
+    assert_eq!(report.hands, 40);
+    assert!(report.bots.iter().all(|bot| bot.hands == 40));
+    assert_eq!(report.bots.iter().map(|bot| bot.net).sum::<i64>(), 0);
+}
+
+#[test]
+fn test_same_seed_same_report() {
+    assert_eq!(arena().run(20).unwrap(), arena().run(20).unwrap());
+
+    // Running on deals fresh cards
+    let mut arena = arena();
+    let first = arena.run(20).unwrap();
+    assert_ne!(arena.run(20).unwrap(), first);
+}
+
+#[test]
+fn test_duplicate_cancels_the_cards_between_equal_bots() {
+    let mut arena = Arena::new(Stakes::new(1, 2), 100)
+        .with_bot("ann", AlwaysCall)
+        .with_bot("ben", AlwaysCall);
+    let report = arena.run(25).unwrap();
+    for bot in &report.bots {
+        assert_eq!(bot.net, 0);
+        assert_eq!(bot.bb_per_100, 0.0);
+        assert_eq!(bot.margin, 0.0);
+    }
+
+    // Dealt once each, the same bots win and lose with the cards
+    let report = arena.with_duplicate(false).run(25).unwrap();
+    let ann = report.bot("ann").unwrap();
+    assert_ne!(ann.net, 0);
+    assert_eq!(report.bot("ben").unwrap().net, -ann.net);
+    let (low, high) = ann.interval();
+    assert!(low < ann.bb_per_100 && ann.bb_per_100 < high);
+}
+
+#[test]
+fn test_win_rate_is_in_big_blinds_per_100_hands() {
+    // Folding to every bet gives up the blinds and any pot the other bot bets at
+    let folds = |view: &View<'_>| {
+        if view.legal_actions().check {
+            Action::Check
+        } else {
+            Action::Fold
+        }
+    };
+    let mut arena = Arena::new(Stakes::new(1, 2), 100)
+        .with_bot("folder", folds)
+        .with_bot("station", AlwaysCall);
+    let report = arena.run(10).unwrap();
+
+    let folder = report.bot("folder").unwrap();
+    assert_eq!(folder.hands, 20);
+    assert!(folder.net < 0);
+    assert_eq!(folder.bb_per_100, folder.net as f64 / 20.0 / 2.0 * 100.0);
+    assert_eq!(report.bot("nobody"), None);
+}
+
+#[test]
+fn test_one_deal_has_no_interval() {
+    let report = arena().run(1).unwrap();
+    assert!(report.bots.iter().all(|bot| bot.margin == f64::INFINITY));
+
+    let report = arena().run(0).unwrap();
+    assert_eq!(report.hands, 0);
+    assert!(report.bots.iter().all(|bot| bot.bb_per_100 == 0.0));
+}
+
+#[test]
+fn test_errors() {
+    let mut alone = Arena::new(Stakes::new(1, 2), 100).with_bot("ann", AlwaysCall);
+    assert_eq!(
+        alone.run(1).unwrap_err(),
+        ArenaError::Table(TableError::InvalidSeatCount(1))
+    );
+
+    let mut arena = Arena::new(Stakes::new(1, 2), 100)
+        .with_bot("ann", AlwaysCall)
+        .with_bot("cheat", |_: &View<'_>| Action::Bet(1));
+    let error = arena.run(1).unwrap_err();
+    assert_eq!(
+        error,
+        ArenaError::IllegalAction {
+            bot: String::from("cheat"),
+            action: Action::Bet(1),
+            error: ActionError::CannotBet,
+        }
+    );
+    assert_eq!(
+        error.to_string(),
+        "cheat chose Bet(1): there is already a bet, raise instead"
+    );
+}
+
+#[test]
+fn test_report_display() {
+    let report = Report {
+        hands: 200,
+        bots: vec![
+            BotResult {
+                name: String::from("ann"),
+                hands: 200,
+                net: 37,
+                bb_per_100: 9.25,
+                margin: 4.5,
+            },
+            BotResult {
+                name: String::from("ben"),
+                hands: 200,
+                net: -37,
+                bb_per_100: -9.25,
+                margin: 4.5,
+            },
+        ],
+    };
+    assert_eq!(
+        report.to_string(),
+        "200 hands\nann: +9.25 bb/100 ± 4.50 (+37 chips)\nben: -9.25 bb/100 ± 4.50 (-37 chips)\n"
+    );
+}
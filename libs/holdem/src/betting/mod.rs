@@ -0,0 +1,144 @@
+use crate::Chips;
+use crate::hand::Street;
+
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
+#[cfg(test)]
+mod tests;
+
+/// Most bets a fixed-limit street allows unless set otherwise: a bet and three raises
+pub const DEFAULT_CAP: u32 = 4;
+
+/// How much a player may bet or raise
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub enum BettingStructure {
+    /// Any amount from a minimum raise up to the whole stack
+    #[default]
+    NoLimit,
+    /// Any amount from a minimum raise up to the size of the pot after calling
+    PotLimit,
+    /// Bets and raises of exactly one small bet before the turn and one big bet after, with
+    /// at most `cap` bets on a street counting the big blind
+    FixedLimit {
+        small_bet: Chips,
+        big_bet: Chips,
+        cap: u32,
+    },
+    /// Bets of any amount from `min` to `max`, with raises at least the size of the last bet
+    /// or raise
+    SpreadLimit { min: Chips, max: Chips },
+}
+
+impl BettingStructure {
+    /// Fixed limit with the small bet equal to the big blind, a big bet of twice that and
+    /// the usual cap of four bets
+    pub fn fixed_limit(big_blind: Chips) -> Self {
+        BettingStructure::FixedLimit {
+            small_bet: big_blind,
+            big_bet: 2 * big_blind,
+            cap: DEFAULT_CAP,
+        }
+    }
+
+    /// Whether the structure's amounts make sense
+    pub fn is_valid(&self) -> bool {
+        match *self {
+            BettingStructure::NoLimit | BettingStructure::PotLimit => true,
+            BettingStructure::FixedLimit {
+                small_bet,
+                big_bet,
+                cap,
+            } => small_bet > 0 && big_bet > 0 && cap > 0,
+            BettingStructure::SpreadLimit { min, max } => min > 0 && max >= min,
+        }
+    }
+
+    /// The smallest increase that counts as a full bet or raise when a street opens
+    pub fn opening_raise(&self, street: Street, big_blind: Chips) -> Chips {
+        match *self {
+            BettingStructure::NoLimit | BettingStructure::PotLimit => big_blind,
+            BettingStructure::FixedLimit {
+                small_bet, big_bet, ..
+            } => match street {
+                Street::Preflop | Street::Flop => small_bet,
+                _ => big_bet,
+            },
+            BettingStructure::SpreadLimit { min, .. } => min,
+        }
+    }
+
+    /// The increase a short all-in must reach to count as a raise and reopen the betting
+    ///
+    /// In fixed limit half a bet is enough; otherwise it takes a full minimum raise.
+    pub fn full_raise(&self, min_raise: Chips) -> Chips {
+        match self {
+            BettingStructure::FixedLimit { .. } => min_raise.div_ceil(2),
+            _ => min_raise,
+        }
+    }
+
+    /// Whether another raise is allowed after `bets` bets on the street
+    pub fn allows_raise(&self, bets: u32) -> bool {
+        match *self {
+            BettingStructure::FixedLimit { cap, .. } => bets < cap,
+            _ => true,
+        }
+    }
+
+    /// The range of totals a player may bet or raise to, ignoring their stack
+    ///
+    /// `min_raise` is the size of the last full bet or raise on the street and `pot` every
+    /// chip in the middle, including bets on this street.
+    pub fn bet_range(
+        &self,
+        current_bet: Chips,
+        min_raise: Chips,
+        pot: Chips,
+        to_call: Chips,
+    ) -> BetRange {
+        let min = current_bet + min_raise;
+        match *self {
+            BettingStructure::NoLimit => BetRange {
+                min,
+                max: Chips::MAX,
+            },
+            // A pot-sized raise calls first and then raises by the pot as it then stands
+            BettingStructure::PotLimit => BetRange {
+                min,
+                max: min.max(current_bet + pot + to_call),
+            },
+            BettingStructure::FixedLimit { .. } => BetRange { min, max: min },
+            BettingStructure::SpreadLimit { max, .. } => BetRange {
+                min,
+                max: (current_bet + max).max(min),
+            },
+        }
+    }
+}
+
+/// The smallest and largest totals a bet or raise can be, in chips on the street
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct BetRange {
+    pub min: Chips,
+    pub max: Chips,
+}
+
+impl BetRange {
+    pub fn contains(&self, amount: Chips) -> bool {
+        (self.min..=self.max).contains(&amount)
+    }
+}
+
+/// What the player to act may do; folding is always allowed
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct LegalActions {
+    pub check: bool,
+    /// The chips needed to call, which is everything the player has if they are short
+    pub call: Option<Chips>,
+    /// The totals a bet can be, when nobody has bet on the street
+    pub bet: Option<BetRange>,
+    /// The totals a raise can be, when there is a bet and raising is allowed
+    pub raise: Option<BetRange>,
+}
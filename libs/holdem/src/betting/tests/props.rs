@@ -0,0 +1,98 @@
+use super::super::*;
+use crate::hand::{Action, Hand};
+use crate::table::{Stakes, Table};
+use cards::Deck;
+use proptest::prelude::*;
+
+fn arb_structure() -> impl Strategy<Value = BettingStructure> {
+    prop_oneof![
+        Just(BettingStructure::NoLimit),
+        Just(BettingStructure::PotLimit),
+        Just(BettingStructure::fixed_limit(10)),
+        (1 as Chips..20, 0 as Chips..50).prop_map(|(min, extra)| BettingStructure::SpreadLimit {
+            min,
+            max: min + extra
+        }),
+    ]
+}
+
+fn arb_game() -> impl Strategy<Value = (BettingStructure, Vec<Chips>, Vec<(u8, f64)>)> {
+    (
+        arb_structure(),
+        prop::collection::vec(1 as Chips..500, 2..=6),
+        prop::collection::vec((0u8..4, 0.0f64..1.0), 1..40),
+    )
+}
+
+fn start(structure: BettingStructure, stacks: &[Chips]) -> Hand {
+    let mut table = Table::new(stacks.len(), Stakes::new(5, 10))
+        .unwrap()
+        .with_betting_structure(structure);
+    for (seat, &stack) in stacks.iter().enumerate() {
+        table.sit(seat, format!("p{seat}"), stack).unwrap();
+    }
+    table.start_hand(Deck::new()).unwrap()
+}
+
+// Picks one of the legal actions, sizing bets and raises within their range
+fn choose(options: &LegalActions, choice: u8, size: f64) -> Action {
+    let sized = |range: BetRange| range.min + ((range.max - range.min) as f64 * size) as Chips;
+    match (choice, options.bet, options.raise) {
+        (0, _, _) => Action::Fold,
+        (1, Some(range), _) => Action::Bet(sized(range)),
+        (1, _, Some(range)) => Action::Raise(sized(range)),
+        _ if options.check => Action::Check,
+        _ => Action::Call,
+    }
+}
+
+proptest! {
+    #[test]
+    fn legal_actions_match_what_apply_accepts((structure, stacks, choices) in arb_game()) {
+        let mut hand = start(structure, &stacks);
+        let mut choices = choices.iter().cycle();
+        while let Some(seat) = hand.to_act() {
+            let options = hand.legal_actions(seat).unwrap();
+
+            prop_assert!(hand.apply(seat, Action::Fold).is_ok());
+            prop_assert_eq!(hand.apply(seat, Action::Check).is_ok(), options.check);
+            prop_assert_eq!(hand.apply(seat, Action::Call).is_ok(), options.call.is_some());
+            for (range, action) in [
+                (options.bet, Action::Bet as fn(Chips) -> Action),
+                (options.raise, Action::Raise),
+            ] {
+                match range {
+                    Some(range) => {
+                        prop_assert!(range.min <= range.max);
+                        prop_assert!(hand.apply(seat, action(range.min)).is_ok());
+                        prop_assert!(hand.apply(seat, action(range.max)).is_ok());
+                        prop_assert!(hand.apply(seat, action(range.max + 1)).is_err());
+                        prop_assert!(hand.apply(seat, action(range.min - 1)).is_err());
+                    }
+                    None => prop_assert!(hand.apply(seat, action(hand.min_raise_to())).is_err()),
+                }
+            }
+
+            let &(choice, size) = choices.next().unwrap();
+            hand = hand.apply(seat, choose(&options, choice, size)).unwrap();
+        }
+    }
+
+    #[test]
+    fn fixed_limit_never_exceeds_the_cap((stacks, choices) in (
+        // Deep enough that nobody is all in, as a short all-in can go past the cap
+        prop::collection::vec(300 as Chips..1_000, 2..=6),
+        prop::collection::vec((0u8..4, 0.0f64..1.0), 1..40),
+    )) {
+        let mut hand = start(BettingStructure::fixed_limit(10), &stacks);
+        let mut choices = choices.iter().cycle();
+        while let Some(seat) = hand.to_act() {
+            let options = hand.legal_actions(seat).unwrap();
+            // Every bet is a whole number of small bets before the turn and big bets after
+            let unit = BettingStructure::fixed_limit(10).opening_raise(hand.street(), 10);
+            prop_assert!(hand.current_bet() <= DEFAULT_CAP as Chips * unit);
+            let &(choice, size) = choices.next().unwrap();
+            hand = hand.apply(seat, choose(&options, choice, size)).unwrap();
+        }
+    }
+}
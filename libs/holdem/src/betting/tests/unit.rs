@@ -0,0 +1,271 @@
+use super::super::*;
+use crate::hand::{Action, ActionError, Hand};
+use crate::table::{Stakes, Table, TableError};
+use cards::Deck;
+
+// Seats `p0`, `p1`, ... with the given stacks at 1/2 blinds under `structure`
+fn start(structure: BettingStructure, stacks: &[Chips]) -> Hand {
+    let mut table = Table::new(stacks.len(), Stakes::new(1, 2))
+        .unwrap()
+        .with_betting_structure(structure);
+    for (seat, &stack) in stacks.iter().enumerate() {
+        table.sit(seat, format!("p{seat}"), stack).unwrap();
+    }
+    table.start_hand(Deck::new()).unwrap()
+}
+
+fn play(mut hand: Hand, actions: &[(usize, Action)]) -> Hand {
+    for &(seat, action) in actions {
+        hand = hand.apply(seat, action).unwrap();
+    }
+    hand
+}
+
+fn range(min: Chips, max: Chips) -> Option<BetRange> {
+    Some(BetRange { min, max })
+}
+
+#[test]
+fn test_fixed_limit_defaults() {
+    assert_eq!(
+        BettingStructure::fixed_limit(10),
+        BettingStructure::FixedLimit {
+            small_bet: 10,
+            big_bet: 20,
+            cap: DEFAULT_CAP
+        }
+    );
+    assert_eq!(
+        BettingStructure::fixed_limit(10).opening_raise(Street::Turn, 10),
+        20
+    );
+}
+
+#[test]
+fn test_structure_validity() {
+    assert!(BettingStructure::NoLimit.is_valid());
+    assert!(BettingStructure::SpreadLimit { min: 2, max: 6 }.is_valid());
+    assert!(!BettingStructure::SpreadLimit { min: 6, max: 2 }.is_valid());
+    assert!(
+        !BettingStructure::FixedLimit {
+            small_bet: 2,
+            big_bet: 4,
+            cap: 0
+        }
+        .is_valid()
+    );
+}
+
+#[test]
+fn test_invalid_structure_is_refused_when_dealing() {
+    let structure = BettingStructure::SpreadLimit { min: 0, max: 6 };
+    let mut table = Table::new(2, Stakes::new(1, 2))
+        .unwrap()
+        .with_betting_structure(structure);
+    table.sit(0, "p0", 100).unwrap();
+    table.sit(1, "p1", 100).unwrap();
+
+    assert_eq!(
+        table.start_hand(Deck::new()).unwrap_err(),
+        TableError::InvalidStructure(structure)
+    );
+}
+
+#[test]
+fn test_pot_limit_raise_math() {
+    let structure = BettingStructure::PotLimit;
+    // Pot of 3 and 2 to call: raising the pot makes it 2 + 3 + 2 = 7
+    assert_eq!(structure.bet_range(2, 2, 3, 2), BetRange { min: 4, max: 7 });
+    // Facing a raise to 7 from the small blind: 7 + (10 + 6) = 23
+    assert_eq!(
+        structure.bet_range(7, 5, 10, 6),
+        BetRange { min: 12, max: 23 }
+    );
+    // Opening a pot of 21
+    assert_eq!(
+        structure.bet_range(0, 2, 21, 0),
+        BetRange { min: 2, max: 21 }
+    );
+}
+
+#[test]
+fn test_no_limit_legal_actions() {
+    let hand = start(BettingStructure::NoLimit, &[100, 100, 100]);
+
+    assert_eq!(hand.legal_actions(1), None);
+    assert_eq!(
+        hand.legal_actions(0),
+        Some(LegalActions {
+            check: false,
+            call: Some(2),
+            bet: None,
+            raise: range(4, 100),
+        })
+    );
+
+    let hand = play(hand, &[(0, Action::Call), (1, Action::Call)]);
+    let options = hand.legal_actions(2).unwrap();
+    assert!(options.check);
+    assert_eq!(options.call, None);
+    assert_eq!(options.raise, range(4, 100));
+}
+
+#[test]
+fn test_pot_limit_legal_actions() {
+    let hand = start(BettingStructure::PotLimit, &[100, 100, 100]);
+    assert_eq!(hand.legal_actions(0).unwrap().raise, range(4, 7));
+    assert_eq!(
+        hand.apply(0, Action::Raise(8)).unwrap_err(),
+        ActionError::AboveMaximum {
+            amount: 8,
+            maximum: 7
+        }
+    );
+    assert_eq!(
+        hand.apply(0, Action::AllIn).unwrap_err(),
+        ActionError::AboveMaximum {
+            amount: 100,
+            maximum: 7
+        }
+    );
+
+    let hand = hand.apply(0, Action::Raise(7)).unwrap();
+    assert_eq!(hand.legal_actions(1).unwrap().raise, range(12, 23));
+
+    let hand = play(hand, &[(1, Action::Call), (2, Action::Call)]);
+    assert_eq!(hand.legal_actions(1).unwrap().bet, range(2, 21));
+}
+
+#[test]
+fn test_fixed_limit_bet_sizes() {
+    let hand = start(BettingStructure::fixed_limit(2), &[100, 100, 100]);
+    assert_eq!(hand.legal_actions(0).unwrap().raise, range(4, 4));
+    assert_eq!(
+        hand.apply(0, Action::Raise(6)).unwrap_err(),
+        ActionError::AboveMaximum {
+            amount: 6,
+            maximum: 4
+        }
+    );
+
+    let flop = play(
+        hand,
+        &[(0, Action::Call), (1, Action::Call), (2, Action::Check)],
+    );
+    assert_eq!(flop.legal_actions(1).unwrap().bet, range(2, 2));
+
+    let turn = play(
+        flop,
+        &[(1, Action::Check), (2, Action::Check), (0, Action::Check)],
+    );
+    assert_eq!(turn.legal_actions(1).unwrap().bet, range(4, 4));
+}
+
+#[test]
+fn test_fixed_limit_cap() {
+    let hand = start(BettingStructure::fixed_limit(2), &[100, 100, 100]);
+    // The big blind is the first bet, then three raises cap the betting
+    let hand = play(
+        hand,
+        &[
+            (0, Action::Raise(4)),
+            (1, Action::Raise(6)),
+            (2, Action::Raise(8)),
+        ],
+    );
+
+    let options = hand.legal_actions(0).unwrap();
+    assert_eq!(options.call, Some(4));
+    assert_eq!(options.raise, None);
+    assert_eq!(
+        hand.apply(0, Action::Raise(10)).unwrap_err(),
+        ActionError::CapReached
+    );
+}
+
+#[test]
+fn test_spread_limit_ranges() {
+    let structure = BettingStructure::SpreadLimit { min: 2, max: 6 };
+    let hand = start(structure, &[100, 100, 100]);
+    assert_eq!(hand.legal_actions(0).unwrap().raise, range(4, 8));
+
+    let flop = play(
+        hand,
+        &[(0, Action::Call), (1, Action::Call), (2, Action::Check)],
+    );
+    assert_eq!(flop.legal_actions(1).unwrap().bet, range(2, 6));
+
+    // A raise must be at least the size of the bet it raises
+    let flop = flop.apply(1, Action::Bet(5)).unwrap();
+    assert_eq!(flop.legal_actions(2).unwrap().raise, range(10, 11));
+}
+
+#[test]
+fn test_short_stack_can_only_go_all_in_for_less() {
+    let hand = start(BettingStructure::NoLimit, &[100, 100, 100]);
+    let hand = hand.apply(0, Action::Raise(95)).unwrap();
+
+    // The small blind's 100 is short of a minimum raise to 188
+    assert_eq!(hand.legal_actions(1).unwrap().raise, range(100, 100));
+}
+
+#[test]
+fn test_incomplete_all_in_does_not_reopen_the_betting() {
+    let hand = start(BettingStructure::NoLimit, &[200, 30, 200]);
+    // Seat 0 raises by 18, then the small blind moves in for only 10 more
+    let hand = play(hand, &[(0, Action::Raise(20)), (1, Action::AllIn)]);
+
+    // The big blind has not acted yet and may still raise
+    assert_eq!(hand.legal_actions(2).unwrap().raise, range(48, 200));
+    let hand = hand.apply(2, Action::Call).unwrap();
+
+    let options = hand.legal_actions(0).unwrap();
+    assert_eq!(options.call, Some(10));
+    assert_eq!(options.raise, None);
+    assert_eq!(
+        hand.apply(0, Action::Raise(60)).unwrap_err(),
+        ActionError::NotReopened
+    );
+    assert!(hand.apply(0, Action::Call).is_ok());
+}
+
+#[test]
+fn test_short_all_ins_adding_up_to_a_full_raise_reopen_the_betting() {
+    let hand = start(BettingStructure::NoLimit, &[200, 30, 45, 200]);
+    // Seat 3 raises to 20, then two short all-ins take the bet to 45
+    let hand = play(
+        hand,
+        &[
+            (3, Action::Raise(20)),
+            (0, Action::Call),
+            (1, Action::AllIn),
+            (2, Action::AllIn),
+        ],
+    );
+
+    // 25 more than seat 3 last faced is at least the full raise of 18
+    assert_eq!(hand.legal_actions(3).unwrap().raise, range(63, 200));
+}
+
+#[test]
+fn test_fixed_limit_half_bet_all_in_reopens_the_betting() {
+    let structure = BettingStructure::FixedLimit {
+        small_bet: 10,
+        big_bet: 20,
+        cap: 4,
+    };
+    let mut table = Table::new(3, Stakes::new(5, 10))
+        .unwrap()
+        .with_betting_structure(structure);
+    table.sit(0, "p0", 200).unwrap();
+    table.sit(1, "p1", 200).unwrap();
+    table.sit(2, "p2", 16).unwrap();
+    let hand = table.start_hand(Deck::new()).unwrap();
+
+    // The big blind's all-in for 6 more is over half a bet, so it counts as a raise
+    let hand = play(
+        hand,
+        &[(0, Action::Call), (1, Action::Call), (2, Action::AllIn)],
+    );
+    assert_eq!(hand.legal_actions(0).unwrap().raise, range(26, 26));
+}
@@ -0,0 +1,127 @@
+use std::fmt;
+
+use cards::{Card, Deck, Rank, Suit};
+
+use super::{Game, Node};
+
+/// A move in Kuhn poker: passing checks or folds, betting bets or calls
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum KuhnAction {
+    Pass,
+    Bet,
+}
+
+impl fmt::Display for KuhnAction {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            KuhnAction::Pass => write!(f, "p"),
+            KuhnAction::Bet => write!(f, "b"),
+        }
+    }
+}
+
+/// A point in a hand of Kuhn poker
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct KuhnState {
+    /// Each player's card, once dealt
+    pub cards: Option<[Card; 2]>,
+    pub history: Vec<KuhnAction>,
+}
+
+/// Kuhn poker: three cards, one each, one bet
+///
+/// Both players ante a chip and are dealt one card from a jack, queen and king. Player 0
+/// passes or bets a chip; facing a bet a player calls by betting or folds by passing, and
+/// after a pass player 1 may bet or pass too. Unless someone folds, the higher card wins.
+///
+/// Information sets are the player's card then the actions so far, such as `Q:pb`.
+#[derive(Debug, Clone, Default)]
+pub struct Kuhn;
+
+impl Kuhn {
+    pub fn new() -> Self {
+        Kuhn
+    }
+
+    /// The deck the game is dealt from
+    pub fn deck() -> Deck {
+        Deck::from_cards(
+            [Rank::Jack, Rank::Queen, Rank::King]
+                .into_iter()
+                .map(|rank| Card::new(rank, Some(Suit::Spades)))
+                .collect(),
+        )
+    }
+}
+
+impl Game for Kuhn {
+    type State = KuhnState;
+    type Action = KuhnAction;
+
+    fn root(&self) -> KuhnState {
+        KuhnState {
+            cards: None,
+            history: Vec::new(),
+        }
+    }
+
+    fn node(&self, state: &KuhnState) -> Node<KuhnState, KuhnAction> {
+        use KuhnAction::*;
+
+        let Some(cards) = state.cards else {
+            let deck = Kuhn::deck();
+            let deals: Vec<[Card; 2]> = deck
+                .cards()
+                .iter()
+                .flat_map(|&first| {
+                    deck.cards()
+                        .iter()
+                        .filter(move |&&second| second != first)
+                        .map(move |&second| [first, second])
+                })
+                .collect();
+            let p = 1.0 / deals.len() as f64;
+            return Node::Chance(
+                deals
+                    .into_iter()
+                    .map(|cards| {
+                        let next = KuhnState {
+                            cards: Some(cards),
+                            history: Vec::new(),
+                        };
+                        (next, p)
+                    })
+                    .collect(),
+            );
+        };
+
+        let showdown = |stake: f64| {
+            let ahead = cards[0].rank.poker_value() > cards[1].rank.poker_value();
+            Node::Terminal(if ahead { stake } else { -stake })
+        };
+        match state.history[..] {
+            [Pass, Pass] => showdown(1.0),
+            [Bet, Bet] | [Pass, Bet, Bet] => showdown(2.0),
+            [Bet, Pass] => Node::Terminal(1.0),
+            [Pass, Bet, Pass] => Node::Terminal(-1.0),
+            _ => Node::Decision {
+                player: state.history.len() % 2,
+                actions: vec![Pass, Bet],
+            },
+        }
+    }
+
+    fn play(&self, state: &KuhnState, action: KuhnAction) -> KuhnState {
+        let mut next = state.clone();
+        next.history.push(action);
+        next
+    }
+
+    fn info_set(&self, state: &KuhnState) -> String {
+        let card = state
+            .cards
+            .map_or('?', |cards| cards[state.history.len() % 2].rank.to_char());
+        let history: String = state.history.iter().map(ToString::to_string).collect();
+        format!("{card}:{history}")
+    }
+}
@@ -0,0 +1,225 @@
+use std::fmt;
+
+use cards::{Card, Deck, Rank, Suit};
+
+use super::{Game, Node};
+
+/// Chips each player antes
+const ANTE: f64 = 1.0;
+/// The size of a bet or raise in each round
+const RAISES: [f64; 2] = [2.0, 4.0];
+/// Bets and raises allowed in a round
+const MAX_RAISES: usize = 2;
+
+/// A move in Leduc hold'em; calling with nothing to call is checking, and raising with
+/// nothing to raise is betting
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum LeducAction {
+    Fold,
+    Call,
+    Raise,
+}
+
+impl fmt::Display for LeducAction {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            LeducAction::Fold => write!(f, "f"),
+            LeducAction::Call => write!(f, "c"),
+            LeducAction::Raise => write!(f, "r"),
+        }
+    }
+}
+
+/// A point in a hand of Leduc hold'em
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct LeducState {
+    /// Each player's card, once dealt
+    pub cards: Option<[Card; 2]>,
+    /// The shared card, dealt between the two rounds
+    pub board: Option<Card>,
+    /// The actions in each round
+    pub rounds: [Vec<LeducAction>; 2],
+}
+
+impl LeducState {
+    fn round(&self) -> usize {
+        usize::from(self.board.is_some())
+    }
+
+    // Chips each player has put in, antes included
+    fn contributions(&self) -> [f64; 2] {
+        let mut total = [ANTE; 2];
+        for (round, actions) in self.rounds.iter().enumerate() {
+            let mut level = 0.0;
+            let mut bets = [0.0; 2];
+            for (index, action) in actions.iter().enumerate() {
+                let player = index % 2;
+                match action {
+                    LeducAction::Fold => {}
+                    LeducAction::Call => bets[player] = level,
+                    LeducAction::Raise => {
+                        level += RAISES[round];
+                        bets[player] = level;
+                    }
+                }
+            }
+            total[0] += bets[0];
+            total[1] += bets[1];
+        }
+        total
+    }
+}
+
+// A round ends when a check or call closes it
+fn round_over(actions: &[LeducAction]) -> bool {
+    actions.len() >= 2 && actions.last() == Some(&LeducAction::Call)
+}
+
+/// Leduc hold'em: six cards, one each and one on the board, two rounds of limit betting
+///
+/// The deck holds a jack, queen and king in each of two suits. Both players ante a chip and
+/// are dealt one card, then bet a round with bets and raises of 2, the board card is turned
+/// and they bet again with bets and raises of 4. Each round allows a bet and one raise, and
+/// player 0 acts first in both. Pairing the board wins at showdown, then the higher card;
+/// equal cards split the pot.
+///
+/// Information sets are the player's card, the board card once dealt, then the actions of
+/// each round, such as `KQ:rc/r`.
+#[derive(Debug, Clone, Default)]
+pub struct Leduc;
+
+impl Leduc {
+    pub fn new() -> Self {
+        Leduc
+    }
+
+    /// The deck the game is dealt from
+    pub fn deck() -> Deck {
+        let mut cards = Vec::with_capacity(6);
+        for suit in [Suit::Hearts, Suit::Spades] {
+            for rank in [Rank::Jack, Rank::Queen, Rank::King] {
+                cards.push(Card::new(rank, Some(suit)));
+            }
+        }
+        Deck::from_cards(cards)
+    }
+
+    fn showdown(cards: [Card; 2], board: Card, pot: f64) -> f64 {
+        let strength = |card: Card| {
+            let paired = card.rank == board.rank;
+            (paired, card.rank.poker_value())
+        };
+        match strength(cards[0]).cmp(&strength(cards[1])) {
+            std::cmp::Ordering::Greater => pot,
+            std::cmp::Ordering::Less => -pot,
+            std::cmp::Ordering::Equal => 0.0,
+        }
+    }
+}
+
+impl Game for Leduc {
+    type State = LeducState;
+    type Action = LeducAction;
+
+    fn root(&self) -> LeducState {
+        LeducState {
+            cards: None,
+            board: None,
+            rounds: [Vec::new(), Vec::new()],
+        }
+    }
+
+    fn node(&self, state: &LeducState) -> Node<LeducState, LeducAction> {
+        let deck = Leduc::deck();
+        let Some(cards) = state.cards else {
+            let mut deals = Vec::with_capacity(30);
+            for &first in deck.cards() {
+                for &second in deck.cards().iter().filter(|&&card| card != first) {
+                    let mut next = state.clone();
+                    next.cards = Some([first, second]);
+                    deals.push(next);
+                }
+            }
+            let p = 1.0 / deals.len() as f64;
+            return Node::Chance(deals.into_iter().map(|next| (next, p)).collect());
+        };
+
+        let round = state.round();
+        let actions = &state.rounds[round];
+        let contributions = state.contributions();
+        if actions.last() == Some(&LeducAction::Fold) {
+            // Whoever folded loses what they put in
+            let folder = (actions.len() - 1) % 2;
+            return Node::Terminal(if folder == 0 {
+                -contributions[0]
+            } else {
+                contributions[1]
+            });
+        }
+        if round_over(actions) {
+            let Some(board) = state.board else {
+                let boards: Vec<Card> = deck
+                    .cards()
+                    .iter()
+                    .filter(|card| !cards.contains(card))
+                    .copied()
+                    .collect();
+                let p = 1.0 / boards.len() as f64;
+                return Node::Chance(
+                    boards
+                        .into_iter()
+                        .map(|board| {
+                            let mut next = state.clone();
+                            next.board = Some(board);
+                            (next, p)
+                        })
+                        .collect(),
+                );
+            };
+            return Node::Terminal(Leduc::showdown(cards, board, contributions[0]));
+        }
+
+        let raises = actions
+            .iter()
+            .filter(|&&action| action == LeducAction::Raise)
+            .count();
+        let facing = actions.last() == Some(&LeducAction::Raise);
+        let mut choices = Vec::with_capacity(3);
+        if facing {
+            choices.push(LeducAction::Fold);
+        }
+        choices.push(LeducAction::Call);
+        if raises < MAX_RAISES {
+            choices.push(LeducAction::Raise);
+        }
+        Node::Decision {
+            player: actions.len() % 2,
+            actions: choices,
+        }
+    }
+
+    fn play(&self, state: &LeducState, action: LeducAction) -> LeducState {
+        let mut next = state.clone();
+        next.rounds[state.round()].push(action);
+        next
+    }
+
+    fn info_set(&self, state: &LeducState) -> String {
+        let round = state.round();
+        let player = state.rounds[round].len() % 2;
+        let mut key = String::new();
+        if let Some(cards) = state.cards {
+            key.push(cards[player].rank.to_char());
+        }
+        if let Some(board) = state.board {
+            key.push(board.rank.to_char());
+        }
+        key.push(':');
+        key.extend(state.rounds[0].iter().map(ToString::to_string));
+        if state.board.is_some() {
+            key.push('/');
+            key.extend(state.rounds[1].iter().map(ToString::to_string));
+        }
+        key
+    }
+}
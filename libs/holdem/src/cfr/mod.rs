@@ -0,0 +1,420 @@
+use std::collections::{BTreeMap, HashMap};
+use std::fmt;
+
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
+mod kuhn;
+mod leduc;
+
+pub use kuhn::{Kuhn, KuhnAction, KuhnState};
+pub use leduc::{Leduc, LeducAction, LeducState};
+
+#[cfg(test)]
+mod tests;
+
+/// Where a game stands, as the solver walks its tree
+#[derive(Debug, Clone, PartialEq)]
+pub enum Node<S, A> {
+    /// The game is over, and player 0 wins this much from player 1 (or loses it when negative)
+    Terminal(f64),
+    /// Chance moves to one of the states, each with its probability
+    Chance(Vec<(S, f64)>),
+    /// A player chooses one of the actions
+    Decision { player: usize, actions: Vec<A> },
+}
+
+/// A two-player zero-sum game with hidden information, as counterfactual regret minimization
+/// sees it
+///
+/// Each decision belongs to an information set, a key naming everything the player to act
+/// knows. States that differ only in what the player cannot see share a key, and the game
+/// must list the same actions, in the same order, at every state with that key.
+pub trait Game {
+    type State: Clone;
+    type Action: Copy + fmt::Debug;
+
+    /// The state before anything has happened, chance included
+    fn root(&self) -> Self::State;
+
+    fn node(&self, state: &Self::State) -> Node<Self::State, Self::Action>;
+
+    /// The state after the player to act takes `action`
+    fn play(&self, state: &Self::State, action: Self::Action) -> Self::State;
+
+    /// The information set of the player to act
+    fn info_set(&self, state: &Self::State) -> String;
+}
+
+/// How regrets are kept and the average strategy weighted
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum Variant {
+    /// Vanilla CFR: regrets accumulate as they come and every iteration counts the same
+    #[default]
+    Cfr,
+    /// CFR+: regrets never go below zero and later iterations count for more
+    CfrPlus,
+}
+
+// What the solver has learnt about one information set
+#[derive(Debug, Clone)]
+struct InfoSet {
+    regrets: Vec<f64>,
+    strategy_sum: Vec<f64>,
+}
+
+impl InfoSet {
+    fn new(actions: usize) -> Self {
+        InfoSet {
+            regrets: vec![0.0; actions],
+            strategy_sum: vec![0.0; actions],
+        }
+    }
+
+    // Regret matching: play in proportion to positive regret, or uniformly without any
+    fn strategy(&self) -> Vec<f64> {
+        normalized(self.regrets.iter().map(|regret| regret.max(0.0)).collect())
+    }
+
+    fn average(&self) -> Vec<f64> {
+        normalized(self.strategy_sum.clone())
+    }
+}
+
+// Scales the weights to sum to 1, or spreads evenly when they are all zero
+fn normalized(mut weights: Vec<f64>) -> Vec<f64> {
+    let total: f64 = weights.iter().sum();
+    if total > 0.0 {
+        weights.iter_mut().for_each(|weight| *weight /= total);
+    } else {
+        let uniform = 1.0 / weights.len() as f64;
+        weights.iter_mut().for_each(|weight| *weight = uniform);
+    }
+    weights
+}
+
+/// Solves a game by counterfactual regret minimization
+///
+/// Each iteration walks the whole game tree once for each player, updating that player's
+/// regrets. The average of the strategies played converges to a Nash equilibrium, so its
+/// exploitability falls towards zero as iterations go by.
+///
+/// # Examples
+///
+/// ```
+/// use holdem::cfr::{Kuhn, Solver, Variant};
+///
+/// let mut solver = Solver::new(Kuhn::new()).with_variant(Variant::CfrPlus);
+/// solver.run(1_000);
+///
+/// let profile = solver.average_strategy();
+/// assert!(profile.exploitability(&Kuhn::new()) < 0.01);
+///
+/// // The first player loses an eighteenth of a chip a hand at equilibrium
+/// assert!((profile.value(&Kuhn::new()) + 1.0 / 18.0).abs() < 0.005);
+/// ```
+#[derive(Debug, Clone)]
+pub struct Solver<G: Game> {
+    game: G,
+    variant: Variant,
+    iterations: u64,
+    info_sets: HashMap<String, InfoSet>,
+}
+
+impl<G: Game> Solver<G> {
+    pub fn new(game: G) -> Self {
+        Solver {
+            game,
+            variant: Variant::default(),
+            iterations: 0,
+            info_sets: HashMap::new(),
+        }
+    }
+
+    /// Sets the variant to run, vanilla CFR unless set
+    pub fn with_variant(mut self, variant: Variant) -> Self {
+        self.variant = variant;
+        self
+    }
+
+    pub fn game(&self) -> &G {
+        &self.game
+    }
+
+    /// Iterations run so far
+    pub fn iterations(&self) -> u64 {
+        self.iterations
+    }
+
+    /// Runs `iterations` more iterations
+    pub fn run(&mut self, iterations: u64) {
+        for _ in 0..iterations {
+            self.iterations += 1;
+            for player in 0..2 {
+                let root = self.game.root();
+                self.walk(&root, player, [1.0, 1.0], 1.0);
+            }
+        }
+    }
+
+    /// The strategy the regrets currently call for, which need not converge
+    pub fn current_strategy(&self) -> Profile {
+        self.profile(InfoSet::strategy)
+    }
+
+    /// The average strategy over every iteration, which converges to an equilibrium
+    pub fn average_strategy(&self) -> Profile {
+        self.profile(InfoSet::average)
+    }
+
+    fn profile(&self, strategy: impl Fn(&InfoSet) -> Vec<f64>) -> Profile {
+        let strategies = self
+            .info_sets
+            .iter()
+            .map(|(key, info_set)| (key.clone(), strategy(info_set)))
+            .collect();
+        Profile { strategies }
+    }
+
+    // Returns the value of `state` to `player`, updating their regrets below it. `reach` is
+    // each player's probability of playing to the state and `chance` that of chance dealing it.
+    fn walk(&mut self, state: &G::State, player: usize, reach: [f64; 2], chance: f64) -> f64 {
+        let (acting, actions) = match self.game.node(state) {
+            Node::Terminal(payoff) => return if player == 0 { payoff } else { -payoff },
+            Node::Chance(outcomes) => {
+                return outcomes
+                    .iter()
+                    .map(|(next, p)| p * self.walk(next, player, reach, chance * p))
+                    .sum();
+            }
+            Node::Decision { player, actions } => (player, actions),
+        };
+
+        let key = self.game.info_set(state);
+        let strategy = self
+            .info_sets
+            .entry(key.clone())
+            .or_insert_with(|| InfoSet::new(actions.len()))
+            .strategy();
+
+        let mut values = vec![0.0; actions.len()];
+        let mut value = 0.0;
+        for (index, &action) in actions.iter().enumerate() {
+            let mut reach = reach;
+            reach[acting] *= strategy[index];
+            values[index] = self.walk(&self.game.play(state, action), player, reach, chance);
+            value += strategy[index] * values[index];
+        }
+
+        if acting == player {
+            let opponent = reach[1 - player] * chance;
+            let weight = match self.variant {
+                Variant::Cfr => 1.0,
+                Variant::CfrPlus => self.iterations as f64,
+            };
+            let info_set = self.info_sets.get_mut(&key).expect("inserted above");
+            for (index, regret) in info_set.regrets.iter_mut().enumerate() {
+                *regret += opponent * (values[index] - value);
+                if self.variant == Variant::CfrPlus {
+                    *regret = regret.max(0.0);
+                }
+            }
+            for (sum, p) in info_set.strategy_sum.iter_mut().zip(&strategy) {
+                *sum += weight * reach[player] * p;
+            }
+        }
+        value
+    }
+}
+
+/// A strategy for every information set: the probability of each action, in the order the
+/// game lists them
+///
+/// Information sets the profile has nothing for are played uniformly at random.
+#[derive(Debug, Clone, Default, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct Profile {
+    strategies: BTreeMap<String, Vec<f64>>,
+}
+
+impl Profile {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Sets the probabilities of the actions at an information set
+    pub fn insert(&mut self, info_set: impl Into<String>, probabilities: Vec<f64>) {
+        self.strategies.insert(info_set.into(), probabilities);
+    }
+
+    pub fn get(&self, info_set: &str) -> Option<&[f64]> {
+        self.strategies.get(info_set).map(Vec::as_slice)
+    }
+
+    pub fn len(&self) -> usize {
+        self.strategies.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.strategies.is_empty()
+    }
+
+    /// Every information set with its probabilities, in key order
+    pub fn iter(&self) -> impl Iterator<Item = (&str, &[f64])> {
+        self.strategies
+            .iter()
+            .map(|(key, probabilities)| (key.as_str(), probabilities.as_slice()))
+    }
+
+    /// What player 0 expects to win when both players follow the profile
+    pub fn value<G: Game>(&self, game: &G) -> f64 {
+        self.expected(game, &game.root())
+    }
+
+    /// How much a best response wins against the profile, averaged over the two seats
+    ///
+    /// This is half the total that each player could gain by switching to a best response
+    /// while the other keeps to the profile. It is zero exactly when the profile is a Nash
+    /// equilibrium, and is in the game's units (chips for the poker games here).
+    pub fn exploitability<G: Game>(&self, game: &G) -> f64 {
+        let gains: f64 = (0..2)
+            .map(|player| BestResponse::new(game, self, player).value())
+            .sum();
+        gains / 2.0
+    }
+
+    // The probabilities at a state, uniform when the profile has none that fit
+    fn strategy<G: Game>(&self, game: &G, state: &G::State, actions: usize) -> Vec<f64> {
+        match self.get(&game.info_set(state)) {
+            Some(probabilities) if probabilities.len() == actions => probabilities.to_vec(),
+            _ => vec![1.0 / actions as f64; actions],
+        }
+    }
+
+    fn expected<G: Game>(&self, game: &G, state: &G::State) -> f64 {
+        match game.node(state) {
+            Node::Terminal(payoff) => payoff,
+            Node::Chance(outcomes) => outcomes
+                .iter()
+                .map(|(next, p)| p * self.expected(game, next))
+                .sum(),
+            Node::Decision { actions, .. } => {
+                let strategy = self.strategy(game, state, actions.len());
+                actions
+                    .iter()
+                    .zip(strategy)
+                    .filter(|&(_, p)| p > 0.0)
+                    .map(|(&action, p)| p * self.expected(game, &game.play(state, action)))
+                    .sum()
+            }
+        }
+    }
+}
+
+// A best response for one player against a profile. The responder's choice at an
+// information set weighs every state in it by how likely the opponent and chance are to
+// reach it, so the states are gathered first and choices worked out from the leaves up.
+struct BestResponse<'a, G: Game> {
+    game: &'a G,
+    profile: &'a Profile,
+    responder: usize,
+    states: HashMap<String, Vec<(G::State, f64)>>,
+    choices: HashMap<String, usize>,
+}
+
+impl<'a, G: Game> BestResponse<'a, G> {
+    fn new(game: &'a G, profile: &'a Profile, responder: usize) -> Self {
+        let mut response = BestResponse {
+            game,
+            profile,
+            responder,
+            states: HashMap::new(),
+            choices: HashMap::new(),
+        };
+        response.gather(&game.root(), 1.0);
+        response
+    }
+
+    // The responder's expected winnings
+    fn value(&mut self) -> f64 {
+        self.value_of(&self.game.root())
+    }
+
+    // Notes each of the responder's states with the chance of the others reaching it
+    fn gather(&mut self, state: &G::State, reach: f64) {
+        match self.game.node(state) {
+            Node::Terminal(_) => {}
+            Node::Chance(outcomes) => {
+                for (next, p) in &outcomes {
+                    self.gather(next, reach * p);
+                }
+            }
+            Node::Decision { player, actions } => {
+                if player == self.responder {
+                    self.states
+                        .entry(self.game.info_set(state))
+                        .or_default()
+                        .push((state.clone(), reach));
+                    for &action in &actions {
+                        self.gather(&self.game.play(state, action), reach);
+                    }
+                } else {
+                    let strategy = self.profile.strategy(self.game, state, actions.len());
+                    for (&action, p) in actions.iter().zip(strategy) {
+                        if p > 0.0 {
+                            self.gather(&self.game.play(state, action), reach * p);
+                        }
+                    }
+                }
+            }
+        }
+    }
+
+    fn value_of(&mut self, state: &G::State) -> f64 {
+        match self.game.node(state) {
+            Node::Terminal(payoff) => {
+                if self.responder == 0 {
+                    payoff
+                } else {
+                    -payoff
+                }
+            }
+            Node::Chance(outcomes) => outcomes
+                .iter()
+                .map(|(next, p)| p * self.value_of(next))
+                .sum(),
+            Node::Decision { player, actions } if player == self.responder => {
+                let choice = self.choose(&self.game.info_set(state), &actions);
+                self.value_of(&self.game.play(state, actions[choice]))
+            }
+            Node::Decision { actions, .. } => {
+                let strategy = self.profile.strategy(self.game, state, actions.len());
+                actions
+                    .iter()
+                    .zip(strategy)
+                    .filter(|&(_, p)| p > 0.0)
+                    .map(|(&action, p)| p * self.value_of(&self.game.play(state, action)))
+                    .sum()
+            }
+        }
+    }
+
+    // The action that does best over every state in the information set
+    fn choose(&mut self, key: &str, actions: &[G::Action]) -> usize {
+        if let Some(&choice) = self.choices.get(key) {
+            return choice;
+        }
+        let states = self.states.get(key).cloned().unwrap_or_default();
+        let mut totals = vec![0.0; actions.len()];
+        for (state, reach) in &states {
+            for (index, &action) in actions.iter().enumerate() {
+                totals[index] += reach * self.value_of(&self.game.play(state, action));
+            }
+        }
+        let choice = (0..actions.len())
+            .max_by(|&a, &b| totals[a].total_cmp(&totals[b]).then(b.cmp(&a)))
+            .unwrap_or(0);
+        self.choices.insert(key.to_string(), choice);
+        choice
+    }
+}
@@ -0,0 +1,72 @@
+use super::super::*;
+use proptest::prelude::*;
+
+// The known equilibria of Kuhn poker: the first player bluffs the jack with probability
+// `alpha`, bets the king three times as often and calls with the queen `alpha + 1/3`
+fn kuhn_equilibrium(alpha: f64) -> Profile {
+    let mut profile = Profile::new();
+    let bet = |p: f64| vec![1.0 - p, p];
+    for (key, p) in [
+        ("J:", alpha),
+        ("Q:", 0.0),
+        ("K:", 3.0 * alpha),
+        ("J:pb", 0.0),
+        ("Q:pb", alpha + 1.0 / 3.0),
+        ("K:pb", 1.0),
+        ("J:b", 0.0),
+        ("Q:b", 1.0 / 3.0),
+        ("K:b", 1.0),
+        ("J:p", 1.0 / 3.0),
+        ("Q:p", 0.0),
+        ("K:p", 1.0),
+    ] {
+        profile.insert(key, bet(p));
+    }
+    profile
+}
+
+// A Kuhn profile with arbitrary betting probabilities
+fn arb_kuhn_profile() -> impl Strategy<Value = Profile> {
+    prop::collection::vec(0.0f64..=1.0, 12).prop_map(|bets| {
+        let mut profile = Profile::new();
+        for (card, p) in ["J", "Q", "K"].iter().zip(bets.chunks(4)) {
+            for (history, &bet) in ["", "p", "b", "pb"].iter().zip(p) {
+                profile.insert(format!("{card}:{history}"), vec![1.0 - bet, bet]);
+            }
+        }
+        profile
+    })
+}
+
+proptest! {
+    #[test]
+    fn kuhn_equilibria_are_unexploitable(alpha in 0.0f64..=1.0 / 3.0) {
+        let profile = kuhn_equilibrium(alpha);
+        prop_assert!(profile.exploitability(&Kuhn).abs() < 1e-9);
+        prop_assert!((profile.value(&Kuhn) + 1.0 / 18.0).abs() < 1e-9);
+    }
+
+    #[test]
+    fn no_profile_beats_a_best_response(profile in arb_kuhn_profile()) {
+        // Each best response wins at least what the profile's own play does
+        let exploitability = profile.exploitability(&Kuhn);
+        prop_assert!(exploitability > -1e-9);
+
+        let value = profile.value(&Kuhn);
+        prop_assert!((-2.0..=2.0).contains(&value));
+        prop_assert!(exploitability <= 2.0);
+    }
+
+    #[test]
+    fn strategies_are_distributions(iterations in 1u64..50, plus in any::<bool>()) {
+        let variant = if plus { Variant::CfrPlus } else { Variant::Cfr };
+        let mut solver = Solver::new(Kuhn).with_variant(variant);
+        solver.run(iterations);
+        for profile in [solver.average_strategy(), solver.current_strategy()] {
+            for (_, probabilities) in profile.iter() {
+                prop_assert!(probabilities.iter().all(|p| (0.0..=1.0).contains(p)));
+                prop_assert!((probabilities.iter().sum::<f64>() - 1.0).abs() < 1e-9);
+            }
+        }
+    }
+}
@@ -0,0 +1,276 @@
+use super::super::*;
+use cards::notation::parse_cards;
+use cards::{Card, Deck};
+
+fn card(text: &str) -> Card {
+    parse_cards(text).unwrap()[0]
+}
+
+fn kuhn(cards: &str, history: &[KuhnAction]) -> KuhnState {
+    let cards = parse_cards(cards).unwrap();
+    KuhnState {
+        cards: Some([cards[0], cards[1]]),
+        history: history.to_vec(),
+    }
+}
+
+fn leduc(cards: &str, board: Option<&str>, rounds: [&[LeducAction]; 2]) -> LeducState {
+    let cards = parse_cards(cards).unwrap();
+    LeducState {
+        cards: Some([cards[0], cards[1]]),
+        board: board.map(card),
+        rounds: [rounds[0].to_vec(), rounds[1].to_vec()],
+    }
+}
+
+fn close(a: f64, b: f64, tolerance: f64) -> bool {
+    (a - b).abs() < tolerance
+}
+
+// Counts the information sets reachable in a game
+fn info_sets<G: Game>(game: &G) -> usize {
+    fn walk<G: Game>(game: &G, state: &G::State, keys: &mut std::collections::HashSet<String>) {
+        match game.node(state) {
+            Node::Terminal(_) => {}
+            Node::Chance(outcomes) => {
+                for (next, _) in &outcomes {
+                    walk(game, next, keys);
+                }
+            }
+            Node::Decision { actions, .. } => {
+                keys.insert(game.info_set(state));
+                for action in actions {
+                    walk(game, &game.play(state, action), keys);
+                }
+            }
+        }
+    }
+    let mut keys = std::collections::HashSet::new();
+    walk(game, &game.root(), &mut keys);
+    keys.len()
+}
+
+#[test]
+fn test_kuhn_deals_every_pair_of_cards() {
+    assert_eq!(Kuhn::deck().len(), 3);
+    let Node::Chance(deals) = Kuhn.node(&Kuhn.root()) else {
+        panic!("the hand starts with the deal");
+    };
+    assert_eq!(deals.len(), 6);
+    assert!(close(deals.iter().map(|(_, p)| p).sum(), 1.0, 1e-12));
+    assert!(
+        deals
+            .iter()
+            .all(|(state, _)| state.history.is_empty() && state.cards.is_some())
+    );
+}
+
+#[test]
+fn test_kuhn_payoffs() {
+    use KuhnAction::*;
+
+    let game = Kuhn::new();
+    assert_eq!(
+        game.node(&kuhn("Ks Js", &[Pass, Pass])),
+        Node::Terminal(1.0)
+    );
+    assert_eq!(game.node(&kuhn("Js Ks", &[Bet, Bet])), Node::Terminal(-2.0));
+    assert_eq!(
+        game.node(&kuhn("Qs Ks", &[Pass, Bet, Bet])),
+        Node::Terminal(-2.0)
+    );
+    assert_eq!(game.node(&kuhn("Js Ks", &[Bet, Pass])), Node::Terminal(1.0));
+    assert_eq!(
+        game.node(&kuhn("Ks Js", &[Pass, Bet, Pass])),
+        Node::Terminal(-1.0)
+    );
+    assert_eq!(
+        game.node(&kuhn("Ks Js", &[Pass, Bet])),
+        Node::Decision {
+            player: 0,
+            actions: vec![Pass, Bet]
+        }
+    );
+
+    assert_eq!(game.info_set(&kuhn("Qs Ks", &[])), "Q:");
+    assert_eq!(game.info_set(&kuhn("Qs Ks", &[Pass])), "K:p");
+    assert_eq!(game.info_set(&kuhn("Qs Ks", &[Pass, Bet])), "Q:pb");
+    assert_eq!(info_sets(&game), 12);
+}
+
+#[test]
+fn test_kuhn_solution() {
+    let game = Kuhn::new();
+    let mut solver = Solver::new(game.clone()).with_variant(Variant::CfrPlus);
+    solver.run(2_000);
+    assert_eq!(solver.iterations(), 2_000);
+
+    let profile = solver.average_strategy();
+    assert_eq!(profile.len(), 12);
+    assert!(profile.exploitability(&game) < 0.004);
+    assert!(close(profile.value(&game), -1.0 / 18.0, 0.002));
+
+    // The second player's equilibrium strategy is unique: [pass, bet]
+    let bet = |key: &str| profile.get(key).unwrap()[1];
+    assert!(close(bet("J:b"), 0.0, 0.02));
+    assert!(close(bet("Q:b"), 1.0 / 3.0, 0.02));
+    assert!(close(bet("K:b"), 1.0, 0.02));
+    assert!(close(bet("J:p"), 1.0 / 3.0, 0.02));
+    assert!(close(bet("Q:p"), 0.0, 0.02));
+    assert!(close(bet("K:p"), 1.0, 0.02));
+
+    // The first player bluffs the jack a third as often as they bet the king
+    assert!(close(bet("K:"), 3.0 * bet("J:"), 0.05));
+    assert!(close(bet("Q:pb"), bet("J:") + 1.0 / 3.0, 0.05));
+}
+
+#[test]
+fn test_vanilla_cfr_converges_too() {
+    let game = Kuhn::new();
+    let mut solver = Solver::new(game.clone());
+    solver.run(10);
+    let early = solver.average_strategy().exploitability(&game);
+    solver.run(990);
+    let late = solver.average_strategy().exploitability(&game);
+    assert!(late < early);
+    assert!(late < 0.01);
+
+    // Only the average converges; the current strategy is a valid profile all the same
+    let current = solver.current_strategy();
+    assert_eq!(current.len(), 12);
+    for (_, probabilities) in current.iter() {
+        assert!(close(probabilities.iter().sum(), 1.0, 1e-9));
+    }
+}
+
+#[test]
+fn test_uniform_play_is_exploitable() {
+    assert!(close(
+        Profile::new().exploitability(&Kuhn),
+        11.0 / 24.0,
+        1e-9
+    ));
+    assert!(close(Profile::new().value(&Kuhn), 0.125, 1e-9));
+
+    // A profile that ignores a fitting info set falls back to uniform there too
+    let mut profile = Profile::new();
+    profile.insert("K:", vec![1.0, 0.0, 0.0]);
+    assert!(close(profile.exploitability(&Kuhn), 11.0 / 24.0, 1e-9));
+}
+
+#[test]
+fn test_leduc_deals_and_turns_the_board() {
+    let deck: Deck = Leduc::deck();
+    assert_eq!(deck.len(), 6);
+
+    let game = Leduc::new();
+    let Node::Chance(deals) = game.node(&game.root()) else {
+        panic!("the hand starts with the deal");
+    };
+    assert_eq!(deals.len(), 30);
+
+    use LeducAction::*;
+    let Node::Chance(boards) = game.node(&leduc("Kh Qs", None, [&[Raise, Call], &[]])) else {
+        panic!("the board is turned after the first round");
+    };
+    assert_eq!(boards.len(), 4);
+    assert!(close(boards.iter().map(|(_, p)| p).sum(), 1.0, 1e-12));
+}
+
+#[test]
+fn test_leduc_betting_and_payoffs() {
+    use LeducAction::*;
+
+    let game = Leduc::new();
+    assert_eq!(
+        game.node(&leduc("Kh Qs", None, [&[], &[]])),
+        Node::Decision {
+            player: 0,
+            actions: vec![Call, Raise]
+        }
+    );
+    assert_eq!(
+        game.node(&leduc("Kh Qs", None, [&[Call, Raise, Raise], &[]])),
+        Node::Decision {
+            player: 1,
+            actions: vec![Fold, Call]
+        }
+    );
+
+    // Folding loses the ante and whatever was bet before
+    assert_eq!(
+        game.node(&leduc("Kh Qs", None, [&[Raise, Fold], &[]])),
+        Node::Terminal(1.0)
+    );
+    assert_eq!(
+        game.node(&leduc(
+            "Kh Qs",
+            Some("Jh"),
+            [&[Raise, Raise, Call], &[Raise, Fold]]
+        )),
+        Node::Terminal(5.0)
+    );
+    assert_eq!(
+        game.node(&leduc(
+            "Kh Qs",
+            Some("Jh"),
+            [&[Call, Call], &[Raise, Raise, Fold]]
+        )),
+        Node::Terminal(-5.0)
+    );
+
+    // Pairing the board beats a higher card, and equal cards split
+    let pot = [&[Raise, Call][..], &[Raise, Call][..]];
+    assert_eq!(
+        game.node(&leduc("Kh Qs", Some("Qh"), pot)),
+        Node::Terminal(-7.0)
+    );
+    assert_eq!(
+        game.node(&leduc("Kh Qs", Some("Js"), pot)),
+        Node::Terminal(7.0)
+    );
+    assert_eq!(
+        game.node(&leduc("Kh Ks", Some("Js"), pot)),
+        Node::Terminal(0.0)
+    );
+
+    assert_eq!(game.info_set(&leduc("Kh Qs", None, [&[Raise], &[]])), "Q:r");
+    assert_eq!(
+        game.info_set(&leduc("Kh Qs", Some("Jh"), [&[Call, Call], &[Raise]])),
+        "QJ:cc/r"
+    );
+    // Suits never matter, so 18 first-round and 270 second-round sets once they are merged
+    assert_eq!(info_sets(&game), 288);
+}
+
+#[test]
+fn test_leduc_solution_improves() {
+    let game = Leduc::new();
+    let uniform = Profile::new().exploitability(&game);
+    assert!(close(uniform, 4.747222 / 2.0, 1e-5));
+
+    let mut solver = Solver::new(game.clone()).with_variant(Variant::CfrPlus);
+    solver.run(10);
+    let early = solver.average_strategy().exploitability(&game);
+    solver.run(90);
+    let profile = solver.average_strategy();
+    let late = profile.exploitability(&game);
+    assert!(early < uniform);
+    assert!(late < early);
+    assert!(late < 0.1);
+    assert_eq!(profile.len(), 288);
+}
+
+#[cfg(feature = "serde")]
+#[test]
+fn test_profile_round_trips_through_json() {
+    let mut solver = Solver::new(Kuhn::new());
+    solver.run(50);
+    let profile = solver.average_strategy();
+
+    let json = serde_json::to_string(&profile).unwrap();
+    assert!(json.contains("\"K:pb\""));
+    let read: Profile = serde_json::from_str(&json).unwrap();
+    assert_eq!(read, profile);
+    assert_eq!(read.exploitability(&Kuhn), profile.exploitability(&Kuhn));
+}
@@ -0,0 +1,240 @@
+use std::fmt;
+
+use cards::{Card, Deck};
+
+use crate::Chips;
+use crate::betting::BettingStructure;
+use crate::hand::{Action, ActionError, Hand, Street};
+use crate::pot::{Award, OddChipRule};
+use crate::table::{Seat, Stakes, Table, TableError};
+
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
+#[cfg(test)]
+mod tests;
+
+/// Which forced bet a player posted
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub enum Blind {
+    Small,
+    Big,
+}
+
+/// Something that happened during a hand, in the order it happened
+///
+/// A hand records every event from the deal onwards; see `Hand::events`. Amounts are the
+/// chips that moved, which may be less than the blind or bet when a player is short.
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub enum Event {
+    /// The hand was dealt, with the table as it stood and the rules the hand is played by
+    Started {
+        button: usize,
+        stakes: Stakes,
+        structure: BettingStructure,
+        odd_chips: OddChipRule,
+        hi_lo: bool,
+        /// Every seat at the table, empty or not
+        seats: Vec<Option<Seat>>,
+    },
+    AntePosted {
+        seat: usize,
+        amount: Chips,
+    },
+    BlindPosted {
+        seat: usize,
+        blind: Blind,
+        amount: Chips,
+    },
+    HoleCardsDealt {
+        seat: usize,
+        cards: [Card; 2],
+    },
+    /// A player acted, putting `amount` more chips in
+    Acted {
+        seat: usize,
+        action: Action,
+        amount: Chips,
+    },
+    /// The cards turned over for a street, after a burn
+    BoardDealt {
+        street: Street,
+        cards: Vec<Card>,
+    },
+    /// A bet nobody called went back to the player who made it
+    BetReturned {
+        seat: usize,
+        amount: Chips,
+    },
+    PotAwarded(Award),
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ReplayError {
+    /// The events do not open with `Event::Started`
+    NotStarted,
+    /// The recorded table could not deal the hand
+    Table(TableError),
+    /// The recorded action at `index` is not allowed
+    Action { index: usize, error: ActionError },
+    /// Replaying produces something other than the event at `index`, so the events were not
+    /// dealt from this deck
+    Diverged { index: usize },
+}
+
+impl fmt::Display for ReplayError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ReplayError::NotStarted => write!(f, "the events do not start a hand"),
+            ReplayError::Table(error) => write!(f, "the hand cannot be dealt: {error}"),
+            ReplayError::Action { index, error } => {
+                write!(f, "the action at event {index} is not allowed: {error}")
+            }
+            ReplayError::Diverged { index } => {
+                write!(f, "event {index} does not match the replayed hand")
+            }
+        }
+    }
+}
+
+impl std::error::Error for ReplayError {}
+
+impl From<TableError> for ReplayError {
+    fn from(error: TableError) -> Self {
+        ReplayError::Table(error)
+    }
+}
+
+/// The states a hand went through, rebuilt from its events and the deck it was dealt from
+///
+/// Only the start of the hand and the players' actions are replayed; everything else is
+/// dealt by the engine and checked against the recorded events. A state covers the action
+/// that made it and the cards dealt and pots awarded as a result.
+///
+/// # Examples
+///
+/// ```
+/// use cards::Deck;
+/// use holdem::event::Replay;
+/// use holdem::hand::{Action, Street};
+/// use holdem::table::{Stakes, Table};
+///
+/// let mut table = Table::new(2, Stakes::new(1, 2)).unwrap();
+/// table.sit(0, "ann", 100).unwrap();
+/// table.sit(1, "ben", 100).unwrap();
+///
+/// let hand = table.start_hand(Deck::new_seeded(42)).unwrap();
+/// let hand = hand.apply(0, Action::Call).unwrap();
+/// let hand = hand.apply(1, Action::Check).unwrap();
+///
+/// let replay = Replay::new(hand.events(), 42).unwrap();
+/// assert_eq!(replay.len(), hand.events().len());
+/// assert_eq!(replay.state_at(0).unwrap().street(), Street::Preflop);
+/// assert_eq!(replay.last().board(), hand.board());
+/// ```
+#[derive(Debug, Clone)]
+pub struct Replay {
+    states: Vec<Hand>,
+    len: usize,
+}
+
+impl Replay {
+    /// Replays `events` with a full deck shuffled from `seed`; see `Deck::new_seeded`
+    pub fn new(events: &[Event], seed: u64) -> Result<Self, ReplayError> {
+        Self::with_deck(events, Deck::new_seeded(seed))
+    }
+
+    /// Replays `events` dealing from `deck`, as it was passed to `Table::start_hand`
+    ///
+    /// The events may stop part way through a hand, but not before the deal.
+    pub fn with_deck(events: &[Event], deck: Deck) -> Result<Self, ReplayError> {
+        let Some(Event::Started {
+            button,
+            stakes,
+            structure,
+            odd_chips,
+            hi_lo,
+            seats,
+        }) = events.first()
+        else {
+            return Err(ReplayError::NotStarted);
+        };
+        if !structure.is_valid() {
+            return Err(TableError::InvalidStructure(*structure).into());
+        }
+        let mut table = Table::new(seats.len(), *stakes)?
+            .with_betting_structure(*structure)
+            .with_odd_chip_rule(*odd_chips)
+            .with_hi_lo(*hi_lo);
+        for (index, seat) in seats.iter().enumerate() {
+            if let Some(seat) = seat {
+                table.sit(index, seat.name(), seat.stack())?;
+            }
+        }
+        if table.seat(*button).is_none_or(|seat| seat.stack() == 0) {
+            return Err(TableError::SeatEmpty(*button).into());
+        }
+
+        let mut hand = Hand::deal(&table, *button, deck)?;
+        check(&hand, events)?;
+        let mut states = Vec::new();
+        while let Some(event) = events.get(hand.events().len()) {
+            let index = hand.events().len();
+            let Event::Acted { seat, action, .. } = *event else {
+                return Err(ReplayError::Diverged { index });
+            };
+            let next = hand
+                .apply(seat, action)
+                .map_err(|error| ReplayError::Action { index, error })?;
+            states.push(hand);
+            hand = next;
+            check(&hand, events)?;
+        }
+        states.push(hand);
+        Ok(Self {
+            states,
+            len: events.len(),
+        })
+    }
+
+    /// Number of events replayed
+    pub fn len(&self) -> usize {
+        self.len
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+
+    /// The state of the hand once the event at `index` has happened
+    pub fn state_at(&self, index: usize) -> Option<&Hand> {
+        if index >= self.len {
+            return None;
+        }
+        let state = self
+            .states
+            .partition_point(|hand| hand.events().len() <= index);
+        self.states.get(state)
+    }
+
+    /// The state after the last event
+    pub fn last(&self) -> &Hand {
+        // A replay always has the state after the deal
+        &self.states[self.states.len() - 1]
+    }
+
+    /// Every state in order, one for the deal and one for each action
+    pub fn states(&self) -> &[Hand] {
+        &self.states
+    }
+}
+
+// Checks the events the hand has produced against those recorded, up to where either ends
+fn check(hand: &Hand, events: &[Event]) -> Result<(), ReplayError> {
+    match hand.events().iter().zip(events).position(|(a, b)| a != b) {
+        Some(index) => Err(ReplayError::Diverged { index }),
+        None => Ok(()),
+    }
+}
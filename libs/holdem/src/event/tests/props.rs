@@ -0,0 +1,76 @@
+use super::super::*;
+use crate::test_support::{action, seated};
+use proptest::prelude::*;
+
+fn start(stacks: &[Chips], seed: u64) -> Hand {
+    seated(Stakes::new(5, 10).with_ante(1), stacks)
+        .with_hi_lo(seed.is_multiple_of(2))
+        .start_hand(Deck::new_seeded(seed))
+        .unwrap()
+}
+
+// Plays a hand to the end, keeping every state along the way
+fn play(hand: Hand, choices: &[(u8, f64)]) -> Vec<Hand> {
+    let mut choices = choices.iter().cycle();
+    let mut states = vec![hand];
+    while let Some(hand) = states.last()
+        && let Some(seat) = hand.to_act()
+    {
+        let &(choice, size) = choices.next().unwrap();
+        let chosen = action(hand, seat, choice, size);
+        let next = [chosen, Action::Check, Action::Call, Action::Fold]
+            .into_iter()
+            .find_map(|action| hand.apply(seat, action).ok())
+            .unwrap();
+        states.push(next);
+    }
+    states
+}
+
+fn arb_game() -> impl Strategy<Value = (Vec<Chips>, u64, Vec<(u8, f64)>)> {
+    (
+        prop::collection::vec(1 as Chips..2_000, 2..=9),
+        any::<u64>(),
+        prop::collection::vec((0u8..5, 0.0f64..1.0), 1..40),
+    )
+}
+
+proptest! {
+    #[test]
+    fn replay_rebuilds_every_state((stacks, seed, choices) in arb_game()) {
+        let played = play(start(&stacks, seed), &choices);
+        let hand = played.last().unwrap();
+
+        let replay = Replay::new(hand.events(), seed).unwrap();
+        prop_assert_eq!(replay.states().len(), played.len());
+        for (state, played) in replay.states().iter().zip(&played) {
+            prop_assert_eq!(state.events(), played.events());
+            prop_assert_eq!(state.to_act(), played.to_act());
+            prop_assert_eq!(state.pot(), played.pot());
+        }
+        for index in 0..replay.len() {
+            let state = replay.state_at(index).unwrap();
+            prop_assert!(state.events().len() > index);
+        }
+    }
+
+    #[test]
+    fn events_account_for_every_chip((stacks, seed, choices) in arb_game()) {
+        let played = play(start(&stacks, seed), &choices);
+        let hand = played.last().unwrap();
+
+        let (mut put_in, mut paid_out) = (0, 0);
+        for event in hand.events() {
+            match event {
+                Event::AntePosted { amount, .. }
+                | Event::BlindPosted { amount, .. }
+                | Event::Acted { amount, .. } => put_in += amount,
+                Event::BetReturned { amount, .. } => paid_out += amount,
+                Event::PotAwarded(award) => paid_out += award.amount,
+                _ => {}
+            }
+        }
+        prop_assert_eq!(put_in, hand.pot());
+        prop_assert_eq!(paid_out, hand.pot());
+    }
+}
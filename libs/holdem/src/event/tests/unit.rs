@@ -0,0 +1,309 @@
+use super::super::*;
+use crate::pot::Share;
+use crate::test_support::{deck, play, table};
+use cards::notation::parse_cards;
+
+fn cards(notation: &str) -> Vec<Card> {
+    parse_cards(notation).unwrap()
+}
+
+fn hole(notation: &str) -> [Card; 2] {
+    let cards = cards(notation);
+    [cards[0], cards[1]]
+}
+
+#[test]
+fn test_deal_records_the_table_blinds_and_hole_cards() {
+    let table = table(&[100, 100, 100]).with_betting_structure(BettingStructure::PotLimit);
+    let seats = table.seats().to_vec();
+    let hand = table.clone().start_hand(deck("2c 3c 4c 5c 6c 7c")).unwrap();
+
+    assert_eq!(
+        hand.events(),
+        [
+            Event::Started {
+                button: 0,
+                stakes: Stakes::new(1, 2),
+                structure: BettingStructure::PotLimit,
+                odd_chips: OddChipRule::LeftOfButton,
+                hi_lo: false,
+                seats,
+            },
+            Event::BlindPosted {
+                seat: 1,
+                blind: Blind::Small,
+                amount: 1,
+            },
+            Event::BlindPosted {
+                seat: 2,
+                blind: Blind::Big,
+                amount: 2,
+            },
+            Event::HoleCardsDealt {
+                seat: 1,
+                cards: hole("2c 5c"),
+            },
+            Event::HoleCardsDealt {
+                seat: 2,
+                cards: hole("3c 6c"),
+            },
+            Event::HoleCardsDealt {
+                seat: 0,
+                cards: hole("4c 7c"),
+            },
+        ]
+    );
+}
+
+#[test]
+fn test_antes_and_short_blinds_record_the_chips_posted() {
+    let mut table = Table::new(3, Stakes::new(5, 10).with_ante(2)).unwrap();
+    table.sit(0, "ann", 100).unwrap();
+    table.sit(1, "ben", 4).unwrap();
+    table.sit(2, "cat", 7).unwrap();
+    let hand = table.start_hand(Deck::new()).unwrap();
+
+    assert_eq!(
+        hand.events()[1..6],
+        [
+            Event::AntePosted { seat: 1, amount: 2 },
+            Event::AntePosted { seat: 2, amount: 2 },
+            Event::AntePosted { seat: 0, amount: 2 },
+            Event::BlindPosted {
+                seat: 1,
+                blind: Blind::Small,
+                amount: 2,
+            },
+            Event::BlindPosted {
+                seat: 2,
+                blind: Blind::Big,
+                amount: 5,
+            },
+        ]
+    );
+}
+
+#[test]
+fn test_actions_and_streets_are_recorded_as_played() {
+    let deck = deck("2c 3c 4c 5c 6c 7c 8d Ah Kh Qh 9d Jh");
+    let hand = table(&[100, 100, 100]).start_hand(deck).unwrap();
+    let dealt = hand.events().len();
+    let hand = play(
+        hand,
+        &[
+            (0, Action::Raise(6)),
+            (1, Action::Fold),
+            (2, Action::Call),
+            (2, Action::Check),
+            (0, Action::Bet(10)),
+            (2, Action::Call),
+        ],
+    );
+
+    assert_eq!(
+        hand.events()[dealt..],
+        [
+            Event::Acted {
+                seat: 0,
+                action: Action::Raise(6),
+                amount: 6,
+            },
+            Event::Acted {
+                seat: 1,
+                action: Action::Fold,
+                amount: 0,
+            },
+            Event::Acted {
+                seat: 2,
+                action: Action::Call,
+                amount: 4,
+            },
+            Event::BoardDealt {
+                street: Street::Flop,
+                cards: cards("Ah Kh Qh"),
+            },
+            Event::Acted {
+                seat: 2,
+                action: Action::Check,
+                amount: 0,
+            },
+            Event::Acted {
+                seat: 0,
+                action: Action::Bet(10),
+                amount: 10,
+            },
+            Event::Acted {
+                seat: 2,
+                action: Action::Call,
+                amount: 10,
+            },
+            Event::BoardDealt {
+                street: Street::Turn,
+                cards: cards("Jh"),
+            },
+        ]
+    );
+}
+
+#[test]
+fn test_uncalled_bet_is_returned_before_the_pot_is_awarded() {
+    let hand = table(&[100, 100, 100]).start_hand(Deck::new()).unwrap();
+    let hand = play(hand, &[(0, Action::Fold), (1, Action::Fold)]);
+
+    assert_eq!(
+        hand.events()[hand.events().len() - 2..],
+        [
+            Event::BetReturned { seat: 2, amount: 1 },
+            Event::PotAwarded(Award {
+                seat: 2,
+                pot: 0,
+                amount: 2,
+                share: Share::Uncontested,
+            }),
+        ]
+    );
+}
+
+#[test]
+fn test_all_in_deals_each_remaining_street() {
+    let hand = table(&[50, 50]).start_hand(Deck::new_seeded(3)).unwrap();
+    let hand = play(hand, &[(0, Action::AllIn), (1, Action::Call)]);
+
+    let streets: Vec<Street> = hand
+        .events()
+        .iter()
+        .filter_map(|event| match event {
+            Event::BoardDealt { street, .. } => Some(*street),
+            _ => None,
+        })
+        .collect();
+    assert_eq!(streets, [Street::Flop, Street::Turn, Street::River]);
+    assert!(matches!(hand.events().last(), Some(Event::PotAwarded(_))));
+}
+
+#[test]
+fn test_replay_rebuilds_every_state() {
+    let mut table = table(&[100, 100, 100]);
+    let start = table.start_hand(Deck::new_seeded(11)).unwrap();
+    let actions = [
+        (0, Action::Call),
+        (1, Action::Raise(8)),
+        (2, Action::Fold),
+        (0, Action::Call),
+        (1, Action::Bet(10)),
+        (0, Action::Fold),
+    ];
+    let mut played = vec![start.clone()];
+    for &(seat, action) in &actions {
+        played.push(played.last().unwrap().apply(seat, action).unwrap());
+    }
+    let hand = played.last().unwrap();
+
+    let replay = Replay::new(hand.events(), 11).unwrap();
+    assert_eq!(replay.len(), hand.events().len());
+    assert_eq!(replay.states().len(), played.len());
+    for (state, played) in replay.states().iter().zip(&played) {
+        assert_eq!(state.events(), played.events());
+        assert_eq!(state.to_act(), played.to_act());
+        assert_eq!(state.board(), played.board());
+    }
+
+    // The deal covers every event up to the first action
+    let first_action = start.events().len();
+    assert_eq!(replay.state_at(0).unwrap().events(), start.events());
+    assert_eq!(
+        replay.state_at(first_action - 1).unwrap().events(),
+        start.events()
+    );
+    assert_eq!(
+        replay.state_at(first_action).unwrap().events(),
+        played[1].events()
+    );
+    // Dealing the flop belongs to the call that closed the betting
+    let flop = hand
+        .events()
+        .iter()
+        .position(|event| matches!(event, Event::BoardDealt { .. }))
+        .unwrap();
+    assert_eq!(replay.state_at(flop).unwrap().events(), played[4].events());
+    assert_eq!(
+        replay.state_at(replay.len() - 1).unwrap().events(),
+        hand.events()
+    );
+    assert!(replay.state_at(replay.len()).is_none());
+    assert!(replay.last().is_complete());
+}
+
+#[test]
+fn test_replay_of_part_of_a_hand() {
+    let hand = table(&[100, 100]).start_hand(Deck::new_seeded(5)).unwrap();
+    let hand = play(hand, &[(0, Action::Call), (1, Action::Check)]);
+
+    // Stopping partway through the flop's cards is fine
+    let flop = hand.events().len() - 1;
+    let replay = Replay::new(&hand.events()[..flop], 5).unwrap();
+    assert_eq!(replay.len(), flop);
+    assert_eq!(replay.last().street(), Street::Flop);
+}
+
+#[test]
+fn test_replay_with_the_wrong_deck_diverges() {
+    let hand = table(&[100, 100]).start_hand(Deck::new_seeded(5)).unwrap();
+
+    let hole_cards = hand
+        .events()
+        .iter()
+        .position(|event| matches!(event, Event::HoleCardsDealt { .. }))
+        .unwrap();
+    assert_eq!(
+        Replay::new(hand.events(), 6).unwrap_err(),
+        ReplayError::Diverged { index: hole_cards }
+    );
+}
+
+#[test]
+fn test_replay_rejects_bad_events() {
+    let hand = table(&[100, 100]).start_hand(Deck::new_seeded(5)).unwrap();
+    let mut events = hand.events().to_vec();
+
+    assert_eq!(
+        Replay::new(&events[1..], 5).unwrap_err(),
+        ReplayError::NotStarted
+    );
+    assert_eq!(Replay::new(&[], 5).unwrap_err(), ReplayError::NotStarted);
+
+    let index = events.len();
+    events.push(Event::Acted {
+        seat: 0,
+        action: Action::Check,
+        amount: 0,
+    });
+    assert_eq!(
+        Replay::new(&events, 5).unwrap_err(),
+        ReplayError::Action {
+            index,
+            error: ActionError::CannotCheck { to_call: 1 },
+        }
+    );
+
+    // Only actions can follow the deal; the engine deals the board itself
+    events[index] = Event::BoardDealt {
+        street: Street::Flop,
+        cards: Vec::new(),
+    };
+    assert_eq!(
+        Replay::new(&events, 5).unwrap_err(),
+        ReplayError::Diverged { index }
+    );
+
+    // A recorded amount that does not match the engine is caught too
+    events[index] = Event::Acted {
+        seat: 0,
+        action: Action::Call,
+        amount: 2,
+    };
+    assert_eq!(
+        Replay::new(&events, 5).unwrap_err(),
+        ReplayError::Diverged { index }
+    );
+}
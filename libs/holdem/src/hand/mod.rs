@@ -0,0 +1,720 @@
+use std::fmt;
+
+use cards::eval::{evaluate, evaluate_low};
+use cards::{Card, Deck};
+
+use crate::Chips;
+use crate::betting::{BetRange, BettingStructure, LegalActions};
+use crate::event::{Blind, Event};
+use crate::pot::{Award, Contender, Contribution, OddChipRule, Pots};
+use crate::table::{Stakes, Table, TableError};
+
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
+#[cfg(test)]
+mod tests;
+
+/// Cards burned before the flop, turn and river
+const BURNS: usize = 3;
+
+/// The betting rounds of a hand, and the showdown that may end it
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub enum Street {
+    Preflop,
+    Flop,
+    Turn,
+    River,
+    Showdown,
+}
+
+impl Street {
+    /// The street that follows this one
+    pub fn next(self) -> Option<Street> {
+        match self {
+            Street::Preflop => Some(Street::Flop),
+            Street::Flop => Some(Street::Turn),
+            Street::Turn => Some(Street::River),
+            Street::River => Some(Street::Showdown),
+            Street::Showdown => None,
+        }
+    }
+
+    /// Number of board cards dealt by the time betting on this street starts
+    pub fn board_len(self) -> usize {
+        match self {
+            Street::Preflop => 0,
+            Street::Flop => 3,
+            Street::Turn => 4,
+            Street::River | Street::Showdown => 5,
+        }
+    }
+}
+
+/// A decision by the player to act
+///
+/// Bet and raise amounts are the player's total bet on the street once the chips are in,
+/// not the amount added.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub enum Action {
+    Fold,
+    Check,
+    Call,
+    /// Opens the betting on a street where nobody has bet
+    Bet(Chips),
+    /// Raises an existing bet, including the big blind
+    Raise(Chips),
+    /// Puts in every remaining chip, as a call, bet or raise
+    AllIn,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ActionError {
+    /// The hand is over and nobody is left to act
+    HandComplete,
+    /// Another seat is to act
+    OutOfTurn { seat: usize, to_act: usize },
+    /// There is a bet to call, fold or raise
+    CannotCheck { to_call: Chips },
+    /// There is no bet to call; check instead
+    NothingToCall,
+    /// The street already has a bet; raise instead
+    CannotBet,
+    /// Nobody has bet on the street yet; bet instead
+    CannotRaise,
+    /// The bet or raise is smaller than allowed, and not all in
+    BelowMinimum { amount: Chips, minimum: Chips },
+    /// The bet or raise is larger than allowed
+    AboveMaximum { amount: Chips, maximum: Chips },
+    /// The player already acted and has only faced all-ins short of a full raise since,
+    /// so they may call or fold but not raise
+    NotReopened,
+    /// The street has had as many bets and raises as a fixed-limit cap allows
+    CapReached,
+}
+
+impl fmt::Display for ActionError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ActionError::HandComplete => write!(f, "the hand is complete"),
+            ActionError::OutOfTurn { seat, to_act } => {
+                write!(f, "seat {seat} acted but seat {to_act} is to act")
+            }
+            ActionError::CannotCheck { to_call } => {
+                write!(f, "cannot check facing a bet of {to_call} to call")
+            }
+            ActionError::NothingToCall => write!(f, "there is no bet to call"),
+            ActionError::CannotBet => write!(f, "there is already a bet, raise instead"),
+            ActionError::CannotRaise => write!(f, "there is no bet to raise, bet instead"),
+            ActionError::BelowMinimum { amount, minimum } => {
+                write!(f, "{amount} is below the minimum of {minimum}")
+            }
+            ActionError::AboveMaximum { amount, maximum } => {
+                write!(f, "{amount} is above the maximum of {maximum}")
+            }
+            ActionError::NotReopened => {
+                write!(
+                    f,
+                    "the betting was not reopened by a full raise, call or fold"
+                )
+            }
+            ActionError::CapReached => write!(f, "the betting is capped, call or fold"),
+        }
+    }
+}
+
+impl std::error::Error for ActionError {}
+
+/// A player dealt into a hand
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Player {
+    name: String,
+    starting_stack: Chips,
+    stack: Chips,
+    hole_cards: [Card; 2],
+    street_bet: Chips,
+    total_bet: Chips,
+    folded: bool,
+    acted: bool,
+    // The bet on the street when the player last acted
+    acted_at: Chips,
+}
+
+impl Player {
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    /// Chips the player had before posting anything
+    pub fn starting_stack(&self) -> Chips {
+        self.starting_stack
+    }
+
+    /// Chips the player has behind, including any winnings once the hand is complete
+    pub fn stack(&self) -> Chips {
+        self.stack
+    }
+
+    pub fn hole_cards(&self) -> [Card; 2] {
+        self.hole_cards
+    }
+
+    /// Chips bet on the current street
+    pub fn street_bet(&self) -> Chips {
+        self.street_bet
+    }
+
+    /// Chips put into the pot over the whole hand, antes included
+    pub fn total_bet(&self) -> Chips {
+        self.total_bet
+    }
+
+    pub fn has_folded(&self) -> bool {
+        self.folded
+    }
+
+    pub fn is_all_in(&self) -> bool {
+        !self.folded && self.stack == 0 && self.total_bet > 0
+    }
+
+    // Still contesting the pot and with chips to bet
+    fn can_act(&self) -> bool {
+        !self.folded && self.stack > 0
+    }
+
+    fn put(&mut self, chips: Chips) {
+        self.stack -= chips;
+        self.street_bet += chips;
+        self.total_bet += chips;
+    }
+}
+
+/// One hand of Texas Hold'em, from the blinds to the awarding of the pot
+///
+/// A hand is a pure state machine: `apply` checks an action from the player to act and
+/// returns the resulting state, dealing the board as betting rounds close and settling the
+/// pots once the hand ends. Bets and raises are checked against the table's betting
+/// structure, and `legal_actions` lists what the player to act may do.
+///
+/// # Examples
+///
+/// ```
+/// use cards::Deck;
+/// use holdem::hand::{Action, Street};
+/// use holdem::table::{Stakes, Table};
+///
+/// let mut table = Table::new(3, Stakes::new(1, 2)).unwrap();
+/// for (seat, name) in ["ann", "ben", "cat"].into_iter().enumerate() {
+///     table.sit(seat, name, 200).unwrap();
+/// }
+///
+/// // Seat 0 has the button, so seat 1 posts the small blind and seat 2 the big blind
+/// let hand = table.start_hand(Deck::new_shuffled()).unwrap();
+/// let hand = hand.apply(0, Action::Raise(6)).unwrap();
+/// let hand = hand.apply(1, Action::Fold).unwrap();
+/// let hand = hand.apply(2, Action::Call).unwrap();
+///
+/// assert_eq!(hand.street(), Street::Flop);
+/// assert_eq!(hand.board().len(), 3);
+/// assert_eq!(hand.pot(), 13);
+/// assert_eq!(hand.to_act(), Some(2));
+/// ```
+#[derive(Debug, Clone)]
+pub struct Hand {
+    stakes: Stakes,
+    structure: BettingStructure,
+    odd_chips: OddChipRule,
+    hi_lo: bool,
+    button: usize,
+    small_blind: Option<usize>,
+    big_blind: usize,
+    players: Vec<Option<Player>>,
+    deck: Deck,
+    board: Vec<Card>,
+    street: Street,
+    to_act: Option<usize>,
+    current_bet: Chips,
+    min_raise: Chips,
+    // Full bets and raises on the street, counting the big blind before the flop
+    bets: u32,
+    awards: Vec<Award>,
+    events: Vec<Event>,
+}
+
+impl Hand {
+    // Posts antes and blinds and deals hole cards to every seated player with chips
+    pub(crate) fn deal(table: &Table, button: usize, deck: Deck) -> Result<Self, TableError> {
+        // Jokers and any other unsuited cards are left out, keeping the order of the rest
+        let mut deck = Deck::from_cards(
+            deck.cards()
+                .iter()
+                .filter(|card| card.suit.is_some())
+                .copied()
+                .collect(),
+        );
+        let seats = table.seats();
+        let stakes = table.stakes();
+        let count = seats.len();
+        let order: Vec<usize> = (1..=count)
+            .map(|offset| (button + offset) % count)
+            .filter(|&seat| seats[seat].as_ref().is_some_and(|s| s.stack() > 0))
+            .collect();
+        if order.len() < 2 {
+            return Err(TableError::NotEnoughPlayers(order.len()));
+        }
+        let needed = 2 * order.len() + BURNS + Street::River.board_len();
+        if deck.len() < needed {
+            return Err(TableError::NotEnoughCards {
+                needed,
+                available: deck.len(),
+            });
+        }
+
+        // One card at a time, starting left of the button
+        let mut dealt = vec![Vec::with_capacity(2); count];
+        for _ in 0..2 {
+            for &seat in &order {
+                dealt[seat].extend(deck.draw());
+            }
+        }
+        let mut players: Vec<Option<Player>> = seats
+            .iter()
+            .zip(&dealt)
+            .map(|(seat, cards)| {
+                let seat = seat.as_ref().filter(|s| s.stack() > 0)?;
+                Some(Player {
+                    name: seat.name().to_string(),
+                    starting_stack: seat.stack(),
+                    stack: seat.stack(),
+                    hole_cards: [cards[0], cards[1]],
+                    street_bet: 0,
+                    total_bet: 0,
+                    folded: false,
+                    acted: false,
+                    acted_at: 0,
+                })
+            })
+            .collect();
+
+        // Heads up the button posts the small blind
+        let (small_blind, big_blind) = if order.len() == 2 {
+            (order[1], order[0])
+        } else {
+            (order[0], order[1])
+        };
+        let mut events = vec![Event::Started {
+            button,
+            stakes,
+            structure: table.betting_structure(),
+            odd_chips: table.odd_chip_rule(),
+            hi_lo: table.is_hi_lo(),
+            seats: seats.to_vec(),
+        }];
+        for &seat in &order {
+            if let Some(player) = players[seat].as_mut() {
+                let ante = stakes.ante.min(player.stack);
+                player.stack -= ante;
+                player.total_bet += ante;
+                if ante > 0 {
+                    events.push(Event::AntePosted { seat, amount: ante });
+                }
+            }
+        }
+        for (seat, blind, amount) in [
+            (small_blind, Blind::Small, stakes.small_blind),
+            (big_blind, Blind::Big, stakes.big_blind),
+        ] {
+            if let Some(player) = players[seat].as_mut() {
+                let amount = amount.min(player.stack);
+                player.put(amount);
+                if amount > 0 {
+                    events.push(Event::BlindPosted {
+                        seat,
+                        blind,
+                        amount,
+                    });
+                }
+            }
+        }
+        events.extend(order.iter().map(|&seat| Event::HoleCardsDealt {
+            seat,
+            cards: [dealt[seat][0], dealt[seat][1]],
+        }));
+
+        let structure = table.betting_structure();
+        let mut hand = Self {
+            stakes,
+            structure,
+            odd_chips: table.odd_chip_rule(),
+            hi_lo: table.is_hi_lo(),
+            button,
+            small_blind: (stakes.small_blind > 0).then_some(small_blind),
+            big_blind,
+            players,
+            deck,
+            board: Vec::with_capacity(Street::River.board_len()),
+            street: Street::Preflop,
+            to_act: None,
+            current_bet: stakes.big_blind,
+            min_raise: structure.opening_raise(Street::Preflop, stakes.big_blind),
+            bets: 1,
+            awards: Vec::new(),
+            events,
+        };
+        hand.advance(big_blind);
+        Ok(hand)
+    }
+
+    /// Applies an action by `seat`, returning the next state of the hand
+    pub fn apply(&self, seat: usize, action: Action) -> Result<Hand, ActionError> {
+        let to_act = self.to_act.ok_or(ActionError::HandComplete)?;
+        if seat != to_act {
+            return Err(ActionError::OutOfTurn { seat, to_act });
+        }
+        let mut next = self.clone();
+        next.act(seat, action)?;
+        next.advance(seat);
+        Ok(next)
+    }
+
+    /// What the player in `seat` may do, or `None` unless they are the player to act
+    pub fn legal_actions(&self, seat: usize) -> Option<LegalActions> {
+        if self.to_act != Some(seat) {
+            return None;
+        }
+        let to_call = self.to_call(seat);
+        let stack = self.player(seat)?.stack;
+        let range = self.raise_range(seat).ok();
+        Some(LegalActions {
+            check: to_call == 0,
+            call: (to_call > 0).then(|| to_call.min(stack)),
+            bet: range.filter(|_| self.current_bet == 0),
+            raise: range.filter(|_| self.current_bet > 0),
+        })
+    }
+
+    pub fn stakes(&self) -> Stakes {
+        self.stakes
+    }
+
+    pub fn betting_structure(&self) -> BettingStructure {
+        self.structure
+    }
+
+    /// Splits the pot between the best high hand and the best eight-or-better low
+    pub fn is_hi_lo(&self) -> bool {
+        self.hi_lo
+    }
+
+    /// Number of seats at the table, empty or not
+    pub fn seat_count(&self) -> usize {
+        self.players.len()
+    }
+
+    pub fn button(&self) -> usize {
+        self.button
+    }
+
+    /// The seat that posted the small blind, if there is one
+    pub fn small_blind(&self) -> Option<usize> {
+        self.small_blind
+    }
+
+    pub fn big_blind(&self) -> usize {
+        self.big_blind
+    }
+
+    /// The player in a seat, if they were dealt in
+    pub fn player(&self, seat: usize) -> Option<&Player> {
+        self.players.get(seat).and_then(Option::as_ref)
+    }
+
+    /// Every player dealt in, with their seat
+    pub fn players(&self) -> impl Iterator<Item = (usize, &Player)> {
+        self.players
+            .iter()
+            .enumerate()
+            .filter_map(|(seat, player)| Some((seat, player.as_ref()?)))
+    }
+
+    pub fn board(&self) -> &[Card] {
+        &self.board
+    }
+
+    pub fn street(&self) -> Street {
+        self.street
+    }
+
+    /// The seat whose action is awaited, or `None` once the hand is complete
+    pub fn to_act(&self) -> Option<usize> {
+        self.to_act
+    }
+
+    pub fn is_complete(&self) -> bool {
+        self.to_act.is_none()
+    }
+
+    /// The largest bet on the current street
+    pub fn current_bet(&self) -> Chips {
+        self.current_bet
+    }
+
+    /// Chips a seat needs to add to call, which may be more than they have
+    pub fn to_call(&self, seat: usize) -> Chips {
+        self.player(seat)
+            .map_or(0, |p| self.current_bet.saturating_sub(p.street_bet))
+    }
+
+    /// The smallest total a full bet or raise can be
+    pub fn min_raise_to(&self) -> Chips {
+        self.current_bet + self.min_raise
+    }
+
+    /// Chips put into the pot so far, including bets on the current street
+    pub fn pot(&self) -> Chips {
+        self.players().map(|(_, p)| p.total_bet).sum()
+    }
+
+    /// The main pot and side pots as they stand, counting bets on the current street
+    pub fn pots(&self) -> Pots {
+        let contributions: Vec<Contribution> = self
+            .players()
+            .map(|(seat, p)| Contribution {
+                seat,
+                amount: p.total_bet,
+                folded: p.folded,
+            })
+            .collect();
+        Pots::new(&contributions)
+    }
+
+    /// The chips won from each pot, empty until the hand is complete
+    ///
+    /// A bet nobody called is not won but returned; see `Pots::uncalled`.
+    pub fn awards(&self) -> &[Award] {
+        &self.awards
+    }
+
+    /// Everything that has happened in the hand so far, starting with the deal
+    ///
+    /// The events and the deck the hand was dealt from are enough to rebuild every state
+    /// of the hand; see `Replay`.
+    pub fn events(&self) -> &[Event] {
+        &self.events
+    }
+
+    // Validates an action and updates the player and the bet to match
+    fn act(&mut self, seat: usize, action: Action) -> Result<(), ActionError> {
+        let to_call = self.to_call(seat);
+        let all_in = match self.player(seat) {
+            Some(player) => player.street_bet + player.stack,
+            None => return Err(ActionError::HandComplete),
+        };
+        let check_range = |amount: Chips| {
+            let range = self.raise_range(seat).map_err(|e| match e {
+                ActionError::AboveMaximum { maximum, .. } => {
+                    ActionError::AboveMaximum { amount, maximum }
+                }
+                e => e,
+            })?;
+            if amount > range.max {
+                return Err(ActionError::AboveMaximum {
+                    amount,
+                    maximum: range.max,
+                });
+            }
+            if amount < range.min {
+                return Err(ActionError::BelowMinimum {
+                    amount,
+                    minimum: range.min,
+                });
+            }
+            Ok(Some(amount))
+        };
+
+        let raise_to = match action {
+            Action::Fold => None,
+            Action::Check if to_call > 0 => return Err(ActionError::CannotCheck { to_call }),
+            Action::Check => None,
+            Action::Call if to_call == 0 => return Err(ActionError::NothingToCall),
+            Action::Call => Some(self.current_bet.min(all_in)),
+            Action::Bet(_) if self.current_bet > 0 => return Err(ActionError::CannotBet),
+            Action::Raise(_) if self.current_bet == 0 => return Err(ActionError::CannotRaise),
+            Action::Bet(amount) | Action::Raise(amount) => check_range(amount)?,
+            Action::AllIn if all_in <= self.current_bet => Some(all_in),
+            Action::AllIn => check_range(all_in)?,
+        };
+
+        let mut amount = 0;
+        if let Some(total) = raise_to {
+            if total > self.current_bet {
+                let increase = total - self.current_bet;
+                // Everyone else now has to call, but a short all-in only lets those who have
+                // not acted yet raise
+                if increase >= self.structure.full_raise(self.min_raise) {
+                    if !matches!(self.structure, BettingStructure::FixedLimit { .. }) {
+                        self.min_raise = self.min_raise.max(increase);
+                    }
+                    self.bets += 1;
+                }
+                self.current_bet = total;
+            }
+            if let Some(player) = self.players[seat].as_mut() {
+                amount = total.saturating_sub(player.street_bet).min(player.stack);
+                player.put(amount);
+            }
+        }
+        let current_bet = self.current_bet;
+        if let Some(player) = self.players[seat].as_mut() {
+            player.folded |= action == Action::Fold;
+            player.acted = true;
+            player.acted_at = current_bet;
+        }
+        self.events.push(Event::Acted {
+            seat,
+            action,
+            amount,
+        });
+        Ok(())
+    }
+
+    // The totals the player in `seat` may bet or raise to, or why they may not
+    fn raise_range(&self, seat: usize) -> Result<BetRange, ActionError> {
+        let player = self.player(seat).ok_or(ActionError::HandComplete)?;
+        let all_in = player.street_bet + player.stack;
+        let reopened =
+            self.current_bet >= player.acted_at + self.structure.full_raise(self.min_raise);
+        if player.acted && !reopened {
+            return Err(ActionError::NotReopened);
+        }
+        if !self.structure.allows_raise(self.bets) {
+            return Err(ActionError::CapReached);
+        }
+        // Any raise needs more than the current bet
+        if all_in <= self.current_bet {
+            return Err(ActionError::AboveMaximum {
+                amount: self.current_bet + 1,
+                maximum: all_in,
+            });
+        }
+
+        let range = self.structure.bet_range(
+            self.current_bet,
+            self.min_raise,
+            self.pot(),
+            self.to_call(seat),
+        );
+        // Going all in for less than the minimum is always allowed
+        Ok(if all_in < range.min {
+            BetRange {
+                min: all_in,
+                max: all_in,
+            }
+        } else {
+            BetRange {
+                min: range.min,
+                max: range.max.min(all_in),
+            }
+        })
+    }
+
+    // Moves to the next player to act, dealing streets and settling the pot as betting ends
+    fn advance(&mut self, last: usize) {
+        if self.players().filter(|(_, p)| !p.folded).count() == 1 {
+            self.to_act = None;
+            self.settle();
+            return;
+        }
+        let mut from = last;
+        loop {
+            self.to_act = self.next_to_act(from);
+            if self.to_act.is_some() {
+                return;
+            }
+            if self.street == Street::River {
+                self.street = Street::Showdown;
+                self.settle();
+                return;
+            }
+            self.next_street();
+            from = self.button;
+        }
+    }
+
+    // The first seat after `from` that still has to act on this street
+    fn next_to_act(&self, from: usize) -> Option<usize> {
+        let count = self.players.len();
+        let able = self.players().filter(|(_, p)| p.can_act()).count();
+        (1..=count)
+            .map(|offset| (from + offset) % count)
+            .find(|&seat| {
+                self.player(seat).is_some_and(|p| {
+                    let behind = p.street_bet < self.current_bet;
+                    // A lone player with chips has nobody left to bet against
+                    p.can_act() && (behind || (!p.acted && able > 1))
+                })
+            })
+    }
+
+    fn next_street(&mut self) {
+        for player in self.players.iter_mut().flatten() {
+            player.street_bet = 0;
+            player.acted = false;
+            player.acted_at = 0;
+        }
+        self.street = self.street.next().unwrap_or(Street::Showdown);
+        self.current_bet = 0;
+        self.min_raise = self
+            .structure
+            .opening_raise(self.street, self.stakes.big_blind);
+        self.bets = 0;
+
+        self.deck.draw();
+        let dealt = self.board.len();
+        while self.board.len() < self.street.board_len() {
+            self.board.extend(self.deck.draw());
+        }
+        self.events.push(Event::BoardDealt {
+            street: self.street,
+            cards: self.board[dealt..].to_vec(),
+        });
+    }
+
+    // Returns any uncalled bet and awards the pots, to the best hands at a showdown
+    fn settle(&mut self) {
+        let contenders: Vec<Contender> = if self.street == Street::Showdown {
+            self.players()
+                .filter(|(_, p)| !p.folded)
+                .map(|(seat, p)| {
+                    let cards: Vec<Card> =
+                        p.hole_cards.iter().chain(&self.board).copied().collect();
+                    Contender {
+                        seat,
+                        high: evaluate(&cards),
+                        low: evaluate_low(&cards).filter(|_| self.hi_lo),
+                        hole_cards: p.hole_cards.to_vec(),
+                    }
+                })
+                .collect()
+        } else {
+            Vec::new()
+        };
+
+        let pots = self.pots();
+        self.awards = pots.award(&contenders, self.button, self.players.len(), self.odd_chips);
+        let returned = pots.uncalled().into_iter();
+        let won = self.awards.iter().map(|award| (award.seat, award.amount));
+        for (seat, chips) in returned.chain(won) {
+            if let Some(player) = self.players[seat].as_mut() {
+                player.stack += chips;
+            }
+        }
+        if let Some((seat, amount)) = pots.uncalled() {
+            self.events.push(Event::BetReturned { seat, amount });
+        }
+        self.events
+            .extend(self.awards.iter().copied().map(Event::PotAwarded));
+    }
+}
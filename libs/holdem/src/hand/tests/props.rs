@@ -0,0 +1,76 @@
+use super::super::*;
+use crate::pot::OddChipRule;
+use crate::test_support::{action, play_out, seated, seeded};
+use proptest::prelude::*;
+
+fn start(stacks: &[Chips], seed: u64) -> Hand {
+    // Hi-lo with odd chips by suit exercises every way of splitting a pot
+    let rule = if seed.is_multiple_of(3) {
+        OddChipRule::SuitOrder
+    } else {
+        OddChipRule::LeftOfButton
+    };
+    seated(Stakes::new(5, 10).with_ante(1), stacks)
+        .with_hi_lo(seed.is_multiple_of(2))
+        .with_odd_chip_rule(rule)
+        .start_hand(seeded(seed))
+        .unwrap()
+}
+
+fn arb_game() -> impl Strategy<Value = (Vec<Chips>, u64, Vec<(u8, f64)>)> {
+    (
+        prop::collection::vec(1 as Chips..2_000, 2..=9),
+        any::<u64>(),
+        prop::collection::vec((0u8..5, 0.0f64..1.0), 1..40),
+    )
+}
+
+proptest! {
+    #[test]
+    fn chips_are_conserved((stacks, seed, choices) in arb_game()) {
+        let hand = play_out(start(&stacks, seed), &choices);
+
+        let after: Chips = hand.players().map(|(_, p)| p.stack()).sum();
+        prop_assert_eq!(after, stacks.iter().sum::<Chips>());
+        let awarded: Chips = hand.awards().iter().map(|a| a.amount).sum();
+        let returned = hand.pots().uncalled().map_or(0, |(_, chips)| chips);
+        prop_assert_eq!(awarded + returned, hand.pot());
+    }
+
+    #[test]
+    fn hands_end_with_a_winner((stacks, seed, choices) in arb_game()) {
+        let hand = play_out(start(&stacks, seed), &choices);
+
+        prop_assert!(hand.is_complete());
+        prop_assert!(!hand.awards().is_empty());
+        for award in hand.awards() {
+            prop_assert!(!hand.player(award.seat).unwrap().has_folded());
+        }
+        if hand.street() == Street::Showdown {
+            prop_assert_eq!(hand.board().len(), 5);
+        }
+    }
+
+    #[test]
+    fn no_player_bets_more_than_their_stack((stacks, seed, choices) in arb_game()) {
+        let mut hand = start(&stacks, seed);
+        let mut choices = choices.iter().cycle();
+        while let Some(seat) = hand.to_act() {
+            let &(choice, size) = choices.next().unwrap();
+            if let Ok(next) = hand.apply(seat, action(&hand, seat, choice, size)) {
+                hand = next;
+            } else {
+                hand = hand.apply(seat, Action::Fold).unwrap();
+            }
+            if let Some(next) = hand.to_act() {
+                prop_assert!(hand.player(next).unwrap().stack() > 0);
+                for (_, player) in hand.players() {
+                    prop_assert_eq!(
+                        player.stack() + player.total_bet(),
+                        player.starting_stack()
+                    );
+                }
+            }
+        }
+    }
+}
@@ -0,0 +1,331 @@
+use super::super::*;
+use crate::pot::Share;
+use crate::table::Table;
+use crate::test_support::{deck, play, table};
+use cards::notation::parse_cards;
+
+fn stacks(hand: &Hand) -> Vec<Chips> {
+    hand.players().map(|(_, p)| p.stack()).collect()
+}
+
+#[test]
+fn test_heads_up_button_posts_small_blind_and_acts_first() {
+    let hand = table(&[100, 100]).start_hand(Deck::new()).unwrap();
+
+    assert_eq!(hand.button(), 0);
+    assert_eq!(hand.small_blind(), Some(0));
+    assert_eq!(hand.big_blind(), 1);
+    assert_eq!(hand.player(0).unwrap().street_bet(), 1);
+    assert_eq!(hand.player(1).unwrap().street_bet(), 2);
+    assert_eq!(hand.to_act(), Some(0));
+    assert_eq!(hand.to_call(0), 1);
+
+    // After the flop the big blind acts first
+    let hand = play(hand, &[(0, Action::Call), (1, Action::Check)]);
+    assert_eq!(hand.street(), Street::Flop);
+    assert_eq!(hand.to_act(), Some(1));
+}
+
+#[test]
+fn test_three_handed_positions_and_order() {
+    let hand = table(&[100, 100, 100]).start_hand(Deck::new()).unwrap();
+
+    assert_eq!(hand.small_blind(), Some(1));
+    assert_eq!(hand.big_blind(), 2);
+    assert_eq!(hand.to_act(), Some(0));
+
+    let hand = play(
+        hand,
+        &[(0, Action::Call), (1, Action::Call), (2, Action::Check)],
+    );
+    assert_eq!(hand.street(), Street::Flop);
+    assert_eq!(hand.to_act(), Some(1));
+    assert_eq!(hand.pot(), 6);
+}
+
+#[test]
+fn test_hole_cards_and_board_are_dealt_in_order() {
+    // Two rounds of hole cards from the small blind, then a burn before each street
+    let deck = deck("2c 3c 4c 5c 6c 7c 8d Ah Kh Qh 9d Jh 9s Th");
+    let hand = table(&[100, 100, 100]).start_hand(deck).unwrap();
+    let hand = play(
+        hand,
+        &[(0, Action::Call), (1, Action::Call), (2, Action::Check)],
+    );
+
+    assert_eq!(
+        hand.player(1).unwrap().hole_cards(),
+        [card("2c"), card("5c")]
+    );
+    assert_eq!(
+        hand.player(2).unwrap().hole_cards(),
+        [card("3c"), card("6c")]
+    );
+    assert_eq!(
+        hand.player(0).unwrap().hole_cards(),
+        [card("4c"), card("7c")]
+    );
+    assert_eq!(hand.board(), parse_cards("Ah Kh Qh").unwrap());
+
+    let hand = play(
+        hand,
+        &[(1, Action::Check), (2, Action::Check), (0, Action::Check)],
+    );
+    assert_eq!(hand.street(), Street::Turn);
+    assert_eq!(hand.board(), parse_cards("Ah Kh Qh Jh").unwrap());
+}
+
+fn card(notation: &str) -> Card {
+    notation.parse().unwrap()
+}
+
+#[test]
+fn test_big_blind_has_the_option() {
+    let hand = table(&[100, 100, 100]).start_hand(Deck::new()).unwrap();
+    let hand = play(hand, &[(0, Action::Call), (1, Action::Call)]);
+
+    assert_eq!(hand.street(), Street::Preflop);
+    assert_eq!(hand.to_act(), Some(2));
+    let raised = hand.apply(2, Action::Raise(6)).unwrap();
+    assert_eq!(raised.to_act(), Some(0));
+    assert_eq!(raised.to_call(0), 4);
+}
+
+#[test]
+fn test_fold_to_the_big_blind() {
+    let mut table = table(&[100, 100, 100]);
+    let hand = table.start_hand(Deck::new()).unwrap();
+    let hand = play(hand, &[(0, Action::Fold), (1, Action::Fold)]);
+
+    assert!(hand.is_complete());
+    assert_eq!(
+        hand.awards(),
+        [Award {
+            seat: 2,
+            pot: 0,
+            amount: 2,
+            share: Share::Uncontested,
+        }]
+    );
+    // The big blind's extra chip was never called
+    assert_eq!(hand.pots().uncalled(), Some((2, 1)));
+    assert_eq!(stacks(&hand), [100, 99, 101]);
+    assert_eq!(
+        hand.apply(2, Action::Check).unwrap_err(),
+        ActionError::HandComplete
+    );
+}
+
+#[test]
+fn test_invalid_actions_are_rejected() {
+    let hand = table(&[100, 100, 100]).start_hand(Deck::new()).unwrap();
+
+    assert_eq!(
+        hand.apply(1, Action::Call).unwrap_err(),
+        ActionError::OutOfTurn { seat: 1, to_act: 0 }
+    );
+    assert_eq!(
+        hand.apply(0, Action::Check).unwrap_err(),
+        ActionError::CannotCheck { to_call: 2 }
+    );
+    assert_eq!(
+        hand.apply(0, Action::Bet(10)).unwrap_err(),
+        ActionError::CannotBet
+    );
+    assert_eq!(
+        hand.apply(0, Action::Raise(3)).unwrap_err(),
+        ActionError::BelowMinimum {
+            amount: 3,
+            minimum: 4
+        }
+    );
+    assert_eq!(
+        hand.apply(0, Action::Raise(101)).unwrap_err(),
+        ActionError::AboveMaximum {
+            amount: 101,
+            maximum: 100
+        }
+    );
+
+    let flop = play(
+        hand,
+        &[(0, Action::Call), (1, Action::Call), (2, Action::Check)],
+    );
+    assert_eq!(
+        flop.apply(1, Action::Call).unwrap_err(),
+        ActionError::NothingToCall
+    );
+    assert_eq!(
+        flop.apply(1, Action::Raise(10)).unwrap_err(),
+        ActionError::CannotRaise
+    );
+    assert_eq!(
+        flop.apply(1, Action::Bet(1)).unwrap_err(),
+        ActionError::BelowMinimum {
+            amount: 1,
+            minimum: 2
+        }
+    );
+}
+
+#[test]
+fn test_rejected_action_leaves_the_hand_unchanged() {
+    let hand = table(&[100, 100, 100]).start_hand(Deck::new()).unwrap();
+    let before = format!("{hand:?}");
+    assert!(hand.apply(0, Action::Check).is_err());
+    assert_eq!(format!("{hand:?}"), before);
+}
+
+#[test]
+fn test_minimum_raise_follows_the_last_raise() {
+    let hand = table(&[200, 200, 200]).start_hand(Deck::new()).unwrap();
+    let hand = hand.apply(0, Action::Raise(10)).unwrap();
+    assert_eq!(hand.min_raise_to(), 18);
+    assert!(hand.apply(1, Action::Raise(17)).is_err());
+    let hand = hand.apply(1, Action::Raise(18)).unwrap();
+    assert_eq!(hand.current_bet(), 18);
+    assert_eq!(hand.to_call(2), 16);
+}
+
+#[test]
+fn test_short_all_in_is_allowed_below_the_minimum_raise() {
+    let hand = table(&[100, 15, 100]).start_hand(Deck::new()).unwrap();
+    let hand = hand.apply(0, Action::Raise(10)).unwrap();
+
+    // Raising to 15 is less than the minimum of 18, but it is every chip seat 1 has
+    let hand = hand.apply(1, Action::Raise(15)).unwrap();
+    assert!(hand.player(1).unwrap().is_all_in());
+    assert_eq!(hand.current_bet(), 15);
+    assert_eq!(hand.min_raise_to(), 23);
+}
+
+#[test]
+fn test_showdown_awards_the_best_hand() {
+    // Seat 1 holds aces, seat 0 kings; the board gives nobody more
+    let deck = deck("As Ks Ad Kd 2c 7h 8d 3s 4c Jd 9c 5h");
+    let hand = table(&[100, 100]).start_hand(deck).unwrap();
+    let hand = play(hand, &[(0, Action::Raise(10)), (1, Action::Call)]);
+    let hand = play(
+        hand,
+        &[
+            (1, Action::Check),
+            (0, Action::Check),
+            (1, Action::Check),
+            (0, Action::Check),
+            (1, Action::Bet(20)),
+            (0, Action::Call),
+        ],
+    );
+
+    assert!(hand.is_complete());
+    assert_eq!(hand.street(), Street::Showdown);
+    assert_eq!(hand.board().len(), 5);
+    assert_eq!(hand.awards().len(), 1);
+    assert_eq!(hand.awards()[0].seat, 1);
+    assert_eq!(hand.awards()[0].amount, 60);
+    assert!(matches!(hand.awards()[0].share, Share::High(_)));
+    assert_eq!(stacks(&hand), [70, 130]);
+}
+
+#[test]
+fn test_split_pot_gives_the_odd_chip_left_of_the_button() {
+    // Everyone plays the broadway straight on the board
+    let deck = deck("2c 3c 4c 2d 3d 4d 5s As Ks Qh 6s Jd 7s Th");
+    let hand = table(&[100, 100, 100]).start_hand(deck).unwrap();
+    // The small blind folds, leaving a pot of 5 to split between seats 0 and 2
+    let hand = play(
+        hand,
+        &[(0, Action::Call), (1, Action::Fold), (2, Action::Check)],
+    );
+    let hand = play(
+        hand,
+        &[
+            (2, Action::Check),
+            (0, Action::Check),
+            (2, Action::Check),
+            (0, Action::Check),
+            (2, Action::Check),
+            (0, Action::Check),
+        ],
+    );
+
+    assert!(hand.is_complete());
+    let won: Vec<(usize, Chips)> = hand.awards().iter().map(|a| (a.seat, a.amount)).collect();
+    assert_eq!(won, [(2, 3), (0, 2)]);
+    assert_eq!(stacks(&hand), [100, 99, 101]);
+}
+
+#[test]
+fn test_all_in_runs_out_the_board_and_builds_side_pots() {
+    // Seat 1 (short, aces) beats seat 2 (kings), who beats seat 0 (queens)
+    let deck = deck("As Ks Qs Ad Kd Qd 2c 7h 8d 3s 4c Jd 9c 5h");
+    let hand = table(&[300, 50, 200]).start_hand(deck).unwrap();
+    let hand = play(
+        hand,
+        &[(0, Action::AllIn), (1, Action::AllIn), (2, Action::AllIn)],
+    );
+
+    assert!(hand.is_complete());
+    assert_eq!(hand.street(), Street::Showdown);
+    assert_eq!(hand.board().len(), 5);
+    let won: Vec<(usize, usize, Chips)> = hand
+        .awards()
+        .iter()
+        .map(|a| (a.seat, a.pot, a.amount))
+        .collect();
+    // The main pot of 150 to seat 1, the side pot of 300 to seat 2, and seat 0's
+    // uncalled 100 back to them
+    assert_eq!(won, [(1, 0, 150), (2, 1, 300)]);
+    assert_eq!(hand.pots().uncalled(), Some((0, 100)));
+    assert_eq!(stacks(&hand), [100, 150, 300]);
+}
+
+#[test]
+fn test_antes_are_posted_before_the_blinds() {
+    let mut table = Table::new(3, Stakes::new(1, 2).with_ante(1)).unwrap();
+    for seat in 0..3 {
+        table.sit(seat, format!("p{seat}"), 100).unwrap();
+    }
+    let hand = table.start_hand(Deck::new()).unwrap();
+
+    assert_eq!(hand.pot(), 6);
+    assert_eq!(hand.player(0).unwrap().street_bet(), 0);
+    assert_eq!(hand.player(2).unwrap().street_bet(), 2);
+    assert_eq!(hand.player(2).unwrap().total_bet(), 3);
+    assert_eq!(stacks(&hand), [99, 98, 97]);
+}
+
+#[test]
+fn test_short_big_blind_is_all_in() {
+    let hand = table(&[100, 100, 1]).start_hand(Deck::new()).unwrap();
+
+    assert!(hand.player(2).unwrap().is_all_in());
+    assert_eq!(hand.current_bet(), 2);
+    let hand = play(hand, &[(0, Action::Fold), (1, Action::Call)]);
+
+    // Nobody is left to bet against the small blind, so the board runs out
+    assert!(hand.is_complete());
+    assert_eq!(hand.board().len(), 5);
+    assert_eq!(hand.players().map(|(_, p)| p.stack()).sum::<Chips>(), 201);
+}
+
+#[test]
+fn test_hi_lo_table_splits_at_showdown() {
+    let mut table = Table::new(2, Stakes::new(1, 2)).unwrap().with_hi_lo(true);
+    table.sit(0, "p0", 100).unwrap();
+    table.sit(1, "p1", 100).unwrap();
+    // Seat 1 makes a seven low, seat 0 trip kings
+    let deck = deck("2c Kd 3h 9c Qc Ah 4c 7d Jc Ks Tc Kh");
+    let hand = table.start_hand(deck).unwrap();
+    let hand = play(hand, &[(0, Action::Call), (1, Action::Check)]);
+    let hand = play(hand, &[(1, Action::Check), (0, Action::Check)].repeat(3));
+
+    assert!(hand.is_complete());
+    let shares: Vec<(usize, Chips, bool)> = hand
+        .awards()
+        .iter()
+        .map(|a| (a.seat, a.amount, matches!(a.share, Share::Low(_))))
+        .collect();
+    assert_eq!(shares, [(0, 2, false), (1, 2, true)]);
+    assert_eq!(stacks(&hand), [100, 100]);
+}
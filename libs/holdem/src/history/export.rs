@@ -0,0 +1,476 @@
+use std::fmt;
+use std::time::{SystemTime, UNIX_EPOCH};
+
+use cards::Card;
+use cards::eval::{HandCategory, HandValue, LowValue, evaluate, evaluate_low};
+
+use crate::Chips;
+use crate::betting::BettingStructure;
+use crate::event::{Blind, Event};
+use crate::hand::{Action, Hand, Street};
+use crate::pot::Pots;
+use crate::table::TableError;
+
+use super::Utc;
+
+/// A completed hand written out as a PokerStars text hand history
+///
+/// Every player's hole cards are listed after `*** HOLE CARDS ***` unless a hero is set, in
+/// which case only theirs are, as in a history downloaded by a player. Amounts are whole
+/// chips and no rake is taken.
+///
+/// # Examples
+///
+/// ```
+/// use cards::Deck;
+/// use holdem::hand::Action;
+/// use holdem::history::Export;
+/// use holdem::table::{Stakes, Table};
+///
+/// let mut table = Table::new(2, Stakes::new(1, 2)).unwrap();
+/// table.sit(0, "ann", 100).unwrap();
+/// table.sit(1, "ben", 100).unwrap();
+///
+/// let hand = table.start_hand(Deck::new_seeded(1)).unwrap();
+/// let hand = hand.apply(0, Action::Fold).unwrap();
+///
+/// let history = Export::new(&hand, 42).unwrap().with_table_name("Home").to_string();
+/// assert!(history.starts_with("PokerStars Hand #42:  Hold'em No Limit (1/2)"));
+/// assert!(history.contains("Table 'Home' 2-max Seat #1 is the button\n"));
+/// assert!(history.contains("ben collected 2 from pot\n"));
+/// ```
+#[derive(Debug, Clone)]
+pub struct Export<'a> {
+    hand: &'a Hand,
+    id: u64,
+    table_name: String,
+    time: SystemTime,
+    hero: Option<usize>,
+}
+
+impl<'a> Export<'a> {
+    /// Exports `hand` as hand number `id`, played at the Unix epoch unless a time is set
+    pub fn new(hand: &'a Hand, id: u64) -> Result<Self, TableError> {
+        if !hand.is_complete() {
+            return Err(TableError::HandNotComplete);
+        }
+        Ok(Self {
+            hand,
+            id,
+            table_name: String::from("pokeme"),
+            time: UNIX_EPOCH,
+            hero: None,
+        })
+    }
+
+    pub fn with_table_name(mut self, name: impl Into<String>) -> Self {
+        self.table_name = name.into();
+        self
+    }
+
+    /// Sets when the hand was played, written in UTC
+    pub fn with_time(mut self, time: SystemTime) -> Self {
+        self.time = time;
+        self
+    }
+
+    /// Shows only the hole cards of the player in `seat` until the showdown
+    pub fn with_hero(mut self, seat: usize) -> Self {
+        self.hero = Some(seat);
+        self
+    }
+
+    fn name(&self, seat: usize) -> &str {
+        self.hand.player(seat).map_or("", |player| player.name())
+    }
+
+    fn header(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let hand = self.hand;
+        let stakes = hand.stakes();
+        let (limit, (small, big)) = match hand.betting_structure() {
+            BettingStructure::NoLimit => ("No Limit", (stakes.small_blind, stakes.big_blind)),
+            BettingStructure::PotLimit => ("Pot Limit", (stakes.small_blind, stakes.big_blind)),
+            BettingStructure::FixedLimit {
+                small_bet, big_bet, ..
+            } => ("Limit", (small_bet, big_bet)),
+            BettingStructure::SpreadLimit { .. } => {
+                ("Spread Limit", (stakes.small_blind, stakes.big_blind))
+            }
+        };
+        let hi_lo = if hand.is_hi_lo() { "Hi/Lo " } else { "" };
+        writeln!(
+            f,
+            "PokerStars Hand #{}:  Hold'em {hi_lo}{limit} ({small}/{big}) - {} UTC",
+            self.id,
+            Timestamp(self.time)
+        )?;
+        writeln!(
+            f,
+            "Table '{}' {}-max Seat #{} is the button",
+            self.table_name,
+            hand.seat_count(),
+            hand.button() + 1
+        )?;
+        for (seat, player) in hand.players() {
+            writeln!(
+                f,
+                "Seat {}: {} ({} in chips)",
+                seat + 1,
+                player.name(),
+                player.starting_stack()
+            )?;
+        }
+        Ok(())
+    }
+
+    // The betting, streets and showdown as they happened
+    fn body(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let hand = self.hand;
+        let seats = hand.seat_count();
+        let mut stacks = vec![0; seats];
+        for (seat, player) in hand.players() {
+            stacks[seat] = player.starting_stack();
+        }
+        let mut street_bets = vec![0; seats];
+        let mut current_bet = 0;
+        let mut board: Vec<Card> = Vec::new();
+        let mut dealt = false;
+        let mut shown = false;
+        let pots = hand.pots();
+
+        for event in hand.events() {
+            match *event {
+                Event::Started { .. } => {}
+                Event::AntePosted { seat, amount } => {
+                    stacks[seat] -= amount;
+                    write!(f, "{}: posts the ante {amount}", self.name(seat))?;
+                    all_in(f, stacks[seat])?;
+                }
+                Event::BlindPosted {
+                    seat,
+                    blind,
+                    amount,
+                } => {
+                    stacks[seat] -= amount;
+                    street_bets[seat] += amount;
+                    // Everyone calls the full big blind, even one posted short
+                    let blind = match blind {
+                        Blind::Small => "small",
+                        Blind::Big => {
+                            current_bet = hand.stakes().big_blind;
+                            "big"
+                        }
+                    };
+                    write!(f, "{}: posts {blind} blind {amount}", self.name(seat))?;
+                    all_in(f, stacks[seat])?;
+                }
+                Event::HoleCardsDealt { seat, cards } => {
+                    if !dealt {
+                        writeln!(f, "*** HOLE CARDS ***")?;
+                        dealt = true;
+                    }
+                    if self.hero.is_none_or(|hero| hero == seat) {
+                        writeln!(f, "Dealt to {} {}", self.name(seat), Cards(&cards))?;
+                    }
+                }
+                Event::Acted {
+                    seat,
+                    action,
+                    amount,
+                } => {
+                    stacks[seat] -= amount;
+                    street_bets[seat] += amount;
+                    let total = street_bets[seat];
+                    write!(f, "{}: ", self.name(seat))?;
+                    match action {
+                        Action::Fold => write!(f, "folds")?,
+                        Action::Check => write!(f, "checks")?,
+                        Action::Call => write!(f, "calls {amount}")?,
+                        _ if total <= current_bet => write!(f, "calls {amount}")?,
+                        _ if current_bet == 0 => write!(f, "bets {amount}")?,
+                        _ => write!(f, "raises {} to {total}", total - current_bet)?,
+                    }
+                    current_bet = current_bet.max(total);
+                    all_in(f, stacks[seat])?;
+                }
+                Event::BoardDealt { street, ref cards } => {
+                    let name = match street {
+                        Street::Flop => "FLOP",
+                        Street::Turn => "TURN",
+                        _ => "RIVER",
+                    };
+                    write!(f, "*** {name} *** ")?;
+                    if !board.is_empty() {
+                        write!(f, "{} ", Cards(&board))?;
+                    }
+                    writeln!(f, "{}", Cards(cards))?;
+                    board.extend(cards);
+                    street_bets.fill(0);
+                    current_bet = 0;
+                }
+                Event::BetReturned { seat, amount } => {
+                    writeln!(f, "Uncalled bet ({amount}) returned to {}", self.name(seat))?;
+                }
+                Event::PotAwarded(award) => {
+                    if !shown && hand.street() == Street::Showdown {
+                        writeln!(f, "*** SHOW DOWN ***")?;
+                        for (seat, player) in hand.players().filter(|(_, p)| !p.has_folded()) {
+                            writeln!(
+                                f,
+                                "{}: shows {} ({})",
+                                player.name(),
+                                Cards(&player.hole_cards()),
+                                self.describe(seat)
+                            )?;
+                        }
+                        shown = true;
+                    }
+                    writeln!(
+                        f,
+                        "{} collected {} from {}",
+                        self.name(award.seat),
+                        award.amount,
+                        pot_name(&pots, award.pot)
+                    )?;
+                }
+            }
+        }
+        Ok(())
+    }
+
+    fn summary(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let hand = self.hand;
+        let pots = hand.pots();
+        writeln!(f, "*** SUMMARY ***")?;
+        write!(f, "Total pot {}", pots.total())?;
+        if !pots.side_pots().is_empty() {
+            for (index, pot) in pots.pots().iter().enumerate() {
+                let name = pot_name(&pots, index);
+                write!(
+                    f,
+                    " {}{} {}.",
+                    name[..1].to_uppercase(),
+                    &name[1..],
+                    pot.amount()
+                )?;
+            }
+        }
+        writeln!(f, " | Rake 0")?;
+        if !hand.board().is_empty() {
+            writeln!(f, "Board {}", Cards(hand.board()))?;
+        }
+
+        let folds = self.folds();
+        for (seat, player) in hand.players() {
+            write!(f, "Seat {}: {}", seat + 1, player.name())?;
+            if seat == hand.button() {
+                write!(f, " (button)")?;
+            }
+            if hand.small_blind() == Some(seat) {
+                write!(f, " (small blind)")?;
+            } else if seat == hand.big_blind() {
+                write!(f, " (big blind)")?;
+            }
+            let won: Chips = hand
+                .awards()
+                .iter()
+                .filter(|award| award.seat == seat)
+                .map(|award| award.amount)
+                .sum();
+            match folds[seat] {
+                Some((Street::Preflop, bet)) => {
+                    write!(f, " folded before Flop")?;
+                    if !bet {
+                        write!(f, " (didn't bet)")?;
+                    }
+                }
+                Some((street, _)) => write!(f, " folded on the {}", street_name(street))?,
+                None if hand.street() == Street::Showdown => {
+                    write!(f, " showed {} and ", Cards(&player.hole_cards()))?;
+                    if won > 0 {
+                        write!(f, "won ({won})")?;
+                    } else {
+                        write!(f, "lost")?;
+                    }
+                    write!(f, " with {}", self.describe(seat))?;
+                }
+                None => write!(f, " collected ({won})")?,
+            }
+            writeln!(f)?;
+        }
+        Ok(())
+    }
+
+    // The street each seat folded on, and whether they had put chips in beyond an ante
+    fn folds(&self) -> Vec<Option<(Street, bool)>> {
+        let mut folds = vec![None; self.hand.seat_count()];
+        let mut bet = vec![false; self.hand.seat_count()];
+        let mut street = Street::Preflop;
+        for event in self.hand.events() {
+            match *event {
+                Event::BlindPosted { seat, .. } => bet[seat] = true,
+                Event::Acted {
+                    seat,
+                    action,
+                    amount,
+                } => {
+                    bet[seat] |= amount > 0;
+                    if action == Action::Fold {
+                        folds[seat] = Some((street, bet[seat]));
+                    }
+                }
+                Event::BoardDealt { street: next, .. } => street = next,
+                _ => {}
+            }
+        }
+        folds
+    }
+
+    // The player's hand in the words PokerStars uses, with the low in a hi-lo game
+    fn describe(&self, seat: usize) -> String {
+        let Some(player) = self.hand.player(seat) else {
+            return String::new();
+        };
+        let cards: Vec<Card> = player
+            .hole_cards()
+            .iter()
+            .chain(self.hand.board())
+            .copied()
+            .collect();
+        let high = describe_high(evaluate(&cards));
+        if !self.hand.is_hi_lo() {
+            return high;
+        }
+        match evaluate_low(&cards) {
+            Some(low) => format!("HI: {high}; LO: {}", describe_low(low)),
+            None => format!("HI: {high}"),
+        }
+    }
+}
+
+impl fmt::Display for Export<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        self.header(f)?;
+        self.body(f)?;
+        self.summary(f)
+    }
+}
+
+fn all_in(f: &mut fmt::Formatter<'_>, stack: Chips) -> fmt::Result {
+    if stack == 0 {
+        write!(f, " and is all-in")?;
+    }
+    writeln!(f)
+}
+
+fn pot_name(pots: &Pots, index: usize) -> String {
+    match (index, pots.side_pots().len()) {
+        (_, 0) => String::from("pot"),
+        (0, _) => String::from("main pot"),
+        (_, 1) => String::from("side pot"),
+        (index, _) => format!("side pot-{index}"),
+    }
+}
+
+fn street_name(street: Street) -> &'static str {
+    match street {
+        Street::Preflop => "Preflop",
+        Street::Flop => "Flop",
+        Street::Turn => "Turn",
+        Street::River => "River",
+        Street::Showdown => "Showdown",
+    }
+}
+
+fn describe_high(value: HandValue) -> String {
+    let ranks = value.ranks();
+    let rank = |i: usize| rank_name(ranks.get(i).copied().unwrap_or(0));
+    let ranks_of = |i: usize| plural(ranks.get(i).copied().unwrap_or(0));
+    // A straight is named by its high card, running down four ranks with the wheel's ace low
+    let straight = || {
+        let high = ranks.first().copied().unwrap_or(5);
+        let low = if high == 5 { 14 } else { high - 4 };
+        format!("{} to {}", rank_name(low), rank_name(high))
+    };
+    match value.category() {
+        HandCategory::HighCard => format!("high card {}", rank(0)),
+        HandCategory::OnePair => format!("a pair of {}", ranks_of(0)),
+        HandCategory::TwoPair => format!("two pair, {} and {}", ranks_of(0), ranks_of(1)),
+        HandCategory::ThreeOfAKind => format!("three of a kind, {}", ranks_of(0)),
+        HandCategory::Straight => format!("a straight, {}", straight()),
+        HandCategory::Flush => format!("a flush, {} high", rank(0)),
+        HandCategory::FullHouse => {
+            format!("a full house, {} full of {}", ranks_of(0), ranks_of(1))
+        }
+        HandCategory::FourOfAKind => format!("four of a kind, {}", ranks_of(0)),
+        HandCategory::StraightFlush if ranks.first() == Some(&14) => String::from("a Royal Flush"),
+        HandCategory::StraightFlush => format!("a straight flush, {}", straight()),
+    }
+}
+
+fn describe_low(value: LowValue) -> String {
+    let ranks: Vec<String> = value
+        .ranks()
+        .iter()
+        .map(|&rank| match rank {
+            1 => String::from("A"),
+            rank => rank.to_string(),
+        })
+        .collect();
+    ranks.join(",")
+}
+
+fn rank_name(value: u8) -> &'static str {
+    match value {
+        2 => "Deuce",
+        3 => "Three",
+        4 => "Four",
+        5 => "Five",
+        6 => "Six",
+        7 => "Seven",
+        8 => "Eight",
+        9 => "Nine",
+        10 => "Ten",
+        11 => "Jack",
+        12 => "Queen",
+        13 => "King",
+        _ => "Ace",
+    }
+}
+
+fn plural(value: u8) -> String {
+    match value {
+        6 => String::from("Sixes"),
+        value => format!("{}s", rank_name(value)),
+    }
+}
+
+// Cards in brackets, as in `[Ah Kd]`
+struct Cards<'a>(&'a [Card]);
+
+impl fmt::Display for Cards<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "[")?;
+        for (i, card) in self.0.iter().enumerate() {
+            if i > 0 {
+                write!(f, " ")?;
+            }
+            write!(f, "{card}")?;
+        }
+        write!(f, "]")
+    }
+}
+
+// A time as a UTC date and time, in the form `2024/01/31 9:05:00`
+struct Timestamp(SystemTime);
+
+impl fmt::Display for Timestamp {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let utc = Utc::new(self.0);
+        write!(
+            f,
+            "{}/{:02}/{:02} {}:{:02}:{:02}",
+            utc.year, utc.month, utc.day, utc.hour, utc.minute, utc.second
+        )
+    }
+}
@@ -0,0 +1,63 @@
+use std::time::{SystemTime, UNIX_EPOCH};
+
+mod export;
+#[cfg(feature = "serde")]
+mod ohh;
+mod parse;
+
+pub use export::Export;
+#[cfg(feature = "serde")]
+pub use ohh::{
+    OhhAction, OhhActionKind, OhhBetLimit, OhhBetType, OhhError, OhhGameType, OhhPlayer, OhhPot,
+    OhhRound, OhhWin, OpenHandHistory,
+};
+pub use parse::{
+    ActionRecord, HandRecord, HistoryErrorKind, HistoryReader, ParseHistoryError, RecordedAction,
+    SeatRecord,
+};
+
+#[cfg(test)]
+mod tests;
+
+// A time as a UTC calendar date and time of day
+struct Utc {
+    year: i64,
+    month: i64,
+    day: i64,
+    hour: u64,
+    minute: u64,
+    second: u64,
+}
+
+impl Utc {
+    fn new(time: SystemTime) -> Self {
+        let seconds = time
+            .duration_since(UNIX_EPOCH)
+            .unwrap_or_default()
+            .as_secs();
+        let (days, time) = (seconds / 86_400, seconds % 86_400);
+
+        // Days since the epoch to a civil date, counting in 400 year eras from March 2000
+        let days = days as i64 + 719_468;
+        let era = days.div_euclid(146_097);
+        let day_of_era = days.rem_euclid(146_097);
+        let year_of_era =
+            (day_of_era - day_of_era / 1_460 + day_of_era / 36_524 - day_of_era / 146_096) / 365;
+        let day_of_year = day_of_era - (365 * year_of_era + year_of_era / 4 - year_of_era / 100);
+        let shifted_month = (5 * day_of_year + 2) / 153;
+        let day = day_of_year - (153 * shifted_month + 2) / 5 + 1;
+        let month = if shifted_month < 10 {
+            shifted_month + 3
+        } else {
+            shifted_month - 9
+        };
+        Utc {
+            year: era * 400 + year_of_era + i64::from(month <= 2),
+            month,
+            day,
+            hour: time / 3_600,
+            minute: time / 60 % 60,
+            second: time % 60,
+        }
+    }
+}
@@ -0,0 +1,579 @@
+use std::collections::HashSet;
+use std::fmt;
+use std::time::{SystemTime, UNIX_EPOCH};
+
+use cards::Card;
+use serde::{Deserialize, Serialize};
+
+use crate::Chips;
+use crate::betting::BettingStructure;
+use crate::event::{Blind, Event};
+use crate::hand::{Action, Hand, Street};
+use crate::table::TableError;
+
+use super::Utc;
+
+// The version of the Open Hand History specification written
+const SPEC_VERSION: &str = "1.4.7";
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum OhhError {
+    /// Not JSON, or not the shape the specification sets out, such as a required field missing
+    /// or a card that cannot be read
+    Json(String),
+    /// Converting a hand that cannot be written out
+    Table(TableError),
+    /// The specification has no bet type for spread limit
+    UnsupportedStructure(BettingStructure),
+    /// A hand needs at least two players
+    NotEnoughPlayers(usize),
+    /// Two players share an id
+    DuplicatePlayer(u32),
+    /// A seat number outside the table, or taken by two players
+    InvalidSeat(u32),
+    /// An action, win or the hero refers to a player id that is not listed
+    UnknownPlayer(u32),
+    /// A completed hand has at least one pot
+    NoPots,
+}
+
+impl fmt::Display for OhhError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            OhhError::Json(message) => write!(f, "invalid hand history: {message}"),
+            OhhError::Table(error) => write!(f, "{error}"),
+            OhhError::UnsupportedStructure(structure) => {
+                write!(
+                    f,
+                    "betting structure {structure:?} has no Open Hand History bet type"
+                )
+            }
+            OhhError::NotEnoughPlayers(count) => {
+                write!(f, "a hand needs at least 2 players, got {count}")
+            }
+            OhhError::DuplicatePlayer(id) => write!(f, "player id {id} is listed twice"),
+            OhhError::InvalidSeat(seat) => write!(f, "seat {seat} is not valid"),
+            OhhError::UnknownPlayer(id) => write!(f, "player id {id} is not listed"),
+            OhhError::NoPots => write!(f, "a completed hand has at least one pot"),
+        }
+    }
+}
+
+impl std::error::Error for OhhError {}
+
+impl From<TableError> for OhhError {
+    fn from(error: TableError) -> Self {
+        OhhError::Table(error)
+    }
+}
+
+/// A completed hand in the Open Hand History JSON format
+///
+/// Fields follow the specification's names. Optional fields are left out when written, and
+/// amounts are decimal numbers in the currency of the game, or chips when there is none.
+/// Reading checks that the fields the specification requires are present and that players,
+/// seats and pots fit together.
+///
+/// # Examples
+///
+/// ```
+/// use cards::Deck;
+/// use holdem::hand::Action;
+/// use holdem::history::OpenHandHistory;
+/// use holdem::table::{Stakes, Table};
+///
+/// let mut table = Table::new(2, Stakes::new(1, 2)).unwrap();
+/// table.sit(0, "ann", 100).unwrap();
+/// table.sit(1, "ben", 100).unwrap();
+///
+/// let hand = table.start_hand(Deck::new_seeded(1)).unwrap();
+/// let hand = hand.apply(0, Action::Fold).unwrap();
+///
+/// let json = OpenHandHistory::from_hand(&hand, "42").unwrap().to_json().unwrap();
+/// let history = OpenHandHistory::from_json(&json).unwrap();
+/// assert_eq!(history.game_number, "42");
+/// assert_eq!(history.pots[0].player_wins[0].win_amount, 2.0);
+/// ```
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct OpenHandHistory {
+    pub spec_version: String,
+    pub site_name: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub network_name: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub internal_version: Option<String>,
+    #[serde(default)]
+    pub tournament: bool,
+    pub game_number: String,
+    /// When the hand started, in ISO 8601 form such as `2024-01-31T09:05:00Z`
+    pub start_date_utc: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub table_name: Option<String>,
+    pub game_type: OhhGameType,
+    pub bet_limit: OhhBetLimit,
+    pub table_size: u32,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub currency: Option<String>,
+    pub dealer_seat: u32,
+    pub small_blind_amount: f64,
+    pub big_blind_amount: f64,
+    pub ante_amount: f64,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub hero_player_id: Option<u32>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub flags: Vec<String>,
+    pub players: Vec<OhhPlayer>,
+    pub rounds: Vec<OhhRound>,
+    pub pots: Vec<OhhPot>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum OhhGameType {
+    Holdem,
+    Omaha,
+    OmahaFiveCard,
+    Courchevel,
+    FiveCardDraw,
+    SevenCardStud,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct OhhBetLimit {
+    pub bet_type: OhhBetType,
+    /// The most a player may bet in the hand, 0 for no cap
+    #[serde(default)]
+    pub bet_cap: f64,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum OhhBetType {
+    #[serde(rename = "NL")]
+    NoLimit,
+    #[serde(rename = "PL")]
+    PotLimit,
+    #[serde(rename = "FL")]
+    FixedLimit,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct OhhPlayer {
+    pub id: u32,
+    /// Seat number, counting from 1
+    pub seat: u32,
+    pub name: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub display: Option<String>,
+    pub starting_stack: f64,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub player_bounty: Option<f64>,
+    #[serde(default, skip_serializing_if = "is_false")]
+    pub is_sitting_out: bool,
+}
+
+/// A street's new board cards and the actions taken on it
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct OhhRound {
+    pub id: u32,
+    pub street: Street,
+    #[serde(default, with = "card_strings", skip_serializing_if = "Vec::is_empty")]
+    pub cards: Vec<Card>,
+    pub actions: Vec<OhhAction>,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct OhhAction {
+    /// Position of the action in the hand, counting from 1
+    pub action_number: u32,
+    pub player_id: u32,
+    pub action: OhhActionKind,
+    /// The chips the action puts in
+    #[serde(default)]
+    pub amount: f64,
+    #[serde(default, skip_serializing_if = "is_false")]
+    pub is_allin: bool,
+    #[serde(default, with = "card_strings", skip_serializing_if = "Vec::is_empty")]
+    pub cards: Vec<Card>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum OhhActionKind {
+    #[serde(rename = "Dealt Cards")]
+    DealtCards,
+    #[serde(rename = "Mucks Cards")]
+    MucksCards,
+    #[serde(rename = "Shows Cards")]
+    ShowsCards,
+    #[serde(rename = "Post Ante")]
+    PostAnte,
+    #[serde(rename = "Post SB")]
+    PostSmallBlind,
+    #[serde(rename = "Post BB")]
+    PostBigBlind,
+    Straddle,
+    #[serde(rename = "Post Dead")]
+    PostDead,
+    #[serde(rename = "Post Extra Blind")]
+    PostExtraBlind,
+    Fold,
+    Check,
+    Bet,
+    Raise,
+    Call,
+    #[serde(rename = "Added Chips")]
+    AddedChips,
+    #[serde(rename = "Sits Down")]
+    SitsDown,
+    #[serde(rename = "Stands Up")]
+    StandsUp,
+    #[serde(rename = "Add To Stack")]
+    AddToStack,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct OhhPot {
+    /// 0 for the main pot and side pots after it
+    pub number: u32,
+    pub amount: f64,
+    #[serde(default)]
+    pub rake: f64,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub jackpot: Option<f64>,
+    pub player_wins: Vec<OhhWin>,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct OhhWin {
+    pub player_id: u32,
+    pub win_amount: f64,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub contributed_rake: Option<f64>,
+}
+
+// The document wraps the hand in an `ohh` object
+#[derive(Serialize, Deserialize)]
+struct Document<T> {
+    ohh: T,
+}
+
+impl OpenHandHistory {
+    /// Converts a completed hand, numbered `game_number` and started at the Unix epoch
+    ///
+    /// Each player's id is their seat index. Hole cards are dealt to every player, and a
+    /// hi-lo hand is written as Hold'em with the split in its pots.
+    pub fn from_hand(hand: &Hand, game_number: impl Into<String>) -> Result<Self, OhhError> {
+        if !hand.is_complete() {
+            return Err(TableError::HandNotComplete.into());
+        }
+        let stakes = hand.stakes();
+        let bet_limit = match hand.betting_structure() {
+            BettingStructure::NoLimit => OhhBetType::NoLimit,
+            BettingStructure::PotLimit => OhhBetType::PotLimit,
+            BettingStructure::FixedLimit { .. } => OhhBetType::FixedLimit,
+            structure @ BettingStructure::SpreadLimit { .. } => {
+                return Err(OhhError::UnsupportedStructure(structure));
+            }
+        };
+        let players = hand
+            .players()
+            .map(|(seat, player)| OhhPlayer {
+                id: seat as u32,
+                seat: seat as u32 + 1,
+                name: player.name().to_string(),
+                display: None,
+                starting_stack: amount(player.starting_stack()),
+                player_bounty: None,
+                is_sitting_out: false,
+            })
+            .collect();
+
+        Ok(Self {
+            spec_version: String::from(SPEC_VERSION),
+            site_name: String::from("pokeme"),
+            network_name: None,
+            internal_version: None,
+            tournament: false,
+            game_number: game_number.into(),
+            start_date_utc: start_date(UNIX_EPOCH),
+            table_name: None,
+            game_type: OhhGameType::Holdem,
+            bet_limit: OhhBetLimit {
+                bet_type: bet_limit,
+                bet_cap: 0.0,
+            },
+            table_size: hand.seat_count() as u32,
+            currency: None,
+            dealer_seat: hand.button() as u32 + 1,
+            small_blind_amount: amount(stakes.small_blind),
+            big_blind_amount: amount(stakes.big_blind),
+            ante_amount: amount(stakes.ante),
+            hero_player_id: None,
+            flags: Vec::new(),
+            players,
+            rounds: rounds(hand),
+            pots: pots(hand),
+        })
+    }
+
+    pub fn with_table_name(mut self, name: impl Into<String>) -> Self {
+        self.table_name = Some(name.into());
+        self
+    }
+
+    /// Sets when the hand started
+    pub fn with_start_time(mut self, time: SystemTime) -> Self {
+        self.start_date_utc = start_date(time);
+        self
+    }
+
+    /// Marks the player in `seat` as the one the history was recorded for
+    pub fn with_hero(mut self, seat: usize) -> Self {
+        self.hero_player_id = Some(seat as u32);
+        self
+    }
+
+    /// Reads a hand from an Open Hand History document and checks it
+    pub fn from_json(json: &str) -> Result<Self, OhhError> {
+        let document: Document<Self> =
+            serde_json::from_str(json).map_err(|error| OhhError::Json(error.to_string()))?;
+        document.ohh.validate()?;
+        Ok(document.ohh)
+    }
+
+    /// Writes the hand as an Open Hand History document
+    pub fn to_json(&self) -> Result<String, OhhError> {
+        serde_json::to_string_pretty(&Document { ohh: self })
+            .map_err(|error| OhhError::Json(error.to_string()))
+    }
+
+    /// Checks that the players, seats, actions and pots refer to each other consistently
+    pub fn validate(&self) -> Result<(), OhhError> {
+        if self.players.len() < 2 {
+            return Err(OhhError::NotEnoughPlayers(self.players.len()));
+        }
+        let seat_range = 1..=self.table_size;
+        if !seat_range.contains(&self.dealer_seat) {
+            return Err(OhhError::InvalidSeat(self.dealer_seat));
+        }
+        let mut ids = HashSet::new();
+        let mut seats = HashSet::new();
+        for player in &self.players {
+            if !ids.insert(player.id) {
+                return Err(OhhError::DuplicatePlayer(player.id));
+            }
+            if !seat_range.contains(&player.seat) || !seats.insert(player.seat) {
+                return Err(OhhError::InvalidSeat(player.seat));
+            }
+        }
+
+        let referenced = self
+            .hero_player_id
+            .into_iter()
+            .chain(
+                self.rounds
+                    .iter()
+                    .flat_map(|round| round.actions.iter().map(|action| action.player_id)),
+            )
+            .chain(
+                self.pots
+                    .iter()
+                    .flat_map(|pot| pot.player_wins.iter().map(|win| win.player_id)),
+            );
+        for id in referenced {
+            if !ids.contains(&id) {
+                return Err(OhhError::UnknownPlayer(id));
+            }
+        }
+
+        if self.pots.is_empty() {
+            return Err(OhhError::NoPots);
+        }
+        Ok(())
+    }
+}
+
+// The rounds of the hand from its events, with an id for each in order
+fn rounds(hand: &Hand) -> Vec<OhhRound> {
+    let mut stacks = vec![0; hand.seat_count()];
+    for (seat, player) in hand.players() {
+        stacks[seat] = player.starting_stack();
+    }
+    let mut rounds = vec![OhhRound {
+        id: 0,
+        street: Street::Preflop,
+        cards: Vec::new(),
+        actions: Vec::new(),
+    }];
+    let mut number = 0;
+    let mut push = |rounds: &mut Vec<OhhRound>, seat: usize, action, chips: Chips, cards| {
+        stacks[seat] -= chips;
+        number += 1;
+        if let Some(round) = rounds.last_mut() {
+            round.actions.push(OhhAction {
+                action_number: number,
+                player_id: seat as u32,
+                action,
+                amount: amount(chips),
+                is_allin: chips > 0 && stacks[seat] == 0,
+                cards,
+            });
+        }
+    };
+
+    let mut current_bet = 0;
+    let mut street_bets = vec![0; hand.seat_count()];
+    for event in hand.events() {
+        match *event {
+            Event::Started { .. } | Event::BetReturned { .. } | Event::PotAwarded(_) => {}
+            Event::AntePosted { seat, amount } => {
+                push(
+                    &mut rounds,
+                    seat,
+                    OhhActionKind::PostAnte,
+                    amount,
+                    Vec::new(),
+                );
+            }
+            Event::BlindPosted {
+                seat,
+                blind,
+                amount,
+            } => {
+                street_bets[seat] += amount;
+                let action = match blind {
+                    Blind::Small => OhhActionKind::PostSmallBlind,
+                    Blind::Big => {
+                        current_bet = hand.stakes().big_blind;
+                        OhhActionKind::PostBigBlind
+                    }
+                };
+                push(&mut rounds, seat, action, amount, Vec::new());
+            }
+            Event::HoleCardsDealt { seat, cards } => {
+                push(
+                    &mut rounds,
+                    seat,
+                    OhhActionKind::DealtCards,
+                    0,
+                    cards.to_vec(),
+                );
+            }
+            Event::Acted {
+                seat,
+                action,
+                amount,
+            } => {
+                street_bets[seat] += amount;
+                let total = street_bets[seat];
+                let kind = match action {
+                    Action::Fold => OhhActionKind::Fold,
+                    Action::Check => OhhActionKind::Check,
+                    Action::Call => OhhActionKind::Call,
+                    _ if total <= current_bet => OhhActionKind::Call,
+                    _ if current_bet == 0 => OhhActionKind::Bet,
+                    _ => OhhActionKind::Raise,
+                };
+                current_bet = current_bet.max(total);
+                push(&mut rounds, seat, kind, amount, Vec::new());
+            }
+            Event::BoardDealt { street, ref cards } => {
+                rounds.push(OhhRound {
+                    id: rounds.len() as u32,
+                    street,
+                    cards: cards.clone(),
+                    actions: Vec::new(),
+                });
+                street_bets.fill(0);
+                current_bet = 0;
+            }
+        }
+    }
+
+    if hand.street() == Street::Showdown {
+        rounds.push(OhhRound {
+            id: rounds.len() as u32,
+            street: Street::Showdown,
+            cards: Vec::new(),
+            actions: Vec::new(),
+        });
+        for (seat, player) in hand.players().filter(|(_, p)| !p.has_folded()) {
+            push(
+                &mut rounds,
+                seat,
+                OhhActionKind::ShowsCards,
+                0,
+                player.hole_cards().to_vec(),
+            );
+        }
+    }
+    rounds
+}
+
+// Each pot with what every winner took from it, both halves of a hi-lo split together
+fn pots(hand: &Hand) -> Vec<OhhPot> {
+    let mut pots: Vec<OhhPot> = hand
+        .pots()
+        .pots()
+        .iter()
+        .enumerate()
+        .map(|(number, pot)| OhhPot {
+            number: number as u32,
+            amount: amount(pot.amount()),
+            rake: 0.0,
+            jackpot: None,
+            player_wins: Vec::new(),
+        })
+        .collect();
+    for award in hand.awards() {
+        let Some(pot) = pots.get_mut(award.pot) else {
+            continue;
+        };
+        let player_id = award.seat as u32;
+        match pot
+            .player_wins
+            .iter_mut()
+            .find(|win| win.player_id == player_id)
+        {
+            Some(win) => win.win_amount += amount(award.amount),
+            None => pot.player_wins.push(OhhWin {
+                player_id,
+                win_amount: amount(award.amount),
+                contributed_rake: None,
+            }),
+        }
+    }
+    pots
+}
+
+fn amount(chips: Chips) -> f64 {
+    chips as f64
+}
+
+fn start_date(time: SystemTime) -> String {
+    let utc = Utc::new(time);
+    format!(
+        "{:04}-{:02}-{:02}T{:02}:{:02}:{:02}Z",
+        utc.year, utc.month, utc.day, utc.hour, utc.minute, utc.second
+    )
+}
+
+fn is_false(value: &bool) -> bool {
+    !*value
+}
+
+// Cards written as strings such as `"Ah"`, rather than as rank and suit
+mod card_strings {
+    use cards::Card;
+    use serde::de::Error;
+    use serde::{Deserialize, Deserializer, Serializer};
+
+    pub fn serialize<S: Serializer>(cards: &[Card], serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.collect_seq(cards.iter().map(Card::to_string))
+    }
+
+    pub fn deserialize<'de, D: Deserializer<'de>>(deserializer: D) -> Result<Vec<Card>, D::Error> {
+        Vec::<String>::deserialize(deserializer)?
+            .iter()
+            .map(|card| card.parse().map_err(D::Error::custom))
+            .collect()
+    }
+}
@@ -0,0 +1,685 @@
+use std::fmt;
+use std::io::{self, BufRead};
+
+use cards::Card;
+use cards::notation::{ParseCardError, parse_cards};
+
+use crate::Chips;
+use crate::hand::Street;
+
+/// Lines about the table rather than the hand, which are skipped wherever they appear
+const NOTICES: [&str; 9] = [
+    " joins the table at seat #",
+    " leaves the table",
+    " is disconnected",
+    " is connected",
+    " has timed out",
+    " has returned",
+    " will be allowed to play after the button",
+    " was removed from the table",
+    " said, \"",
+];
+
+/// A hand read from a text hand history
+///
+/// Names, seats and amounts are as written. Seats are numbered from 1, and in a game played
+/// for money, with a currency symbol before each amount, amounts are in cents.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct HandRecord {
+    /// The line the hand starts on
+    pub line: usize,
+    /// The site named at the start of the hand, such as `PokerStars`
+    pub site: String,
+    pub id: u64,
+    /// The game as written before the stakes, such as `Hold'em No Limit`
+    pub game: String,
+    pub small_blind: Chips,
+    pub big_blind: Chips,
+    /// The symbol money amounts are written with, if the hand was played for money
+    pub currency: Option<char>,
+    /// The date and time as written
+    pub date: String,
+    pub table: String,
+    pub max_seats: Option<usize>,
+    pub button: Option<usize>,
+    pub seats: Vec<SeatRecord>,
+    pub actions: Vec<ActionRecord>,
+    pub board: Vec<Card>,
+    pub total_pot: Option<Chips>,
+    pub rake: Option<Chips>,
+}
+
+impl HandRecord {
+    /// The seat of the player with `name`
+    pub fn seat(&self, name: &str) -> Option<&SeatRecord> {
+        self.seats.iter().find(|seat| seat.name == name)
+    }
+}
+
+/// A player sitting at the table when the hand was dealt
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SeatRecord {
+    pub seat: usize,
+    pub name: String,
+    pub stack: Chips,
+    /// The player's cards, if they were dealt to the writer of the history or shown
+    pub hole_cards: Vec<Card>,
+    pub sitting_out: bool,
+}
+
+/// Something a player did, or that happened to their chips, on a street
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ActionRecord {
+    /// The street the action happened on, `Showdown` once hands are shown
+    pub street: Street,
+    pub player: String,
+    pub action: RecordedAction,
+    pub all_in: bool,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum RecordedAction {
+    Ante(Chips),
+    SmallBlind(Chips),
+    BigBlind(Chips),
+    /// Both blinds posted together by a player coming into the game
+    DeadBlinds(Chips),
+    Fold,
+    Check,
+    Call(Chips),
+    Bet(Chips),
+    /// A raise of `by` chips, making the player's bet on the street `to`
+    Raise {
+        by: Chips,
+        to: Chips,
+    },
+    Show(Vec<Card>),
+    Muck,
+    /// A bet nobody called, handed back
+    Returned(Chips),
+    /// Chips won from a pot
+    Collected(Chips),
+}
+
+/// What is wrong with a line of a hand history
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum HistoryErrorKind {
+    /// The history could not be read
+    Io(io::ErrorKind),
+    /// Something other than a hand header where a hand should start
+    ExpectedHeader,
+    /// A hand header without an id or stakes
+    InvalidHeader,
+    /// A seat line without a number, name or stack
+    InvalidSeat,
+    InvalidAmount(String),
+    InvalidCards(ParseCardError),
+    /// A line that is not part of any hand history this reads
+    UnrecognizedLine(String),
+    /// The hand ends before its summary
+    Truncated,
+}
+
+impl fmt::Display for HistoryErrorKind {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            HistoryErrorKind::Io(kind) => write!(f, "the history could not be read: {kind}"),
+            HistoryErrorKind::ExpectedHeader => write!(f, "expected the start of a hand"),
+            HistoryErrorKind::InvalidHeader => write!(f, "the hand header is not valid"),
+            HistoryErrorKind::InvalidSeat => write!(f, "the seat is not valid"),
+            HistoryErrorKind::InvalidAmount(s) => write!(f, "invalid amount \"{s}\""),
+            HistoryErrorKind::InvalidCards(e) => write!(f, "invalid cards: {e}"),
+            HistoryErrorKind::UnrecognizedLine(s) => write!(f, "unrecognized line \"{s}\""),
+            HistoryErrorKind::Truncated => write!(f, "the hand ends before its summary"),
+        }
+    }
+}
+
+impl From<ParseCardError> for HistoryErrorKind {
+    fn from(e: ParseCardError) -> Self {
+        HistoryErrorKind::InvalidCards(e)
+    }
+}
+
+/// An error in a hand history and the line it is on, counting from 1
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ParseHistoryError {
+    pub line: usize,
+    pub kind: HistoryErrorKind,
+}
+
+impl fmt::Display for ParseHistoryError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "line {}: {}", self.line, self.kind)
+    }
+}
+
+impl std::error::Error for ParseHistoryError {}
+
+/// Reads the hands in a PokerStars-style text hand history one at a time
+///
+/// Only the hand being read is held in memory, so archives of any size can be read from a
+/// file. Hands are separated by blank lines or start straight after the last. A hand with an
+/// error is skipped up to the next hand header, so one bad hand does not stop the rest being
+/// read; reading stops at the first I/O error.
+///
+/// # Examples
+///
+/// ```
+/// use holdem::history::{HistoryReader, RecordedAction};
+///
+/// let history = "\
+/// PokerStars Hand #1:  Hold'em No Limit (1/2) - 2024/01/31 9:05:00 ET
+/// Table 'Home' 2-max Seat #1 is the button
+/// Seat 1: ann (100 in chips)
+/// Seat 2: ben (100 in chips)
+/// ann: posts small blind 1
+/// ben: posts big blind 2
+/// *** HOLE CARDS ***
+/// Dealt to ann [Ah Kd]
+/// ann: folds
+/// Uncalled bet (1) returned to ben
+/// ben collected 2 from pot
+/// *** SUMMARY ***
+/// Total pot 2 | Rake 0
+/// Seat 1: ann (button) (small blind) folded before Flop
+/// Seat 2: ben (big blind) collected (2)
+/// ";
+///
+/// let hands: Vec<_> = HistoryReader::new(history.as_bytes()).collect();
+/// let hand = hands[0].as_ref().unwrap();
+/// assert_eq!(hand.id, 1);
+/// assert_eq!(hand.seat("ann").unwrap().hole_cards[0].to_string(), "Ah");
+/// assert_eq!(hand.actions.last().unwrap().action, RecordedAction::Collected(2));
+/// ```
+#[derive(Debug)]
+pub struct HistoryReader<R> {
+    lines: io::Lines<R>,
+    line: usize,
+    // A header read while finishing the hand before it
+    pending: Option<(usize, String)>,
+    done: bool,
+}
+
+impl<R: BufRead> HistoryReader<R> {
+    pub fn new(reader: R) -> Self {
+        Self {
+            lines: reader.lines(),
+            line: 0,
+            pending: None,
+            done: false,
+        }
+    }
+
+    // The next line and its number, without any byte order mark or carriage return
+    fn read(&mut self) -> Option<Result<(usize, String), ParseHistoryError>> {
+        if self.done {
+            return None;
+        }
+        if let Some(pending) = self.pending.take() {
+            return Some(Ok(pending));
+        }
+        self.line += 1;
+        match self.lines.next() {
+            None => {
+                self.done = true;
+                None
+            }
+            Some(Err(e)) => {
+                self.done = true;
+                Some(Err(ParseHistoryError {
+                    line: self.line,
+                    kind: HistoryErrorKind::Io(e.kind()),
+                }))
+            }
+            Some(Ok(text)) => {
+                let text = text.strip_prefix('\u{feff}').unwrap_or(&text);
+                Some(Ok((self.line, text.trim_end_matches('\r').to_string())))
+            }
+        }
+    }
+
+    // Skips the rest of a hand, up to the next header
+    fn skip(&mut self) {
+        while let Some(Ok((line, text))) = self.read() {
+            if is_header(&text) {
+                self.pending = Some((line, text));
+                return;
+            }
+        }
+    }
+
+    fn fail(&mut self, line: usize, kind: HistoryErrorKind) -> ParseHistoryError {
+        self.skip();
+        ParseHistoryError { line, kind }
+    }
+}
+
+impl<R: BufRead> Iterator for HistoryReader<R> {
+    type Item = Result<HandRecord, ParseHistoryError>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let mut builder = loop {
+            let (line, text) = match self.read()? {
+                Ok(read) => read,
+                Err(e) => return Some(Err(e)),
+            };
+            if text.trim().is_empty() {
+                continue;
+            }
+            if !is_header(&text) {
+                return Some(Err(self.fail(line, HistoryErrorKind::ExpectedHeader)));
+            }
+            match Builder::new(line, &text) {
+                Ok(builder) => break builder,
+                Err(kind) => return Some(Err(self.fail(line, kind))),
+            }
+        };
+
+        let mut last = builder.record.line;
+        while let Some(read) = self.read() {
+            let (line, text) = match read {
+                Ok(read) => read,
+                Err(e) => return Some(Err(e)),
+            };
+            if text.trim().is_empty() {
+                break;
+            }
+            if is_header(&text) {
+                self.pending = Some((line, text));
+                break;
+            }
+            if let Err(kind) = builder.line(&text) {
+                return Some(Err(self.fail(line, kind)));
+            }
+            last = line;
+        }
+        Some(
+            builder
+                .finish()
+                .map_err(|kind| ParseHistoryError { line: last, kind }),
+        )
+    }
+}
+
+// Whether a line starts a hand, as in `PokerStars Hand #1: ...` or `Full Tilt Poker Game #1: ...`
+fn is_header(text: &str) -> bool {
+    [" Hand #", " Game #"].iter().any(|marker| {
+        text.split_once(marker).is_some_and(|(site, rest)| {
+            !site.contains(':') && rest.starts_with(|c: char| c.is_ascii_digit())
+        })
+    })
+}
+
+// Which part of the hand the lines being read belong to
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Section {
+    Seats,
+    Play,
+    Summary,
+}
+
+// A hand record filled in line by line
+struct Builder {
+    record: HandRecord,
+    street: Street,
+    section: Section,
+}
+
+impl Builder {
+    fn new(line: usize, header: &str) -> Result<Self, HistoryErrorKind> {
+        let invalid = || HistoryErrorKind::InvalidHeader;
+        let (site, rest) = header
+            .split_once(" Hand #")
+            .or_else(|| header.split_once(" Game #"))
+            .ok_or_else(invalid)?;
+        let (id, rest) = rest.split_once(':').ok_or_else(invalid)?;
+        let id = id.trim().parse().map_err(|_| invalid())?;
+
+        // `game (stakes) - date`, or `tournament - level (stakes) - date`
+        let parts: Vec<&str> = rest.trim().split(" - ").collect();
+        let date = if parts.len() > 1 {
+            parts[parts.len() - 1]
+        } else {
+            ""
+        };
+        let stakes = parts
+            .iter()
+            .rev()
+            .find_map(|part| {
+                let (_, inside) = part.rsplit_once('(')?;
+                let (inside, _) = inside.split_once(')')?;
+                inside.split_once('/')
+            })
+            .ok_or_else(invalid)?;
+        let small = stakes.0.trim();
+        let big = stakes.1.split_whitespace().next().unwrap_or_default();
+        let currency = small.chars().next().filter(|c| !c.is_ascii_digit());
+        let cents = currency.is_some();
+        let game = parts[0].split(" (").next().unwrap_or_default().trim();
+
+        Ok(Self {
+            record: HandRecord {
+                line,
+                site: site.trim().to_string(),
+                id,
+                game: game.to_string(),
+                small_blind: amount(small, cents)?,
+                big_blind: amount(big, cents)?,
+                currency,
+                date: date.trim().to_string(),
+                table: String::new(),
+                max_seats: None,
+                button: None,
+                seats: Vec::new(),
+                actions: Vec::new(),
+                board: Vec::new(),
+                total_pot: None,
+                rake: None,
+            },
+            street: Street::Preflop,
+            section: Section::Seats,
+        })
+    }
+
+    fn finish(self) -> Result<HandRecord, HistoryErrorKind> {
+        if self.section != Section::Summary {
+            return Err(HistoryErrorKind::Truncated);
+        }
+        Ok(self.record)
+    }
+
+    fn cents(&self) -> bool {
+        self.record.currency.is_some()
+    }
+
+    fn amount(&self, text: &str) -> Result<Chips, HistoryErrorKind> {
+        amount(text, self.cents())
+    }
+
+    fn line(&mut self, text: &str) -> Result<(), HistoryErrorKind> {
+        let unrecognized = || HistoryErrorKind::UnrecognizedLine(text.to_string());
+        if let Some(marker) = text.strip_prefix("*** ") {
+            return self.street_marker(marker).ok_or_else(unrecognized)?;
+        }
+        if NOTICES.iter().any(|notice| text.contains(notice)) {
+            return Ok(());
+        }
+        if self.section == Section::Summary {
+            return self.summary(text)?.ok_or_else(unrecognized);
+        }
+        if let Some(table) = text.strip_prefix("Table '") {
+            return self.table(table).ok_or_else(unrecognized);
+        }
+        if let Some(seat) = text.strip_prefix("Seat ")
+            && self.section == Section::Seats
+        {
+            return self.seat(seat);
+        }
+        if let Some(dealt) = text.strip_prefix("Dealt to ") {
+            return self.dealt(dealt).ok_or_else(unrecognized)?;
+        }
+        if let Some(returned) = text.strip_prefix("Uncalled bet (") {
+            let (amount, name) = returned
+                .split_once(") returned to ")
+                .ok_or_else(unrecognized)?;
+            let amount = self.amount(amount)?;
+            self.act(name, RecordedAction::Returned(amount), false);
+            return Ok(());
+        }
+        if let Some((name, rest)) = self.player(text, ": ") {
+            let name = name.to_string();
+            return self.action(&name, rest)?.ok_or_else(unrecognized);
+        }
+        if let Some((name, rest)) = self.player(text, " collected ") {
+            let name = name.to_string();
+            let amount = self.amount(rest.split_whitespace().next().unwrap_or_default())?;
+            self.act(&name, RecordedAction::Collected(amount), false);
+            return Ok(());
+        }
+        Err(unrecognized())
+    }
+
+    // `*** FLOP *** [Ah Kh Qh]` and the like
+    fn street_marker(&mut self, marker: &str) -> Option<Result<(), HistoryErrorKind>> {
+        let (name, cards) = marker.split_once(" ***")?;
+        let street = match name {
+            "HOLE CARDS" => Street::Preflop,
+            "FLOP" => Street::Flop,
+            "TURN" => Street::Turn,
+            "RIVER" => Street::River,
+            "SHOW DOWN" => Street::Showdown,
+            "SUMMARY" => {
+                self.section = Section::Summary;
+                return Some(Ok(()));
+            }
+            _ => return None,
+        };
+        self.section = Section::Play;
+        self.street = street;
+        if street.board_len() > 0 && street != Street::Showdown {
+            match bracketed(cards) {
+                Ok(board) => self.record.board = board,
+                Err(e) => return Some(Err(e)),
+            }
+        }
+        Some(Ok(()))
+    }
+
+    // `Table 'name' 6-max Seat #1 is the button`
+    fn table(&mut self, table: &str) -> Option<()> {
+        let (name, rest) = table.rsplit_once('\'')?;
+        self.record.table = name.to_string();
+        for word in rest.split_whitespace() {
+            if let Some(max) = word.strip_suffix("-max") {
+                self.record.max_seats = max.parse().ok();
+            }
+            if let Some(button) = word.strip_prefix('#') {
+                self.record.button = Some(button.parse().ok()?);
+            }
+        }
+        Some(())
+    }
+
+    // `Seat 1: name (100 in chips)`, possibly followed by a bounty or `is sitting out`
+    fn seat(&mut self, seat: &str) -> Result<(), HistoryErrorKind> {
+        let (number, rest) = seat.split_once(": ").ok_or(HistoryErrorKind::InvalidSeat)?;
+        let number = number.parse().map_err(|_| HistoryErrorKind::InvalidSeat)?;
+        // The stack is in the last parentheses that hold one, since names may have their own
+        let (name, stack) = rest
+            .rmatch_indices(" (")
+            .find_map(|(at, _)| {
+                let (stack, _) = rest[at + 2..].split_once(" in chips")?;
+                Some((&rest[..at], stack))
+            })
+            .ok_or(HistoryErrorKind::InvalidSeat)?;
+        self.record.seats.push(SeatRecord {
+            seat: number,
+            name: name.to_string(),
+            stack: self.amount(stack)?,
+            hole_cards: Vec::new(),
+            sitting_out: rest.ends_with("is sitting out"),
+        });
+        Ok(())
+    }
+
+    // `Dealt to name [Ah Kd]`, where other players' cards may be left out
+    fn dealt(&mut self, dealt: &str) -> Option<Result<(), HistoryErrorKind>> {
+        self.section = Section::Play;
+        let (name, cards) = match self.player(dealt, " [") {
+            Some((name, cards)) => (name.to_string(), cards),
+            None => return self.player(dealt, "").map(|_| Ok(())),
+        };
+        Some(bracketed(&format!("[{cards}")).map(|cards| self.show(&name, cards)))
+    }
+
+    // What follows `name: `
+    fn action(&mut self, name: &str, text: &str) -> Result<Option<()>, HistoryErrorKind> {
+        let (text, all_in) = match text.strip_suffix(" and is all-in") {
+            Some(text) => (text, true),
+            None => (text, false),
+        };
+        let (verb, rest) = text.split_once(' ').unwrap_or((text, ""));
+        let action = match (verb, rest) {
+            ("folds", "") => RecordedAction::Fold,
+            ("folds", cards) => {
+                let cards = bracketed(cards)?;
+                self.show(name, cards);
+                RecordedAction::Fold
+            }
+            ("checks", "") => RecordedAction::Check,
+            ("calls", amount) => RecordedAction::Call(self.amount(amount)?),
+            ("bets", amount) => RecordedAction::Bet(self.amount(amount)?),
+            ("raises", amounts) => {
+                let Some((by, to)) = amounts.split_once(" to ") else {
+                    return Ok(None);
+                };
+                RecordedAction::Raise {
+                    by: self.amount(by)?,
+                    to: self.amount(to)?,
+                }
+            }
+            ("posts", blind) => {
+                let Some((blind, amount)) = blind.rsplit_once(' ') else {
+                    return Ok(None);
+                };
+                let amount = self.amount(amount)?;
+                match blind {
+                    "small blind" => RecordedAction::SmallBlind(amount),
+                    "big blind" => RecordedAction::BigBlind(amount),
+                    "the ante" => RecordedAction::Ante(amount),
+                    "small & big blinds" => RecordedAction::DeadBlinds(amount),
+                    _ => return Ok(None),
+                }
+            }
+            ("shows", cards) => {
+                let cards = bracketed(cards)?;
+                self.show(name, cards.clone());
+                RecordedAction::Show(cards)
+            }
+            ("mucks", _) => RecordedAction::Muck,
+            ("doesn't", "show hand") | ("sits", "out") | ("is", "sitting out") => {
+                return Ok(Some(()));
+            }
+            _ => return Ok(None),
+        };
+        self.act(name, action, all_in);
+        Ok(Some(()))
+    }
+
+    fn summary(&mut self, text: &str) -> Result<Option<()>, HistoryErrorKind> {
+        if let Some(pot) = text.strip_prefix("Total pot ") {
+            let (total, rest) = pot.split_once(' ').unwrap_or((pot, ""));
+            self.record.total_pot = Some(self.amount(total)?);
+            if let Some((_, rake)) = rest.split_once("| Rake ") {
+                let rake = rake.split_whitespace().next().unwrap_or_default();
+                self.record.rake = Some(self.amount(rake)?);
+            }
+            return Ok(Some(()));
+        }
+        if let Some(board) = text.strip_prefix("Board ") {
+            self.record.board = bracketed(board)?;
+            return Ok(Some(()));
+        }
+        if let Some(seat) = text.strip_prefix("Seat ") {
+            // Cards shown or mucked at the showdown
+            let Some((number, rest)) = seat.split_once(": ") else {
+                return Ok(None);
+            };
+            let number: usize = number.parse().map_err(|_| HistoryErrorKind::InvalidSeat)?;
+            let cards = ["showed [", "mucked ["]
+                .iter()
+                .find_map(|marker| rest.split_once(marker).map(|(_, cards)| cards));
+            if let Some(cards) = cards {
+                let cards = bracketed(&format!("[{cards}"))?;
+                if let Some(seat) = self.record.seats.iter_mut().find(|s| s.seat == number)
+                    && seat.hole_cards.is_empty()
+                {
+                    seat.hole_cards = cards;
+                }
+            }
+            return Ok(Some(()));
+        }
+        Ok(None)
+    }
+
+    // The seated player whose name starts `text` followed by `separator`, and what follows;
+    // the longest name wins, as one name may start another
+    fn player<'t>(&self, text: &'t str, separator: &str) -> Option<(&'t str, &'t str)> {
+        self.record
+            .seats
+            .iter()
+            .filter_map(|seat| {
+                let rest = text.strip_prefix(seat.name.as_str())?;
+                let rest = rest.strip_prefix(separator)?;
+                Some((&text[..seat.name.len()], rest))
+            })
+            .max_by_key(|(name, _)| name.len())
+    }
+
+    fn act(&mut self, name: &str, action: RecordedAction, all_in: bool) {
+        self.section = Section::Play;
+        self.record.actions.push(ActionRecord {
+            street: self.street,
+            player: name.to_string(),
+            action,
+            all_in,
+        });
+    }
+
+    fn show(&mut self, name: &str, cards: Vec<Card>) {
+        if let Some(seat) = self.record.seats.iter_mut().find(|s| s.name == name) {
+            seat.hole_cards = cards;
+        }
+    }
+}
+
+// An amount such as `1,500`, or `$0.25` in cents when `cents` is set
+fn amount(text: &str, cents: bool) -> Result<Chips, HistoryErrorKind> {
+    let invalid = || HistoryErrorKind::InvalidAmount(text.to_string());
+    let digits = text
+        .trim()
+        .trim_start_matches(|c: char| !c.is_ascii_digit() && c != '.')
+        .replace(',', "");
+    let (whole, fraction) = digits.split_once('.').unwrap_or((&digits, ""));
+    let parse = |s: &str| -> Result<Chips, HistoryErrorKind> {
+        if s.is_empty() || !s.bytes().all(|b| b.is_ascii_digit()) {
+            return Err(invalid());
+        }
+        s.parse().map_err(|_| invalid())
+    };
+    if !cents {
+        if digits.contains('.') {
+            return Err(invalid());
+        }
+        return parse(whole);
+    }
+    let whole = if whole.is_empty() { 0 } else { parse(whole)? };
+    let fraction = match fraction.len() {
+        0 => 0,
+        1 => parse(fraction)? * 10,
+        2 => parse(fraction)?,
+        _ => return Err(invalid()),
+    };
+    whole
+        .checked_mul(100)
+        .and_then(|whole| whole.checked_add(fraction))
+        .ok_or_else(invalid)
+}
+
+// The cards in every bracketed group of `text`, as in `[Ah Kh Qh] [Jh]`, ignoring what follows
+fn bracketed(text: &str) -> Result<Vec<Card>, HistoryErrorKind> {
+    let mut cards = Vec::new();
+    let mut rest = text.trim_start();
+    while let Some(group) = rest.strip_prefix('[') {
+        let (group, after) = group
+            .split_once(']')
+            .ok_or_else(|| HistoryErrorKind::UnrecognizedLine(text.to_string()))?;
+        cards.extend(parse_cards(group)?);
+        rest = after.trim_start();
+    }
+    if cards.is_empty() {
+        return Err(HistoryErrorKind::UnrecognizedLine(text.to_string()));
+    }
+    Ok(cards)
+}
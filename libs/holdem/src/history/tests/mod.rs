@@ -0,0 +1,4 @@
+#[cfg(feature = "serde")]
+mod ohh;
+mod props;
+mod unit;
@@ -0,0 +1,293 @@
+use std::time::{Duration, UNIX_EPOCH};
+
+use super::super::*;
+use crate::betting::BettingStructure;
+use crate::hand::{Action, Street};
+use crate::table::TableError;
+use crate::test_support::{play, seeded, table};
+use cards::notation::parse_cards;
+use cards::{Card, Deck};
+
+fn cards(notation: &str) -> Vec<Card> {
+    parse_cards(notation).unwrap()
+}
+
+fn sample() -> OpenHandHistory {
+    OpenHandHistory::from_json(include_str!("golden/ohh_sample.json")).unwrap()
+}
+
+fn actions(round: &OhhRound) -> Vec<(u32, OhhActionKind, f64)> {
+    round
+        .actions
+        .iter()
+        .map(|action| (action.player_id, action.action, action.amount))
+        .collect()
+}
+
+#[test]
+fn test_read_sample_document() {
+    let history = sample();
+
+    assert_eq!(history.site_name, "Example Poker");
+    assert_eq!(history.game_type, OhhGameType::Holdem);
+    assert_eq!(history.bet_limit.bet_type, OhhBetType::NoLimit);
+    assert_eq!(history.currency.as_deref(), Some("USD"));
+    assert_eq!(history.big_blind_amount, 0.02);
+    assert_eq!(history.hero_player_id, Some(2));
+    assert_eq!(history.players.len(), 3);
+    assert_eq!(history.players[1].seat, 3);
+    assert_eq!(history.players[1].display, None);
+
+    let streets: Vec<Street> = history.rounds.iter().map(|round| round.street).collect();
+    assert_eq!(
+        streets,
+        [
+            Street::Preflop,
+            Street::Flop,
+            Street::Turn,
+            Street::River,
+            Street::Showdown
+        ]
+    );
+    assert_eq!(history.rounds[1].cards, cards("Ts 7h 2c"));
+    assert_eq!(history.rounds[0].actions[2].cards, cards("Ah Kd"));
+    assert!(history.rounds[1].actions[1].is_allin);
+    assert_eq!(history.pots[0].player_wins[0].win_amount, 3.81);
+}
+
+#[test]
+fn test_sample_survives_a_round_trip() {
+    let history = sample();
+    let json = history.to_json().unwrap();
+    assert_eq!(OpenHandHistory::from_json(&json).unwrap(), history);
+
+    // Cards are written as strings
+    let value: serde_json::Value = serde_json::from_str(&json).unwrap();
+    assert_eq!(
+        value["ohh"]["rounds"][0]["actions"][2]["cards"],
+        serde_json::json!(["Ah", "Kd"])
+    );
+}
+
+#[test]
+fn test_write_showdown_hand() {
+    let deck = seeded(7);
+    let hand = table(&[100, 100, 100]).start_hand(deck).unwrap();
+    let hand = play(
+        hand,
+        &[
+            (0, Action::Raise(6)),
+            (1, Action::Fold),
+            (2, Action::Raise(20)),
+            (0, Action::Call),
+            (2, Action::Bet(30)),
+            (0, Action::Call),
+            (2, Action::Check),
+            (0, Action::Check),
+            (2, Action::Check),
+            (0, Action::Check),
+        ],
+    );
+    let history = OpenHandHistory::from_hand(&hand, "7")
+        .unwrap()
+        .with_table_name("Home")
+        .with_hero(2)
+        .with_start_time(UNIX_EPOCH + Duration::from_secs(1_700_000_000));
+
+    assert_eq!(history.start_date_utc, "2023-11-14T22:13:20Z");
+    assert_eq!(history.table_name.as_deref(), Some("Home"));
+    assert_eq!(history.hero_player_id, Some(2));
+    assert_eq!(history.dealer_seat, 1);
+    assert_eq!(history.table_size, 3);
+    assert_eq!(history.players[2].starting_stack, 100.0);
+
+    let preflop = &history.rounds[0];
+    assert_eq!(
+        actions(preflop),
+        [
+            (1, OhhActionKind::PostSmallBlind, 1.0),
+            (2, OhhActionKind::PostBigBlind, 2.0),
+            (1, OhhActionKind::DealtCards, 0.0),
+            (2, OhhActionKind::DealtCards, 0.0),
+            (0, OhhActionKind::DealtCards, 0.0),
+            (0, OhhActionKind::Raise, 6.0),
+            (1, OhhActionKind::Fold, 0.0),
+            (2, OhhActionKind::Raise, 18.0),
+            (0, OhhActionKind::Call, 14.0),
+        ]
+    );
+    assert_eq!(
+        preflop.actions[2].cards,
+        hand.player(1).unwrap().hole_cards()
+    );
+    assert_eq!(
+        actions(&history.rounds[1]),
+        [
+            (2, OhhActionKind::Bet, 30.0),
+            (0, OhhActionKind::Call, 30.0),
+        ]
+    );
+    assert_eq!(history.rounds[1].cards, hand.board()[..3]);
+    assert_eq!(history.rounds[3].cards, hand.board()[4..]);
+
+    let numbers: Vec<u32> = history
+        .rounds
+        .iter()
+        .flat_map(|round| round.actions.iter().map(|action| action.action_number))
+        .collect();
+    assert_eq!(numbers, (1..=numbers.len() as u32).collect::<Vec<_>>());
+
+    let showdown = history.rounds.last().unwrap();
+    assert_eq!(showdown.id, 4);
+    assert_eq!(showdown.street, Street::Showdown);
+    assert_eq!(
+        actions(showdown),
+        [
+            (0, OhhActionKind::ShowsCards, 0.0),
+            (2, OhhActionKind::ShowsCards, 0.0),
+        ]
+    );
+
+    assert_eq!(history.pots.len(), 1);
+    assert_eq!(history.pots[0].amount, 101.0);
+    let won: f64 = history.pots[0]
+        .player_wins
+        .iter()
+        .map(|win| win.win_amount)
+        .sum();
+    assert_eq!(won, 101.0);
+
+    let json = history.to_json().unwrap();
+    assert_eq!(OpenHandHistory::from_json(&json).unwrap(), history);
+}
+
+#[test]
+fn test_write_all_in_side_pots() {
+    let hand = table(&[20, 50, 100]).start_hand(seeded(3)).unwrap();
+    let hand = play(
+        hand,
+        &[(0, Action::AllIn), (1, Action::AllIn), (2, Action::Call)],
+    );
+    let history = OpenHandHistory::from_hand(&hand, "3").unwrap();
+
+    let allins: Vec<u32> = history.rounds[0]
+        .actions
+        .iter()
+        .filter(|action| action.is_allin)
+        .map(|action| action.player_id)
+        .collect();
+    assert_eq!(allins, [0, 1]);
+    let pots: Vec<f64> = history.pots.iter().map(|pot| pot.amount).collect();
+    assert_eq!(pots, [60.0, 60.0]);
+    assert_eq!(history.pots[1].number, 1);
+}
+
+#[test]
+fn test_write_needs_a_completed_hand_in_a_supported_structure() {
+    let hand = table(&[100, 100]).start_hand(Deck::new()).unwrap();
+    assert_eq!(
+        OpenHandHistory::from_hand(&hand, "1").unwrap_err(),
+        OhhError::Table(TableError::HandNotComplete)
+    );
+
+    let spread = BettingStructure::SpreadLimit { min: 2, max: 10 };
+    let hand = table(&[100, 100])
+        .with_betting_structure(spread)
+        .start_hand(Deck::new())
+        .unwrap();
+    let hand = play(hand, &[(0, Action::Fold)]);
+    assert_eq!(
+        OpenHandHistory::from_hand(&hand, "1").unwrap_err(),
+        OhhError::UnsupportedStructure(spread)
+    );
+}
+
+#[test]
+fn test_read_rejects_missing_required_fields() {
+    let json = include_str!("golden/ohh_sample.json");
+    for field in ["spec_version", "game_number", "dealer_seat", "pots"] {
+        let mut value: serde_json::Value = serde_json::from_str(json).unwrap();
+        value["ohh"].as_object_mut().unwrap().remove(field);
+        let error = OpenHandHistory::from_json(&value.to_string()).unwrap_err();
+        assert!(
+            matches!(&error, OhhError::Json(message) if message.contains(field)),
+            "{error}"
+        );
+    }
+
+    // Optional fields can be left out
+    let mut value: serde_json::Value = serde_json::from_str(json).unwrap();
+    for field in ["network_name", "table_name", "currency", "hero_player_id"] {
+        value["ohh"].as_object_mut().unwrap().remove(field);
+    }
+    assert!(OpenHandHistory::from_json(&value.to_string()).is_ok());
+
+    assert!(matches!(
+        OpenHandHistory::from_json("{}"),
+        Err(OhhError::Json(_))
+    ));
+}
+
+#[test]
+fn test_read_rejects_bad_cards_and_names() {
+    let json = include_str!("golden/ohh_sample.json");
+    for (from, to) in [
+        ("\"Ts\"", "\"Tx\""),
+        ("\"Ah\", \"Kd\"", "\"AhKd\""),
+        ("\"Raise\"", "\"Raises\""),
+        ("\"Flop\"", "\"FLOP\""),
+        ("\"NL\"", "\"No Limit\""),
+    ] {
+        let error = OpenHandHistory::from_json(&json.replacen(from, to, 1)).unwrap_err();
+        assert!(matches!(error, OhhError::Json(_)), "{from}: {error}");
+    }
+}
+
+#[test]
+fn test_validate_checks_references() {
+    let mut history = sample();
+    history.players[2].id = 0;
+    assert_eq!(history.validate(), Err(OhhError::DuplicatePlayer(0)));
+
+    let mut history = sample();
+    history.players[2].seat = 1;
+    assert_eq!(history.validate(), Err(OhhError::InvalidSeat(1)));
+
+    let mut history = sample();
+    history.players[2].seat = 7;
+    assert_eq!(history.validate(), Err(OhhError::InvalidSeat(7)));
+
+    let mut history = sample();
+    history.dealer_seat = 0;
+    assert_eq!(history.validate(), Err(OhhError::InvalidSeat(0)));
+
+    let mut history = sample();
+    history.rounds[1].actions[0].player_id = 9;
+    assert_eq!(history.validate(), Err(OhhError::UnknownPlayer(9)));
+
+    let mut history = sample();
+    history.pots[0].player_wins[0].player_id = 4;
+    assert_eq!(history.validate(), Err(OhhError::UnknownPlayer(4)));
+
+    let mut history = sample();
+    history.hero_player_id = Some(3);
+    assert_eq!(history.validate(), Err(OhhError::UnknownPlayer(3)));
+
+    let mut history = sample();
+    history.players.truncate(1);
+    assert_eq!(history.validate(), Err(OhhError::NotEnoughPlayers(1)));
+
+    let mut history = sample();
+    history.pots.clear();
+    assert_eq!(history.validate(), Err(OhhError::NoPots));
+
+    // Checks run when reading, too
+    let json = sample()
+        .to_json()
+        .unwrap()
+        .replace("\"dealer_seat\": 1", "\"dealer_seat\": 9");
+    assert_eq!(
+        OpenHandHistory::from_json(&json).unwrap_err(),
+        OhhError::InvalidSeat(9)
+    );
+}
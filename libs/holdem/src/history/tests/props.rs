@@ -0,0 +1,85 @@
+use super::super::*;
+use crate::Chips;
+use crate::event::Event;
+use crate::hand::Hand;
+use crate::table::Stakes;
+use crate::test_support::{Game, arb_game, play_out, seated, seeded};
+use proptest::prelude::*;
+
+fn start(stacks: &[Chips], seed: u64) -> Hand {
+    seated(Stakes::new(5, 10).with_ante(1), stacks)
+        .with_hi_lo(seed.is_multiple_of(2))
+        .start_hand(seeded(seed))
+        .unwrap()
+}
+
+fn arb_games() -> impl Strategy<Value = Vec<Game>> {
+    prop::collection::vec(arb_game(), 1..4)
+}
+
+proptest! {
+    #[test]
+    fn exported_hands_read_back(games in arb_games()) {
+        let hands: Vec<Hand> = games
+            .iter()
+            .map(|(stacks, seed, choices)| play_out(start(stacks, *seed), choices))
+            .collect();
+        let history: Vec<String> = hands
+            .iter()
+            .enumerate()
+            .map(|(id, hand)| Export::new(hand, id as u64).unwrap().to_string())
+            .collect();
+
+        let records: Vec<HandRecord> = HistoryReader::new(history.join("\n\n\n").as_bytes())
+            .collect::<Result<_, _>>()
+            .unwrap();
+        prop_assert_eq!(records.len(), hands.len());
+        for (record, hand) in records.iter().zip(&hands) {
+            prop_assert_eq!(record.board.as_slice(), hand.board());
+            prop_assert_eq!(record.seats.len(), hand.players().count());
+            for (seat, player) in hand.players() {
+                let recorded = record.seat(player.name()).unwrap();
+                prop_assert_eq!(recorded.seat, seat + 1);
+                prop_assert_eq!(recorded.stack, player.starting_stack());
+                prop_assert_eq!(recorded.hole_cards.as_slice(), player.hole_cards());
+            }
+
+            // Every decision is recorded, and every chip in the pot paid out
+            let decisions = hand
+                .events()
+                .iter()
+                .filter(|event| matches!(event, Event::Acted { .. }))
+                .count();
+            let recorded = record
+                .actions
+                .iter()
+                .filter(|action| {
+                    matches!(
+                        action.action,
+                        RecordedAction::Fold
+                            | RecordedAction::Check
+                            | RecordedAction::Call(_)
+                            | RecordedAction::Bet(_)
+                            | RecordedAction::Raise { .. }
+                    )
+                })
+                .count();
+            prop_assert_eq!(recorded, decisions);
+            prop_assert_eq!(
+                record.total_pot,
+                Some(hand.awards().iter().map(|award| award.amount).sum::<Chips>())
+            );
+            let paid: Chips = record
+                .actions
+                .iter()
+                .filter_map(|action| match action.action {
+                    RecordedAction::Collected(amount) | RecordedAction::Returned(amount) => {
+                        Some(amount)
+                    }
+                    _ => None,
+                })
+                .sum();
+            prop_assert_eq!(paid, hand.pot());
+        }
+    }
+}
@@ -0,0 +1,446 @@
+use std::io;
+use std::time::{Duration, UNIX_EPOCH};
+
+use super::super::*;
+use crate::Chips;
+use crate::betting::BettingStructure;
+use crate::hand::{Action, Hand, Street};
+use crate::table::{Stakes, TableError};
+use crate::test_support::{deck, play, seated, seeded};
+use cards::notation::{ParseCardError, parse_cards};
+use cards::{Card, Deck};
+
+fn export(hand: &Hand, id: u64) -> Export<'_> {
+    Export::new(hand, id)
+        .unwrap()
+        .with_table_name("Golden")
+        .with_time(UNIX_EPOCH + Duration::from_secs(1_700_000_000))
+}
+
+fn assert_golden(actual: Export, expected: &str) {
+    let actual = actual.to_string();
+    assert_eq!(actual, expected, "\n{actual}");
+}
+
+#[test]
+fn test_export_three_way_showdown() {
+    let deck = deck("Qs Ah 7c Qd Kd 7d 2h Ks 8h 3c 2d Js 2s 4d");
+    let hand = seated(Stakes::new(1, 2), &[100, 100, 100])
+        .start_hand(deck)
+        .unwrap();
+    let hand = play(
+        hand,
+        &[
+            (0, Action::Raise(6)),
+            (1, Action::Call),
+            (2, Action::Call),
+            (1, Action::Check),
+            (2, Action::Bet(10)),
+            (0, Action::Fold),
+            (1, Action::Call),
+            (1, Action::Check),
+            (2, Action::Check),
+            (1, Action::Bet(20)),
+            (2, Action::Raise(60)),
+            (1, Action::Call),
+        ],
+    );
+
+    assert_golden(
+        export(&hand, 1),
+        include_str!("golden/three_way_showdown.txt"),
+    );
+}
+
+#[test]
+fn test_export_fold_to_the_big_blind_heads_up() {
+    let hand = seated(Stakes::new(1, 2), &[100, 100])
+        .start_hand(seeded(2))
+        .unwrap();
+    let hand = play(hand, &[(0, Action::Fold)]);
+
+    assert_golden(export(&hand, 2), include_str!("golden/heads_up_fold.txt"));
+}
+
+#[test]
+fn test_export_side_pots_with_antes_and_a_short_big_blind() {
+    let hand = seated(Stakes::new(5, 10).with_ante(1), &[200, 40, 8, 120])
+        .start_hand(seeded(7))
+        .unwrap();
+    let hand = play(
+        hand,
+        &[(3, Action::AllIn), (0, Action::Call), (1, Action::AllIn)],
+    );
+
+    assert_golden(export(&hand, 3), include_str!("golden/side_pots.txt"));
+}
+
+#[test]
+fn test_export_fixed_limit_hi_lo_split_for_a_hero() {
+    let deck = deck("As Kh 9c 2s Kd 9d 2h Kc 3d 5h 2d 8s 2c Jc");
+    let hand = seated(Stakes::new(1, 2), &[100, 100, 100])
+        .with_betting_structure(BettingStructure::fixed_limit(2))
+        .with_hi_lo(true)
+        .start_hand(deck)
+        .unwrap();
+    let hand = play(
+        hand,
+        &[
+            (0, Action::Call),
+            (1, Action::Call),
+            (2, Action::Check),
+            (1, Action::Bet(2)),
+            (2, Action::Raise(4)),
+            (0, Action::Fold),
+            (1, Action::Call),
+            (1, Action::Check),
+            (2, Action::Bet(4)),
+            (1, Action::Call),
+            (1, Action::Check),
+            (2, Action::Check),
+        ],
+    );
+
+    assert_golden(
+        export(&hand, 4).with_hero(1),
+        include_str!("golden/hi_lo_split.txt"),
+    );
+}
+
+#[test]
+fn test_export_needs_a_complete_hand() {
+    let hand = seated(Stakes::new(1, 2), &[100, 100])
+        .start_hand(Deck::new())
+        .unwrap();
+
+    assert_eq!(
+        Export::new(&hand, 1).unwrap_err(),
+        TableError::HandNotComplete
+    );
+}
+
+#[test]
+fn test_export_dates_are_written_in_utc() {
+    let hand = seated(Stakes::new(1, 2), &[100, 100])
+        .start_hand(Deck::new())
+        .unwrap();
+    let hand = play(hand, &[(0, Action::Fold)]);
+
+    let at = |seconds| {
+        let history = Export::new(&hand, 1)
+            .unwrap()
+            .with_time(UNIX_EPOCH + Duration::from_secs(seconds))
+            .to_string();
+        history.lines().next().unwrap().to_string()
+    };
+    assert!(at(0).ends_with(" - 1970/01/01 0:00:00 UTC"));
+    // A leap day, then the last second of a year
+    assert!(at(951_825_600).ends_with(" - 2000/02/29 12:00:00 UTC"));
+    assert!(at(1_735_689_599).ends_with(" - 2024/12/31 23:59:59 UTC"));
+}
+
+fn read(history: &str) -> Vec<Result<HandRecord, ParseHistoryError>> {
+    HistoryReader::new(history.as_bytes()).collect()
+}
+
+fn cards(notation: &str) -> Vec<Card> {
+    parse_cards(notation).unwrap()
+}
+
+fn error(line: usize, kind: HistoryErrorKind) -> Result<HandRecord, ParseHistoryError> {
+    Err(ParseHistoryError { line, kind })
+}
+
+#[test]
+fn test_read_exported_histories() {
+    let golden = [
+        include_str!("golden/three_way_showdown.txt"),
+        include_str!("golden/heads_up_fold.txt"),
+        include_str!("golden/side_pots.txt"),
+        include_str!("golden/hi_lo_split.txt"),
+    ];
+    let hands: Vec<HandRecord> = read(&golden.join("\n\n"))
+        .into_iter()
+        .collect::<Result<_, _>>()
+        .unwrap();
+
+    assert_eq!(hands.len(), 4);
+    assert_eq!(
+        hands.iter().map(|hand| hand.id).collect::<Vec<_>>(),
+        [1, 2, 3, 4]
+    );
+    let hand = &hands[0];
+    assert_eq!(hand.line, 1);
+    assert_eq!(hand.site, "PokerStars");
+    assert_eq!(hand.game, "Hold'em No Limit");
+    assert_eq!((hand.small_blind, hand.big_blind), (1, 2));
+    assert_eq!(hand.currency, None);
+    assert_eq!(hand.date, "2023/11/14 22:13:20 UTC");
+    assert_eq!(hand.table, "Golden");
+    assert_eq!(hand.max_seats, Some(3));
+    assert_eq!(hand.button, Some(1));
+    assert_eq!(hand.board, cards("Ks 8h 3c Js 4d"));
+    assert_eq!((hand.total_pot, hand.rake), (Some(158), Some(0)));
+    assert_eq!(
+        hand.seats[1],
+        SeatRecord {
+            seat: 2,
+            name: String::from("p1"),
+            stack: 100,
+            hole_cards: cards("Qs Qd"),
+            sitting_out: false,
+        }
+    );
+    assert_eq!(
+        hand.actions[2],
+        ActionRecord {
+            street: Street::Preflop,
+            player: String::from("p0"),
+            action: RecordedAction::Raise { by: 4, to: 6 },
+            all_in: false,
+        }
+    );
+    assert_eq!(
+        hand.actions.last().unwrap(),
+        &ActionRecord {
+            street: Street::Showdown,
+            player: String::from("p2"),
+            action: RecordedAction::Collected(158),
+            all_in: false,
+        }
+    );
+
+    // Side pots, antes and all-ins
+    let hand = &hands[2];
+    assert_eq!(hand.actions[0].action, RecordedAction::Ante(1));
+    assert_eq!(hand.actions[5].action, RecordedAction::BigBlind(7));
+    assert!(hand.actions[5].all_in);
+    let collected: Chips = hand
+        .actions
+        .iter()
+        .filter_map(|action| match action.action {
+            RecordedAction::Collected(amount) => Some(amount),
+            _ => None,
+        })
+        .sum();
+    assert_eq!(Some(collected), hand.total_pot);
+
+    // The hero's cards are dealt and the other player's come from the showdown
+    let hand = &hands[3];
+    assert_eq!(hand.game, "Hold'em Hi/Lo Limit");
+    assert_eq!(hand.seat("p1").unwrap().hole_cards, cards("As 2s"));
+    assert_eq!(hand.seat("p2").unwrap().hole_cards, cards("Kh Kd"));
+    assert!(hand.seat("p0").unwrap().hole_cards.is_empty());
+}
+
+#[test]
+fn test_read_a_cash_game_as_written_by_a_site() {
+    // Windows line endings, a byte order mark, table chatter and hands back to back
+    let history = "\u{feff}PokerStars Hand #230000000001:  Hold'em No Limit ($0.01/$0.02 USD) - 2024/01/31 9:05:00 ET\r
+Table 'Alcyone IV' 6-max Seat #4 is the button\r
+Seat 1: Mr. Jones (1) ($2.14 in chips)\r
+Seat 2: ann: the great ($1 in chips) is sitting out\r
+Seat 4: ben ($1,000.50 in chips)\r
+Mr. Jones (1): posts small blind $0.01\r
+ben: posts big blind $0.02\r
+*** HOLE CARDS ***\r
+Dealt to Mr. Jones (1) [Th 9h]\r
+Dealt to ben\r
+cat joins the table at seat #3\r
+Mr. Jones (1): raises $0.04 to $0.06\r
+ben said, \"nice\"\r
+ben: folds [2c 7d]\r
+Uncalled bet ($0.04) returned to Mr. Jones (1)\r
+Mr. Jones (1) collected $0.04 from pot\r
+Mr. Jones (1): doesn't show hand\r
+*** SUMMARY ***\r
+Total pot $0.04 | Rake $0\r
+Seat 1: Mr. Jones (1) (small blind) collected ($0.04)\r
+Seat 4: ben (button) (big blind) folded before Flop\r
+PokerStars Hand #230000000002: Tournament #99, $1+$0.10 USD Hold'em No Limit - Level II (15/30) - 2024/01/31 9:06:00 ET\r
+Table '99 1' 9-max Seat #1 is the button\r
+Seat 1: ann (1,500 in chips)\r
+Seat 2: ben (1,500 in chips)\r
+ann: posts small blind 15\r
+ben: posts big blind 30\r
+*** HOLE CARDS ***\r
+ann: raises 1,470 to 1,500 and is all-in\r
+ben: calls 1,470 and is all-in\r
+*** FLOP *** [Ah 7c 2d]\r
+*** TURN *** [Ah 7c 2d] [Kd]\r
+*** RIVER *** [Ah 7c 2d Kd] [5s]\r
+*** SHOW DOWN ***\r
+ann: shows [As Qs] (a pair of Aces)\r
+ben: shows [7h 7d] (three of a kind, Sevens)\r
+ben collected 3,000 from pot\r
+*** SUMMARY ***\r
+Total pot 3,000 | Rake 0\r
+Board [Ah 7c 2d Kd 5s]\r
+Seat 1: ann (button) (small blind) showed [As Qs] and lost with a pair of Aces\r
+Seat 2: ben (big blind) showed [7h 7d] and won (3,000) with three of a kind, Sevens\r
+";
+    let hands: Vec<HandRecord> = read(history).into_iter().collect::<Result<_, _>>().unwrap();
+
+    let cash = &hands[0];
+    assert_eq!(cash.id, 230_000_000_001);
+    assert_eq!(cash.currency, Some('$'));
+    assert_eq!((cash.small_blind, cash.big_blind), (1, 2));
+    assert_eq!(cash.table, "Alcyone IV");
+    assert_eq!(cash.button, Some(4));
+    assert_eq!(cash.date, "2024/01/31 9:05:00 ET");
+    let seats: Vec<(&str, Chips, bool)> = cash
+        .seats
+        .iter()
+        .map(|seat| (seat.name.as_str(), seat.stack, seat.sitting_out))
+        .collect();
+    assert_eq!(
+        seats,
+        [
+            ("Mr. Jones (1)", 214, false),
+            ("ann: the great", 100, true),
+            ("ben", 100_050, false)
+        ]
+    );
+    assert_eq!(cash.seats[0].hole_cards, cards("Th 9h"));
+    assert_eq!(cash.seats[2].hole_cards, cards("2c 7d"));
+    let actions: Vec<&RecordedAction> = cash.actions.iter().map(|a| &a.action).collect();
+    assert_eq!(
+        actions,
+        [
+            &RecordedAction::SmallBlind(1),
+            &RecordedAction::BigBlind(2),
+            &RecordedAction::Raise { by: 4, to: 6 },
+            &RecordedAction::Fold,
+            &RecordedAction::Returned(4),
+            &RecordedAction::Collected(4),
+        ]
+    );
+
+    let tournament = &hands[1];
+    assert_eq!(tournament.line, 22);
+    assert_eq!(tournament.currency, None);
+    assert_eq!((tournament.small_blind, tournament.big_blind), (15, 30));
+    assert_eq!(
+        tournament.game,
+        "Tournament #99, $1+$0.10 USD Hold'em No Limit"
+    );
+    assert_eq!(
+        tournament.actions[3],
+        ActionRecord {
+            street: Street::Preflop,
+            player: String::from("ben"),
+            action: RecordedAction::Call(1_470),
+            all_in: true,
+        }
+    );
+    assert_eq!(tournament.board, cards("Ah 7c 2d Kd 5s"));
+    assert_eq!(
+        tournament.actions[4].action,
+        RecordedAction::Show(cards("As Qs"))
+    );
+    assert_eq!(tournament.actions[4].street, Street::Showdown);
+}
+
+const FOLD: &str = "PokerStars Hand #7:  Hold'em No Limit (1/2) - 2024/01/31 9:05:00 ET
+Table 'Home' 2-max Seat #1 is the button
+Seat 1: ann (100 in chips)
+Seat 2: ben (100 in chips)
+ann: posts small blind 1
+ben: posts big blind 2
+*** HOLE CARDS ***
+ann: folds
+Uncalled bet (1) returned to ben
+ben collected 2 from pot
+*** SUMMARY ***
+Total pot 2 | Rake 0
+";
+
+#[test]
+fn test_read_errors_give_the_line_and_skip_the_hand() {
+    let bad_amount = FOLD.replace("posts big blind 2", "posts big blind 2x");
+    let history = format!("some notes\nmore notes\n\n{bad_amount}\n\n{FOLD}");
+    let hands = read(&history);
+
+    assert_eq!(hands.len(), 3);
+    assert_eq!(hands[0], error(1, HistoryErrorKind::ExpectedHeader));
+    assert_eq!(
+        hands[1],
+        error(9, HistoryErrorKind::InvalidAmount(String::from("2x")))
+    );
+    assert_eq!(hands[2].as_ref().unwrap().line, 18);
+}
+
+#[test]
+fn test_read_errors() {
+    let first = |history: String| read(&history).remove(0);
+
+    assert_eq!(
+        first(FOLD.replace("ann: folds", "ann: dances")),
+        error(
+            8,
+            HistoryErrorKind::UnrecognizedLine(String::from("ann: dances"))
+        )
+    );
+    assert_eq!(
+        first(FOLD.replace("ann: folds", "cat: folds")),
+        error(
+            8,
+            HistoryErrorKind::UnrecognizedLine(String::from("cat: folds"))
+        )
+    );
+    assert_eq!(
+        first(FOLD.replace("(1/2)", "")),
+        error(1, HistoryErrorKind::InvalidHeader)
+    );
+    assert_eq!(
+        first(FOLD.replace("ann (100 in chips)", "ann")),
+        error(3, HistoryErrorKind::InvalidSeat)
+    );
+    assert_eq!(
+        first(FOLD.replace("ann: folds", "ann: folds [Ah Kx]")),
+        error(
+            8,
+            HistoryErrorKind::InvalidCards(ParseCardError::InvalidSuit('x'))
+        )
+    );
+    // Money amounts need a currency symbol
+    assert_eq!(
+        first(FOLD.replace("bet (1)", "bet (0.50)")),
+        error(9, HistoryErrorKind::InvalidAmount(String::from("0.50")))
+    );
+    assert_eq!(
+        first(FOLD.replace("*** SUMMARY ***\nTotal pot 2 | Rake 0\n", "")),
+        error(10, HistoryErrorKind::Truncated)
+    );
+    assert_eq!(
+        first(FOLD.replace("\nann: folds", "\n\nann: folds")),
+        error(7, HistoryErrorKind::Truncated)
+    );
+}
+
+// Reads the bytes it holds, then fails
+struct Failing<'a>(&'a [u8]);
+
+impl io::Read for Failing<'_> {
+    fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+        if self.0.is_empty() {
+            return Err(io::Error::from(io::ErrorKind::ConnectionReset));
+        }
+        self.0.read(buf)
+    }
+}
+
+#[test]
+fn test_read_stops_at_an_io_error() {
+    let history = format!("{FOLD}\n\n{FOLD}");
+    let cut = history.len() - 20;
+    let reader = io::BufReader::new(Failing(&history.as_bytes()[..cut]));
+    let hands: Vec<_> = HistoryReader::new(reader).collect();
+
+    assert_eq!(hands.len(), 2);
+    assert!(hands[0].is_ok());
+    assert_eq!(
+        hands[1],
+        error(26, HistoryErrorKind::Io(io::ErrorKind::ConnectionReset))
+    );
+}
@@ -0,0 +1,41 @@
+//! A Texas Hold'em engine built on the `cards` crate
+//!
+//! A `Table` seats players and moves the button between hands. Each hand is a `Hand`, a pure
+//! state machine: applying a player's action returns the next state or explains why the action
+//! is not allowed, leaving the current state untouched.
+//!
+//! Every hand records what happens in it as a stream of `Event`s, from which a `Replay`
+//! rebuilds each state of the hand given the deck it was dealt from. Completed hands can be
+//! exported as PokerStars text hand histories, and histories read back as hand records. With
+//! the `serde` feature, hands can also be written and read as Open Hand History JSON.
+//!
+//! A stats `Tracker` builds the usual HUD stats for each player from played or imported
+//! hands, and with the `storage` feature a `Store` keeps hands and stats in SQLite.
+//!
+//! Bots implement `Strategy`, choosing actions from the `View` a player has of the hand, and
+//! an `Arena` plays them against each other to measure their win rates.
+//!
+//! The `cfr` module solves toy poker games such as Kuhn poker and Leduc hold'em by
+//! counterfactual regret minimization.
+//!
+//! A `Tournament` runs a sit-and-go or multi-table tournament over several tables: its blind
+//! schedule and breaks, knockouts and finishing places, table balancing and payouts.
+
+pub mod arena;
+pub mod betting;
+pub mod cfr;
+pub mod event;
+pub mod hand;
+pub mod history;
+pub mod pot;
+pub mod stats;
+#[cfg(feature = "storage")]
+pub mod storage;
+pub mod strategy;
+pub mod table;
+#[cfg(test)]
+pub(crate) mod test_support;
+pub mod tournament;
+
+/// An amount of chips
+pub type Chips = u64;
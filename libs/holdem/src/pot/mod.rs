@@ -0,0 +1,313 @@
+use cards::eval::{HandValue, LowValue};
+use cards::{Card, Suit};
+
+use crate::Chips;
+
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
+#[cfg(test)]
+mod tests;
+
+/// What one player put into the pot over a hand
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Contribution {
+    pub seat: usize,
+    pub amount: Chips,
+    /// A player who folded leaves their chips in the pot but cannot win any of it
+    pub folded: bool,
+}
+
+/// The main pot or a side pot, and the players who can win it
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Pot {
+    amount: Chips,
+    eligible: Vec<usize>,
+}
+
+impl Pot {
+    pub fn amount(&self) -> Chips {
+        self.amount
+    }
+
+    /// The seats still in the hand that put in enough to win this pot, in seat order
+    pub fn eligible(&self) -> &[usize] {
+        &self.eligible
+    }
+}
+
+/// How chips that do not divide evenly between tied winners are handed out
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub enum OddChipRule {
+    /// One chip at a time to the winners closest to the left of the button
+    #[default]
+    LeftOfButton,
+    /// One chip at a time to the winners holding the highest card, with ties in rank broken
+    /// by suit: spades, then hearts, diamonds and clubs
+    SuitOrder,
+}
+
+/// A player still in the hand when the pots are awarded
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Contender {
+    pub seat: usize,
+    pub high: HandValue,
+    /// The player's qualifying low in a hi-lo game, `None` otherwise
+    pub low: Option<LowValue>,
+    /// The player's own cards, which decide odd chips by suit order
+    pub hole_cards: Vec<Card>,
+}
+
+/// What a share of a pot was won with
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub enum Share {
+    /// Everyone else able to win the pot folded
+    Uncontested,
+    /// The best hand, or the high half of a hi-lo pot
+    High(HandValue),
+    /// The low half of a hi-lo pot
+    Low(LowValue),
+}
+
+/// Chips won from a pot at the end of a hand
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct Award {
+    pub seat: usize,
+    /// The pot the chips come from, 0 for the main pot and side pots after it
+    pub pot: usize,
+    pub amount: Chips,
+    pub share: Share,
+}
+
+/// The main pot and side pots built from what each player put in
+///
+/// Each all-in amount caps a pot: every player contributes up to that amount to it, and only
+/// players who put in at least that much and did not fold can win it. Chips that no other
+/// player matched are not part of any pot and go back to the player who bet them.
+///
+/// # Examples
+///
+/// ```
+/// use holdem::pot::{Contribution, Pots};
+///
+/// // Seat 1 is all in for 50, seat 2 calls 200 of seat 0's 300
+/// let pots = Pots::new(&[
+///     Contribution { seat: 0, amount: 300, folded: false },
+///     Contribution { seat: 1, amount: 50, folded: false },
+///     Contribution { seat: 2, amount: 200, folded: false },
+/// ]);
+///
+/// assert_eq!(pots.main().unwrap().amount(), 150);
+/// assert_eq!(pots.side_pots()[0].amount(), 300);
+/// assert_eq!(pots.side_pots()[0].eligible(), [0, 2]);
+/// assert_eq!(pots.uncalled(), Some((0, 100)));
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Pots {
+    pots: Vec<Pot>,
+    uncalled: Option<(usize, Chips)>,
+}
+
+impl Pots {
+    pub fn new(contributions: &[Contribution]) -> Self {
+        let mut amounts: Vec<Chips> = contributions.iter().map(|c| c.amount).collect();
+
+        // The largest contribution only counts up to the next largest
+        let mut uncalled = None;
+        if let Some(top) = (0..amounts.len()).max_by_key(|&i| amounts[i]) {
+            let matched = (0..amounts.len())
+                .filter(|&i| i != top)
+                .map(|i| amounts[i])
+                .max()
+                .unwrap_or(0);
+            if amounts[top] > matched {
+                uncalled = Some((contributions[top].seat, amounts[top] - matched));
+                amounts[top] = matched;
+            }
+        }
+
+        let mut levels: Vec<Chips> = contributions
+            .iter()
+            .zip(&amounts)
+            .filter(|(c, _)| !c.folded)
+            .map(|(_, &amount)| amount)
+            .collect();
+        levels.push(amounts.iter().copied().max().unwrap_or(0));
+        levels.sort_unstable();
+        levels.dedup();
+
+        let mut pots: Vec<Pot> = Vec::new();
+        let mut floor = 0;
+        for level in levels.into_iter().filter(|&level| level > 0) {
+            let amount: Chips = amounts
+                .iter()
+                .map(|&amount| amount.min(level) - amount.min(floor))
+                .sum();
+            let mut eligible: Vec<usize> = contributions
+                .iter()
+                .zip(&amounts)
+                .filter(|&(c, &amount)| !c.folded && amount >= level)
+                .map(|(c, _)| c.seat)
+                .collect();
+            // Dead money below every live player's contribution, as when everyone still in
+            // put in nothing, goes to all of them
+            if eligible.is_empty() && pots.is_empty() {
+                eligible = contributions
+                    .iter()
+                    .filter(|c| !c.folded)
+                    .map(|c| c.seat)
+                    .collect();
+            }
+            eligible.sort_unstable();
+            floor = level;
+            match pots.last_mut() {
+                // Chips above every live player's contribution stay with the pot below
+                Some(previous) if eligible.is_empty() || previous.eligible == eligible => {
+                    previous.amount += amount
+                }
+                _ => pots.push(Pot { amount, eligible }),
+            }
+        }
+
+        Self { pots, uncalled }
+    }
+
+    /// The main pot, which every player still in the hand can win
+    pub fn main(&self) -> Option<&Pot> {
+        self.pots.first()
+    }
+
+    /// Pots beyond the main pot, from the smallest all-in up
+    pub fn side_pots(&self) -> &[Pot] {
+        self.pots.get(1..).unwrap_or_default()
+    }
+
+    /// The main pot followed by the side pots
+    pub fn pots(&self) -> &[Pot] {
+        &self.pots
+    }
+
+    /// The seat and amount of a bet nobody matched, returned to the player who made it
+    pub fn uncalled(&self) -> Option<(usize, Chips)> {
+        self.uncalled
+    }
+
+    /// Chips in the pots, not counting any uncalled bet
+    pub fn total(&self) -> Chips {
+        self.pots.iter().map(|pot| pot.amount).sum()
+    }
+
+    /// Awards every pot to the best of its eligible contenders
+    ///
+    /// A pot with a single eligible seat goes to that seat uncontested, whether or not it is
+    /// among `contenders`. When any eligible contender has a low, the pot is split into a high
+    /// half and a low half, with the odd chip going to the high half; otherwise the best high
+    /// hand takes all of it. Tied hands share equally, with leftover chips handed out by
+    /// `rule` using the button and number of seats at the table.
+    ///
+    /// Every seat eligible for a contested pot must be among `contenders`.
+    pub fn award(
+        &self,
+        contenders: &[Contender],
+        button: usize,
+        seats: usize,
+        rule: OddChipRule,
+    ) -> Vec<Award> {
+        let mut awards = Vec::new();
+        for (index, pot) in self.pots.iter().enumerate() {
+            let eligible: Vec<&Contender> = contenders
+                .iter()
+                .filter(|c| pot.eligible.contains(&c.seat))
+                .collect();
+            if let [seat] = pot.eligible[..] {
+                awards.push(Award {
+                    seat,
+                    pot: index,
+                    amount: pot.amount,
+                    share: Share::Uncontested,
+                });
+                continue;
+            }
+
+            let lows: Vec<(&Contender, LowValue)> =
+                eligible.iter().filter_map(|c| Some((*c, c.low?))).collect();
+            let low_half = if lows.is_empty() { 0 } else { pot.amount / 2 };
+
+            let Some(best) = eligible.iter().map(|c| c.high).max() else {
+                continue;
+            };
+            let high_winners: Vec<&Contender> = eligible
+                .iter()
+                .filter(|c| c.high == best)
+                .copied()
+                .collect();
+            for (seat, amount) in split(pot.amount - low_half, &high_winners, button, seats, rule) {
+                awards.push(Award {
+                    seat,
+                    pot: index,
+                    amount,
+                    share: Share::High(best),
+                });
+            }
+
+            if let Some(best_low) = lows.iter().map(|&(_, low)| low).max() {
+                let low_winners: Vec<&Contender> = lows
+                    .iter()
+                    .filter(|&&(_, low)| low == best_low)
+                    .map(|&(c, _)| c)
+                    .collect();
+                for (seat, amount) in split(low_half, &low_winners, button, seats, rule) {
+                    awards.push(Award {
+                        seat,
+                        pot: index,
+                        amount,
+                        share: Share::Low(best_low),
+                    });
+                }
+            }
+        }
+        awards
+    }
+}
+
+// Divides chips equally between winners, handing leftover chips out one at a time by `rule`
+fn split(
+    amount: Chips,
+    winners: &[&Contender],
+    button: usize,
+    seats: usize,
+    rule: OddChipRule,
+) -> Vec<(usize, Chips)> {
+    let mut order: Vec<&Contender> = winners.to_vec();
+    let left_of_button = |seat: usize| (seat + seats - button % seats - 1) % seats;
+    match rule {
+        OddChipRule::LeftOfButton => order.sort_by_key(|c| left_of_button(c.seat)),
+        OddChipRule::SuitOrder => order.sort_by_key(|c| {
+            let high = c.hole_cards.iter().map(|&card| card_order(card)).max();
+            (std::cmp::Reverse(high), left_of_button(c.seat))
+        }),
+    }
+
+    let count = order.len() as Chips;
+    let odd = amount % count;
+    order
+        .iter()
+        .enumerate()
+        .map(|(i, c)| (c.seat, amount / count + Chips::from((i as Chips) < odd)))
+        .collect()
+}
+
+// Ranks a card by poker value, then by suit with spades highest
+fn card_order(card: Card) -> (u8, u8) {
+    let suit = match card.suit {
+        Some(Suit::Spades) => 4,
+        Some(Suit::Hearts) => 3,
+        Some(Suit::Diamonds) => 2,
+        Some(Suit::Clubs) => 1,
+        None => 0,
+    };
+    (card.rank.poker_value(), suit)
+}
@@ -0,0 +1,121 @@
+use super::super::*;
+use cards::Deck;
+use cards::eval::{evaluate, evaluate_low};
+use proptest::prelude::*;
+
+// Contributions from two to nine seats, at least one of them still in the hand
+fn arb_contributions() -> impl Strategy<Value = Vec<Contribution>> {
+    prop::collection::vec((0 as Chips..1_000, any::<bool>()), 2..=9).prop_map(|seats| {
+        seats
+            .into_iter()
+            .enumerate()
+            .map(|(seat, (amount, folded))| Contribution {
+                seat,
+                amount,
+                folded: folded && seat > 0,
+            })
+            .collect()
+    })
+}
+
+// Deals every seat two cards from a shuffled deck and a shared board
+fn arb_contenders(seats: usize, hi_lo: bool) -> impl Strategy<Value = Vec<Contender>> {
+    let deck = Deck::new().cards().to_vec();
+    Just(deck).prop_shuffle().prop_map(move |cards| {
+        let board = &cards[2 * seats..2 * seats + 5];
+        (0..seats)
+            .map(|seat| {
+                let hole_cards = cards[2 * seat..2 * seat + 2].to_vec();
+                let all: Vec<Card> = hole_cards.iter().chain(board).copied().collect();
+                Contender {
+                    seat,
+                    high: evaluate(&all),
+                    low: evaluate_low(&all).filter(|_| hi_lo),
+                    hole_cards,
+                }
+            })
+            .collect()
+    })
+}
+
+fn arb_showdown() -> impl Strategy<Value = (Vec<Contribution>, Vec<Contender>, usize, bool)> {
+    (arb_contributions(), any::<bool>(), any::<bool>()).prop_flat_map(
+        |(contributions, hi_lo, suit_order)| {
+            let seats = contributions.len();
+            (
+                Just(contributions),
+                arb_contenders(seats, hi_lo),
+                0..seats,
+                Just(suit_order),
+            )
+        },
+    )
+}
+
+proptest! {
+    #[test]
+    fn pots_hold_every_chip(contributions in arb_contributions()) {
+        let pots = Pots::new(&contributions);
+
+        let put_in: Chips = contributions.iter().map(|c| c.amount).sum();
+        let returned = pots.uncalled().map_or(0, |(_, chips)| chips);
+        prop_assert_eq!(pots.total() + returned, put_in);
+    }
+
+    #[test]
+    fn only_live_players_are_eligible(contributions in arb_contributions()) {
+        let pots = Pots::new(&contributions);
+
+        for pot in pots.pots() {
+            prop_assert!(!pot.eligible().is_empty());
+            for seat in pot.eligible() {
+                prop_assert!(!contributions[*seat].folded);
+            }
+        }
+        // Each side pot is contested by fewer players than the pot before it
+        for pair in pots.pots().windows(2) {
+            prop_assert!(pair[1].eligible().len() < pair[0].eligible().len());
+        }
+    }
+
+    #[test]
+    fn awards_conserve_chips(
+        (contributions, mut contenders, button, suit_order) in arb_showdown(),
+    ) {
+        contenders.retain(|c| !contributions[c.seat].folded);
+        let rule = if suit_order { OddChipRule::SuitOrder } else { OddChipRule::LeftOfButton };
+        let pots = Pots::new(&contributions);
+        let awards = pots.award(&contenders, button, contributions.len(), rule);
+
+        let awarded: Chips = awards.iter().map(|a| a.amount).sum();
+        prop_assert_eq!(awarded, pots.total());
+        for (index, pot) in pots.pots().iter().enumerate() {
+            let from_pot: Chips = awards.iter().filter(|a| a.pot == index).map(|a| a.amount).sum();
+            prop_assert_eq!(from_pot, pot.amount());
+            for award in awards.iter().filter(|a| a.pot == index) {
+                prop_assert!(pot.eligible().contains(&award.seat));
+            }
+        }
+    }
+
+    #[test]
+    fn tied_winners_differ_by_at_most_a_chip(
+        (contributions, mut contenders, button, _) in arb_showdown(),
+    ) {
+        contenders.retain(|c| !contributions[c.seat].folded);
+        let pots = Pots::new(&contributions);
+        let awards = pots.award(&contenders, button, contributions.len(), OddChipRule::default());
+
+        for index in 0..pots.pots().len() {
+            for share in awards.iter().filter(|a| a.pot == index).map(|a| a.share) {
+                let amounts: Vec<Chips> = awards
+                    .iter()
+                    .filter(|a| a.pot == index && a.share == share)
+                    .map(|a| a.amount)
+                    .collect();
+                let (low, high) = (amounts.iter().min(), amounts.iter().max());
+                prop_assert!(high.unwrap() - low.unwrap() <= 1);
+            }
+        }
+    }
+}
@@ -0,0 +1,212 @@
+use super::super::*;
+use cards::eval::{evaluate, evaluate_low};
+use cards::notation::parse_cards;
+
+fn put(seat: usize, amount: Chips) -> Contribution {
+    Contribution {
+        seat,
+        amount,
+        folded: false,
+    }
+}
+
+fn folded(seat: usize, amount: Chips) -> Contribution {
+    Contribution {
+        seat,
+        amount,
+        folded: true,
+    }
+}
+
+fn contender(seat: usize, hole: &str, board: &str) -> Contender {
+    let hole_cards = parse_cards(hole).unwrap();
+    let cards: Vec<Card> = hole_cards
+        .iter()
+        .chain(&parse_cards(board).unwrap())
+        .copied()
+        .collect();
+    Contender {
+        seat,
+        high: evaluate(&cards),
+        low: evaluate_low(&cards),
+        hole_cards,
+    }
+}
+
+fn won(awards: &[Award]) -> Vec<(usize, usize, Chips)> {
+    awards.iter().map(|a| (a.seat, a.pot, a.amount)).collect()
+}
+
+fn amounts(pots: &Pots) -> Vec<Chips> {
+    pots.pots().iter().map(Pot::amount).collect()
+}
+
+#[test]
+fn test_single_pot_without_all_ins() {
+    let pots = Pots::new(&[put(0, 100), put(1, 100), put(2, 100)]);
+
+    assert_eq!(amounts(&pots), [300]);
+    assert_eq!(pots.main().unwrap().eligible(), [0, 1, 2]);
+    assert!(pots.side_pots().is_empty());
+    assert_eq!(pots.uncalled(), None);
+    assert_eq!(pots.total(), 300);
+}
+
+#[test]
+fn test_side_pot_for_each_all_in_amount() {
+    let pots = Pots::new(&[put(0, 100), put(1, 25), put(2, 100), put(3, 50)]);
+
+    assert_eq!(amounts(&pots), [100, 75, 100]);
+    let eligible: Vec<&[usize]> = pots.pots().iter().map(Pot::eligible).collect();
+    assert_eq!(eligible, [&[0, 1, 2, 3][..], &[0, 2, 3], &[0, 2]]);
+}
+
+#[test]
+fn test_folded_chips_stay_in_the_pot() {
+    let pots = Pots::new(&[folded(0, 100), put(1, 200), put(2, 200)]);
+
+    assert_eq!(amounts(&pots), [500]);
+    assert_eq!(pots.main().unwrap().eligible(), [1, 2]);
+}
+
+#[test]
+fn test_folded_player_above_an_all_in() {
+    // Seat 1 folded after putting in more than seat 0's all-in
+    let pots = Pots::new(&[put(0, 30), folded(1, 100), put(2, 100)]);
+
+    assert_eq!(amounts(&pots), [90, 140]);
+    assert_eq!(pots.side_pots()[0].eligible(), [2]);
+    assert_eq!(pots.uncalled(), None);
+}
+
+#[test]
+fn test_dead_money_goes_to_players_who_put_in_nothing() {
+    let pots = Pots::new(&[put(0, 0), folded(1, 1), folded(2, 1)]);
+
+    assert_eq!(amounts(&pots), [2]);
+    assert_eq!(pots.main().unwrap().eligible(), [0]);
+
+    let board = "Ah Kc Qd 9s 8h";
+    let awards = pots.award(
+        &[contender(0, "2c 3h", board)],
+        0,
+        3,
+        OddChipRule::default(),
+    );
+    assert_eq!(won(&awards), [(0, 0, 2)]);
+}
+
+#[test]
+fn test_uncalled_bet_is_returned() {
+    let pots = Pots::new(&[put(0, 50), folded(1, 20), put(2, 10)]);
+
+    assert_eq!(pots.uncalled(), Some((0, 30)));
+    assert_eq!(amounts(&pots), [30, 20]);
+    assert_eq!(pots.total() + 30, 80);
+}
+
+#[test]
+fn test_best_hand_wins_each_pot() {
+    let board = "Jc 9d 6h 3s 2c";
+    let pots = Pots::new(&[put(0, 300), put(1, 50), put(2, 200)]);
+    let contenders = [
+        contender(0, "Qs Qd", board),
+        contender(1, "As Ad", board),
+        contender(2, "Ks Kd", board),
+    ];
+
+    let awards = pots.award(&contenders, 0, 3, OddChipRule::LeftOfButton);
+    assert_eq!(won(&awards), [(1, 0, 150), (2, 1, 300)]);
+    assert_eq!(pots.uncalled(), Some((0, 100)));
+    assert_eq!(awards[1].share, Share::High(contenders[2].high));
+}
+
+#[test]
+fn test_single_eligible_pot_is_uncontested() {
+    let pots = Pots::new(&[put(0, 30), folded(1, 100), put(2, 100)]);
+    let board = "Kc 9d 5h 3s 2c";
+    let contenders = [contender(0, "As Ad", board), contender(2, "Qs Qd", board)];
+
+    let awards = pots.award(&contenders, 0, 3, OddChipRule::LeftOfButton);
+    assert_eq!(won(&awards), [(0, 0, 90), (2, 1, 140)]);
+    assert_eq!(awards[1].share, Share::Uncontested);
+}
+
+#[test]
+fn test_odd_chip_goes_left_of_the_button() {
+    let board = "As Ks Qh Jd Th";
+    let pots = Pots::new(&[put(0, 2), folded(1, 1), put(2, 2)]);
+    let contenders = [contender(0, "2c 3c", board), contender(2, "2d 3d", board)];
+
+    // Seat 2 is closer to the left of a button on seat 0
+    let awards = pots.award(&contenders, 0, 3, OddChipRule::LeftOfButton);
+    assert_eq!(won(&awards), [(2, 0, 3), (0, 0, 2)]);
+
+    // With the button on seat 1, seat 2 is first to the left
+    let awards = pots.award(&contenders, 1, 3, OddChipRule::LeftOfButton);
+    assert_eq!(won(&awards), [(2, 0, 3), (0, 0, 2)]);
+
+    // With the button on seat 2, seat 0 is first to the left
+    let awards = pots.award(&contenders, 2, 3, OddChipRule::LeftOfButton);
+    assert_eq!(won(&awards), [(0, 0, 3), (2, 0, 2)]);
+}
+
+#[test]
+fn test_odd_chip_by_suit_order() {
+    let board = "As Ks Qh Jd Th";
+    let pots = Pots::new(&[put(0, 2), folded(1, 1), put(2, 2)]);
+    // Both hold a three as their highest card; the heart outranks the club
+    let contenders = [contender(0, "2d 3c", board), contender(2, "2c 3h", board)];
+
+    let awards = pots.award(&contenders, 2, 3, OddChipRule::SuitOrder);
+    assert_eq!(won(&awards), [(2, 0, 3), (0, 0, 2)]);
+}
+
+#[test]
+fn test_hi_lo_splits_the_pot() {
+    let board = "Ah 4c 7d Ks Kh";
+    let pots = Pots::new(&[put(0, 50), put(1, 51)]);
+    // Seat 0 has the nut low, seat 1 trips and no low
+    let contenders = [contender(0, "2c 3h", board), contender(1, "Kd 9c", board)];
+
+    let awards = pots.award(&contenders, 0, 2, OddChipRule::LeftOfButton);
+    assert_eq!(pots.uncalled(), Some((1, 1)));
+    assert_eq!(won(&awards), [(1, 0, 50), (0, 0, 50)]);
+    assert!(matches!(awards[1].share, Share::Low(_)));
+}
+
+#[test]
+fn test_odd_chip_of_a_hi_lo_split_goes_high() {
+    let board = "Ah 4c 7d Ks Kh";
+    let pots = Pots::new(&[put(0, 50), put(1, 50), folded(2, 1)]);
+    let contenders = [contender(0, "2c 3h", board), contender(1, "Kd 9c", board)];
+
+    let awards = pots.award(&contenders, 2, 3, OddChipRule::LeftOfButton);
+    assert_eq!(won(&awards), [(1, 0, 51), (0, 0, 50)]);
+}
+
+#[test]
+fn test_hi_lo_without_a_qualifying_low_scoops() {
+    let board = "Ah Kc Qd 9s 8h";
+    let pots = Pots::new(&[put(0, 50), put(1, 50)]);
+    let contenders = [contender(0, "2c 3h", board), contender(1, "Kd 9c", board)];
+    assert!(contenders.iter().all(|c| c.low.is_none()));
+
+    let awards = pots.award(&contenders, 0, 2, OddChipRule::LeftOfButton);
+    assert_eq!(won(&awards), [(1, 0, 100)]);
+}
+
+#[test]
+fn test_hi_lo_quartered() {
+    let board = "Ah 4c 7d Ks Kh";
+    let pots = Pots::new(&[put(0, 100), put(1, 100), put(2, 100)]);
+    // Seats 0 and 1 share the low, seat 2 wins the high
+    let contenders = [
+        contender(0, "2c 3h", board),
+        contender(1, "2d 3s", board),
+        contender(2, "Kd 9c", board),
+    ];
+
+    let awards = pots.award(&contenders, 2, 3, OddChipRule::LeftOfButton);
+    assert_eq!(won(&awards), [(2, 0, 150), (0, 0, 75), (1, 0, 75)]);
+}
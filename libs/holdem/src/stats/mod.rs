@@ -0,0 +1,564 @@
+use std::collections::BTreeMap;
+
+use crate::event::{Blind, Event};
+use crate::hand::{Action, Hand, Street};
+use crate::history::{HandRecord, RecordedAction};
+use crate::table::TableError;
+
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
+#[cfg(test)]
+mod tests;
+
+/// Where a player sat relative to the button, for stats broken down by position
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub enum Position {
+    /// The first seats to act before the flop at a full table
+    Early,
+    /// The seats between early position and the hijack
+    Middle,
+    Hijack,
+    Cutoff,
+    Button,
+    SmallBlind,
+    BigBlind,
+}
+
+impl Position {
+    /// The positions at a hand with `players` players, starting from the first seat left of
+    /// the button
+    ///
+    /// Heads up, the button posts the small blind and is counted as the button.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use holdem::stats::Position;
+    ///
+    /// assert_eq!(
+    ///     Position::for_players(6),
+    ///     [
+    ///         Position::SmallBlind,
+    ///         Position::BigBlind,
+    ///         Position::Early,
+    ///         Position::Hijack,
+    ///         Position::Cutoff,
+    ///         Position::Button,
+    ///     ]
+    /// );
+    /// ```
+    pub fn for_players(players: usize) -> Vec<Position> {
+        match players {
+            0 => Vec::new(),
+            1 => vec![Position::Button],
+            2 => vec![Position::BigBlind, Position::Button],
+            _ => {
+                let before_button = players - 3;
+                let late = [Position::Hijack, Position::Cutoff];
+                let late = &late[late.len() - before_button.min(2)..];
+                let rest = before_button - late.len();
+                let early = rest.div_ceil(2);
+                let mut positions = vec![Position::SmallBlind, Position::BigBlind];
+                positions.extend(std::iter::repeat_n(Position::Early, early));
+                positions.extend(std::iter::repeat_n(Position::Middle, rest - early));
+                positions.extend(late);
+                positions.push(Position::Button);
+                positions
+            }
+        }
+    }
+
+    // The position of each seat in a recorded hand, in the record's order; players sitting
+    // out have none, and nobody has one without a button
+    pub(crate) fn of_record(record: &HandRecord) -> Vec<Option<Position>> {
+        let Some(button) = record.button else {
+            return vec![None; record.seats.len()];
+        };
+        let mut seats: Vec<usize> = record
+            .seats
+            .iter()
+            .filter(|seat| !seat.sitting_out)
+            .map(|seat| seat.seat)
+            .collect();
+        seats.sort_unstable();
+        // Counted round from the first seat after the button
+        let first = seats.iter().position(|&seat| seat > button).unwrap_or(0);
+        let mut positions = Position::for_players(seats.len());
+        positions.rotate_right(first);
+        record
+            .seats
+            .iter()
+            .map(|seat| {
+                let index = seats
+                    .iter()
+                    .position(|&s| s == seat.seat && !seat.sitting_out)?;
+                Some(positions[index])
+            })
+            .collect()
+    }
+}
+
+/// How often a player did something, out of the chances they had to
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct Counter {
+    pub count: u32,
+    pub opportunities: u32,
+}
+
+impl Counter {
+    /// The share of opportunities taken as a percentage, if there were any
+    pub fn percent(&self) -> Option<f64> {
+        (self.opportunities > 0).then(|| 100.0 * self.count as f64 / self.opportunities as f64)
+    }
+
+    pub fn merge(&mut self, other: Counter) {
+        self.count += other.count;
+        self.opportunities += other.opportunities;
+    }
+
+    fn add(&mut self, taken: bool) {
+        self.opportunities += 1;
+        self.count += u32::from(taken);
+    }
+}
+
+/// The usual HUD stats for one player, over the hands they were dealt into
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct PlayerStats {
+    pub hands: u32,
+    /// Put chips in before the flop other than by posting, out of hands dealt
+    pub vpip: Counter,
+    /// Bet or raised before the flop, out of hands dealt
+    pub pfr: Counter,
+    /// Re-raised the first raise before the flop, out of times facing just that raise
+    pub three_bet: Counter,
+    /// Folded a first raise to a re-raise, out of times facing one
+    pub fold_to_three_bet: Counter,
+    /// Bets and raises after the flop
+    pub aggressive_actions: u32,
+    /// Calls after the flop
+    pub calls: u32,
+    /// Went to showdown, out of flops seen
+    pub wtsd: Counter,
+    /// Won chips at showdown, out of showdowns
+    pub wsd: Counter,
+    /// Bet the flop as the last raiser before it, out of times the betting was still open
+    pub cbet: Counter,
+    /// Folded to a continuation bet, out of times facing one before any raise
+    pub fold_to_cbet: Counter,
+}
+
+impl PlayerStats {
+    /// Bets and raises for each call after the flop, if there were any calls
+    pub fn aggression_factor(&self) -> Option<f64> {
+        (self.calls > 0).then(|| self.aggressive_actions as f64 / self.calls as f64)
+    }
+
+    pub fn merge(&mut self, other: PlayerStats) {
+        self.hands += other.hands;
+        self.vpip.merge(other.vpip);
+        self.pfr.merge(other.pfr);
+        self.three_bet.merge(other.three_bet);
+        self.fold_to_three_bet.merge(other.fold_to_three_bet);
+        self.aggressive_actions += other.aggressive_actions;
+        self.calls += other.calls;
+        self.wtsd.merge(other.wtsd);
+        self.wsd.merge(other.wsd);
+        self.cbet.merge(other.cbet);
+        self.fold_to_cbet.merge(other.fold_to_cbet);
+    }
+}
+
+/// Stats for every player seen, overall and by position
+///
+/// Hands can come from imported hand records or from hands played on the engine. Trackers
+/// fed different hands can be merged, so files can be read in parallel and the results
+/// combined. Players are told apart by name.
+///
+/// # Examples
+///
+/// ```
+/// use cards::Deck;
+/// use holdem::hand::Action;
+/// use holdem::stats::{Position, Tracker};
+/// use holdem::table::{Stakes, Table};
+///
+/// let mut table = Table::new(3, Stakes::new(1, 2)).unwrap();
+/// for (seat, name) in ["ann", "ben", "cat"].into_iter().enumerate() {
+///     table.sit(seat, name, 100).unwrap();
+/// }
+/// let hand = table.start_hand(Deck::new_seeded(1)).unwrap();
+/// let hand = hand.apply(0, Action::Raise(6)).unwrap();
+/// let hand = hand.apply(1, Action::Fold).unwrap();
+/// let hand = hand.apply(2, Action::Fold).unwrap();
+///
+/// let mut tracker = Tracker::new();
+/// tracker.record_hand(&hand).unwrap();
+/// let ann = tracker.player("ann").unwrap();
+/// assert_eq!(ann.pfr.percent(), Some(100.0));
+/// assert_eq!(tracker.position("ann", Position::Button), Some(ann));
+/// assert_eq!(tracker.player("ben").unwrap().vpip.percent(), Some(0.0));
+/// ```
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct Tracker {
+    hands: u32,
+    players: BTreeMap<String, Tracked>,
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+struct Tracked {
+    total: PlayerStats,
+    positions: BTreeMap<Position, PlayerStats>,
+}
+
+impl Tracker {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Number of hands recorded
+    pub fn hands(&self) -> u32 {
+        self.hands
+    }
+
+    /// Adds a hand read from a hand history
+    ///
+    /// Players sitting out are left out. Positions are only known when the record has a
+    /// button.
+    pub fn record(&mut self, record: &HandRecord) {
+        self.add(Summary::from_record(record));
+    }
+
+    /// Adds a hand played on the engine, which must be complete
+    pub fn record_hand(&mut self, hand: &Hand) -> Result<(), TableError> {
+        if !hand.is_complete() {
+            return Err(TableError::HandNotComplete);
+        }
+        self.add(Summary::from_hand(hand));
+        Ok(())
+    }
+
+    /// A player's stats over every hand they were dealt into
+    pub fn player(&self, name: &str) -> Option<&PlayerStats> {
+        self.players.get(name).map(|tracked| &tracked.total)
+    }
+
+    /// A player's stats over the hands they played from `position`
+    pub fn position(&self, name: &str, position: Position) -> Option<&PlayerStats> {
+        self.players.get(name)?.positions.get(&position)
+    }
+
+    /// Every player seen with their overall stats, in name order
+    pub fn players(&self) -> impl Iterator<Item = (&str, &PlayerStats)> {
+        self.players
+            .iter()
+            .map(|(name, tracked)| (name.as_str(), &tracked.total))
+    }
+
+    /// A player's stats for each position they have played from
+    pub fn positions(&self, name: &str) -> impl Iterator<Item = (Position, &PlayerStats)> {
+        self.players
+            .get(name)
+            .into_iter()
+            .flat_map(|tracked| tracked.positions.iter().map(|(&p, stats)| (p, stats)))
+    }
+
+    // A tracker of `hands` hands from stats kept elsewhere, overall when there is no position
+    #[cfg(feature = "storage")]
+    pub(crate) fn from_parts(
+        hands: u32,
+        stats: impl IntoIterator<Item = (String, Option<Position>, PlayerStats)>,
+    ) -> Self {
+        let mut tracker = Tracker {
+            hands,
+            players: BTreeMap::new(),
+        };
+        for (name, position, stats) in stats {
+            let tracked = tracker.players.entry(name).or_default();
+            match position {
+                Some(position) => tracked.positions.entry(position).or_default().merge(stats),
+                None => tracked.total.merge(stats),
+            }
+        }
+        tracker
+    }
+
+    /// Adds the hands recorded by another tracker
+    pub fn merge(&mut self, other: Tracker) {
+        self.hands += other.hands;
+        for (name, other) in other.players {
+            let tracked = self.players.entry(name).or_default();
+            tracked.total.merge(other.total);
+            for (position, stats) in other.positions {
+                tracked.positions.entry(position).or_default().merge(stats);
+            }
+        }
+    }
+
+    fn add(&mut self, summary: Summary) {
+        self.hands += 1;
+        let stats = summary.stats();
+        for (player, stats) in summary.players.into_iter().zip(stats) {
+            let tracked = self.players.entry(player.name).or_default();
+            tracked.total.merge(stats);
+            if let Some(position) = player.position {
+                tracked.positions.entry(position).or_default().merge(stats);
+            }
+        }
+    }
+}
+
+// A hand reduced to the players and the decisions they made
+struct Summary {
+    players: Vec<Seen>,
+    moves: Vec<Move>,
+    flop: bool,
+    showdown: bool,
+    // Whether each player won chips at showdown
+    winners: Vec<bool>,
+}
+
+struct Seen {
+    name: String,
+    position: Option<Position>,
+}
+
+struct Move {
+    player: usize,
+    street: Street,
+    kind: Kind,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Kind {
+    Fold,
+    Check,
+    Call,
+    Bet,
+    Raise,
+}
+
+impl Summary {
+    fn from_record(record: &HandRecord) -> Self {
+        let positions = Position::of_record(record);
+        let mut seats: Vec<_> = record
+            .seats
+            .iter()
+            .zip(positions)
+            .filter(|(seat, _)| !seat.sitting_out)
+            .collect();
+        seats.sort_by_key(|(seat, _)| seat.seat);
+        let players: Vec<Seen> = seats
+            .into_iter()
+            .map(|(seat, position)| Seen {
+                name: seat.name.clone(),
+                position,
+            })
+            .collect();
+        let index = |name: &str| players.iter().position(|p| p.name == name);
+
+        let mut moves = Vec::new();
+        let mut winners = vec![false; players.len()];
+        for action in &record.actions {
+            let Some(player) = index(&action.player) else {
+                continue;
+            };
+            let kind = match action.action {
+                RecordedAction::Fold => Kind::Fold,
+                RecordedAction::Check => Kind::Check,
+                RecordedAction::Call(_) => Kind::Call,
+                RecordedAction::Bet(_) => Kind::Bet,
+                RecordedAction::Raise { .. } => Kind::Raise,
+                RecordedAction::Collected(_) if action.street == Street::Showdown => {
+                    winners[player] = true;
+                    continue;
+                }
+                _ => continue,
+            };
+            moves.push(Move {
+                player,
+                street: action.street,
+                kind,
+            });
+        }
+
+        Self {
+            players,
+            moves,
+            flop: record.board.len() >= Street::Flop.board_len(),
+            showdown: record.actions.iter().any(|a| a.street == Street::Showdown),
+            winners,
+        }
+    }
+
+    fn from_hand(hand: &Hand) -> Self {
+        let seats: Vec<usize> = hand.players().map(|(seat, _)| seat).collect();
+        let first = seats.iter().position(|&s| s > hand.button()).unwrap_or(0);
+        let mut positions = Position::for_players(seats.len());
+        positions.rotate_right(first);
+        let players = hand
+            .players()
+            .zip(positions)
+            .map(|((_, player), position)| Seen {
+                name: player.name().to_string(),
+                position: Some(position),
+            })
+            .collect();
+        let index = |seat: usize| seats.iter().position(|&s| s == seat).unwrap_or(0);
+
+        // Bets are told from calls and raises by the street's bets, as in a history
+        let mut moves = Vec::new();
+        let mut street = Street::Preflop;
+        let mut street_bets = vec![0; hand.seat_count()];
+        let mut current_bet = 0;
+        for event in hand.events() {
+            match *event {
+                Event::BlindPosted {
+                    seat,
+                    blind,
+                    amount,
+                } => {
+                    street_bets[seat] += amount;
+                    if blind == Blind::Big {
+                        current_bet = hand.stakes().big_blind;
+                    }
+                }
+                Event::Acted {
+                    seat,
+                    action,
+                    amount,
+                } => {
+                    street_bets[seat] += amount;
+                    let total = street_bets[seat];
+                    let kind = match action {
+                        Action::Fold => Kind::Fold,
+                        Action::Check => Kind::Check,
+                        Action::Call => Kind::Call,
+                        _ if total <= current_bet => Kind::Call,
+                        _ if current_bet == 0 => Kind::Bet,
+                        _ => Kind::Raise,
+                    };
+                    current_bet = current_bet.max(total);
+                    moves.push(Move {
+                        player: index(seat),
+                        street,
+                        kind,
+                    });
+                }
+                Event::BoardDealt { street: next, .. } => {
+                    street = next;
+                    street_bets.fill(0);
+                    current_bet = 0;
+                }
+                _ => {}
+            }
+        }
+
+        let showdown = hand.street() == Street::Showdown;
+        let mut winners = vec![false; seats.len()];
+        if showdown {
+            for award in hand.awards() {
+                winners[index(award.seat)] = true;
+            }
+        }
+        Self {
+            players,
+            moves,
+            flop: hand.board().len() >= Street::Flop.board_len(),
+            showdown,
+            winners,
+        }
+    }
+
+    // Each player's stats for this one hand
+    fn stats(&self) -> Vec<PlayerStats> {
+        let players = self.players.len();
+        let mut stats = vec![
+            PlayerStats {
+                hands: 1,
+                ..PlayerStats::default()
+            };
+            players
+        ];
+        let mut vpip = vec![false; players];
+        let mut pfr = vec![false; players];
+        let mut folded_preflop = vec![false; players];
+        let mut folded = vec![false; players];
+        let mut faced_raise = vec![false; players];
+        let mut faced_three_bet = vec![false; players];
+        let mut faced_cbet = vec![false; players];
+
+        let mut raises = 0;
+        let mut opener = None;
+        let mut aggressor = None;
+        let mut flop_bet = false;
+        let mut cbet_open = false;
+
+        for &Move {
+            player,
+            street,
+            kind,
+        } in &self.moves
+        {
+            let stats = &mut stats[player];
+            let aggressive = matches!(kind, Kind::Bet | Kind::Raise);
+            if street == Street::Preflop {
+                if raises == 1 && opener != Some(player) && !faced_raise[player] {
+                    faced_raise[player] = true;
+                    stats.three_bet.add(aggressive);
+                }
+                if raises == 2 && opener == Some(player) && !faced_three_bet[player] {
+                    faced_three_bet[player] = true;
+                    stats.fold_to_three_bet.add(kind == Kind::Fold);
+                }
+                match kind {
+                    Kind::Call => vpip[player] = true,
+                    Kind::Bet | Kind::Raise => {
+                        vpip[player] = true;
+                        pfr[player] = true;
+                        raises += 1;
+                        opener = opener.or(Some(player));
+                        aggressor = Some(player);
+                    }
+                    Kind::Fold => folded_preflop[player] = true,
+                    Kind::Check => {}
+                }
+            } else {
+                if street == Street::Flop {
+                    if aggressor == Some(player) && !flop_bet {
+                        stats.cbet.add(kind == Kind::Bet);
+                        cbet_open = kind == Kind::Bet;
+                    } else if cbet_open && !faced_cbet[player] {
+                        faced_cbet[player] = true;
+                        stats.fold_to_cbet.add(kind == Kind::Fold);
+                    }
+                    flop_bet |= aggressive;
+                    cbet_open &= kind != Kind::Raise;
+                }
+                if aggressive {
+                    stats.aggressive_actions += 1;
+                } else if kind == Kind::Call {
+                    stats.calls += 1;
+                }
+            }
+            folded[player] |= kind == Kind::Fold;
+        }
+
+        for (player, stats) in stats.iter_mut().enumerate() {
+            stats.vpip.add(vpip[player]);
+            stats.pfr.add(pfr[player]);
+            let at_showdown = self.showdown && !folded[player];
+            if self.flop && !folded_preflop[player] {
+                stats.wtsd.add(at_showdown);
+            }
+            if at_showdown {
+                stats.wsd.add(self.winners[player]);
+            }
+        }
+        stats
+    }
+}
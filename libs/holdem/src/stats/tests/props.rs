@@ -0,0 +1,65 @@
+use super::super::*;
+use crate::test_support::{arb_game, play_out, read_back, start};
+use proptest::prelude::*;
+
+fn arb_hands() -> impl Strategy<Value = Vec<Hand>> {
+    prop::collection::vec(arb_game(), 1..6).prop_map(|games| {
+        games
+            .iter()
+            .map(|(stacks, seed, choices)| play_out(start(stacks, *seed), choices))
+            .collect()
+    })
+}
+
+proptest! {
+    #[test]
+    fn played_and_imported_hands_agree(hands in arb_hands()) {
+        let mut played = Tracker::new();
+        let mut imported = Tracker::new();
+        for (id, hand) in hands.iter().enumerate() {
+            played.record_hand(hand).unwrap();
+            imported.record(&read_back(hand, id as u64));
+        }
+        prop_assert_eq!(played, imported);
+    }
+
+    #[test]
+    fn merging_matches_recording_together(hands in arb_hands(), split in any::<prop::sample::Index>()) {
+        let split = split.index(hands.len() + 1);
+        let mut together = Tracker::new();
+        let mut first = Tracker::new();
+        let mut second = Tracker::new();
+        for (i, hand) in hands.iter().enumerate() {
+            together.record_hand(hand).unwrap();
+            let part = if i < split { &mut first } else { &mut second };
+            part.record_hand(hand).unwrap();
+        }
+        first.merge(second);
+        prop_assert_eq!(first, together);
+    }
+
+    #[test]
+    fn counts_never_exceed_opportunities(hands in arb_hands()) {
+        let mut tracker = Tracker::new();
+        for hand in &hands {
+            tracker.record_hand(hand).unwrap();
+        }
+        for (_, stats) in tracker.players() {
+            for counter in [
+                stats.vpip,
+                stats.pfr,
+                stats.three_bet,
+                stats.fold_to_three_bet,
+                stats.wtsd,
+                stats.wsd,
+                stats.cbet,
+                stats.fold_to_cbet,
+            ] {
+                prop_assert!(counter.count <= counter.opportunities);
+            }
+            prop_assert!(stats.pfr.count <= stats.vpip.count);
+            prop_assert_eq!(stats.vpip.opportunities, stats.hands);
+            prop_assert!(stats.wsd.opportunities <= stats.wtsd.count);
+        }
+    }
+}
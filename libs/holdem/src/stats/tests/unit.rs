@@ -0,0 +1,266 @@
+use super::super::*;
+use crate::history::HistoryReader;
+use crate::test_support::{deck, play, seeded, table};
+use cards::Deck;
+
+fn counter(count: u32, opportunities: u32) -> Counter {
+    Counter {
+        count,
+        opportunities,
+    }
+}
+
+// The button opens, the small blind re-raises and bets the flop, and wins at showdown
+fn three_bet_pot() -> Hand {
+    let deck = deck("As Kc Ks Ad 2h Kd 4c 7s 8h 2c 5d Jc 6h 3d");
+    let hand = table(&[200, 200, 200]).start_hand(deck).unwrap();
+    play(
+        hand,
+        &[
+            (0, Action::Raise(6)),
+            (1, Action::Raise(18)),
+            (2, Action::Fold),
+            (0, Action::Call),
+            (1, Action::Bet(20)),
+            (0, Action::Call),
+            (1, Action::Check),
+            (0, Action::Bet(30)),
+            (1, Action::Call),
+            (1, Action::Check),
+            (0, Action::Check),
+        ],
+    )
+}
+
+#[test]
+fn test_positions_for_each_table_size() {
+    use Position::*;
+
+    assert_eq!(Position::for_players(2), [BigBlind, Button]);
+    assert_eq!(Position::for_players(3), [SmallBlind, BigBlind, Button]);
+    assert_eq!(
+        Position::for_players(4),
+        [SmallBlind, BigBlind, Cutoff, Button]
+    );
+    assert_eq!(
+        Position::for_players(5),
+        [SmallBlind, BigBlind, Hijack, Cutoff, Button]
+    );
+    assert_eq!(
+        Position::for_players(9),
+        [
+            SmallBlind, BigBlind, Early, Early, Middle, Middle, Hijack, Cutoff, Button
+        ]
+    );
+    assert_eq!(
+        Position::for_players(10),
+        [
+            SmallBlind, BigBlind, Early, Early, Early, Middle, Middle, Hijack, Cutoff, Button
+        ]
+    );
+}
+
+#[test]
+fn test_counter_percent() {
+    assert_eq!(counter(0, 0).percent(), None);
+    assert_eq!(counter(1, 4).percent(), Some(25.0));
+    assert_eq!(counter(3, 3).percent(), Some(100.0));
+}
+
+#[test]
+fn test_three_bet_pot_to_showdown() {
+    let mut tracker = Tracker::new();
+    tracker.record_hand(&three_bet_pot()).unwrap();
+    assert_eq!(tracker.hands(), 1);
+
+    let opener = tracker.player("p0").unwrap();
+    assert_eq!(opener.hands, 1);
+    assert_eq!(opener.vpip, counter(1, 1));
+    assert_eq!(opener.pfr, counter(1, 1));
+    assert_eq!(opener.three_bet, counter(0, 0));
+    assert_eq!(opener.fold_to_three_bet, counter(0, 1));
+    assert_eq!(opener.cbet, counter(0, 0));
+    assert_eq!(opener.fold_to_cbet, counter(0, 1));
+    assert_eq!((opener.aggressive_actions, opener.calls), (1, 1));
+    assert_eq!(opener.aggression_factor(), Some(1.0));
+    assert_eq!(opener.wtsd, counter(1, 1));
+    assert_eq!(opener.wsd, counter(0, 1));
+
+    let three_bettor = tracker.player("p1").unwrap();
+    assert_eq!(three_bettor.vpip, counter(1, 1));
+    assert_eq!(three_bettor.pfr, counter(1, 1));
+    assert_eq!(three_bettor.three_bet, counter(1, 1));
+    assert_eq!(three_bettor.fold_to_three_bet, counter(0, 0));
+    assert_eq!(three_bettor.cbet, counter(1, 1));
+    assert_eq!(three_bettor.fold_to_cbet, counter(0, 0));
+    assert_eq!(three_bettor.wtsd, counter(1, 1));
+    assert_eq!(three_bettor.wsd, counter(1, 1));
+
+    // Facing a 3-bet is not a chance to 3-bet
+    let big_blind = tracker.player("p2").unwrap();
+    assert_eq!(big_blind.vpip, counter(0, 1));
+    assert_eq!(big_blind.three_bet, counter(0, 0));
+    assert_eq!(big_blind.wtsd, counter(0, 0));
+    assert_eq!(big_blind.aggression_factor(), None);
+
+    assert_eq!(tracker.position("p0", Position::Button), Some(opener));
+    assert_eq!(
+        tracker.position("p1", Position::SmallBlind),
+        Some(three_bettor)
+    );
+    assert_eq!(tracker.position("p2", Position::BigBlind), Some(big_blind));
+    assert_eq!(tracker.position("p2", Position::Button), None);
+    assert_eq!(tracker.position("nobody", Position::Button), None);
+}
+
+#[test]
+fn test_continuation_bet_folded_to() {
+    let hand = table(&[100, 100, 100]).start_hand(seeded(2)).unwrap();
+    let hand = play(
+        hand,
+        &[
+            (0, Action::Raise(6)),
+            (1, Action::Fold),
+            (2, Action::Call),
+            (2, Action::Check),
+            (0, Action::Bet(8)),
+            (2, Action::Fold),
+        ],
+    );
+    let mut tracker = Tracker::new();
+    tracker.record_hand(&hand).unwrap();
+
+    let raiser = tracker.player("p0").unwrap();
+    assert_eq!(raiser.cbet, counter(1, 1));
+    assert_eq!(raiser.wtsd, counter(0, 1));
+    assert_eq!(raiser.wsd, counter(0, 0));
+
+    let caller = tracker.player("p2").unwrap();
+    assert_eq!(caller.vpip, counter(1, 1));
+    assert_eq!(caller.pfr, counter(0, 1));
+    assert_eq!(caller.three_bet, counter(0, 1));
+    assert_eq!(caller.fold_to_cbet, counter(1, 1));
+    assert_eq!(caller.wtsd, counter(0, 1));
+
+    let folder = tracker.player("p1").unwrap();
+    assert_eq!(folder.three_bet, counter(0, 1));
+    assert_eq!(folder.wtsd, counter(0, 0));
+}
+
+#[test]
+fn test_checking_the_flop_misses_the_continuation_bet() {
+    let hand = table(&[100, 100]).start_hand(seeded(4)).unwrap();
+    let hand = play(
+        hand,
+        &[
+            (0, Action::Raise(6)),
+            (1, Action::Call),
+            (1, Action::Check),
+            (0, Action::Check),
+            (1, Action::Bet(4)),
+            (0, Action::Fold),
+        ],
+    );
+    let mut tracker = Tracker::new();
+    tracker.record_hand(&hand).unwrap();
+
+    // The big blind's turn bet is not a response to a continuation bet
+    assert_eq!(tracker.player("p0").unwrap().cbet, counter(0, 1));
+    assert_eq!(tracker.player("p1").unwrap().fold_to_cbet, counter(0, 0));
+    assert_eq!(tracker.position("p0", Position::Button).unwrap().hands, 1);
+}
+
+#[test]
+fn test_record_hand_needs_a_completed_hand() {
+    let hand = table(&[100, 100]).start_hand(Deck::new()).unwrap();
+    let mut tracker = Tracker::new();
+    assert_eq!(tracker.record_hand(&hand), Err(TableError::HandNotComplete));
+    assert_eq!(tracker, Tracker::new());
+}
+
+#[test]
+fn test_imported_hand() {
+    let history = "\
+PokerStars Hand #7:  Hold'em No Limit ($0.01/$0.02 USD) - 2024/01/31 9:05:00 ET
+Table 'Alcyone IV' 6-max Seat #5 is the button
+Seat 1: ann ($2 in chips)
+Seat 3: ben ($2 in chips)
+Seat 5: cat ($2 in chips)
+Seat 6: dan ($2 in chips) is sitting out
+ann: posts small blind $0.01
+ben: posts big blind $0.02
+*** HOLE CARDS ***
+Dealt to cat [Ah Kd]
+cat: raises $0.04 to $0.06
+ann: calls $0.05
+ben: folds
+*** FLOP *** [7c 8d 2s]
+ann: checks
+cat: bets $0.08
+ann: raises $0.16 to $0.24
+cat: folds
+Uncalled bet ($0.16) returned to ann
+ann collected $0.30 from pot
+*** SUMMARY ***
+Total pot $0.30 | Rake $0
+Board [7c 8d 2s]
+Seat 1: ann (small blind) collected ($0.30)
+Seat 3: ben (big blind) folded before Flop
+Seat 5: cat (button) folded on the Flop
+Seat 6: dan is sitting out
+";
+    let record = HistoryReader::new(history.as_bytes())
+        .next()
+        .unwrap()
+        .unwrap();
+    let mut tracker = Tracker::new();
+    tracker.record(&record);
+
+    let names: Vec<&str> = tracker.players().map(|(name, _)| name).collect();
+    assert_eq!(names, ["ann", "ben", "cat"]);
+
+    let ann = tracker.position("ann", Position::SmallBlind).unwrap();
+    assert_eq!(ann.vpip, counter(1, 1));
+    assert_eq!(ann.three_bet, counter(0, 1));
+    assert_eq!(ann.fold_to_cbet, counter(0, 1));
+    assert_eq!((ann.aggressive_actions, ann.calls), (1, 0));
+    assert_eq!(ann.aggression_factor(), None);
+
+    let cat = tracker.position("cat", Position::Button).unwrap();
+    assert_eq!(cat.pfr, counter(1, 1));
+    assert_eq!(cat.cbet, counter(1, 1));
+    assert_eq!(cat.wtsd, counter(0, 1));
+    assert!(tracker.position("ben", Position::BigBlind).is_some());
+
+    // Without a button the overall stats are kept, but not by position
+    let mut record = record;
+    record.button = None;
+    let mut tracker = Tracker::new();
+    tracker.record(&record);
+    assert_eq!(tracker.player("cat").unwrap().cbet, counter(1, 1));
+    assert_eq!(tracker.position("cat", Position::Button), None);
+}
+
+#[test]
+fn test_merge_adds_hands_and_players() {
+    let fold = play(
+        table(&[100, 100, 100]).start_hand(Deck::new()).unwrap(),
+        &[(0, Action::Fold), (1, Action::Fold)],
+    );
+    let mut both = Tracker::new();
+    both.record_hand(&three_bet_pot()).unwrap();
+    both.record_hand(&fold).unwrap();
+
+    let mut first = Tracker::new();
+    first.record_hand(&three_bet_pot()).unwrap();
+    let mut second = Tracker::new();
+    second.record_hand(&fold).unwrap();
+    first.merge(second);
+
+    assert_eq!(first, both);
+    assert_eq!(first.hands(), 2);
+    let p0 = first.player("p0").unwrap();
+    assert_eq!(p0.hands, 2);
+    assert_eq!(p0.vpip, counter(1, 2));
+    assert_eq!(p0.vpip.percent(), Some(50.0));
+}
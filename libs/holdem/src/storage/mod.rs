@@ -0,0 +1,670 @@
+use std::fmt;
+use std::path::Path;
+
+use cards::Card;
+use cards::notation::parse_cards;
+use cards::range::{Combo, StartingHand};
+use rusqlite::{Connection, OptionalExtension, Row, Transaction, params, params_from_iter};
+
+use crate::hand::Street;
+use crate::history::{ActionRecord, HandRecord, RecordedAction, SeatRecord};
+use crate::stats::{Counter, PlayerStats, Position, Tracker};
+
+mod schema;
+
+use schema::{MIGRATIONS, STAT_COLUMNS};
+
+#[cfg(test)]
+mod tests;
+
+#[derive(Debug)]
+pub enum StorageError {
+    Sqlite(rusqlite::Error),
+    /// A value in the database that does not read back, such as a card or street
+    InvalidData(String),
+    /// The database has migrations newer than this version knows
+    UnknownVersion(u32),
+}
+
+impl fmt::Display for StorageError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            StorageError::Sqlite(e) => write!(f, "database error: {e}"),
+            StorageError::InvalidData(s) => write!(f, "invalid value \"{s}\" in the database"),
+            StorageError::UnknownVersion(version) => write!(
+                f,
+                "the database is at schema version {version}, newer than the {} known",
+                MIGRATIONS.len()
+            ),
+        }
+    }
+}
+
+impl std::error::Error for StorageError {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            StorageError::Sqlite(e) => Some(e),
+            _ => None,
+        }
+    }
+}
+
+impl From<rusqlite::Error> for StorageError {
+    fn from(e: rusqlite::Error) -> Self {
+        StorageError::Sqlite(e)
+    }
+}
+
+/// Hand records and the stats of the players in them, kept in a SQLite database
+///
+/// Opening a database brings its schema up to date. Each hand is stored once, keyed by its
+/// site and id, and adds to the running stats of its players as it is stored.
+///
+/// # Examples
+///
+/// ```
+/// use holdem::history::HistoryReader;
+/// use holdem::stats::Position;
+/// use holdem::storage::{HandQuery, Store};
+///
+/// let history = "\
+/// PokerStars Hand #1:  Hold'em No Limit (1/2) - 2024/01/31 9:05:00 ET
+/// Table 'Home' 2-max Seat #1 is the button
+/// Seat 1: hero (100 in chips)
+/// Seat 2: ben (100 in chips)
+/// hero: posts small blind 1
+/// ben: posts big blind 2
+/// *** HOLE CARDS ***
+/// Dealt to hero [Ah Kh]
+/// hero: raises 4 to 6
+/// ben: folds
+/// Uncalled bet (4) returned to hero
+/// hero collected 4 from pot
+/// *** SUMMARY ***
+/// Total pot 4 | Rake 0
+/// Seat 1: hero (button) (small blind) collected (4)
+/// Seat 2: ben (big blind) folded before Flop
+/// ";
+///
+/// let mut store = Store::open_in_memory().unwrap();
+/// for record in HistoryReader::new(history.as_bytes()) {
+///     store.insert(&record.unwrap()).unwrap();
+/// }
+///
+/// let query = HandQuery::new()
+///     .with_player("hero")
+///     .with_starting_hand("AKs".parse().unwrap())
+///     .with_position(Position::Button);
+/// let hands = store.hands(&query).unwrap();
+/// assert_eq!(hands.len(), 1);
+/// assert_eq!(store.player_stats("hero").unwrap().unwrap().pfr.count, 1);
+/// ```
+#[derive(Debug)]
+pub struct Store {
+    connection: Connection,
+}
+
+impl Store {
+    /// Opens or creates the database file at `path`
+    pub fn open(path: impl AsRef<Path>) -> Result<Self, StorageError> {
+        Self::new(Connection::open(path)?)
+    }
+
+    /// A database held in memory, gone once the store is dropped
+    pub fn open_in_memory() -> Result<Self, StorageError> {
+        Self::new(Connection::open_in_memory()?)
+    }
+
+    fn new(connection: Connection) -> Result<Self, StorageError> {
+        connection.pragma_update(None, "foreign_keys", true)?;
+        let mut store = Self { connection };
+        store.migrate()?;
+        Ok(store)
+    }
+
+    // Applies each migration the database has not had yet, one transaction apiece
+    fn migrate(&mut self) -> Result<(), StorageError> {
+        let version = self.version()?;
+        if version as usize > MIGRATIONS.len() {
+            return Err(StorageError::UnknownVersion(version));
+        }
+        for (applied, migration) in MIGRATIONS.iter().enumerate().skip(version as usize) {
+            let transaction = self.connection.transaction()?;
+            transaction.execute_batch(migration)?;
+            transaction.pragma_update(None, "user_version", applied as u32 + 1)?;
+            transaction.commit()?;
+        }
+        Ok(())
+    }
+
+    /// The number of migrations applied to the database
+    pub fn version(&self) -> Result<u32, StorageError> {
+        Ok(self
+            .connection
+            .pragma_query_value(None, "user_version", |row| row.get(0))?)
+    }
+
+    /// Stores a hand and adds it to its players' stats
+    ///
+    /// Returns `false`, changing nothing, if a hand with the same site and id is already
+    /// stored.
+    pub fn insert(&mut self, record: &HandRecord) -> Result<bool, StorageError> {
+        let transaction = self.connection.transaction()?;
+        let exists = transaction
+            .query_row(
+                "SELECT 1 FROM hands WHERE site = ?1 AND number = ?2",
+                params![record.site, record.id],
+                |_| Ok(()),
+            )
+            .optional()?
+            .is_some();
+        if exists {
+            return Ok(false);
+        }
+
+        transaction.execute(
+            "INSERT INTO hands (site, number, line, game, small_blind, big_blind, currency, date,
+                table_name, max_seats, button, board, total_pot, rake)
+             VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9, ?10, ?11, ?12, ?13, ?14)",
+            params![
+                record.site,
+                record.id,
+                record.line,
+                record.game,
+                record.small_blind,
+                record.big_blind,
+                record.currency.map(String::from),
+                record.date,
+                record.table,
+                record.max_seats,
+                record.button,
+                card_text(&record.board),
+                record.total_pot,
+                record.rake,
+            ],
+        )?;
+        let hand_id = transaction.last_insert_rowid();
+        insert_seats(&transaction, hand_id, record)?;
+        insert_actions(&transaction, hand_id, record)?;
+
+        let mut tracker = Tracker::new();
+        tracker.record(record);
+        add_stats(&transaction, &tracker)?;
+
+        transaction.commit()?;
+        Ok(true)
+    }
+
+    /// Number of hands stored
+    pub fn len(&self) -> Result<u64, StorageError> {
+        Ok(self
+            .connection
+            .query_row("SELECT COUNT(*) FROM hands", [], |row| row.get(0))?)
+    }
+
+    pub fn is_empty(&self) -> Result<bool, StorageError> {
+        Ok(self.len()? == 0)
+    }
+
+    /// The hand with `id` from `site`, if it is stored
+    pub fn hand(&self, site: &str, id: u64) -> Result<Option<HandRecord>, StorageError> {
+        let hand_id: Option<i64> = self
+            .connection
+            .query_row(
+                "SELECT id FROM hands WHERE site = ?1 AND number = ?2",
+                params![site, id],
+                |row| row.get(0),
+            )
+            .optional()?;
+        hand_id.map(|hand_id| self.load(hand_id)).transpose()
+    }
+
+    /// Every stored hand that matches `query`, in the order they were stored
+    pub fn hands(&self, query: &HandQuery) -> Result<Vec<HandRecord>, StorageError> {
+        let mut conditions = Vec::new();
+        let mut values = Vec::new();
+        let mut condition = |sql: &str, value: String| {
+            conditions.push(format!("{sql} = ?{}", values.len() + 1));
+            values.push(value);
+        };
+        if let Some(site) = &query.site {
+            condition("h.site", site.clone());
+        }
+        if let Some(player) = &query.player {
+            condition("s.name", player.clone());
+        }
+        if let Some(starting_hand) = query.starting_hand {
+            condition("s.starting_hand", starting_hand.to_string());
+        }
+        if let Some(position) = query.position {
+            condition("s.position", position_name(Some(position)).to_string());
+        }
+        let filter = if conditions.is_empty() {
+            String::new()
+        } else {
+            format!("WHERE {}", conditions.join(" AND "))
+        };
+
+        let mut statement = self.connection.prepare(&format!(
+            "SELECT DISTINCT h.id FROM hands h JOIN seats s ON s.hand_id = h.id {filter}
+             ORDER BY h.id"
+        ))?;
+        let ids = statement
+            .query_map(params_from_iter(values), |row| row.get(0))?
+            .collect::<Result<Vec<i64>, _>>()?;
+        ids.into_iter().map(|id| self.load(id)).collect()
+    }
+
+    /// The stats of every player over every hand stored
+    pub fn stats(&self) -> Result<Tracker, StorageError> {
+        let hands = self.len()? as u32;
+        let mut statement = self.connection.prepare(&format!(
+            "SELECT name, position, {} FROM player_stats ORDER BY name",
+            STAT_COLUMNS.join(", ")
+        ))?;
+        let rows = statement
+            .query_map([], |row| {
+                Ok((row.get(0)?, row.get::<_, String>(1)?, stats_from(row, 2)?))
+            })?
+            .collect::<Result<Vec<(String, String, PlayerStats)>, _>>()?;
+        let stats = rows
+            .into_iter()
+            .map(|(name, position, stats)| Ok((name, parse_position(&position)?, stats)))
+            .collect::<Result<Vec<_>, StorageError>>()?;
+        Ok(Tracker::from_parts(hands, stats))
+    }
+
+    /// A player's stats over every hand stored, if they played any
+    pub fn player_stats(&self, name: &str) -> Result<Option<PlayerStats>, StorageError> {
+        Ok(self
+            .connection
+            .query_row(
+                &format!(
+                    "SELECT {} FROM player_stats WHERE name = ?1 AND position = ''",
+                    STAT_COLUMNS.join(", ")
+                ),
+                params![name],
+                |row| stats_from(row, 0),
+            )
+            .optional()?)
+    }
+
+    fn load(&self, hand_id: i64) -> Result<HandRecord, StorageError> {
+        let (mut record, board) = self.connection.query_row(
+            "SELECT site, number, line, game, small_blind, big_blind, currency, date,
+                table_name, max_seats, button, board, total_pot, rake
+             FROM hands WHERE id = ?1",
+            params![hand_id],
+            |row| {
+                let currency: Option<String> = row.get(6)?;
+                let record = HandRecord {
+                    site: row.get(0)?,
+                    id: row.get(1)?,
+                    line: row.get(2)?,
+                    game: row.get(3)?,
+                    small_blind: row.get(4)?,
+                    big_blind: row.get(5)?,
+                    currency: currency.and_then(|c| c.chars().next()),
+                    date: row.get(7)?,
+                    table: row.get(8)?,
+                    max_seats: row.get(9)?,
+                    button: row.get(10)?,
+                    seats: Vec::new(),
+                    actions: Vec::new(),
+                    board: Vec::new(),
+                    total_pot: row.get(12)?,
+                    rake: row.get(13)?,
+                };
+                Ok((record, row.get::<_, String>(11)?))
+            },
+        )?;
+        record.board = cards(&board)?;
+
+        let mut statement = self.connection.prepare(
+            "SELECT seat, name, stack, hole_cards, sitting_out FROM seats
+             WHERE hand_id = ?1 ORDER BY ordinal",
+        )?;
+        let seats = statement
+            .query_map(params![hand_id], |row| {
+                Ok((
+                    row.get(0)?,
+                    row.get(1)?,
+                    row.get(2)?,
+                    row.get::<_, String>(3)?,
+                    row.get(4)?,
+                ))
+            })?
+            .collect::<Result<Vec<_>, _>>()?;
+        for (seat, name, stack, hole_cards, sitting_out) in seats {
+            record.seats.push(SeatRecord {
+                seat,
+                name,
+                stack,
+                hole_cards: cards(&hole_cards)?,
+                sitting_out,
+            });
+        }
+
+        let mut statement = self.connection.prepare(
+            "SELECT street, player, kind, amount, raise_to, cards, all_in FROM actions
+             WHERE hand_id = ?1 ORDER BY ordinal",
+        )?;
+        let actions = statement
+            .query_map(params![hand_id], |row| {
+                Ok((
+                    row.get::<_, String>(0)?,
+                    row.get(1)?,
+                    row.get::<_, String>(2)?,
+                    row.get::<_, Option<u64>>(3)?,
+                    row.get::<_, Option<u64>>(4)?,
+                    row.get::<_, Option<String>>(5)?,
+                    row.get(6)?,
+                ))
+            })?
+            .collect::<Result<Vec<_>, _>>()?;
+        for (street, player, kind, amount, raise_to, shown, all_in) in actions {
+            let amount = amount.unwrap_or_default();
+            let action = match kind.as_str() {
+                "ante" => RecordedAction::Ante(amount),
+                "small_blind" => RecordedAction::SmallBlind(amount),
+                "big_blind" => RecordedAction::BigBlind(amount),
+                "dead_blinds" => RecordedAction::DeadBlinds(amount),
+                "fold" => RecordedAction::Fold,
+                "check" => RecordedAction::Check,
+                "call" => RecordedAction::Call(amount),
+                "bet" => RecordedAction::Bet(amount),
+                "raise" => RecordedAction::Raise {
+                    by: amount,
+                    to: raise_to.unwrap_or_default(),
+                },
+                "show" => RecordedAction::Show(cards(&shown.unwrap_or_default())?),
+                "muck" => RecordedAction::Muck,
+                "returned" => RecordedAction::Returned(amount),
+                "collected" => RecordedAction::Collected(amount),
+                _ => return Err(StorageError::InvalidData(kind)),
+            };
+            record.actions.push(ActionRecord {
+                street: parse_street(&street)?,
+                player,
+                action,
+                all_in,
+            });
+        }
+        Ok(record)
+    }
+}
+
+/// Which stored hands to find: those with a seat matching every condition set
+///
+/// The player, starting hand and position all apply to the same seat, so a query for a
+/// player holding `AKs` from the button finds the hands where that player had it there.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct HandQuery {
+    site: Option<String>,
+    player: Option<String>,
+    starting_hand: Option<StartingHand>,
+    position: Option<Position>,
+}
+
+impl HandQuery {
+    /// A query matching every hand
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn with_site(mut self, site: impl Into<String>) -> Self {
+        self.site = Some(site.into());
+        self
+    }
+
+    pub fn with_player(mut self, name: impl Into<String>) -> Self {
+        self.player = Some(name.into());
+        self
+    }
+
+    /// Only seats whose hole cards are known and make `starting_hand`
+    pub fn with_starting_hand(mut self, starting_hand: StartingHand) -> Self {
+        self.starting_hand = Some(starting_hand);
+        self
+    }
+
+    pub fn with_position(mut self, position: Position) -> Self {
+        self.position = Some(position);
+        self
+    }
+}
+
+fn insert_seats(
+    transaction: &Transaction,
+    hand_id: i64,
+    record: &HandRecord,
+) -> Result<(), StorageError> {
+    let mut statement = transaction.prepare(
+        "INSERT INTO seats (hand_id, ordinal, seat, name, stack, hole_cards, sitting_out,
+            position, starting_hand)
+         VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9)",
+    )?;
+    for (ordinal, (seat, position)) in record
+        .seats
+        .iter()
+        .zip(Position::of_record(record))
+        .enumerate()
+    {
+        let starting_hand = match seat.hole_cards[..] {
+            [first, second] if first != second => {
+                Some(Combo::new(first, second).starting_hand().to_string())
+            }
+            _ => None,
+        };
+        statement.execute(params![
+            hand_id,
+            ordinal,
+            seat.seat,
+            seat.name,
+            seat.stack,
+            card_text(&seat.hole_cards),
+            seat.sitting_out,
+            position.map(|position| position_name(Some(position))),
+            starting_hand,
+        ])?;
+    }
+    Ok(())
+}
+
+fn insert_actions(
+    transaction: &Transaction,
+    hand_id: i64,
+    record: &HandRecord,
+) -> Result<(), StorageError> {
+    let mut statement = transaction.prepare(
+        "INSERT INTO actions (hand_id, ordinal, street, player, kind, amount, raise_to, cards,
+            all_in)
+         VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9)",
+    )?;
+    for (ordinal, action) in record.actions.iter().enumerate() {
+        let (kind, amount, raise_to, shown) = match &action.action {
+            RecordedAction::Ante(amount) => ("ante", Some(*amount), None, None),
+            RecordedAction::SmallBlind(amount) => ("small_blind", Some(*amount), None, None),
+            RecordedAction::BigBlind(amount) => ("big_blind", Some(*amount), None, None),
+            RecordedAction::DeadBlinds(amount) => ("dead_blinds", Some(*amount), None, None),
+            RecordedAction::Fold => ("fold", None, None, None),
+            RecordedAction::Check => ("check", None, None, None),
+            RecordedAction::Call(amount) => ("call", Some(*amount), None, None),
+            RecordedAction::Bet(amount) => ("bet", Some(*amount), None, None),
+            RecordedAction::Raise { by, to } => ("raise", Some(*by), Some(*to), None),
+            RecordedAction::Show(cards) => ("show", None, None, Some(card_text(cards))),
+            RecordedAction::Muck => ("muck", None, None, None),
+            RecordedAction::Returned(amount) => ("returned", Some(*amount), None, None),
+            RecordedAction::Collected(amount) => ("collected", Some(*amount), None, None),
+        };
+        statement.execute(params![
+            hand_id,
+            ordinal,
+            street_name(action.street),
+            action.player,
+            kind,
+            amount,
+            raise_to,
+            shown,
+            action.all_in,
+        ])?;
+    }
+    Ok(())
+}
+
+// Adds a tracker's stats to the running totals, overall and by position
+fn add_stats(transaction: &Transaction, tracker: &Tracker) -> Result<(), StorageError> {
+    let placeholders: Vec<String> = (3..3 + STAT_COLUMNS.len())
+        .map(|i| format!("?{i}"))
+        .collect();
+    let updates: Vec<String> = STAT_COLUMNS
+        .iter()
+        .map(|column| format!("{column} = {column} + excluded.{column}"))
+        .collect();
+    let mut statement = transaction.prepare(&format!(
+        "INSERT INTO player_stats (name, position, {}) VALUES (?1, ?2, {})
+         ON CONFLICT (name, position) DO UPDATE SET {}",
+        STAT_COLUMNS.join(", "),
+        placeholders.join(", "),
+        updates.join(", ")
+    ))?;
+
+    for (name, total) in tracker.players() {
+        let by_position = tracker
+            .positions(name)
+            .map(|(position, stats)| (Some(position), stats));
+        for (position, stats) in std::iter::once((None, total)).chain(by_position) {
+            let position = position_name(position);
+            let values = stat_values(stats);
+            let parameters = [&name as &dyn rusqlite::ToSql, &position]
+                .into_iter()
+                .chain(values.iter().map(|value| value as &dyn rusqlite::ToSql));
+            statement.execute(params_from_iter(parameters))?;
+        }
+    }
+    Ok(())
+}
+
+// The stats in the order of `STAT_COLUMNS`
+fn stat_values(stats: &PlayerStats) -> [u32; 19] {
+    let PlayerStats {
+        hands,
+        vpip,
+        pfr,
+        three_bet,
+        fold_to_three_bet,
+        aggressive_actions,
+        calls,
+        wtsd,
+        wsd,
+        cbet,
+        fold_to_cbet,
+    } = *stats;
+    [
+        hands,
+        vpip.count,
+        vpip.opportunities,
+        pfr.count,
+        pfr.opportunities,
+        three_bet.count,
+        three_bet.opportunities,
+        fold_to_three_bet.count,
+        fold_to_three_bet.opportunities,
+        aggressive_actions,
+        calls,
+        wtsd.count,
+        wtsd.opportunities,
+        wsd.count,
+        wsd.opportunities,
+        cbet.count,
+        cbet.opportunities,
+        fold_to_cbet.count,
+        fold_to_cbet.opportunities,
+    ]
+}
+
+// Stats read from the `STAT_COLUMNS` starting at column `first`
+fn stats_from(row: &Row, first: usize) -> rusqlite::Result<PlayerStats> {
+    let value = |i: usize| row.get::<_, u32>(first + i);
+    let counter = |i: usize| -> rusqlite::Result<Counter> {
+        Ok(Counter {
+            count: value(i)?,
+            opportunities: value(i + 1)?,
+        })
+    };
+    Ok(PlayerStats {
+        hands: value(0)?,
+        vpip: counter(1)?,
+        pfr: counter(3)?,
+        three_bet: counter(5)?,
+        fold_to_three_bet: counter(7)?,
+        aggressive_actions: value(9)?,
+        calls: value(10)?,
+        wtsd: counter(11)?,
+        wsd: counter(13)?,
+        cbet: counter(15)?,
+        fold_to_cbet: counter(17)?,
+    })
+}
+
+// Cards run together, as in `AhKd`
+fn card_text(cards: &[Card]) -> String {
+    cards.iter().map(Card::to_string).collect()
+}
+
+fn cards(text: &str) -> Result<Vec<Card>, StorageError> {
+    parse_cards(text).map_err(|_| StorageError::InvalidData(text.to_string()))
+}
+
+fn street_name(street: Street) -> &'static str {
+    match street {
+        Street::Preflop => "preflop",
+        Street::Flop => "flop",
+        Street::Turn => "turn",
+        Street::River => "river",
+        Street::Showdown => "showdown",
+    }
+}
+
+fn parse_street(text: &str) -> Result<Street, StorageError> {
+    [
+        Street::Preflop,
+        Street::Flop,
+        Street::Turn,
+        Street::River,
+        Street::Showdown,
+    ]
+    .into_iter()
+    .find(|&street| street_name(street) == text)
+    .ok_or_else(|| StorageError::InvalidData(text.to_string()))
+}
+
+// A position's name in the database, empty for stats over every position
+fn position_name(position: Option<Position>) -> &'static str {
+    match position {
+        None => "",
+        Some(Position::Early) => "early",
+        Some(Position::Middle) => "middle",
+        Some(Position::Hijack) => "hijack",
+        Some(Position::Cutoff) => "cutoff",
+        Some(Position::Button) => "button",
+        Some(Position::SmallBlind) => "small_blind",
+        Some(Position::BigBlind) => "big_blind",
+    }
+}
+
+fn parse_position(text: &str) -> Result<Option<Position>, StorageError> {
+    [
+        None,
+        Some(Position::Early),
+        Some(Position::Middle),
+        Some(Position::Hijack),
+        Some(Position::Cutoff),
+        Some(Position::Button),
+        Some(Position::SmallBlind),
+        Some(Position::BigBlind),
+    ]
+    .into_iter()
+    .find(|&position| position_name(position) == text)
+    .ok_or_else(|| StorageError::InvalidData(text.to_string()))
+}
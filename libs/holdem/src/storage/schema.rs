@@ -0,0 +1,101 @@
+// The schema as a list of migrations, the database's `user_version` counting those applied.
+// Migrations are only ever added to the end, never changed once released.
+pub(super) const MIGRATIONS: &[&str] = &[
+    // 1: hands, their seats and actions, and running player stats
+    "
+    CREATE TABLE hands (
+        id INTEGER PRIMARY KEY,
+        site TEXT NOT NULL,
+        number INTEGER NOT NULL,
+        line INTEGER NOT NULL,
+        game TEXT NOT NULL,
+        small_blind INTEGER NOT NULL,
+        big_blind INTEGER NOT NULL,
+        currency TEXT,
+        date TEXT NOT NULL,
+        table_name TEXT NOT NULL,
+        max_seats INTEGER,
+        button INTEGER,
+        board TEXT NOT NULL,
+        total_pot INTEGER,
+        rake INTEGER,
+        UNIQUE (site, number)
+    );
+
+    CREATE TABLE seats (
+        hand_id INTEGER NOT NULL REFERENCES hands (id) ON DELETE CASCADE,
+        ordinal INTEGER NOT NULL,
+        seat INTEGER NOT NULL,
+        name TEXT NOT NULL,
+        stack INTEGER NOT NULL,
+        hole_cards TEXT NOT NULL,
+        sitting_out INTEGER NOT NULL,
+        position TEXT,
+        starting_hand TEXT,
+        PRIMARY KEY (hand_id, ordinal)
+    );
+    CREATE INDEX seats_name ON seats (name);
+    CREATE INDEX seats_starting_hand ON seats (starting_hand);
+
+    CREATE TABLE actions (
+        hand_id INTEGER NOT NULL REFERENCES hands (id) ON DELETE CASCADE,
+        ordinal INTEGER NOT NULL,
+        street TEXT NOT NULL,
+        player TEXT NOT NULL,
+        kind TEXT NOT NULL,
+        amount INTEGER,
+        raise_to INTEGER,
+        cards TEXT,
+        all_in INTEGER NOT NULL,
+        PRIMARY KEY (hand_id, ordinal)
+    );
+
+    CREATE TABLE player_stats (
+        name TEXT NOT NULL,
+        position TEXT NOT NULL,
+        hands INTEGER NOT NULL,
+        vpip INTEGER NOT NULL,
+        vpip_opportunities INTEGER NOT NULL,
+        pfr INTEGER NOT NULL,
+        pfr_opportunities INTEGER NOT NULL,
+        three_bet INTEGER NOT NULL,
+        three_bet_opportunities INTEGER NOT NULL,
+        fold_to_three_bet INTEGER NOT NULL,
+        fold_to_three_bet_opportunities INTEGER NOT NULL,
+        aggressive_actions INTEGER NOT NULL,
+        calls INTEGER NOT NULL,
+        wtsd INTEGER NOT NULL,
+        wtsd_opportunities INTEGER NOT NULL,
+        wsd INTEGER NOT NULL,
+        wsd_opportunities INTEGER NOT NULL,
+        cbet INTEGER NOT NULL,
+        cbet_opportunities INTEGER NOT NULL,
+        fold_to_cbet INTEGER NOT NULL,
+        fold_to_cbet_opportunities INTEGER NOT NULL,
+        PRIMARY KEY (name, position)
+    );
+    ",
+];
+
+// The `player_stats` columns after the name and position, in the order `stat_values` gives
+pub(super) const STAT_COLUMNS: [&str; 19] = [
+    "hands",
+    "vpip",
+    "vpip_opportunities",
+    "pfr",
+    "pfr_opportunities",
+    "three_bet",
+    "three_bet_opportunities",
+    "fold_to_three_bet",
+    "fold_to_three_bet_opportunities",
+    "aggressive_actions",
+    "calls",
+    "wtsd",
+    "wtsd_opportunities",
+    "wsd",
+    "wsd_opportunities",
+    "cbet",
+    "cbet_opportunities",
+    "fold_to_cbet",
+    "fold_to_cbet_opportunities",
+];
@@ -0,0 +1,36 @@
+use super::super::*;
+use crate::test_support::{arb_game, play_out, read_back, start};
+use proptest::prelude::*;
+
+// Records of random hands, read back from their exported histories
+fn arb_records() -> impl Strategy<Value = Vec<HandRecord>> {
+    prop::collection::vec(arb_game(), 1..5).prop_map(|games| {
+        games
+            .iter()
+            .enumerate()
+            .map(|(id, (stacks, seed, choices))| {
+                read_back(&play_out(start(stacks, *seed), choices), id as u64)
+            })
+            .collect()
+    })
+}
+
+proptest! {
+    #![proptest_config(ProptestConfig::with_cases(64))]
+
+    #[test]
+    fn stored_hands_and_stats_read_back(records in arb_records()) {
+        let mut store = Store::open_in_memory().unwrap();
+        let mut tracker = Tracker::new();
+        for record in &records {
+            prop_assert!(store.insert(record).unwrap());
+            tracker.record(record);
+        }
+
+        for record in &records {
+            let stored = store.hand(&record.site, record.id).unwrap();
+            prop_assert_eq!(stored.as_ref(), Some(record));
+        }
+        prop_assert_eq!(store.stats().unwrap(), tracker);
+    }
+}
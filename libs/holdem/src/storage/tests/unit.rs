@@ -0,0 +1,256 @@
+use super::super::*;
+use crate::history::HistoryReader;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::{env, fs, process};
+
+const HISTORY: &str = "\
+PokerStars Hand #1:  Hold'em No Limit ($0.01/$0.02 USD) - 2024/01/31 9:05:00 ET
+Table 'Alcyone IV' 6-max Seat #4 is the button
+Seat 1: hero ($2.14 in chips)
+Seat 2: ann ($1 in chips) is sitting out
+Seat 4: ben ($1,000.50 in chips)
+hero: posts small blind $0.01
+ben: posts big blind $0.02
+*** HOLE CARDS ***
+Dealt to hero [Th 9h]
+hero: raises $0.04 to $0.06
+ben: folds [2c 7d]
+Uncalled bet ($0.04) returned to hero
+hero collected $0.04 from pot
+*** SUMMARY ***
+Total pot $0.04 | Rake $0
+Seat 1: hero (small blind) collected ($0.04)
+Seat 4: ben (button) (big blind) folded before Flop
+
+PokerStars Hand #2:  Hold'em No Limit ($0.01/$0.02 USD) - 2024/01/31 9:06:00 ET
+Table 'Alcyone IV' 6-max Seat #1 is the button
+Seat 1: hero ($2.18 in chips)
+Seat 4: ben ($1,000.48 in chips)
+hero: posts small blind $0.01
+ben: posts big blind $0.02
+*** HOLE CARDS ***
+Dealt to hero [Ah Kh]
+hero: raises $0.04 to $0.06
+ben: calls $0.04
+*** FLOP *** [Kd 7c 2d]
+ben: checks
+hero: bets $0.08
+ben: calls $0.08
+*** TURN *** [Kd 7c 2d] [5s]
+ben: checks
+hero: checks
+*** RIVER *** [Kd 7c 2d 5s] [Qd]
+ben: bets $0.10 and is all-in
+hero: calls $0.10
+*** SHOW DOWN ***
+ben: shows [Ad Td] (a flush, Ace high)
+hero: mucks hand
+ben collected $0.48 from pot
+*** SUMMARY ***
+Total pot $0.48 | Rake $0
+Board [Kd 7c 2d 5s Qd]
+Seat 1: hero (button) (small blind) mucked [Ah Kh]
+Seat 4: ben (big blind) showed [Ad Td] and won ($0.48) with a flush, Ace high
+
+PokerStars Hand #3:  Hold'em No Limit ($0.01/$0.02 USD) - 2024/01/31 9:07:00 ET
+Table 'Alcyone IV' 6-max Seat #4 is the button
+Seat 1: hero ($1.94 in chips)
+Seat 4: ben ($1,000.72 in chips)
+hero: posts small blind $0.01
+ben: posts big blind $0.02
+*** HOLE CARDS ***
+Dealt to hero [Kc As]
+hero: folds
+Uncalled bet ($0.01) returned to ben
+ben collected $0.02 from pot
+*** SUMMARY ***
+Total pot $0.02 | Rake $0
+Seat 1: hero (small blind) folded before Flop
+Seat 4: ben (button) (big blind) collected ($0.02)
+";
+
+fn records() -> Vec<HandRecord> {
+    HistoryReader::new(HISTORY.as_bytes())
+        .collect::<Result<_, _>>()
+        .unwrap()
+}
+
+fn store() -> Store {
+    let mut store = Store::open_in_memory().unwrap();
+    for record in records() {
+        assert!(store.insert(&record).unwrap());
+    }
+    store
+}
+
+fn ids(hands: &[HandRecord]) -> Vec<u64> {
+    hands.iter().map(|hand| hand.id).collect()
+}
+
+// A database file of its own, removed when dropped
+struct TempFile(std::path::PathBuf);
+
+impl TempFile {
+    fn new() -> Self {
+        static NEXT: AtomicUsize = AtomicUsize::new(0);
+        let name = format!(
+            "holdem-storage-{}-{}.sqlite",
+            process::id(),
+            NEXT.fetch_add(1, Ordering::Relaxed)
+        );
+        TempFile(env::temp_dir().join(name))
+    }
+}
+
+impl Drop for TempFile {
+    fn drop(&mut self) {
+        let _ = fs::remove_file(&self.0);
+    }
+}
+
+#[test]
+fn test_new_database_has_every_migration() {
+    let store = Store::open_in_memory().unwrap();
+    assert_eq!(store.version().unwrap(), MIGRATIONS.len() as u32);
+    assert!(store.is_empty().unwrap());
+    assert_eq!(store.stats().unwrap(), Tracker::new());
+}
+
+#[test]
+fn test_reopening_keeps_the_hands() {
+    let file = TempFile::new();
+    {
+        let mut store = Store::open(&file.0).unwrap();
+        store.insert(&records()[0]).unwrap();
+    }
+    let store = Store::open(&file.0).unwrap();
+    assert_eq!(store.version().unwrap(), MIGRATIONS.len() as u32);
+    assert_eq!(store.len().unwrap(), 1);
+    assert_eq!(
+        store.hand("PokerStars", 1).unwrap(),
+        Some(records()[0].clone())
+    );
+}
+
+#[test]
+fn test_newer_database_is_refused() {
+    let file = TempFile::new();
+    let newer = MIGRATIONS.len() as u32 + 1;
+    Connection::open(&file.0)
+        .unwrap()
+        .pragma_update(None, "user_version", newer)
+        .unwrap();
+
+    let error = Store::open(&file.0).unwrap_err();
+    assert!(matches!(error, StorageError::UnknownVersion(version) if version == newer));
+    assert_eq!(
+        error.to_string(),
+        format!(
+            "the database is at schema version {newer}, newer than the {} known",
+            MIGRATIONS.len()
+        )
+    );
+}
+
+#[test]
+fn test_hands_read_back_as_stored() {
+    let store = store();
+    for record in records() {
+        assert_eq!(store.hand(&record.site, record.id).unwrap(), Some(record));
+    }
+    assert_eq!(store.hand("PokerStars", 4).unwrap(), None);
+    assert_eq!(store.hand("Other", 1).unwrap(), None);
+}
+
+#[test]
+fn test_a_hand_is_stored_once() {
+    let mut store = store();
+    let before = store.stats().unwrap();
+    assert!(!store.insert(&records()[1]).unwrap());
+    assert_eq!(store.len().unwrap(), 3);
+    assert_eq!(store.stats().unwrap(), before);
+
+    // The same id from another site is a different hand
+    let mut other = records()[1].clone();
+    other.site = String::from("Other");
+    assert!(store.insert(&other).unwrap());
+    assert_eq!(store.len().unwrap(), 4);
+}
+
+#[test]
+fn test_query_by_player_starting_hand_and_position() {
+    let store = store();
+    let ak = |suited: &str| -> StartingHand { suited.parse().unwrap() };
+
+    let query = HandQuery::new()
+        .with_player("hero")
+        .with_starting_hand(ak("AKs"))
+        .with_position(Position::Button);
+    assert_eq!(ids(&store.hands(&query).unwrap()), [2]);
+
+    let query = HandQuery::new()
+        .with_player("hero")
+        .with_starting_hand(ak("AKo"));
+    assert_eq!(ids(&store.hands(&query).unwrap()), [3]);
+
+    // Heads up the player in the small blind is on the button
+    let query = HandQuery::new()
+        .with_player("hero")
+        .with_position(Position::BigBlind);
+    assert_eq!(ids(&store.hands(&query).unwrap()), [1, 3]);
+
+    // Cards folded face up count too, but only for the player holding them
+    let query = HandQuery::new().with_starting_hand("72o".parse().unwrap());
+    assert_eq!(ids(&store.hands(&query).unwrap()), [1]);
+    let query = query.with_player("hero");
+    assert!(store.hands(&query).unwrap().is_empty());
+
+    assert_eq!(ids(&store.hands(&HandQuery::new()).unwrap()), [1, 2, 3]);
+    let query = HandQuery::new().with_site("PokerStars").with_player("ann");
+    assert_eq!(ids(&store.hands(&query).unwrap()), [1]);
+    let query = HandQuery::new()
+        .with_player("ann")
+        .with_position(Position::Button);
+    assert!(store.hands(&query).unwrap().is_empty());
+}
+
+#[test]
+fn test_stats_add_up_as_hands_are_stored() {
+    let store = store();
+    let mut tracker = Tracker::new();
+    for record in records() {
+        tracker.record(&record);
+    }
+    assert_eq!(store.stats().unwrap(), tracker);
+
+    let hero = store.player_stats("hero").unwrap().unwrap();
+    assert_eq!(hero.hands, 3);
+    assert_eq!(hero.pfr.count, 2);
+    assert_eq!(hero.cbet.count, 1);
+    assert_eq!(hero.wsd.opportunities, 1);
+    assert_eq!(Some(&hero), tracker.player("hero"));
+    assert_eq!(store.player_stats("nobody").unwrap(), None);
+}
+
+#[test]
+fn test_unreadable_values_are_reported() {
+    let store = store();
+    store
+        .connection
+        .execute(
+            "UPDATE actions SET street = 'preflop?' WHERE ordinal = 0",
+            [],
+        )
+        .unwrap();
+    let error = store.hand("PokerStars", 1).unwrap_err();
+    assert!(matches!(&error, StorageError::InvalidData(value) if value == "preflop?"));
+
+    store
+        .connection
+        .execute("UPDATE hands SET board = 'Kd7' WHERE number = 2", [])
+        .unwrap();
+    assert!(matches!(
+        store.hand("PokerStars", 2),
+        Err(StorageError::InvalidData(_))
+    ));
+}
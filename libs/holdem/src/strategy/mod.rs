@@ -0,0 +1,347 @@
+use cards::Card;
+use cards::strength::StrengthCalculator;
+use rand::rngs::StdRng;
+use rand::{Rng, SeedableRng};
+
+use crate::Chips;
+use crate::betting::{BetRange, BettingStructure, LegalActions};
+use crate::event::Event;
+use crate::hand::{Action, Hand, Player, Street};
+use crate::table::Stakes;
+
+#[cfg(test)]
+mod tests;
+
+/// A way of playing a hand, choosing an action whenever its seat is to act
+///
+/// A strategy only sees what its player could see at the table; see `View`. Any closure
+/// taking a view and returning an action is a strategy too.
+///
+/// # Examples
+///
+/// ```
+/// use cards::Deck;
+/// use holdem::strategy::{AlwaysCall, Strategy, View};
+/// use holdem::table::{Stakes, Table};
+///
+/// let mut table = Table::new(2, Stakes::new(1, 2)).unwrap();
+/// table.sit(0, "ann", 100).unwrap();
+/// table.sit(1, "ben", 100).unwrap();
+///
+/// let mut hand = table.start_hand(Deck::new_seeded(7)).unwrap();
+/// let mut bot = AlwaysCall;
+/// while let Some(seat) = hand.to_act() {
+///     let action = bot.act(&View::new(&hand, seat).unwrap());
+///     hand = hand.apply(seat, action).unwrap();
+/// }
+/// assert!(hand.is_complete());
+/// ```
+pub trait Strategy {
+    /// The action to take in the spot the view shows
+    ///
+    /// The action should be one `view.legal_actions()` allows; an illegal action is
+    /// refused by `Hand::apply` like any other.
+    fn act(&mut self, view: &View<'_>) -> Action;
+}
+
+impl<F> Strategy for F
+where
+    F: FnMut(&View<'_>) -> Action,
+{
+    fn act(&mut self, view: &View<'_>) -> Action {
+        self(view)
+    }
+}
+
+/// What everyone at the table can see of a player dealt in
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct SeatView<'a> {
+    pub seat: usize,
+    pub name: &'a str,
+    pub stack: Chips,
+    /// Chips bet on the current street
+    pub street_bet: Chips,
+    /// Chips put into the pot over the whole hand, antes included
+    pub total_bet: Chips,
+    pub folded: bool,
+}
+
+/// A hand as the player to act sees it: their own hole cards and everything public
+///
+/// Other players' hole cards are hidden, so a strategy cannot peek at them.
+#[derive(Debug, Clone, Copy)]
+pub struct View<'a> {
+    hand: &'a Hand,
+    seat: usize,
+    legal: LegalActions,
+}
+
+impl<'a> View<'a> {
+    /// The view of the player in `seat`, or `None` unless they are the player to act
+    pub fn new(hand: &'a Hand, seat: usize) -> Option<Self> {
+        let legal = hand.legal_actions(seat)?;
+        Some(View { hand, seat, legal })
+    }
+
+    /// The seat of the player to act
+    pub fn seat(&self) -> usize {
+        self.seat
+    }
+
+    pub fn hole_cards(&self) -> [Card; 2] {
+        self.me().hole_cards()
+    }
+
+    pub fn board(&self) -> &'a [Card] {
+        self.hand.board()
+    }
+
+    pub fn street(&self) -> Street {
+        self.hand.street()
+    }
+
+    pub fn stakes(&self) -> Stakes {
+        self.hand.stakes()
+    }
+
+    pub fn betting_structure(&self) -> BettingStructure {
+        self.hand.betting_structure()
+    }
+
+    pub fn button(&self) -> usize {
+        self.hand.button()
+    }
+
+    /// Chips put into the pot so far, including bets on the current street
+    pub fn pot(&self) -> Chips {
+        self.hand.pot()
+    }
+
+    /// The largest bet on the current street
+    pub fn current_bet(&self) -> Chips {
+        self.hand.current_bet()
+    }
+
+    /// Chips the player to act needs to add to call, which may be more than they have
+    pub fn to_call(&self) -> Chips {
+        self.hand.to_call(self.seat)
+    }
+
+    /// Chips the player to act has behind
+    pub fn stack(&self) -> Chips {
+        self.me().stack()
+    }
+
+    pub fn legal_actions(&self) -> LegalActions {
+        self.legal
+    }
+
+    /// Every player dealt in, the player to act included, in seat order
+    pub fn players(&self) -> impl Iterator<Item = SeatView<'a>> + use<'a> {
+        self.hand.players().map(|(seat, player)| SeatView {
+            seat,
+            name: player.name(),
+            stack: player.stack(),
+            street_bet: player.street_bet(),
+            total_bet: player.total_bet(),
+            folded: player.has_folded(),
+        })
+    }
+
+    /// Players other than the one to act who have not folded
+    pub fn opponents_in_hand(&self) -> usize {
+        let seat = self.seat;
+        self.players()
+            .filter(|player| player.seat != seat && !player.folded)
+            .count()
+    }
+
+    /// Everything that has happened in the hand so far, less other players' hole cards
+    pub fn history(&self) -> impl Iterator<Item = &'a Event> + use<'a> {
+        let seat = self.seat;
+        self.hand.events().iter().filter(move |event| match event {
+            Event::HoleCardsDealt { seat: dealt, .. } => *dealt == seat,
+            _ => true,
+        })
+    }
+
+    fn me(&self) -> &'a Player {
+        // `new` only builds a view for a seat with a player to act
+        self.hand
+            .player(self.seat)
+            .expect("the seat to act has a player")
+    }
+}
+
+// Bets or raises to `amount`, whichever the spot calls for
+fn bet_or_raise(legal: &LegalActions, amount: Chips) -> Option<Action> {
+    match (legal.bet, legal.raise) {
+        (Some(range), _) => Some(Action::Bet(clamp(range, amount))),
+        (None, Some(range)) => Some(Action::Raise(clamp(range, amount))),
+        (None, None) => None,
+    }
+}
+
+fn clamp(range: BetRange, amount: Chips) -> Chips {
+    amount.clamp(range.min, range.max)
+}
+
+// Checks when it is free, otherwise calls
+fn check_or_call(legal: &LegalActions) -> Action {
+    if legal.check {
+        Action::Check
+    } else {
+        Action::Call
+    }
+}
+
+/// Checks when it can and calls any bet, never folding or raising
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct AlwaysCall;
+
+impl Strategy for AlwaysCall {
+    fn act(&mut self, view: &View<'_>) -> Action {
+        check_or_call(&view.legal_actions())
+    }
+}
+
+/// Picks one of the legal actions at random, and a random size for a bet or raise
+///
+/// Each kind of action is equally likely. Folding only counts as a choice when there is a
+/// bet to call, since folding instead of checking gives up the pot for nothing.
+#[derive(Debug, Clone)]
+pub struct RandomAction {
+    rng: StdRng,
+}
+
+impl RandomAction {
+    /// Creates a strategy seeded from the operating system
+    pub fn new() -> Self {
+        RandomAction {
+            rng: StdRng::from_os_rng(),
+        }
+    }
+
+    /// Creates a strategy seeded from `seed`, so the same seed always makes the same choices
+    pub fn seeded(seed: u64) -> Self {
+        RandomAction {
+            rng: StdRng::seed_from_u64(seed),
+        }
+    }
+}
+
+impl Default for RandomAction {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Strategy for RandomAction {
+    fn act(&mut self, view: &View<'_>) -> Action {
+        let legal = view.legal_actions();
+        let mut choices = Vec::with_capacity(3);
+        if legal.check {
+            choices.push(Action::Check);
+        } else {
+            choices.push(Action::Fold);
+        }
+        if legal.call.is_some() {
+            choices.push(Action::Call);
+        }
+        if let Some(range) = legal.bet {
+            choices.push(Action::Bet(self.rng.random_range(range.min..=range.max)));
+        }
+        if let Some(range) = legal.raise {
+            choices.push(Action::Raise(self.rng.random_range(range.min..=range.max)));
+        }
+        choices[self.rng.random_range(0..choices.len())]
+    }
+}
+
+/// Plays by how strong its hand is, between 0 and 1
+///
+/// At or above the raise threshold it bets or raises the size of the pot, at or above the
+/// call threshold it checks or calls, and below that it checks or folds. Before the flop
+/// strength is the Chen formula score out of 20; from the flop on it is the chance of
+/// holding the best hand against each opponent still in, as `StrengthCalculator` measures.
+#[derive(Debug)]
+pub struct StrengthThreshold {
+    call: f64,
+    raise: f64,
+    calculator: StrengthCalculator,
+}
+
+impl StrengthThreshold {
+    pub fn new(call: f64, raise: f64) -> Self {
+        StrengthThreshold {
+            call,
+            raise,
+            calculator: StrengthCalculator::new(),
+        }
+    }
+
+    /// The strength of the hand the view shows, between 0 and 1
+    pub fn strength(&self, view: &View<'_>) -> f64 {
+        let hole = view.hole_cards();
+        if view.board().is_empty() {
+            return chen(hole).clamp(0.0, 20.0) / 20.0;
+        }
+        // Ahead of each opponent independently; hole cards and a board dealt from one deck
+        // are always valid
+        let opponents = view.opponents_in_hand().max(1) as i32;
+        self.calculator
+            .hand_strength(&hole, view.board())
+            .map_or(0.0, |strength| strength.powi(opponents))
+    }
+}
+
+impl Strategy for StrengthThreshold {
+    fn act(&mut self, view: &View<'_>) -> Action {
+        let legal = view.legal_actions();
+        let strength = self.strength(view);
+        if strength >= self.raise {
+            let pot_sized = view.current_bet() + view.pot() + view.to_call();
+            if let Some(action) = bet_or_raise(&legal, pot_sized) {
+                return action;
+            }
+        }
+        if strength >= self.call || legal.check {
+            check_or_call(&legal)
+        } else {
+            Action::Fold
+        }
+    }
+}
+
+// Bill Chen's quick score for a starting hand, from -1 for 72o up to 20 for aces
+fn chen(hole: [Card; 2]) -> f64 {
+    let high = hole[0].rank.poker_value().max(hole[1].rank.poker_value());
+    let low = hole[0].rank.poker_value().min(hole[1].rank.poker_value());
+    let points = |value: u8| match value {
+        14 => 10.0,
+        13 => 8.0,
+        12 => 7.0,
+        11 => 6.0,
+        value => f64::from(value) / 2.0,
+    };
+
+    if high == low {
+        return (points(high) * 2.0).max(5.0);
+    }
+    let mut score = points(high);
+    if hole[0].suit == hole[1].suit {
+        score += 2.0;
+    }
+    let gap = high - low - 1;
+    score -= match gap {
+        0 => 0.0,
+        1 => 1.0,
+        2 => 2.0,
+        3 => 4.0,
+        _ => 5.0,
+    };
+    if gap <= 1 && high < 12 {
+        score += 1.0;
+    }
+    score.ceil()
+}
@@ -0,0 +1,51 @@
+use super::super::{AlwaysCall, RandomAction, Strategy, StrengthThreshold, View};
+use crate::Chips;
+use crate::event::Event;
+use crate::hand::Action;
+use crate::test_support::start;
+use proptest::prelude::*;
+
+// One of the reference strategies, picked by `kind`
+fn strategy(kind: u8, seed: u64) -> Box<dyn Strategy> {
+    match kind {
+        0 => Box::new(AlwaysCall),
+        1 => Box::new(RandomAction::seeded(seed)),
+        _ => Box::new(StrengthThreshold::new(0.4, 0.8)),
+    }
+}
+
+proptest! {
+    #![proptest_config(ProptestConfig::with_cases(64))]
+
+    #[test]
+    fn strategies_only_choose_legal_actions(
+        players in prop::collection::vec((1 as Chips..2_000, 0u8..3), 2..=9),
+        seed in any::<u64>(),
+    ) {
+        let stacks: Vec<Chips> = players.iter().map(|&(stack, _)| stack).collect();
+        let mut strategies: Vec<Box<dyn Strategy>> = players
+            .iter()
+            .enumerate()
+            .map(|(seat, &(_, kind))| strategy(kind, seed ^ seat as u64))
+            .collect();
+
+        let mut hand = start(&stacks, seed);
+        while let Some(seat) = hand.to_act() {
+            let view = View::new(&hand, seat).unwrap();
+            let hidden = view.history().all(|event| match event {
+                Event::HoleCardsDealt { seat: dealt, .. } => *dealt == seat,
+                _ => true,
+            });
+            prop_assert!(hidden);
+
+            let action = strategies[seat].act(&view);
+            if players[seat].1 == 0 {
+                prop_assert!(matches!(action, Action::Check | Action::Call));
+            }
+            let next = hand.apply(seat, action);
+            prop_assert!(next.is_ok(), "seat {} chose {:?}: {:?}", seat, action, next);
+            hand = next.unwrap();
+        }
+        prop_assert!(hand.is_complete());
+    }
+}
@@ -0,0 +1,139 @@
+use super::super::*;
+use crate::test_support::{deck, seeded, table};
+use cards::Card;
+use cards::notation::parse_cards;
+
+fn hole(cards: &str) -> [Card; 2] {
+    let cards = parse_cards(cards).unwrap();
+    [cards[0], cards[1]]
+}
+
+// Asks the strategy for the action of the seat to act and applies it
+fn step(hand: &Hand, strategy: &mut impl Strategy) -> (Action, Hand) {
+    let seat = hand.to_act().unwrap();
+    let action = strategy.act(&View::new(hand, seat).unwrap());
+    (action, hand.apply(seat, action).unwrap())
+}
+
+#[test]
+fn test_view_shows_only_what_the_player_can_see() {
+    // Seat 1 is dealt 7c 2h, seat 2 Kh Qh and seat 0, on the button, As Ad
+    let hand = table(&[50, 100, 100])
+        .start_hand(deck("7c Kh As 2h Qh Ad"))
+        .unwrap();
+    assert!(View::new(&hand, 1).is_none());
+
+    let view = View::new(&hand, 0).unwrap();
+    assert_eq!(view.seat(), 0);
+    assert_eq!(view.hole_cards(), hole("As Ad"));
+    assert_eq!(view.street(), Street::Preflop);
+    assert!(view.board().is_empty());
+    assert_eq!((view.pot(), view.current_bet(), view.to_call()), (3, 2, 2));
+    assert_eq!(view.stack(), 50);
+    assert_eq!(view.legal_actions(), hand.legal_actions(0).unwrap());
+    assert_eq!(view.opponents_in_hand(), 2);
+
+    let stacks: Vec<(usize, Chips, Chips)> = view
+        .players()
+        .map(|player| (player.seat, player.stack, player.street_bet))
+        .collect();
+    assert_eq!(stacks, [(0, 50, 0), (1, 99, 1), (2, 98, 2)]);
+
+    let dealt: Vec<&Event> = view
+        .history()
+        .filter(|event| matches!(event, Event::HoleCardsDealt { .. }))
+        .collect();
+    assert_eq!(
+        dealt,
+        [&Event::HoleCardsDealt {
+            seat: 0,
+            cards: hole("As Ad")
+        }]
+    );
+    assert_eq!(view.history().count(), hand.events().len() - 2);
+}
+
+#[test]
+fn test_always_call_reaches_showdown() {
+    let mut hand = table(&[100, 100, 100]).start_hand(seeded(3)).unwrap();
+    while !hand.is_complete() {
+        let action;
+        (action, hand) = step(&hand, &mut AlwaysCall);
+        assert!(matches!(action, Action::Check | Action::Call));
+    }
+    assert_eq!(hand.board().len(), 5);
+    assert!(hand.players().all(|(_, player)| player.total_bet() == 2));
+}
+
+#[test]
+fn test_random_action_is_reproducible_from_a_seed() {
+    let play = |seed| {
+        let mut strategy = RandomAction::seeded(seed);
+        let mut hand = table(&[100, 100, 100, 100]).start_hand(seeded(5)).unwrap();
+        let mut actions = Vec::new();
+        while !hand.is_complete() {
+            let action;
+            (action, hand) = step(&hand, &mut strategy);
+            actions.push(action);
+        }
+        actions
+    };
+    assert_eq!(play(11), play(11));
+    assert!((0..20).any(|seed| play(seed) != play(11)));
+}
+
+#[test]
+fn test_chen_scores() {
+    assert_eq!(chen(hole("As Ad")), 20.0);
+    assert_eq!(chen(hole("Kc Kd")), 16.0);
+    assert_eq!(chen(hole("2c 2d")), 5.0);
+    assert_eq!(chen(hole("Ah Kh")), 12.0);
+    assert_eq!(chen(hole("Kd Ac")), 10.0);
+    assert_eq!(chen(hole("Ts 9s")), 8.0);
+    assert_eq!(chen(hole("Jc 9c")), 8.0);
+    assert_eq!(chen(hole("7c 2h")), -1.0);
+}
+
+#[test]
+fn test_strength_threshold_raises_calls_and_folds() {
+    let hand = table(&[100, 100, 100])
+        .start_hand(deck("7c Kh As 2h Qh Ad"))
+        .unwrap();
+    let mut strategy = StrengthThreshold::new(0.5, 0.9);
+
+    // Aces raise the size of the pot: 2 to call, then 5 more
+    let (action, hand) = step(&hand, &mut strategy);
+    assert_eq!(action, Action::Raise(7));
+    // 72o scores nothing and folds to the raise
+    let (action, hand) = step(&hand, &mut strategy);
+    assert_eq!(action, Action::Fold);
+    // KQs scores 10 of 20, just enough to call
+    let view = View::new(&hand, 2).unwrap();
+    assert_eq!(strategy.strength(&view), 0.5);
+    let (action, hand) = step(&hand, &mut strategy);
+    assert_eq!(action, Action::Call);
+    assert_eq!(hand.street(), Street::Flop);
+
+    // From the flop on strength is measured against the board
+    let view = View::new(&hand, 2).unwrap();
+    let strength = strategy.strength(&view);
+    assert!((0.0..=1.0).contains(&strength));
+}
+
+#[test]
+fn test_strength_threshold_checks_when_it_is_free() {
+    // Seat 1 posts the big blind heads up holding 7c 2h
+    let hand = table(&[100, 100]).start_hand(deck("7c As 2h Ad")).unwrap();
+    let hand = hand.apply(0, Action::Call).unwrap();
+    let (action, _) = step(&hand, &mut StrengthThreshold::new(0.5, 0.9));
+    assert_eq!(action, Action::Check);
+}
+
+#[test]
+fn test_closures_are_strategies() {
+    let hand = table(&[100, 100]).start_hand(seeded(1)).unwrap();
+    let mut folds = |_: &View<'_>| Action::Fold;
+    let (action, hand) = step(&hand, &mut folds);
+    assert_eq!(action, Action::Fold);
+    assert!(hand.is_complete());
+}
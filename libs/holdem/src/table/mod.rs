@@ -0,0 +1,301 @@
+use std::fmt;
+
+use cards::Deck;
+
+use crate::Chips;
+use crate::betting::BettingStructure;
+use crate::hand::Hand;
+use crate::pot::OddChipRule;
+
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
+#[cfg(test)]
+mod tests;
+
+/// Most seats a table can have
+pub const MAX_SEATS: usize = 10;
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum TableError {
+    /// A table needs between 2 and `MAX_SEATS` seats
+    InvalidSeatCount(usize),
+    /// The big blind must be positive and at least the small blind
+    InvalidStakes(Stakes),
+    /// A limit structure with a zero bet or cap, or a spread whose maximum is below its minimum
+    InvalidStructure(BettingStructure),
+    /// A seat that is not at the table
+    InvalidSeat(usize),
+    /// The seat already has a player
+    SeatTaken(usize),
+    /// The seat has no player
+    SeatEmpty(usize),
+    /// Fewer than two players have chips
+    NotEnoughPlayers(usize),
+    /// The deck cannot deal every hole card and the board
+    NotEnoughCards { needed: usize, available: usize },
+    /// The hand has not reached its end
+    HandNotComplete,
+}
+
+impl fmt::Display for TableError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            TableError::InvalidSeatCount(seats) => {
+                write!(f, "a table has 2 to {MAX_SEATS} seats, got {seats}")
+            }
+            TableError::InvalidStakes(stakes) => write!(
+                f,
+                "blinds {}/{} are not valid",
+                stakes.small_blind, stakes.big_blind
+            ),
+            TableError::InvalidStructure(structure) => {
+                write!(f, "betting structure {structure:?} is not valid")
+            }
+            TableError::InvalidSeat(seat) => write!(f, "seat {seat} is not at the table"),
+            TableError::SeatTaken(seat) => write!(f, "seat {seat} is already taken"),
+            TableError::SeatEmpty(seat) => write!(f, "seat {seat} is empty"),
+            TableError::NotEnoughPlayers(count) => {
+                write!(f, "a hand needs at least 2 players with chips, got {count}")
+            }
+            TableError::NotEnoughCards { needed, available } => write!(
+                f,
+                "dealing needs {needed} cards but the deck has {available}"
+            ),
+            TableError::HandNotComplete => write!(f, "the hand is still being played"),
+        }
+    }
+}
+
+impl std::error::Error for TableError {}
+
+/// The forced bets posted at the start of each hand
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct Stakes {
+    pub small_blind: Chips,
+    pub big_blind: Chips,
+    /// Posted by every player before the blinds, and not counted towards their bet
+    pub ante: Chips,
+}
+
+impl Stakes {
+    pub fn new(small_blind: Chips, big_blind: Chips) -> Self {
+        Self {
+            small_blind,
+            big_blind,
+            ante: 0,
+        }
+    }
+
+    pub fn with_ante(mut self, ante: Chips) -> Self {
+        self.ante = ante;
+        self
+    }
+}
+
+/// A player sitting at the table between hands
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct Seat {
+    name: String,
+    stack: Chips,
+}
+
+impl Seat {
+    pub fn new(name: impl Into<String>, stack: Chips) -> Self {
+        Self {
+            name: name.into(),
+            stack,
+        }
+    }
+
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    pub fn stack(&self) -> Chips {
+        self.stack
+    }
+}
+
+/// Seats, stacks and the button from one hand to the next
+///
+/// Seats are numbered clockwise from 0. Each hand moves the button to the next seat with
+/// chips, and players without chips sit out until they have some again.
+///
+/// # Examples
+///
+/// ```
+/// use cards::Deck;
+/// use holdem::hand::Action;
+/// use holdem::table::{Stakes, Table};
+///
+/// let mut table = Table::new(6, Stakes::new(5, 10)).unwrap();
+/// table.sit(0, "alice", 1_000).unwrap();
+/// table.sit(3, "bob", 1_000).unwrap();
+///
+/// // Heads up the button posts the small blind and acts first before the flop
+/// let hand = table.start_hand(Deck::new_shuffled()).unwrap();
+/// assert_eq!(hand.to_act(), Some(0));
+///
+/// let hand = hand.apply(0, Action::Fold).unwrap();
+/// assert!(hand.is_complete());
+///
+/// table.finish_hand(&hand).unwrap();
+/// assert_eq!(table.seat(3).unwrap().stack(), 1_005);
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Table {
+    seats: Vec<Option<Seat>>,
+    button: Option<usize>,
+    stakes: Stakes,
+    structure: BettingStructure,
+    odd_chips: OddChipRule,
+    hi_lo: bool,
+}
+
+impl Table {
+    /// Creates an empty table with `seats` seats
+    pub fn new(seats: usize, stakes: Stakes) -> Result<Self, TableError> {
+        if !(2..=MAX_SEATS).contains(&seats) {
+            return Err(TableError::InvalidSeatCount(seats));
+        }
+        check_stakes(stakes)?;
+        Ok(Self {
+            seats: vec![None; seats],
+            button: None,
+            stakes,
+            structure: BettingStructure::default(),
+            odd_chips: OddChipRule::default(),
+            hi_lo: false,
+        })
+    }
+
+    /// Sets how much players may bet and raise, no limit unless set
+    pub fn with_betting_structure(mut self, structure: BettingStructure) -> Self {
+        self.structure = structure;
+        self
+    }
+
+    /// Sets how odd chips are handed out when a pot is split, left of the button unless set
+    pub fn with_odd_chip_rule(mut self, rule: OddChipRule) -> Self {
+        self.odd_chips = rule;
+        self
+    }
+
+    /// Splits each pot between the best high hand and the best eight-or-better low
+    pub fn with_hi_lo(mut self, hi_lo: bool) -> Self {
+        self.hi_lo = hi_lo;
+        self
+    }
+
+    pub fn stakes(&self) -> Stakes {
+        self.stakes
+    }
+
+    /// Changes the blinds and ante from the next hand on, as when a tournament level rises
+    pub fn set_stakes(&mut self, stakes: Stakes) -> Result<(), TableError> {
+        check_stakes(stakes)?;
+        self.stakes = stakes;
+        Ok(())
+    }
+
+    pub fn betting_structure(&self) -> BettingStructure {
+        self.structure
+    }
+
+    pub fn odd_chip_rule(&self) -> OddChipRule {
+        self.odd_chips
+    }
+
+    pub fn is_hi_lo(&self) -> bool {
+        self.hi_lo
+    }
+
+    /// Every seat, empty or not
+    pub fn seats(&self) -> &[Option<Seat>] {
+        &self.seats
+    }
+
+    pub fn seat(&self, seat: usize) -> Option<&Seat> {
+        self.seats.get(seat).and_then(Option::as_ref)
+    }
+
+    /// The seat that had the button in the last hand, if any hand has been dealt
+    pub fn button(&self) -> Option<usize> {
+        self.button
+    }
+
+    /// Seats a player with a stack of chips
+    pub fn sit(
+        &mut self,
+        seat: usize,
+        name: impl Into<String>,
+        stack: Chips,
+    ) -> Result<(), TableError> {
+        match self.seats.get_mut(seat) {
+            None => Err(TableError::InvalidSeat(seat)),
+            Some(Some(_)) => Err(TableError::SeatTaken(seat)),
+            Some(empty) => {
+                *empty = Some(Seat::new(name, stack));
+                Ok(())
+            }
+        }
+    }
+
+    /// Removes the player from a seat, returning them with their chips
+    pub fn leave(&mut self, seat: usize) -> Result<Seat, TableError> {
+        self.seats
+            .get_mut(seat)
+            .ok_or(TableError::InvalidSeat(seat))?
+            .take()
+            .ok_or(TableError::SeatEmpty(seat))
+    }
+
+    /// Moves the button, posts the blinds and deals from the top of `deck`
+    ///
+    /// The deck is dealt as given, so shuffle it first. Jokers are left out of the deal. The
+    /// table is only changed if the hand could be dealt.
+    pub fn start_hand(&mut self, deck: Deck) -> Result<Hand, TableError> {
+        if !self.structure.is_valid() {
+            return Err(TableError::InvalidStructure(self.structure));
+        }
+        let live = self.seats.iter().flatten().filter(|s| s.stack > 0).count();
+        if live < 2 {
+            return Err(TableError::NotEnoughPlayers(live));
+        }
+        let count = self.seats.len();
+        let start = self.button.map_or(0, |button| button + 1);
+        let button = (start..start + count)
+            .map(|seat| seat % count)
+            .find(|&seat| self.seat(seat).is_some_and(|s| s.stack > 0))
+            .ok_or(TableError::NotEnoughPlayers(live))?;
+
+        let hand = Hand::deal(self, button, deck)?;
+        self.button = Some(button);
+        Ok(hand)
+    }
+
+    /// Updates the stacks from a finished hand
+    pub fn finish_hand(&mut self, hand: &Hand) -> Result<(), TableError> {
+        if !hand.is_complete() {
+            return Err(TableError::HandNotComplete);
+        }
+        for (seat, player) in hand.players() {
+            if let Some(Some(occupant)) = self.seats.get_mut(seat)
+                && occupant.name == player.name()
+            {
+                occupant.stack = player.stack();
+            }
+        }
+        Ok(())
+    }
+}
+
+fn check_stakes(stakes: Stakes) -> Result<(), TableError> {
+    if stakes.big_blind == 0 || stakes.small_blind > stakes.big_blind {
+        return Err(TableError::InvalidStakes(stakes));
+    }
+    Ok(())
+}
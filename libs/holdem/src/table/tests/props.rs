@@ -0,0 +1,28 @@
+use super::super::*;
+use crate::hand::Action;
+use proptest::prelude::*;
+
+proptest! {
+    #[test]
+    fn button_visits_every_player_in_turn(
+        occupied in prop::collection::btree_set(0usize..MAX_SEATS, 2..=MAX_SEATS),
+    ) {
+        let mut table = Table::new(MAX_SEATS, Stakes::new(1, 2)).unwrap();
+        for &seat in &occupied {
+            table.sit(seat, format!("p{seat}"), 1_000).unwrap();
+        }
+
+        let mut buttons = Vec::new();
+        for _ in 0..occupied.len() {
+            let mut hand = table.start_hand(Deck::new()).unwrap();
+            buttons.push(hand.button());
+            while let Some(seat) = hand.to_act() {
+                hand = hand.apply(seat, Action::Fold).unwrap();
+            }
+            table.finish_hand(&hand).unwrap();
+        }
+        prop_assert_eq!(buttons, occupied.into_iter().collect::<Vec<_>>());
+        let total: Chips = table.seats().iter().flatten().map(Seat::stack).sum();
+        prop_assert_eq!(total, 1_000 * table.seats().iter().flatten().count() as Chips);
+    }
+}
@@ -0,0 +1,202 @@
+use super::super::*;
+use crate::hand::Action;
+
+fn stakes() -> Stakes {
+    Stakes::new(1, 2)
+}
+
+#[test]
+fn test_new_table_validates_seats_and_stakes() {
+    assert_eq!(
+        Table::new(1, stakes()).unwrap_err(),
+        TableError::InvalidSeatCount(1)
+    );
+    assert_eq!(
+        Table::new(MAX_SEATS + 1, stakes()).unwrap_err(),
+        TableError::InvalidSeatCount(MAX_SEATS + 1)
+    );
+    assert_eq!(
+        Table::new(6, Stakes::new(5, 2)).unwrap_err(),
+        TableError::InvalidStakes(Stakes::new(5, 2))
+    );
+    assert_eq!(
+        Table::new(6, Stakes::new(0, 0)).unwrap_err(),
+        TableError::InvalidStakes(Stakes::new(0, 0))
+    );
+    assert!(Table::new(6, Stakes::new(0, 2)).is_ok());
+}
+
+#[test]
+fn test_set_stakes_applies_to_the_next_hand() {
+    let mut table = Table::new(6, stakes()).unwrap();
+    table.sit(0, "ann", 500).unwrap();
+    table.sit(1, "ben", 500).unwrap();
+
+    assert_eq!(
+        table.set_stakes(Stakes::new(10, 5)).unwrap_err(),
+        TableError::InvalidStakes(Stakes::new(10, 5))
+    );
+    assert_eq!(table.stakes(), stakes());
+
+    let raised = Stakes::new(10, 20).with_ante(5);
+    table.set_stakes(raised).unwrap();
+    let hand = table.start_hand(Deck::new()).unwrap();
+    assert_eq!(hand.stakes(), raised);
+    assert_eq!(hand.pot(), 40);
+}
+
+#[test]
+fn test_sit_and_leave() {
+    let mut table = Table::new(6, stakes()).unwrap();
+    table.sit(2, "ann", 500).unwrap();
+
+    assert_eq!(
+        table.sit(2, "ben", 500).unwrap_err(),
+        TableError::SeatTaken(2)
+    );
+    assert_eq!(
+        table.sit(6, "ben", 500).unwrap_err(),
+        TableError::InvalidSeat(6)
+    );
+    assert_eq!(table.seat(2).unwrap().name(), "ann");
+    assert_eq!(table.seats().iter().flatten().count(), 1);
+
+    let seat = table.leave(2).unwrap();
+    assert_eq!(seat, Seat::new("ann", 500));
+    assert_eq!(table.leave(2).unwrap_err(), TableError::SeatEmpty(2));
+    assert_eq!(table.leave(9).unwrap_err(), TableError::InvalidSeat(9));
+}
+
+#[test]
+fn test_start_hand_needs_two_players_with_chips() {
+    let mut table = Table::new(6, stakes()).unwrap();
+    table.sit(0, "ann", 500).unwrap();
+    table.sit(1, "ben", 0).unwrap();
+
+    assert_eq!(
+        table.start_hand(Deck::new()).unwrap_err(),
+        TableError::NotEnoughPlayers(1)
+    );
+    assert_eq!(table.button(), None);
+}
+
+#[test]
+fn test_start_hand_needs_enough_cards() {
+    let mut table = Table::new(6, stakes()).unwrap();
+    table.sit(0, "ann", 500).unwrap();
+    table.sit(1, "ben", 500).unwrap();
+    let mut deck = Deck::new();
+    while deck.len() > 11 {
+        deck.draw();
+    }
+
+    assert_eq!(
+        table.start_hand(deck).unwrap_err(),
+        TableError::NotEnoughCards {
+            needed: 12,
+            available: 11
+        }
+    );
+    assert_eq!(table.button(), None);
+}
+
+#[test]
+fn test_button_moves_to_the_next_player_with_chips() {
+    let mut table = Table::new(6, stakes()).unwrap();
+    table.sit(1, "ann", 500).unwrap();
+    table.sit(3, "ben", 0).unwrap();
+    table.sit(4, "cat", 500).unwrap();
+    table.sit(5, "dan", 500).unwrap();
+
+    let mut buttons = Vec::new();
+    for _ in 0..4 {
+        let hand = table.start_hand(Deck::new()).unwrap();
+        assert!(hand.player(3).is_none());
+        buttons.push(hand.button());
+    }
+    assert_eq!(buttons, [1, 4, 5, 1]);
+    assert_eq!(table.button(), Some(1));
+}
+
+#[test]
+fn test_finish_hand_updates_stacks() {
+    let mut table = Table::new(3, stakes()).unwrap();
+    for (seat, name) in ["ann", "ben", "cat"].into_iter().enumerate() {
+        table.sit(seat, name, 100).unwrap();
+    }
+    let hand = table.start_hand(Deck::new()).unwrap();
+    assert_eq!(
+        table.finish_hand(&hand).unwrap_err(),
+        TableError::HandNotComplete
+    );
+
+    let hand = hand.apply(0, Action::Raise(10)).unwrap();
+    let hand = hand.apply(1, Action::Fold).unwrap();
+    let hand = hand.apply(2, Action::Fold).unwrap();
+    table.finish_hand(&hand).unwrap();
+
+    let stacks: Vec<Chips> = table.seats().iter().flatten().map(Seat::stack).collect();
+    assert_eq!(stacks, [103, 99, 98]);
+}
+
+#[test]
+fn test_busted_players_sit_out() {
+    let mut table = Table::new(3, stakes()).unwrap();
+    table.sit(0, "ann", 100).unwrap();
+    table.sit(1, "ben", 50).unwrap();
+    table.sit(2, "cat", 100).unwrap();
+
+    // An unshuffled deck gives ben the king-high flush and cat the queen-high flush, so ben
+    // takes the main pot, cat the side pot and ann is left with nothing
+    let mut hand = table.start_hand(Deck::new()).unwrap();
+    while let Some(seat) = hand.to_act() {
+        hand = hand.apply(seat, Action::AllIn).unwrap();
+    }
+    table.finish_hand(&hand).unwrap();
+    let stacks: Vec<Chips> = table.seats().iter().flatten().map(Seat::stack).collect();
+    assert_eq!(stacks, [0, 150, 100]);
+
+    let next = table.start_hand(Deck::new()).unwrap();
+    assert!(next.player(0).is_none());
+    assert_eq!(next.players().count(), 2);
+    assert_eq!(next.button(), 1);
+}
+
+#[cfg(feature = "jokers")]
+#[test]
+fn test_jokers_are_left_out_of_the_deal() {
+    use cards::{Card, Rank};
+
+    let mut table = Table::new(3, stakes()).unwrap();
+    for (seat, name) in ["ann", "ben", "cat"].into_iter().enumerate() {
+        table.sit(seat, name, 100).unwrap();
+    }
+    let mut with_jokers = Deck::new_seeded(9).cards().to_vec();
+    with_jokers.insert(with_jokers.len() - 3, Card::new(Rank::Joker, None));
+    assert!(with_jokers.iter().any(Card::is_joker));
+    let suited: Vec<Card> = with_jokers
+        .iter()
+        .filter(|card| !card.is_joker())
+        .copied()
+        .collect();
+
+    let mut hand = table.start_hand(Deck::from_cards(with_jokers)).unwrap();
+    while let Some(seat) = hand.to_act() {
+        hand = hand.apply(seat, Action::AllIn).unwrap();
+    }
+    for (_, player) in hand.players() {
+        assert!(player.hole_cards().iter().all(|card| card.suit.is_some()));
+    }
+    assert!(hand.board().iter().all(|card| card.suit.is_some()));
+
+    // The hand is dealt as if the jokers were never in the deck
+    let mut again = Table::new(3, stakes()).unwrap();
+    for (seat, name) in ["ann", "ben", "cat"].into_iter().enumerate() {
+        again.sit(seat, name, 100).unwrap();
+    }
+    let mut expected = again.start_hand(Deck::from_cards(suited)).unwrap();
+    while let Some(seat) = expected.to_act() {
+        expected = expected.apply(seat, Action::AllIn).unwrap();
+    }
+    assert_eq!(hand.board(), expected.board());
+}
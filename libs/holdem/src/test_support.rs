@@ -0,0 +1,115 @@
+use cards::notation::parse_cards;
+use cards::{Card, Deck};
+use proptest::prelude::*;
+
+use crate::Chips;
+use crate::hand::{Action, Hand};
+use crate::history::{Export, HandRecord, HistoryReader};
+use crate::table::{Stakes, Table};
+
+// The 52 suited cards, so hands deal the same whether or not jokers are enabled
+fn standard() -> Vec<Card> {
+    Deck::new()
+        .cards()
+        .iter()
+        .filter(|card| card.suit.is_some())
+        .copied()
+        .collect()
+}
+
+// A standard deck shuffled from `seed`
+pub(crate) fn seeded(seed: u64) -> Deck {
+    let mut deck = Deck::from_cards(standard());
+    deck.shuffle_seeded(seed);
+    deck
+}
+
+// A deck that deals `top` first, in order, with the rest of the cards beneath
+pub(crate) fn deck(top: &str) -> Deck {
+    let top = parse_cards(top).unwrap();
+    let mut cards: Vec<Card> = standard()
+        .into_iter()
+        .filter(|card| !top.contains(card))
+        .collect();
+    cards.extend(top.iter().rev());
+    Deck::from_cards(cards)
+}
+
+// Seats `p0`, `p1`, ... with the given stacks
+pub(crate) fn seated(stakes: Stakes, stacks: &[Chips]) -> Table {
+    let mut table = Table::new(stacks.len().max(2), stakes).unwrap();
+    for (seat, &stack) in stacks.iter().enumerate() {
+        table.sit(seat, format!("p{seat}"), stack).unwrap();
+    }
+    table
+}
+
+// Seats `p0`, `p1`, ... with the given stacks at 1/2 blinds
+pub(crate) fn table(stacks: &[Chips]) -> Table {
+    seated(Stakes::new(1, 2), stacks)
+}
+
+pub(crate) fn play(mut hand: Hand, actions: &[(usize, Action)]) -> Hand {
+    for &(seat, action) in actions {
+        hand = hand.apply(seat, action).unwrap();
+    }
+    hand
+}
+
+// Deals a hand at 5/10 with a 1 ante from a deck shuffled from `seed`
+pub(crate) fn start(stacks: &[Chips], seed: u64) -> Hand {
+    seated(Stakes::new(5, 10).with_ante(1), stacks)
+        .start_hand(seeded(seed))
+        .unwrap()
+}
+
+// Turns a choice and a size into an action, sized between the minimum raise and all in
+pub(crate) fn action(hand: &Hand, seat: usize, choice: u8, size: f64) -> Action {
+    let player = hand.player(seat).unwrap();
+    let most = player.street_bet() + player.stack();
+    let least = hand.min_raise_to().min(most);
+    let amount = least + ((most - least) as f64 * size) as Chips;
+    match choice {
+        0 => Action::Fold,
+        1 => Action::Check,
+        2 => Action::Call,
+        3 if hand.current_bet() == 0 => Action::Bet(amount),
+        3 => Action::Raise(amount),
+        _ => Action::AllIn,
+    }
+}
+
+// Plays a hand to the end, falling back to checking, calling or folding when the chosen
+// action is not allowed
+pub(crate) fn play_out(mut hand: Hand, choices: &[(u8, f64)]) -> Hand {
+    let mut choices = choices.iter().cycle();
+    while let Some(seat) = hand.to_act() {
+        let &(choice, size) = choices.next().unwrap();
+        let chosen = action(&hand, seat, choice, size);
+        hand = [chosen, Action::Check, Action::Call, Action::Fold]
+            .into_iter()
+            .find_map(|action| hand.apply(seat, action).ok())
+            .unwrap();
+    }
+    hand
+}
+
+// Stacks, a deck seed and the choices to play with
+pub(crate) type Game = (Vec<Chips>, u64, Vec<(u8, f64)>);
+
+pub(crate) fn arb_game() -> impl Strategy<Value = Game> {
+    (
+        prop::collection::vec(1 as Chips..2_000, 2..=9),
+        any::<u64>(),
+        prop::collection::vec((0u8..5, 0.0f64..1.0), 1..40),
+    )
+}
+
+// Exports a finished hand and reads the history back
+pub(crate) fn read_back(hand: &Hand, id: u64) -> HandRecord {
+    let history = Export::new(hand, id).unwrap().to_string();
+    HistoryReader::new(history.as_bytes())
+        .next()
+        .unwrap()
+        .unwrap()
+}
@@ -0,0 +1,579 @@
+use std::fmt;
+use std::time::Duration;
+
+use cards::Deck;
+use cards::icm::{IcmCalculator, IcmError};
+use rand::SeedableRng;
+use rand::rngs::StdRng;
+use rand::seq::SliceRandom;
+
+use crate::Chips;
+use crate::hand::Hand;
+use crate::table::{MAX_SEATS, Seat, Table, TableError};
+
+mod payouts;
+mod schedule;
+
+pub use payouts::Payouts;
+pub use schedule::{BlindSchedule, Level, LevelStatus, PRESET_STACK};
+
+#[cfg(test)]
+mod tests;
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum TournamentError {
+    /// A table could not be set up or dealt
+    Table(TableError),
+    /// A tournament needs at least two entrants
+    NotEnoughEntrants(usize),
+    /// An entrant with this name is already registered
+    DuplicateName(String),
+    /// Entrants can only register before the start
+    AlreadyStarted,
+    /// Hands can only be dealt after the start
+    NotStarted,
+    /// A table that is not in play
+    InvalidTable(usize),
+    /// The table is already playing a hand
+    HandInProgress(usize),
+    /// The table is not playing a hand
+    NoHandInProgress(usize),
+    /// No hands are dealt during a break
+    OnBreak,
+    /// Only one player is left
+    Finished,
+    /// A schedule needs at least one blind level
+    InvalidSchedule,
+    /// Payouts need at least one place, and every place a share
+    InvalidPayouts,
+}
+
+impl fmt::Display for TournamentError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            TournamentError::Table(e) => write!(f, "{e}"),
+            TournamentError::NotEnoughEntrants(count) => {
+                write!(f, "a tournament needs at least 2 entrants, got {count}")
+            }
+            TournamentError::DuplicateName(name) => write!(f, "{name} is already registered"),
+            TournamentError::AlreadyStarted => write!(f, "the tournament has already started"),
+            TournamentError::NotStarted => write!(f, "the tournament has not started"),
+            TournamentError::InvalidTable(table) => write!(f, "table {table} is not in play"),
+            TournamentError::HandInProgress(table) => {
+                write!(f, "table {table} is already playing a hand")
+            }
+            TournamentError::NoHandInProgress(table) => {
+                write!(f, "table {table} is not playing a hand")
+            }
+            TournamentError::OnBreak => write!(f, "the tournament is on a break"),
+            TournamentError::Finished => write!(f, "the tournament is over"),
+            TournamentError::InvalidSchedule => {
+                write!(f, "a blind schedule needs at least one blind level")
+            }
+            TournamentError::InvalidPayouts => {
+                write!(f, "payouts need at least one place and no empty shares")
+            }
+        }
+    }
+}
+
+impl std::error::Error for TournamentError {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            TournamentError::Table(e) => Some(e),
+            _ => None,
+        }
+    }
+}
+
+impl From<TableError> for TournamentError {
+    fn from(e: TableError) -> Self {
+        TournamentError::Table(e)
+    }
+}
+
+/// Where a player finished and what they won
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Finish {
+    pub name: String,
+    /// 1 for the winner; players who tie share the best of their places
+    pub place: usize,
+    pub prize: u64,
+}
+
+/// A player moved to another table to break or balance tables
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Move {
+    pub name: String,
+    /// Table and seat the player left
+    pub from: (usize, usize),
+    /// Table and seat the player took
+    pub to: (usize, usize),
+}
+
+/// What finishing a hand did to the tournament
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct HandReport {
+    /// Players knocked out in the hand, best finish first, and the winner if only one is left
+    pub finishes: Vec<Finish>,
+    /// The table the hand was played at, if it was broken up
+    pub broken: Option<usize>,
+    pub moves: Vec<Move>,
+}
+
+#[derive(Debug, Clone)]
+struct TableState {
+    table: Table,
+    busy: bool,
+}
+
+impl TableState {
+    fn players(&self) -> usize {
+        self.table.seats().iter().flatten().count()
+    }
+}
+
+/// A multi-table tournament: its blind clock, tables, knockouts and prizes
+///
+/// Entrants register, then `start` seats them at random across as few tables as hold them,
+/// as evenly as possible. Each table deals its own hands at the stakes of the current level,
+/// and the clock only moves when `advance` is called, so the tournament can be run in real
+/// time or simulated.
+///
+/// Players who lose their chips are knocked out when their hand is finished. Players who
+/// bust in the same hand are placed by their stacks at the start of it, and those who
+/// started with the same stack tie, sharing the prizes of the places they cover. Whenever
+/// the players left would fit at fewer tables, the table that just finished a hand is
+/// broken and its players take the empty seats at the shortest tables. Otherwise, players
+/// move from it to the shortest table until it has at most one more player. Players are
+/// only ever moved from the table that just finished its hand, so no one leaves a hand in
+/// progress.
+///
+/// # Examples
+///
+/// ```
+/// use cards::Deck;
+/// use holdem::hand::Action;
+/// use holdem::tournament::{BlindSchedule, Payouts, Tournament};
+///
+/// let mut tournament = Tournament::new(BlindSchedule::turbo(), Payouts::for_entrants(2), 6)
+///     .unwrap()
+///     .with_buy_in(10);
+/// tournament.register("alice").unwrap();
+/// tournament.register("bob").unwrap();
+/// tournament.start(1_500, 7).unwrap();
+///
+/// // Play all in until someone wins
+/// while !tournament.is_complete() {
+///     let mut hand = tournament.start_hand(0, Deck::new_shuffled()).unwrap();
+///     while let Some(seat) = hand.to_act() {
+///         hand = hand.apply(seat, Action::AllIn).unwrap();
+///     }
+///     tournament.finish_hand(0, &hand).unwrap();
+/// }
+///
+/// let results = tournament.results();
+/// assert_eq!(results[0].place, 1);
+/// assert_eq!(results[0].prize, 20);
+/// ```
+#[derive(Debug, Clone)]
+pub struct Tournament {
+    schedule: BlindSchedule,
+    payouts: Payouts,
+    seats_per_table: usize,
+    buy_in: u64,
+    entrants: Vec<String>,
+    started: bool,
+    elapsed: Duration,
+    tables: Vec<Option<TableState>>,
+    prizes: Vec<u64>,
+    finishes: Vec<Finish>,
+}
+
+impl Tournament {
+    /// Creates a tournament with tables of `seats_per_table` seats
+    pub fn new(
+        schedule: BlindSchedule,
+        payouts: Payouts,
+        seats_per_table: usize,
+    ) -> Result<Self, TournamentError> {
+        if !(2..=MAX_SEATS).contains(&seats_per_table) {
+            return Err(TableError::InvalidSeatCount(seats_per_table).into());
+        }
+        if schedule.status(Duration::ZERO).is_none() {
+            return Err(TournamentError::InvalidSchedule);
+        }
+        // Every level must be playable, not just the first
+        for level in schedule.levels() {
+            if let Level::Blinds { stakes, .. } = level {
+                Table::new(seats_per_table, *stakes)?;
+            }
+        }
+        Ok(Self {
+            schedule,
+            payouts,
+            seats_per_table,
+            buy_in: 0,
+            entrants: Vec::new(),
+            started: false,
+            elapsed: Duration::ZERO,
+            tables: Vec::new(),
+            prizes: Vec::new(),
+            finishes: Vec::new(),
+        })
+    }
+
+    /// Sets what each entrant pays into the prize pool, nothing unless set
+    pub fn with_buy_in(mut self, buy_in: u64) -> Self {
+        self.buy_in = buy_in;
+        self
+    }
+
+    pub fn schedule(&self) -> &BlindSchedule {
+        &self.schedule
+    }
+
+    pub fn payouts(&self) -> &Payouts {
+        &self.payouts
+    }
+
+    pub fn seats_per_table(&self) -> usize {
+        self.seats_per_table
+    }
+
+    /// Names of everyone registered, in the order they registered
+    pub fn entrants(&self) -> &[String] {
+        &self.entrants
+    }
+
+    /// The buy-ins of every entrant
+    pub fn prize_pool(&self) -> u64 {
+        self.buy_in * self.entrants.len() as u64
+    }
+
+    /// Registers a player before the start
+    pub fn register(&mut self, name: impl Into<String>) -> Result<(), TournamentError> {
+        if self.started {
+            return Err(TournamentError::AlreadyStarted);
+        }
+        let name = name.into();
+        if self.entrants.contains(&name) {
+            return Err(TournamentError::DuplicateName(name));
+        }
+        self.entrants.push(name);
+        Ok(())
+    }
+
+    /// Seats every entrant with `stack` chips, in an order shuffled from `seed`
+    ///
+    /// Players are dealt round the tables one at a time, so no table has more than one
+    /// player more than another.
+    pub fn start(&mut self, stack: Chips, seed: u64) -> Result<(), TournamentError> {
+        if self.started {
+            return Err(TournamentError::AlreadyStarted);
+        }
+        let count = self.entrants.len();
+        if count < 2 {
+            return Err(TournamentError::NotEnoughEntrants(count));
+        }
+        let stakes = self.level().stakes;
+        let table_count = count.div_ceil(self.seats_per_table);
+        let mut tables = Vec::with_capacity(table_count);
+        for _ in 0..table_count {
+            tables.push(Table::new(self.seats_per_table, stakes)?);
+        }
+
+        let mut order = self.entrants.clone();
+        order.shuffle(&mut StdRng::seed_from_u64(seed));
+        for (i, name) in order.into_iter().enumerate() {
+            tables[i % table_count].sit(i / table_count, name, stack)?;
+        }
+
+        self.tables = tables
+            .into_iter()
+            .map(|table| Some(TableState { table, busy: false }))
+            .collect();
+        self.prizes = self.payouts.prizes(self.prize_pool());
+        self.started = true;
+        Ok(())
+    }
+
+    pub fn is_started(&self) -> bool {
+        self.started
+    }
+
+    /// Whether only one player is left
+    pub fn is_complete(&self) -> bool {
+        self.started && self.finishes.len() == self.entrants.len()
+    }
+
+    /// Time played so far
+    pub fn elapsed(&self) -> Duration {
+        self.elapsed
+    }
+
+    /// Moves the clock on
+    pub fn advance(&mut self, time: Duration) {
+        self.elapsed += time;
+    }
+
+    /// The current level, or break, of the blind schedule
+    pub fn level(&self) -> LevelStatus {
+        self.schedule
+            .status(self.elapsed)
+            .expect("checked for a blind level on creation")
+    }
+
+    /// The tables in play by number, broken tables leaving gaps in the numbering
+    pub fn tables(&self) -> impl Iterator<Item = (usize, &Table)> {
+        self.tables
+            .iter()
+            .enumerate()
+            .filter_map(|(i, state)| state.as_ref().map(|state| (i, &state.table)))
+    }
+
+    pub fn table(&self, table: usize) -> Option<&Table> {
+        self.tables.get(table)?.as_ref().map(|state| &state.table)
+    }
+
+    /// Whether a table is playing a hand
+    pub fn is_playing(&self, table: usize) -> bool {
+        self.tables
+            .get(table)
+            .and_then(Option::as_ref)
+            .is_some_and(|state| state.busy)
+    }
+
+    /// Number of players still in
+    pub fn players_left(&self) -> usize {
+        self.entrants.len() - self.finishes.len()
+    }
+
+    /// Players knocked out so far and the winner once there is one, best finish first
+    pub fn results(&self) -> Vec<Finish> {
+        let mut results = self.finishes.clone();
+        results.reverse();
+        results
+    }
+
+    /// The prize of each paid place, first place first
+    pub fn prizes(&self) -> &[u64] {
+        &self.prizes
+    }
+
+    /// Each remaining player's share of the prizes still to be won, by the independent chip
+    /// model
+    pub fn icm_equities(&self) -> Result<Vec<(String, f64)>, IcmError> {
+        let players: Vec<&Seat> = self
+            .tables()
+            .flat_map(|(_, table)| table.seats().iter().flatten())
+            .collect();
+        let stacks: Vec<u64> = players.iter().map(|seat| seat.stack()).collect();
+        let payouts: Vec<f64> = (0..players.len())
+            .map(|place| self.prize(place) as f64)
+            .collect();
+        let result = IcmCalculator::new(&stacks, &payouts).calculate()?;
+        Ok(players
+            .iter()
+            .zip(result.equities())
+            .map(|(seat, &equity)| (seat.name().to_string(), equity))
+            .collect())
+    }
+
+    /// Deals a hand at a table at the current level's stakes
+    pub fn start_hand(&mut self, table: usize, deck: Deck) -> Result<Hand, TournamentError> {
+        if !self.started {
+            return Err(TournamentError::NotStarted);
+        }
+        if self.is_complete() {
+            return Err(TournamentError::Finished);
+        }
+        let status = self.level();
+        if status.level.is_break() {
+            return Err(TournamentError::OnBreak);
+        }
+        let state = self.state_mut(table)?;
+        if state.busy {
+            return Err(TournamentError::HandInProgress(table));
+        }
+        state.table.set_stakes(status.stakes)?;
+        let hand = state.table.start_hand(deck)?;
+        state.busy = true;
+        Ok(hand)
+    }
+
+    /// Settles a finished hand, knocks out the players who lost their chips and breaks or
+    /// balances tables
+    pub fn finish_hand(
+        &mut self,
+        table: usize,
+        hand: &Hand,
+    ) -> Result<HandReport, TournamentError> {
+        let state = self.state_mut(table)?;
+        if !state.busy {
+            return Err(TournamentError::NoHandInProgress(table));
+        }
+        state.table.finish_hand(hand)?;
+        state.busy = false;
+
+        // Busted players with the bigger stacks at the start of the hand finish higher
+        let mut busted = Vec::new();
+        for seat in 0..state.table.seats().len() {
+            if state.table.seat(seat).is_some_and(|s| s.stack() == 0) {
+                let name = state.table.leave(seat)?.name().to_string();
+                let started_with = hand
+                    .player(seat)
+                    .filter(|player| player.name() == name)
+                    .map_or(0, |player| player.starting_stack());
+                busted.push((name, started_with));
+            }
+        }
+        busted.sort_by_key(|b| std::cmp::Reverse(b.1));
+
+        let mut report = HandReport::default();
+        let left = self.players_left() - busted.len();
+        report.finishes = self.place(busted, left + 1);
+        if left == 1 {
+            let winner = self
+                .tables()
+                .flat_map(|(_, table)| table.seats().iter().flatten())
+                .next()
+                .map(|seat| seat.name().to_string())
+                .expect("one player is left");
+            report.finishes.insert(
+                0,
+                Finish {
+                    name: winner,
+                    place: 1,
+                    prize: self.prize(0),
+                },
+            );
+        }
+        for finish in report.finishes.iter().rev() {
+            self.finishes.push(finish.clone());
+        }
+
+        if !self.is_complete() {
+            self.balance(table, &mut report);
+        }
+        Ok(report)
+    }
+
+    // Gives players who went out together the places from `best` down, splitting the
+    // prizes of tied places evenly and any odd chips to the first of them
+    fn place(&self, busted: Vec<(String, Chips)>, best: usize) -> Vec<Finish> {
+        let mut finishes = Vec::with_capacity(busted.len());
+        let mut start = 0;
+        while start < busted.len() {
+            let stack = busted[start].1;
+            let end = start + busted[start..].iter().take_while(|b| b.1 == stack).count();
+            let tied = (end - start) as u64;
+            let pool: u64 = (start..end).map(|i| self.prize(best - 1 + i)).sum();
+            for (i, (name, _)) in busted[start..end].iter().enumerate() {
+                let odd = u64::from((i as u64) < pool % tied);
+                finishes.push(Finish {
+                    name: name.clone(),
+                    place: best + start,
+                    prize: pool / tied + odd,
+                });
+            }
+            start = end;
+        }
+        finishes
+    }
+
+    // The prize for a place counted from 0
+    fn prize(&self, place: usize) -> u64 {
+        self.prizes.get(place).copied().unwrap_or(0)
+    }
+
+    // Breaks the table if its players fit at the others, or else moves players from it to
+    // the shortest tables until it has at most one more than them
+    fn balance(&mut self, from: usize, report: &mut HandReport) {
+        let in_play = self.tables.iter().flatten().count();
+        let needed = self.players_left().div_ceil(self.seats_per_table);
+        if needed < in_play {
+            while let Some(seat) = self.next_to_move(from) {
+                self.move_player(from, seat, report);
+            }
+            self.tables[from] = None;
+            report.broken = Some(from);
+            return;
+        }
+        while let Some(shortest) = self.shortest_table(from)
+            && self.players_at(from) >= self.players_at(shortest) + 2
+        {
+            let seat = self.next_to_move(from).expect("the table has players");
+            self.move_player(from, seat, report);
+        }
+    }
+
+    // The player at a table who would post the big blind next, or the first player if no
+    // hand has been dealt there
+    fn next_to_move(&self, table: usize) -> Option<usize> {
+        let table = self.table(table)?;
+        let count = table.seats().len();
+        let start = table.button().map_or(0, |button| button + 1);
+        let occupied: Vec<usize> = (start..start + count)
+            .map(|seat| seat % count)
+            .filter(|&seat| table.seat(seat).is_some())
+            .collect();
+        // The next hand's button is the first of these, and heads up it posts the small blind
+        let big_blind = if occupied.len() > 2 { 2 } else { 1 };
+        occupied.get(big_blind).or(occupied.first()).copied()
+    }
+
+    // The table in play with the fewest players and an empty seat, other than `except`
+    fn shortest_table(&self, except: usize) -> Option<usize> {
+        self.tables
+            .iter()
+            .enumerate()
+            .filter(|&(i, state)| i != except && state.is_some())
+            .map(|(i, _)| (self.players_at(i), i))
+            .filter(|&(players, _)| players < self.seats_per_table)
+            .min()
+            .map(|(_, i)| i)
+    }
+
+    fn players_at(&self, table: usize) -> usize {
+        self.tables
+            .get(table)
+            .and_then(Option::as_ref)
+            .map_or(0, TableState::players)
+    }
+
+    // Moves a player to the lowest empty seat of the shortest other table
+    fn move_player(&mut self, from: usize, seat: usize, report: &mut HandReport) {
+        let to = self
+            .shortest_table(from)
+            .expect("the other tables have room");
+        let player = self.tables[from]
+            .as_mut()
+            .and_then(|state| state.table.leave(seat).ok())
+            .expect("the seat has a player");
+        let destination = &mut self.tables[to]
+            .as_mut()
+            .expect("the table is in play")
+            .table;
+        let empty = destination
+            .seats()
+            .iter()
+            .position(Option::is_none)
+            .expect("the table has an empty seat");
+        destination
+            .sit(empty, player.name(), player.stack())
+            .expect("the seat is empty");
+        report.moves.push(Move {
+            name: player.name().to_string(),
+            from: (from, seat),
+            to: (to, empty),
+        });
+    }
+
+    fn state_mut(&mut self, table: usize) -> Result<&mut TableState, TournamentError> {
+        self.tables
+            .get_mut(table)
+            .and_then(Option::as_mut)
+            .ok_or(TournamentError::InvalidTable(table))
+    }
+}
@@ -0,0 +1,92 @@
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
+use super::TournamentError;
+
+/// Share of the field paid in fields too large for the fixed tiers
+const PAID_FRACTION: f64 = 0.15;
+
+/// How a prize pool is split between the top finishers
+///
+/// Each paid place has a share of the pool in any unit, such as percentages. Prizes are
+/// rounded down and the chips left over go one each to the top places, so the prizes always
+/// add up to the whole pool.
+///
+/// # Examples
+///
+/// ```
+/// use holdem::tournament::Payouts;
+///
+/// let payouts = Payouts::new(&[50, 30, 20]).unwrap();
+/// assert_eq!(payouts.prizes(1_001), vec![501, 300, 200]);
+///
+/// // A full nine-player sit-and-go pays three places
+/// assert_eq!(Payouts::for_entrants(9), payouts);
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct Payouts {
+    shares: Vec<u64>,
+}
+
+impl Payouts {
+    /// Pays each place its share of the pool, first place first
+    pub fn new(shares: &[u64]) -> Result<Self, TournamentError> {
+        if shares.is_empty() || shares.contains(&0) {
+            return Err(TournamentError::InvalidPayouts);
+        }
+        Ok(Self {
+            shares: shares.to_vec(),
+        })
+    }
+
+    /// Pays the whole pool to the winner
+    pub fn winner_take_all() -> Self {
+        Self { shares: vec![1] }
+    }
+
+    /// The usual payouts for a field of this size
+    ///
+    /// Up to 27 entrants follow the standard sit-and-go tiers, paying from one place for
+    /// three or fewer up to seven places for three tables. Larger fields pay the top 15%,
+    /// each place's share falling with its number.
+    pub fn for_entrants(entrants: usize) -> Self {
+        let shares = match entrants {
+            0..=3 => vec![100],
+            4..=6 => vec![65, 35],
+            7..=10 => vec![50, 30, 20],
+            11..=18 => vec![40, 30, 20, 10],
+            19..=27 => vec![30, 20, 15, 12, 10, 8, 5],
+            _ => {
+                let places = (entrants as f64 * PAID_FRACTION).ceil() as u64;
+                (1..=places).map(|place| 100_000 / place).collect()
+            }
+        };
+        Self { shares }
+    }
+
+    /// Number of places paid
+    pub fn places(&self) -> usize {
+        self.shares.len()
+    }
+
+    pub fn shares(&self) -> &[u64] {
+        &self.shares
+    }
+
+    /// Splits a pool into the prize for each paid place, first place first
+    pub fn prizes(&self, pool: u64) -> Vec<u64> {
+        let total: u64 = self.shares.iter().sum();
+        let mut prizes: Vec<u64> = self
+            .shares
+            .iter()
+            .map(|&share| (pool as u128 * share as u128 / total as u128) as u64)
+            .collect();
+        let left = pool - prizes.iter().sum::<u64>();
+        // Less than one chip per place is left over after rounding down
+        for prize in prizes.iter_mut().take(left as usize) {
+            *prize += 1;
+        }
+        prizes
+    }
+}
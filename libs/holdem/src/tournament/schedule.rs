@@ -0,0 +1,185 @@
+use std::time::Duration;
+
+use crate::Chips;
+use crate::table::Stakes;
+
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
+/// Blinds and antes of the standard sit-and-go ladder, for 1,500-chip starting stacks
+const LADDER: [(Chips, Chips, Chips); 15] = [
+    (10, 20, 0),
+    (15, 30, 0),
+    (25, 50, 0),
+    (50, 100, 0),
+    (75, 150, 0),
+    (100, 200, 0),
+    (100, 200, 25),
+    (200, 400, 25),
+    (300, 600, 50),
+    (400, 800, 50),
+    (600, 1_200, 75),
+    (800, 1_600, 100),
+    (1_000, 2_000, 100),
+    (1_500, 3_000, 150),
+    (2_000, 4_000, 200),
+];
+
+/// Starting stack the presets are meant for
+pub const PRESET_STACK: Chips = 1_500;
+
+/// One step of a blind schedule
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub enum Level {
+    /// Play at these stakes for this long
+    Blinds { stakes: Stakes, duration: Duration },
+    /// No hands are dealt for this long
+    Break(Duration),
+}
+
+impl Level {
+    pub fn duration(&self) -> Duration {
+        match *self {
+            Level::Blinds { duration, .. } | Level::Break(duration) => duration,
+        }
+    }
+
+    pub fn is_break(&self) -> bool {
+        matches!(self, Level::Break(_))
+    }
+}
+
+/// Where a schedule stands at a moment of the tournament
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct LevelStatus {
+    /// Index of the current step among all levels and breaks
+    pub index: usize,
+    pub level: Level,
+    /// The stakes in play, or the stakes play resumes at when on a break
+    pub stakes: Stakes,
+    /// Time left in the step, or `None` on the last blind level, which lasts until the end
+    pub remaining: Option<Duration>,
+}
+
+/// Blind levels and breaks, each lasting a set time
+///
+/// The last blind level carries on until the tournament ends, and breaks after it are
+/// ignored.
+///
+/// # Examples
+///
+/// ```
+/// use std::time::Duration;
+/// use holdem::table::Stakes;
+/// use holdem::tournament::BlindSchedule;
+///
+/// let minutes = |m: u64| Duration::from_secs(m * 60);
+/// let schedule = BlindSchedule::new()
+///     .with_level(Stakes::new(10, 20), minutes(10))
+///     .with_break(minutes(5))
+///     .with_level(Stakes::new(25, 50).with_ante(5), minutes(10));
+///
+/// let status = schedule.status(minutes(12)).unwrap();
+/// assert!(status.level.is_break());
+/// assert_eq!(status.stakes, Stakes::new(25, 50).with_ante(5));
+/// assert_eq!(status.remaining, Some(minutes(3)));
+///
+/// // The last level never ends
+/// assert_eq!(schedule.status(minutes(500)).unwrap().remaining, None);
+/// ```
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct BlindSchedule {
+    levels: Vec<Level>,
+}
+
+impl BlindSchedule {
+    /// Creates an empty schedule
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// The standard sit-and-go ladder with ten-minute levels and a five-minute break every
+    /// hour
+    pub fn regular() -> Self {
+        Self::ladder(
+            Duration::from_secs(10 * 60),
+            Some(Duration::from_secs(5 * 60)),
+        )
+    }
+
+    /// The standard ladder with five-minute levels and no breaks
+    pub fn turbo() -> Self {
+        Self::ladder(Duration::from_secs(5 * 60), None)
+    }
+
+    /// The standard ladder with three-minute levels and no breaks
+    pub fn hyper_turbo() -> Self {
+        Self::ladder(Duration::from_secs(3 * 60), None)
+    }
+
+    fn ladder(duration: Duration, hourly_break: Option<Duration>) -> Self {
+        let per_hour = (3_600 / duration.as_secs().max(1)) as usize;
+        let mut schedule = Self::new();
+        for (i, &(small_blind, big_blind, ante)) in LADDER.iter().enumerate() {
+            if i > 0
+                && i % per_hour == 0
+                && let Some(break_duration) = hourly_break
+            {
+                schedule = schedule.with_break(break_duration);
+            }
+            let stakes = Stakes::new(small_blind, big_blind).with_ante(ante);
+            schedule = schedule.with_level(stakes, duration);
+        }
+        schedule
+    }
+
+    /// Adds a blind level
+    pub fn with_level(mut self, stakes: Stakes, duration: Duration) -> Self {
+        self.levels.push(Level::Blinds { stakes, duration });
+        self
+    }
+
+    /// Adds a break
+    pub fn with_break(mut self, duration: Duration) -> Self {
+        self.levels.push(Level::Break(duration));
+        self
+    }
+
+    /// Every level and break in order
+    pub fn levels(&self) -> &[Level] {
+        &self.levels
+    }
+
+    /// Where the schedule stands once `elapsed` has passed since the start, or `None` if it
+    /// has no blind levels
+    pub fn status(&self, elapsed: Duration) -> Option<LevelStatus> {
+        let last = self.levels.iter().rposition(|level| !level.is_break())?;
+        let mut start = Duration::ZERO;
+        for (index, level) in self.levels[..=last].iter().enumerate() {
+            let end = start + level.duration();
+            if index == last || elapsed < end {
+                return Some(LevelStatus {
+                    index,
+                    level: *level,
+                    stakes: self.stakes_from(index),
+                    remaining: (index != last).then(|| end - elapsed),
+                });
+            }
+            start = end;
+        }
+        None
+    }
+
+    // The stakes of the first blind level at or after `index`
+    fn stakes_from(&self, index: usize) -> Stakes {
+        self.levels[index..]
+            .iter()
+            .find_map(|level| match level {
+                Level::Blinds { stakes, .. } => Some(*stakes),
+                Level::Break(_) => None,
+            })
+            .expect("a blind level follows")
+    }
+}
@@ -0,0 +1,98 @@
+use cards::Deck;
+
+use super::super::{BlindSchedule, Payouts, Tournament};
+use crate::Chips;
+use crate::hand::Action;
+use proptest::prelude::*;
+
+// Plays a tournament to the end, dealing at every table that can before finishing any of
+// them, with everyone all in or folding at random
+fn run(entrants: usize, seats: usize, stack: Chips, seed: u64) -> Tournament {
+    let mut tournament = Tournament::new(
+        BlindSchedule::hyper_turbo(),
+        Payouts::for_entrants(entrants),
+        seats,
+    )
+    .unwrap()
+    .with_buy_in(10);
+    for i in 0..entrants {
+        tournament.register(format!("p{i}")).unwrap();
+    }
+    tournament.start(stack, seed).unwrap();
+
+    let mut deal = seed;
+    for _ in 0..10_000 {
+        if tournament.is_complete() {
+            break;
+        }
+        let ids: Vec<usize> = tournament.tables().map(|(i, _)| i).collect();
+        let mut hands = Vec::new();
+        for table in ids {
+            deal = deal.wrapping_add(1);
+            if let Ok(hand) = tournament.start_hand(table, Deck::new_seeded(deal)) {
+                hands.push((table, hand));
+            }
+        }
+        for (table, mut hand) in hands {
+            while let Some(seat) = hand.to_act() {
+                let action = if (deal + seat as u64).is_multiple_of(4) {
+                    Action::Fold
+                } else {
+                    Action::AllIn
+                };
+                hand = hand.apply(seat, action).unwrap();
+            }
+            let report = tournament.finish_hand(table, &hand).unwrap();
+            for m in &report.moves {
+                assert_eq!(m.from.0, table);
+                assert!(!tournament.is_playing(table));
+            }
+            let chips: Chips = tournament
+                .tables()
+                .flat_map(|(_, t)| t.seats().iter().flatten())
+                .map(|s| s.stack())
+                .sum();
+            if !tournament.is_complete() {
+                assert_eq!(chips, stack * entrants as Chips);
+            }
+        }
+        tournament.advance(std::time::Duration::from_secs(60));
+    }
+    tournament
+}
+
+proptest! {
+    #![proptest_config(ProptestConfig::with_cases(24))]
+
+    #[test]
+    fn everyone_finishes_once_and_the_pool_is_paid(
+        entrants in 2usize..30,
+        seats in 2usize..=10,
+        stack in 100 as Chips..2_000,
+        seed in any::<u64>(),
+    ) {
+        let tournament = run(entrants, seats, stack, seed);
+        prop_assert!(tournament.is_complete());
+
+        let results = tournament.results();
+        prop_assert_eq!(results.len(), entrants);
+        let mut names: Vec<&str> = results.iter().map(|f| f.name.as_str()).collect();
+        names.sort();
+        names.dedup();
+        prop_assert_eq!(names.len(), entrants);
+
+        prop_assert_eq!(results[0].place, 1);
+        prop_assert!(results.windows(2).all(|w| w[0].place <= w[1].place));
+        let paid: u64 = results.iter().map(|f| f.prize).sum();
+        prop_assert_eq!(paid, tournament.prize_pool());
+    }
+
+    #[test]
+    fn prizes_add_up_to_the_pool(entrants in 1usize..500, pool in 0u64..1_000_000) {
+        let payouts = Payouts::for_entrants(entrants);
+        let prizes = payouts.prizes(pool);
+        prop_assert_eq!(prizes.len(), payouts.places());
+        prop_assert_eq!(prizes.iter().sum::<u64>(), pool);
+        prop_assert!(prizes.windows(2).all(|w| w[0] >= w[1]));
+    }
+}
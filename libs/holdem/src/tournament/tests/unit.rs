@@ -0,0 +1,408 @@
+use std::time::Duration;
+
+use cards::Deck;
+
+use super::super::*;
+use crate::hand::Action;
+use crate::table::Stakes;
+
+fn minutes(m: u64) -> Duration {
+    Duration::from_secs(m * 60)
+}
+
+fn tournament(entrants: usize, seats: usize) -> Tournament {
+    let mut tournament = Tournament::new(
+        BlindSchedule::turbo(),
+        Payouts::for_entrants(entrants),
+        seats,
+    )
+    .unwrap()
+    .with_buy_in(100);
+    for i in 0..entrants {
+        tournament.register(format!("p{i}")).unwrap();
+    }
+    tournament
+}
+
+// Everyone goes all in
+fn play(tournament: &mut Tournament, table: usize, seed: u64) -> HandReport {
+    let mut hand = tournament
+        .start_hand(table, Deck::new_seeded(seed))
+        .unwrap();
+    while let Some(seat) = hand.to_act() {
+        hand = hand.apply(seat, Action::AllIn).unwrap();
+    }
+    tournament.finish_hand(table, &hand).unwrap()
+}
+
+#[test]
+fn test_presets_share_a_ladder_at_different_speeds() {
+    let blinds = |schedule: &BlindSchedule| -> Vec<Stakes> {
+        schedule
+            .levels()
+            .iter()
+            .filter_map(|level| match level {
+                Level::Blinds { stakes, .. } => Some(*stakes),
+                Level::Break(_) => None,
+            })
+            .collect()
+    };
+    let regular = BlindSchedule::regular();
+    let turbo = BlindSchedule::turbo();
+    let hyper = BlindSchedule::hyper_turbo();
+    assert_eq!(blinds(&regular), blinds(&turbo));
+    assert_eq!(blinds(&turbo), blinds(&hyper));
+    assert_eq!(blinds(&turbo)[0], Stakes::new(10, 20));
+    assert!(
+        blinds(&turbo)
+            .windows(2)
+            .all(|w| w[0].big_blind <= w[1].big_blind)
+    );
+
+    assert!(turbo.levels().iter().all(|l| l.duration() == minutes(5)));
+    assert!(hyper.levels().iter().all(|l| l.duration() == minutes(3)));
+
+    // A break after every sixth ten-minute level
+    let breaks: Vec<usize> = regular
+        .levels()
+        .iter()
+        .enumerate()
+        .filter(|(_, level)| level.is_break())
+        .map(|(i, _)| i)
+        .collect();
+    assert_eq!(breaks, [6, 13]);
+    assert_eq!(regular.levels()[6], Level::Break(minutes(5)));
+    assert_eq!(regular.levels()[0].duration(), minutes(10));
+}
+
+#[test]
+fn test_status_follows_the_clock() {
+    let schedule = BlindSchedule::new()
+        .with_level(Stakes::new(10, 20), minutes(10))
+        .with_break(minutes(5))
+        .with_level(Stakes::new(25, 50), minutes(10))
+        .with_break(minutes(5));
+
+    let status = schedule.status(Duration::ZERO).unwrap();
+    assert_eq!(status.index, 0);
+    assert_eq!(status.stakes, Stakes::new(10, 20));
+    assert_eq!(status.remaining, Some(minutes(10)));
+
+    let status = schedule.status(minutes(10)).unwrap();
+    assert_eq!(status.index, 1);
+    assert_eq!(status.level, Level::Break(minutes(5)));
+    assert_eq!(status.stakes, Stakes::new(25, 50));
+
+    // The trailing break is never reached
+    let status = schedule.status(minutes(40)).unwrap();
+    assert_eq!(status.index, 2);
+    assert_eq!(status.remaining, None);
+
+    assert_eq!(BlindSchedule::new().status(Duration::ZERO), None);
+    assert_eq!(
+        BlindSchedule::new()
+            .with_break(minutes(5))
+            .status(Duration::ZERO),
+        None
+    );
+}
+
+#[test]
+fn test_payouts() {
+    assert_eq!(
+        Payouts::new(&[]).unwrap_err(),
+        TournamentError::InvalidPayouts
+    );
+    assert_eq!(
+        Payouts::new(&[60, 0, 40]).unwrap_err(),
+        TournamentError::InvalidPayouts
+    );
+    assert_eq!(Payouts::winner_take_all().prizes(999), [999]);
+
+    assert_eq!(Payouts::for_entrants(2).places(), 1);
+    assert_eq!(Payouts::for_entrants(6).shares(), [65, 35]);
+    assert_eq!(Payouts::for_entrants(10).places(), 3);
+    assert_eq!(Payouts::for_entrants(18).places(), 4);
+    assert_eq!(Payouts::for_entrants(27).places(), 7);
+    assert_eq!(Payouts::for_entrants(100).places(), 15);
+
+    let prizes = Payouts::new(&[1, 1, 1]).unwrap().prizes(100);
+    assert_eq!(prizes, [34, 33, 33]);
+}
+
+#[test]
+fn test_new_validates_seats_and_schedule() {
+    let payouts = Payouts::winner_take_all;
+    assert_eq!(
+        Tournament::new(BlindSchedule::turbo(), payouts(), 1).unwrap_err(),
+        TournamentError::Table(TableError::InvalidSeatCount(1))
+    );
+    assert_eq!(
+        Tournament::new(BlindSchedule::new(), payouts(), 6).unwrap_err(),
+        TournamentError::InvalidSchedule
+    );
+    let bad = BlindSchedule::turbo().with_level(Stakes::new(50, 20), minutes(5));
+    assert_eq!(
+        Tournament::new(bad, payouts(), 6).unwrap_err(),
+        TournamentError::Table(TableError::InvalidStakes(Stakes::new(50, 20)))
+    );
+}
+
+#[test]
+fn test_registration() {
+    let mut tournament = tournament(1, 6);
+    assert_eq!(
+        tournament.register("p0").unwrap_err(),
+        TournamentError::DuplicateName("p0".to_string())
+    );
+    assert_eq!(
+        tournament.start(1_500, 0).unwrap_err(),
+        TournamentError::NotEnoughEntrants(1)
+    );
+    assert_eq!(
+        tournament.start_hand(0, Deck::new()).unwrap_err(),
+        TournamentError::NotStarted
+    );
+
+    tournament.register("p1").unwrap();
+    tournament.start(1_500, 0).unwrap();
+    assert_eq!(
+        tournament.register("p2").unwrap_err(),
+        TournamentError::AlreadyStarted
+    );
+    assert_eq!(
+        tournament.start(1_500, 0).unwrap_err(),
+        TournamentError::AlreadyStarted
+    );
+    assert_eq!(tournament.prize_pool(), 200);
+    assert_eq!(tournament.prizes(), [200]);
+}
+
+#[test]
+fn test_start_seats_everyone_evenly() {
+    let mut tournament = tournament(20, 9);
+    tournament.start(1_500, 4).unwrap();
+
+    let counts: Vec<usize> = tournament
+        .tables()
+        .map(|(_, table)| table.seats().iter().flatten().count())
+        .collect();
+    assert_eq!(counts, [7, 7, 6]);
+
+    let mut names: Vec<String> = tournament
+        .tables()
+        .flat_map(|(_, table)| table.seats().iter().flatten())
+        .map(|seat| seat.name().to_string())
+        .collect();
+    names.sort();
+    let mut entrants = tournament.entrants().to_vec();
+    entrants.sort();
+    assert_eq!(names, entrants);
+
+    // The same seed gives the same seats
+    let mut again = self::tournament(20, 9);
+    again.start(1_500, 4).unwrap();
+    let seats = |t: &Tournament| -> Vec<Table> { t.tables().map(|(_, t)| t.clone()).collect() };
+    assert_eq!(seats(&again), seats(&tournament));
+}
+
+#[test]
+fn test_hands_are_dealt_at_the_current_level() {
+    let schedule = BlindSchedule::new()
+        .with_level(Stakes::new(10, 20), minutes(10))
+        .with_break(minutes(5))
+        .with_level(Stakes::new(25, 50).with_ante(5), minutes(10));
+    let mut tournament = Tournament::new(schedule, Payouts::winner_take_all(), 6).unwrap();
+    tournament.register("ann").unwrap();
+    tournament.register("ben").unwrap();
+    tournament.start(1_500, 1).unwrap();
+
+    let hand = tournament.start_hand(0, Deck::new_seeded(1)).unwrap();
+    assert_eq!(hand.stakes(), Stakes::new(10, 20));
+    assert_eq!(
+        tournament.start_hand(0, Deck::new_seeded(2)).unwrap_err(),
+        TournamentError::HandInProgress(0)
+    );
+    assert_eq!(
+        tournament.start_hand(3, Deck::new_seeded(2)).unwrap_err(),
+        TournamentError::InvalidTable(3)
+    );
+    assert!(tournament.is_playing(0));
+
+    // A hand still being played can finish during the break
+    tournament.advance(minutes(12));
+    assert!(tournament.level().level.is_break());
+    let seat = hand.to_act().unwrap();
+    let hand = hand.apply(seat, Action::Fold).unwrap();
+    tournament.finish_hand(0, &hand).unwrap();
+    assert_eq!(
+        tournament.finish_hand(0, &hand).unwrap_err(),
+        TournamentError::NoHandInProgress(0)
+    );
+    assert_eq!(
+        tournament.start_hand(0, Deck::new_seeded(2)).unwrap_err(),
+        TournamentError::OnBreak
+    );
+
+    tournament.advance(minutes(3));
+    let hand = tournament.start_hand(0, Deck::new_seeded(2)).unwrap();
+    assert_eq!(hand.stakes(), Stakes::new(25, 50).with_ante(5));
+    assert_eq!(tournament.table(0).unwrap().stakes(), hand.stakes());
+}
+
+#[test]
+fn test_players_who_bust_together_are_placed_by_starting_stack() {
+    let mut tournament = tournament(6, 6);
+    tournament.start(1_500, 0).unwrap();
+    assert_eq!(tournament.prizes(), [390, 210]);
+
+    let busted = vec![
+        ("a".to_string(), 900),
+        ("b".to_string(), 500),
+        ("c".to_string(), 500),
+        ("d".to_string(), 100),
+    ];
+    let finishes = tournament.place(busted, 2);
+    let places: Vec<(&str, usize, u64)> = finishes
+        .iter()
+        .map(|f| (f.name.as_str(), f.place, f.prize))
+        .collect();
+    // b and c tie for third and fourth, neither of which is paid
+    assert_eq!(
+        places,
+        [("a", 2, 210), ("b", 3, 0), ("c", 3, 0), ("d", 5, 0)]
+    );
+
+    // Tied players split the prizes of their places, the odd chip going to the first
+    let finishes = tournament.place(vec![("a".to_string(), 0), ("b".to_string(), 0)], 1);
+    assert_eq!(finishes[0].prize, 300);
+    assert_eq!(finishes[1].prize, 300);
+    let mut odd = tournament.clone();
+    odd.prizes = vec![391, 210];
+    let finishes = odd.place(vec![("a".to_string(), 0), ("b".to_string(), 0)], 1);
+    assert_eq!((finishes[0].prize, finishes[1].prize), (301, 300));
+    assert!(finishes.iter().all(|f| f.place == 1));
+}
+
+#[test]
+fn test_heads_up_to_a_winner() {
+    let mut tournament = tournament(2, 6);
+    tournament.start(1_500, 3).unwrap();
+
+    let mut seed = 0;
+    let report = loop {
+        seed += 1;
+        let report = play(&mut tournament, 0, seed);
+        if !report.finishes.is_empty() {
+            break report;
+        }
+    };
+    assert!(tournament.is_complete());
+    assert_eq!(report.finishes.len(), 2);
+    assert_eq!(report.finishes[0].place, 1);
+    assert_eq!(report.finishes[0].prize, 200);
+    assert_eq!(report.finishes[1].place, 2);
+    assert_eq!(tournament.results(), report.finishes);
+    assert_eq!(tournament.players_left(), 0);
+    assert_eq!(
+        tournament.start_hand(0, Deck::new()).unwrap_err(),
+        TournamentError::Finished
+    );
+}
+
+#[test]
+fn test_short_tables_are_broken_up() {
+    // Two heads-up tables
+    let mut tournament = tournament(4, 2);
+    tournament.start(1_500, 0).unwrap();
+    assert_eq!(tournament.tables().count(), 2);
+
+    let mut seed = 0;
+    while tournament.players_left() > 2 {
+        for table in [0, 1] {
+            if tournament
+                .table(table)
+                .is_some_and(|t| t.seats().iter().flatten().count() == 2)
+                && tournament.players_left() > 2
+            {
+                seed += 1;
+                let report = play(&mut tournament, table, seed);
+                if let Some(broken) = report.broken {
+                    assert_eq!(broken, table);
+                    assert_eq!(report.moves.len(), 1);
+                    assert_eq!(report.moves[0].from.0, table);
+                }
+            }
+        }
+    }
+    // The last two players meet at one table
+    let tables: Vec<usize> = tournament.tables().map(|(i, _)| i).collect();
+    assert_eq!(tables.len(), 1);
+    let table = tournament.table(tables[0]).unwrap();
+    assert_eq!(table.seats().iter().flatten().count(), 2);
+    assert!(tournament.table(1 - tables[0]).is_none());
+}
+
+#[test]
+fn test_players_move_from_long_tables_to_short_ones() {
+    let mut tournament = tournament(10, 5);
+    tournament.start(1_500, 0).unwrap();
+
+    // Knock out players at table 1 while table 0 is mid-hand
+    let waiting = tournament.start_hand(0, Deck::new_seeded(0)).unwrap();
+    let mut seed = 0;
+    while tournament
+        .table(1)
+        .unwrap()
+        .seats()
+        .iter()
+        .flatten()
+        .count()
+        > 3
+    {
+        seed += 1;
+        let report = play(&mut tournament, 1, seed);
+        assert!(report.moves.is_empty());
+        assert_eq!(report.broken, None);
+    }
+    // Table 0 has 5 and table 1 has 3 or fewer, so finishing table 0's hand moves players
+    let mut hand = waiting;
+    while let Some(seat) = hand.to_act() {
+        hand = hand.apply(seat, Action::Fold).unwrap();
+    }
+    let report = tournament.finish_hand(0, &hand).unwrap();
+    assert!(report.finishes.is_empty());
+    assert_eq!(report.broken, None);
+    assert!(!report.moves.is_empty());
+    for m in &report.moves {
+        assert_eq!(m.from.0, 0);
+        assert_eq!(m.to.0, 1);
+        let seat = tournament.table(1).unwrap().seat(m.to.1).unwrap();
+        assert_eq!(seat.name(), m.name);
+    }
+    let count = |t| {
+        tournament
+            .table(t)
+            .unwrap()
+            .seats()
+            .iter()
+            .flatten()
+            .count()
+    };
+    assert!(count(0).abs_diff(count(1)) <= 1);
+}
+
+#[test]
+fn test_icm_equities_share_the_remaining_prizes() {
+    let mut tournament = tournament(4, 6);
+    tournament.start(1_000, 2).unwrap();
+    assert_eq!(tournament.prizes(), [260, 140]);
+
+    let equities = tournament.icm_equities().unwrap();
+    assert_eq!(equities.len(), 4);
+    let total: f64 = equities.iter().map(|(_, equity)| equity).sum();
+    assert!((total - 400.0).abs() < 1e-6);
+    for (_, equity) in &equities {
+        assert!((equity - 100.0).abs() < 1e-6);
+    }
+}